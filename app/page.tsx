@@ -357,7 +357,7 @@ export default function App() {
         document.body.removeChild(a);
         URL.revokeObjectURL(url);
         
-        alert(`Withdrawal commitment created! Transaction ID: ${data.transaction_id}\n\nA .mno file has been downloaded. Use it later to claim your withdrawal.`);
+        alert(`Withdrawal commitment created! ${data.transaction_id ? `Transaction ID: ${data.transaction_id}` : data.message}\n\nA .mno file has been downloaded. Use it later to claim your withdrawal.`);
         // Reset form
         setZcashAddress("");
         setWithdrawalAmount("");
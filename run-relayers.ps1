@@ -3,6 +3,7 @@
 # 1. Backend API
 # 2. Zcash Relayer (Zcash → Miden)
 # 3. Miden Exit Relayer (Miden → Zcash)
+# 4. Webhook Dispatcher (optional, needs webhooks.json)
 
 Write-Host "=== Miden-Zcash Bridge Relayers ===" -ForegroundColor Green
 Write-Host ""
@@ -22,3 +23,14 @@ Write-Host "  cd rust-backend"
 Write-Host "  `$env:MIDEN_RELAYER_INTERVAL_SECS = '10'"
 Write-Host "  cargo run --release --bin miden_exit_relayer"
 Write-Host ""
+Write-Host "Terminal 4 - Webhook Dispatcher (optional, subscriptions in webhooks.json):" -ForegroundColor Cyan
+Write-Host "  cd rust-backend"
+Write-Host "  `$env:WEBHOOK_DISPATCH_INTERVAL_SECS = '5'"
+Write-Host "  cargo run --release --bin webhook_dispatcher"
+Write-Host "  # test receiver: cargo run --bin bridgectl -- webhooks listen --port 9200 --secret <secret> [--fail-first n]"
+Write-Host ""
+Write-Host "Terminal 5 - Reconciliation (optional, one-off: cargo run --bin bridgectl -- reconcile run [--json]):" -ForegroundColor Cyan
+Write-Host "  cd rust-backend"
+Write-Host "  `$env:RECONCILE_INTERVAL_SECS = '3600'"
+Write-Host "  cargo run --release --bin bridgectl -- reconcile watch"
+Write-Host ""
@@ -5,6 +5,7 @@
 # 1. Backend API
 # 2. Zcash Relayer (Zcash → Miden)
 # 3. Miden Exit Relayer (Miden → Zcash)
+# 4. Webhook Dispatcher (optional, needs webhooks.json)
 
 echo "=== Miden-Zcash Bridge Relayers ==="
 echo ""
@@ -17,10 +18,25 @@ echo ""
 echo "Terminal 2 - Zcash Relayer (Zcash → Miden):"
 echo "  cd rust-backend"
 echo "  export ZCASH_RELAYER_INTERVAL_SECS=5"
+echo "  export ZCASH_RELAYER_METRICS_PORT=9101   # optional, serves /metrics"
 echo "  cargo run --release --bin zcash_relayer"
 echo ""
 echo "Terminal 3 - Miden Exit Relayer (Miden → Zcash):"
 echo "  cd rust-backend"
 echo "  export MIDEN_RELAYER_INTERVAL_SECS=10"
+echo "  export MIDEN_RELAYER_METRICS_PORT=9102   # optional, serves /metrics"
 echo "  cargo run --release --bin miden_exit_relayer"
 echo ""
+echo "Terminal 4 - Webhook Dispatcher (optional, subscriptions in webhooks.json):"
+echo "  cd rust-backend"
+echo "  export WEBHOOK_DISPATCH_INTERVAL_SECS=5"
+echo "  export WEBHOOK_DISPATCHER_METRICS_PORT=9103   # optional, serves /metrics"
+echo "  cargo run --release --bin webhook_dispatcher"
+echo "  # test receiver: cargo run --bin bridgectl -- webhooks listen --port 9200 --secret <secret> [--fail-first n]"
+echo ""
+echo "Terminal 5 - Reconciliation (optional, one-off: cargo run --bin bridgectl -- reconcile run [--json]):"
+echo "  cd rust-backend"
+echo "  export RECONCILE_INTERVAL_SECS=3600"
+echo "  export RECONCILE_METRICS_PORT=9104   # optional, serves /metrics"
+echo "  cargo run --release --bin bridgectl -- reconcile watch"
+echo ""
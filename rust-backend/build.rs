@@ -134,7 +134,36 @@ fn main() {
         println!("cargo:warning=WARNING: CROSSCHAIN code commitment not computed, using placeholder values");
     }
     
-    // STEP 4: Compile other note scripts (if any) with fungible_wrapper available
+    // STEP 4: Compile the faucet auth component (rotatable Falcon key)
+    let faucet_auth_path = contracts_dir.join("faucet_auth.masm");
+    if faucet_auth_path.exists() {
+        let code = fs::read_to_string(&faucet_auth_path)
+            .expect("Failed to read faucet_auth.masm");
+
+        let source_manager = Arc::new(DefaultSourceManager::default());
+        let library_path = LibraryPath::new("bridge::faucet_auth")
+            .expect("Invalid library path");
+        let module = Module::parser(ModuleKind::Library)
+            .parse_str(
+                library_path,
+                &code,
+                &source_manager,
+            )
+            .expect("Failed to parse faucet_auth module");
+
+        let library = TransactionKernel::assembler()
+            .with_debug_mode(true)
+            .assemble_library([module])
+            .expect("Failed to assemble faucet_auth library");
+
+        let contracts_assets_dir = Path::new(&out_dir).join("assets/contracts");
+        fs::create_dir_all(&contracts_assets_dir).unwrap();
+        let masl_path = contracts_assets_dir.join("faucet_auth.masl");
+        fs::write(&masl_path, library.to_bytes()).unwrap();
+        println!("cargo:warning=Compiled {} -> {}", faucet_auth_path.display(), masl_path.display());
+    }
+
+    // STEP 5: Compile other note scripts (if any) with fungible_wrapper available
     if let Ok(entries) = fs::read_dir(note_scripts_dir) {
         for entry in entries {
             if let Ok(entry) = entry {
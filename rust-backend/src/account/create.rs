@@ -1,20 +1,24 @@
+use crate::error::{BridgeError, BridgeResult};
 use miden_client::{
     account::component::{BasicFungibleFaucet, BasicWallet},
     address::NetworkId,
     auth::AuthSecretKey,
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
     rpc::{Endpoint, GrpcClient},
 };
 use miden_client_sqlite_store::ClientBuilderSqliteExt;
-use miden_lib::account::auth::AuthRpoFalcon512;
+use miden_lib::account::auth::{AuthRpoFalcon512, AuthRpoFalcon512Multisig, AuthRpoFalcon512MultisigConfig};
+use crate::account::faucet_auth::RotatableFalconAuth;
+use crate::account::keystore;
+use crate::account::signer::BridgeSigner;
+use crate::bridge::multisig::MultisigConfig;
 use crate::account::token_wrapper::TokenWrapperAccount;
 use miden_objects::{
-    account::{AccountBuilder, AccountStorageMode, AccountType},
+    account::{AccountBuilder, AccountComponent, AccountStorageMode, AccountType},
     asset::TokenSymbol,
     Felt,
 };
-use rand::{rngs::StdRng, RngCore};
+use rand::RngCore;
 use rand::rng;
 use std::path::PathBuf;
 
@@ -22,16 +26,13 @@ pub async fn create_wallet_account(
     keystore_path: &PathBuf,
     store_path: &PathBuf,
     rpc_url: &str,
-) -> Result<String, String> {
+) -> BridgeResult<String> {
     // Initialize client
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = std::sync::Arc::new(GrpcClient::new(&endpoint, 10_000));
-    let keystore = std::sync::Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore: {}", e))?,
-    );
+    let keystore = keystore::unlock(keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
@@ -40,7 +41,7 @@ pub async fn create_wallet_account(
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Generate account seed
     let mut rng = rng();
@@ -57,24 +58,26 @@ pub async fn create_wallet_account(
         .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key().to_commitment()))
         .with_component(BasicWallet)
         .build()
-        .map_err(|e| format!("Failed to build account: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build account: {}", e)))?;
     
     // Add the account to the client
     client
         .add_account(&account, false)
         .await
-        .map_err(|e| format!("Failed to add account: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to add account: {}", e)))?;
     
     // Add the key pair to the keystore
     keystore
         .add_key(&key_pair)
-        .map_err(|e| format!("Failed to add key to keystore: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to add key to keystore: {}", e)))?;
     
     let account_id_bech32 = account.id().to_bech32(NetworkId::Testnet);
     
     Ok(account_id_bech32)
 }
 
+/// Create a faucet account, controlled by a new key in the keystore or, given `multisig`,
+/// by its approvers' keys
 pub async fn create_faucet_account(
     keystore_path: &PathBuf,
     store_path: &PathBuf,
@@ -82,25 +85,30 @@ pub async fn create_faucet_account(
     symbol: &str,
     decimals: u8,
     max_supply: u64,
-) -> Result<String, String> {
-    // Initialize client
+    multisig: Option<&MultisigConfig>,
+) -> BridgeResult<String> {
+    // A single-key faucet's key is generated here, so it has to happen where the signer runs
+    if multisig.is_none() && BridgeSigner::remote_socket().is_some() {
+        return Err(BridgeError::Conflict(
+            "SIGNER_SOCKET is set: create the faucet on the signer host, with SIGNER_SOCKET unset".to_string(),
+        ));
+    }
+    
+    // Initialize client; a multisig faucet needs no local keys, so this works with SIGNER_SOCKET set
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = std::sync::Arc::new(GrpcClient::new(&endpoint, 10_000));
-    let keystore = std::sync::Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore: {}", e))?,
-    );
+    let signer = BridgeSigner::open(keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path.clone())
-        .authenticator(keystore.clone())
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Generate faucet seed
     let mut rng = rng();
@@ -111,11 +119,26 @@ pub async fn create_faucet_account(
     
     // Faucet parameters
     let token_symbol = TokenSymbol::new(symbol)
-        .map_err(|e| format!("Invalid symbol: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid symbol: {}", e)))?;
     let max_supply_felt = Felt::new(max_supply);
     
-    // Generate key pair
-    let key_pair = AuthSecretKey::new_rpo_falcon512();
+    // Either a new key pair, or M-of-N approvers whose keys live with the relayers
+    let (auth_component, key_pair): (AccountComponent, _) = match multisig {
+        Some(multisig) => {
+            let approvers = multisig.approvers.iter().map(|approver| (*approver).into()).collect();
+            let config = AuthRpoFalcon512MultisigConfig::new(approvers, multisig.threshold)
+                .map_err(|e| BridgeError::InvalidInput(format!("Invalid multisig approvers: {}", e)))?;
+            let auth = AuthRpoFalcon512Multisig::new(config)
+                .map_err(|e| BridgeError::Internal(format!("Failed to create multisig auth component: {}", e)))?;
+            println!("[Create Faucet] Mints need {} of {} approver signatures", multisig.threshold, multisig.approvers.len());
+            (auth.into(), None)
+        }
+        None => {
+            // Rotatable, so a leaked key can be replaced without moving to a new faucet
+            let key_pair = AuthSecretKey::new_rpo_falcon512();
+            (RotatableFalconAuth::new(key_pair.public_key().to_commitment()).into(), Some(key_pair))
+        }
+    };
     
     // Build the faucet account with TokenWrapperAccount component
     // For Zcash bridge, origin_network=0 and origin_address=[0; 3] (not used)
@@ -125,14 +148,14 @@ pub async fn create_faucet_account(
     let faucet_account = AccountBuilder::new(init_seed)
         .account_type(AccountType::FungibleFaucet)
         .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key().to_commitment()))
+        .with_auth_component(auth_component)
         .with_component(TokenWrapperAccount::new(origin_network, origin_address))
         .with_component(
             BasicFungibleFaucet::new(token_symbol, decimals, max_supply_felt)
-                .map_err(|e| format!("Failed to create faucet component: {}", e))?,
+                .map_err(|e| BridgeError::InvalidInput(format!("Failed to create faucet component: {}", e)))?,
         )
         .build()
-        .map_err(|e| format!("Failed to build faucet: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build faucet: {}", e)))?;
     
     let new_faucet_id = faucet_account.id();
     let new_faucet_id_bech32 = new_faucet_id.to_bech32(NetworkId::Testnet);
@@ -142,20 +165,22 @@ pub async fn create_faucet_account(
     client
         .add_account(&faucet_account, false)
         .await
-        .map_err(|e| format!("Failed to add faucet: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to add faucet: {}", e)))?;
     
     println!("[Create Faucet] ✅ Successfully added new faucet account to client");
     
-    // Add the key pair to the keystore
-    keystore
-        .add_key(&key_pair)
-        .map_err(|e| format!("Failed to add key to keystore: {}", e))?;
+    // Add the key pair to the keystore (SIGNER_SOCKET is unset when there is one, checked above)
+    if let Some(key_pair) = &key_pair {
+        keystore::unlock(keystore_path)?
+            .add_key(key_pair)
+            .map_err(|e| BridgeError::Internal(format!("Failed to add key to keystore: {}", e)))?;
+    }
     
     // Sync state - account will deploy automatically on first transaction (minting or withdrawal)
     client
         .sync_state()
         .await
-        .map_err(|e| format!("Failed to sync state: {}", e))?;
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync state: {}", e)))?;
     
     println!("[Create Faucet] ✅ Faucet account created with TokenWrapperAccount component: {}", new_faucet_id_bech32);
     println!("[Create Faucet] Note: Account will deploy automatically on first use (minting or withdrawal)");
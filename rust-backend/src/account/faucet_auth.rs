@@ -0,0 +1,58 @@
+use miden_client::auth::PublicKeyCommitment;
+use miden_objects::{
+    account::{AccountCode, AccountComponent, StorageSlot},
+    assembly::Library,
+    utils::{sync::LazyLock, Deserializable},
+    Word,
+};
+
+static FAUCET_AUTH_CODE: LazyLock<Library> = LazyLock::new(|| {
+    let bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/contracts/faucet_auth.masl"));
+    Library::read_from_bytes(bytes).expect("Shipped faucet auth library is well-formed")
+});
+
+/// Storage slot of the current key; the auth component is the account's first, so it is also
+/// the slot in the account's storage
+pub const PUBLIC_KEY_SLOT: u8 = 0;
+
+/// Procedure that stages a new key, called from the rotation transaction script
+pub const ROTATE_KEY_PROCEDURE: &str = "bridge::faucet_auth::rotate_key";
+
+pub fn faucet_auth_library() -> Library {
+    FAUCET_AUTH_CODE.clone()
+}
+
+/// Single-key faucet auth: `AuthRpoFalcon512` plus a `rotate_key` procedure
+///
+/// A rotation stages the new key in a second slot; it only becomes current once a transaction
+/// signed with the old key is accepted, so calling `rotate_key` without that key changes nothing.
+pub struct RotatableFalconAuth {
+    public_key: PublicKeyCommitment,
+}
+
+impl RotatableFalconAuth {
+    pub fn new(public_key: PublicKeyCommitment) -> Self {
+        Self { public_key }
+    }
+
+    /// Whether an account was built with this component, i.e. its key can be rotated
+    pub fn is_installed(code: &AccountCode) -> bool {
+        faucet_auth_library()
+            .get_procedure_root_by_name(ROTATE_KEY_PROCEDURE)
+            .is_some_and(|root| code.has_procedure(root))
+    }
+}
+
+impl From<RotatableFalconAuth> for AccountComponent {
+    fn from(auth: RotatableFalconAuth) -> Self {
+        AccountComponent::new(
+            faucet_auth_library(),
+            vec![
+                StorageSlot::Value(Word::from(auth.public_key)),
+                StorageSlot::Value(Word::empty()),
+            ],
+        )
+        .expect("Failed to create RotatableFalconAuth component")
+        .with_supports_all_types()
+    }
+}
@@ -0,0 +1,389 @@
+use crate::error::{BridgeError, BridgeResult};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use miden_client::auth::{
+    AuthSecretKey, AuthenticationError, PublicKeyCommitment, Signature, SigningInputs,
+    TransactionAuthenticator,
+};
+use miden_objects::utils::{Deserializable, Serializable};
+use miden_objects::Word;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+/// Holds the salt and a sealed marker that tells a wrong passphrase from a corrupt key file
+const HEADER_FILE: &str = "keystore.json";
+const KEY_EXTENSION: &str = "key";
+const CHECK_LABEL: &str = "keystore-check";
+const CHECK_PLAINTEXT: &[u8] = b"miden-zcash-bridge keystore v1";
+const NONCE_LEN: usize = 12;
+
+/// Secret the keystore is encrypted under, loaded from the environment
+///
+/// KEYSTORE_KEY         64 hex digits used as the encryption key as is, e.g. from a secrets manager
+/// KEYSTORE_PASSPHRASE  passphrase the encryption key is derived from with Argon2id
+///
+/// KEYSTORE_KEY wins when both are set.
+pub enum KeystoreSecret {
+    Passphrase(String),
+    Key([u8; 32]),
+}
+
+impl KeystoreSecret {
+    pub fn from_env() -> BridgeResult<Self> {
+        Self::from_vars("KEYSTORE_KEY", "KEYSTORE_PASSPHRASE")
+    }
+
+    /// Secret from other variables, e.g. the new one while re-encrypting
+    pub fn from_vars(key_var: &str, passphrase_var: &str) -> BridgeResult<Self> {
+        if let Some(key) = std::env::var(key_var).ok().filter(|key| !key.trim().is_empty()) {
+            return Self::parse_key(&key);
+        }
+        match std::env::var(passphrase_var) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(KeystoreSecret::Passphrase(passphrase)),
+            _ => Err(BridgeError::Internal(format!("Set {} or {} to unlock the keystore", passphrase_var, key_var))),
+        }
+    }
+
+    /// A raw 32-byte key as hex, with or without 0x
+    pub fn parse_key(hex: &str) -> BridgeResult<Self> {
+        let hex = hex.trim();
+        let bytes = hex::decode(hex.strip_prefix("0x").unwrap_or(hex))
+            .map_err(|e| BridgeError::InvalidInput(format!("Keystore key is not hex: {}", e)))?;
+        let key = <[u8; 32]>::try_from(bytes.as_slice())
+            .map_err(|_| BridgeError::InvalidInput("Keystore key must be 32 bytes (64 hex digits)".to_string()))?;
+        Ok(KeystoreSecret::Key(key))
+    }
+}
+
+impl fmt::Debug for KeystoreSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreSecret::Passphrase(_) => f.write_str("KeystoreSecret::Passphrase(..)"),
+            KeystoreSecret::Key(_) => f.write_str("KeystoreSecret::Key(..)"),
+        }
+    }
+}
+
+/// Seals and opens keystore entries with ChaCha20-Poly1305
+pub struct KeystoreCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl KeystoreCipher {
+    /// Cipher for `secret`; the salt only matters for passphrases
+    pub fn new(secret: &KeystoreSecret, salt: &[u8]) -> BridgeResult<Self> {
+        let key = match secret {
+            KeystoreSecret::Key(key) => *key,
+            KeystoreSecret::Passphrase(passphrase) => {
+                let mut key = [0u8; 32];
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                    .map_err(|e| BridgeError::Internal(format!("Failed to derive keystore key: {}", e)))?;
+                key
+            }
+        };
+        Ok(Self { cipher: ChaCha20Poly1305::new(Key::from_slice(&key)) })
+    }
+
+    /// Random nonce followed by the ciphertext; `label` is authenticated too, so entries
+    /// can't be swapped between files
+    pub fn seal(&self, label: &str, plaintext: &[u8]) -> BridgeResult<Vec<u8>> {
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = self.cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: label.as_bytes() })
+            .map_err(|_| BridgeError::Internal(format!("Failed to encrypt keystore entry {}", label)))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    pub fn open(&self, label: &str, sealed: &[u8]) -> BridgeResult<Vec<u8>> {
+        if sealed.len() < NONCE_LEN {
+            return Err(BridgeError::Internal(format!("Keystore entry {} is truncated", label)));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: label.as_bytes() })
+            .map_err(|_| BridgeError::Internal(format!("Failed to decrypt keystore entry {}", label)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    salt: String,
+    check: String,
+}
+
+/// Falcon secret keys encrypted at rest, one `<public key commitment>.key` file per key
+///
+/// Keys are decrypted once when the keystore is opened and only kept in memory. Replaces
+/// `FilesystemKeyStore`, whose plain files `bridgectl key encrypt` migrates.
+pub struct EncryptedKeyStore {
+    dir: PathBuf,
+    cipher: KeystoreCipher,
+    keys: RwLock<BTreeMap<String, AuthSecretKey>>,
+}
+
+impl EncryptedKeyStore {
+    /// Start an empty keystore in `dir`
+    pub fn create(dir: &Path, secret: &KeystoreSecret) -> BridgeResult<Self> {
+        if Self::is_encrypted(dir) {
+            return Err(BridgeError::Conflict(format!("{} already holds an encrypted keystore", dir.display())));
+        }
+        std::fs::create_dir_all(dir)
+            .map_err(|e| BridgeError::Internal(format!("Failed to create keystore directory {}: {}", dir.display(), e)))?;
+
+        let salt: [u8; 16] = rand::random();
+        let cipher = KeystoreCipher::new(secret, &salt)?;
+        let header = Header {
+            version: 1,
+            salt: hex::encode(salt),
+            check: hex::encode(cipher.seal(CHECK_LABEL, CHECK_PLAINTEXT)?),
+        };
+        let header = serde_json::to_vec_pretty(&header)
+            .map_err(|e| BridgeError::Internal(format!("Failed to serialize keystore header: {}", e)))?;
+        write_file(&dir.join(HEADER_FILE), &header)?;
+
+        Ok(Self { dir: dir.to_path_buf(), cipher, keys: RwLock::new(BTreeMap::new()) })
+    }
+
+    /// Unlock the keystore in `dir`, decrypting every key
+    pub fn open(dir: &Path, secret: &KeystoreSecret) -> BridgeResult<Self> {
+        let header = read_file(&dir.join(HEADER_FILE))?;
+        let header: Header = serde_json::from_slice(&header)
+            .map_err(|e| BridgeError::Internal(format!("Invalid keystore header in {}: {}", dir.display(), e)))?;
+        if header.version != 1 {
+            return Err(BridgeError::Internal(format!("Unsupported keystore version {}", header.version)));
+        }
+
+        let salt = decode_hex(&header.salt, HEADER_FILE)?;
+        let cipher = KeystoreCipher::new(secret, &salt)?;
+        if cipher.open(CHECK_LABEL, &decode_hex(&header.check, HEADER_FILE)?).ok().as_deref() != Some(CHECK_PLAINTEXT) {
+            return Err(BridgeError::Unauthorized(format!("Wrong passphrase or key for the keystore in {}", dir.display())));
+        }
+
+        let mut keys = BTreeMap::new();
+        for path in list_dir(dir)? {
+            if path.extension().and_then(|e| e.to_str()) != Some(KEY_EXTENSION) {
+                continue;
+            }
+            let label = file_stem(&path);
+            let sealed = decode_hex(&String::from_utf8_lossy(&read_file(&path)?), &label)?;
+            let key = AuthSecretKey::read_from_bytes(&cipher.open(&label, &sealed)?)
+                .map_err(|e| BridgeError::Internal(format!("Invalid secret key in {}: {}", path.display(), e)))?;
+            keys.insert(label, key);
+        }
+
+        Ok(Self { dir: dir.to_path_buf(), cipher, keys: RwLock::new(keys) })
+    }
+
+    /// Whether `dir` holds an encrypted keystore
+    pub fn is_encrypted(dir: &Path) -> bool {
+        dir.join(HEADER_FILE).is_file()
+    }
+
+    /// Files in `dir` that aren't part of an encrypted keystore: `FilesystemKeyStore` keys
+    pub fn plaintext_files(dir: &Path) -> BridgeResult<Vec<PathBuf>> {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        Ok(list_dir(dir)?
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && path.file_name().and_then(|n| n.to_str()) != Some(HEADER_FILE)
+                    && !matches!(path.extension().and_then(|e| e.to_str()), Some(KEY_EXTENSION | "tmp"))
+            })
+            .collect())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Encrypt and store a key
+    pub fn add_key(&self, key: &AuthSecretKey) -> BridgeResult<()> {
+        let label = key_label(Word::from(key.public_key().to_commitment()));
+        let sealed = self.cipher.seal(&label, &key.to_bytes())?;
+        write_file(&self.dir.join(format!("{}.{}", label, KEY_EXTENSION)), hex::encode(sealed).as_bytes())?;
+
+        self.keys.write()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock keystore: {}", e)))?
+            .insert(label, key.clone());
+        Ok(())
+    }
+
+    /// Secret key for a public key commitment
+    pub fn get_key(&self, pub_key: Word) -> BridgeResult<Option<AuthSecretKey>> {
+        Ok(self.keys.read()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock keystore: {}", e)))?
+            .get(&key_label(pub_key))
+            .cloned())
+    }
+
+    /// Public key commitments of the stored keys, as hex
+    pub fn public_keys(&self) -> BridgeResult<Vec<String>> {
+        Ok(self.keys.read()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock keystore: {}", e)))?
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    /// Encrypt the keys of a `FilesystemKeyStore` directory into this keystore
+    ///
+    /// Returns the files imported; deleting them is left to the caller, once it has
+    /// checked the encrypted copies open.
+    pub fn import_plaintext(&self, dir: &Path) -> BridgeResult<Vec<PathBuf>> {
+        let files = Self::plaintext_files(dir)?;
+        for path in &files {
+            let contents = read_file(path)?;
+            let bytes = decode_hex(String::from_utf8_lossy(&contents).trim(), &path.display().to_string())?;
+            let key = AuthSecretKey::read_from_bytes(&bytes)
+                .map_err(|e| BridgeError::Internal(format!("{} is not a secret key: {}", path.display(), e)))?;
+            self.add_key(&key)?;
+        }
+        Ok(files)
+    }
+
+    /// Re-encrypt every key under a new secret
+    ///
+    /// The new keystore is written next to this one and swapped in with two renames; if the
+    /// process dies in between, the old keystore is left in `<dir>.old`.
+    pub fn rekey(self, new_secret: &KeystoreSecret) -> BridgeResult<Self> {
+        // Unmigrated plain keys would be lost in the swap
+        if !Self::plaintext_files(&self.dir)?.is_empty() {
+            return Err(BridgeError::Conflict(format!("{} still holds unencrypted keys; run `bridgectl key encrypt` first", self.dir.display())));
+        }
+
+        let staging = sibling(&self.dir, "rekey");
+        let retired = sibling(&self.dir, "old");
+        if staging.exists() || retired.exists() {
+            return Err(BridgeError::Conflict(format!(
+                "{} or {} is left over from an interrupted rekey; restore or remove it first",
+                staging.display(),
+                retired.display()
+            )));
+        }
+
+        let rekeyed = Self::create(&staging, new_secret)?;
+        for key in self.keys.read()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock keystore: {}", e)))?
+            .values()
+        {
+            rekeyed.add_key(key)?;
+        }
+
+        rename(&self.dir, &retired)?;
+        rename(&staging, &self.dir)?;
+        std::fs::remove_dir_all(&retired)
+            .map_err(|e| BridgeError::Internal(format!("Failed to remove {}: {}", retired.display(), e)))?;
+
+        Ok(Self { dir: self.dir, cipher: rekeyed.cipher, keys: rekeyed.keys })
+    }
+}
+
+impl TransactionAuthenticator for EncryptedKeyStore {
+    async fn get_signature(
+        &self,
+        pub_key: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        let pub_key = Word::from(pub_key);
+        let key = self.get_key(pub_key)
+            .map_err(|e| AuthenticationError::other(e.to_string()))?
+            .ok_or_else(|| AuthenticationError::UnknownPublicKey(format!("public key {} not found", pub_key.to_hex())))?;
+
+        Ok(key.sign(signing_inputs.to_commitment()))
+    }
+}
+
+/// Keystores unlocked by this process, by directory
+static UNLOCKED: OnceLock<Mutex<HashMap<PathBuf, Arc<EncryptedKeyStore>>>> = OnceLock::new();
+
+/// Unlock the keystore in `dir` with the secret from the environment, once per process
+///
+/// An empty or missing directory gets a new encrypted keystore. A directory still holding
+/// `FilesystemKeyStore` keys is refused until they are migrated with `bridgectl key encrypt`.
+pub fn unlock(dir: &Path) -> BridgeResult<Arc<EncryptedKeyStore>> {
+    let mut unlocked = UNLOCKED.get_or_init(Default::default)
+        .lock()
+        .map_err(|e| BridgeError::Internal(format!("Failed to lock keystore cache: {}", e)))?;
+    if let Some(keystore) = unlocked.get(dir) {
+        return Ok(keystore.clone());
+    }
+
+    // Plain keys left next to encrypted ones would otherwise go unnoticed
+    if !EncryptedKeyStore::plaintext_files(dir)?.is_empty() {
+        return Err(BridgeError::Internal(format!(
+            "{} holds unencrypted keys; encrypt them with `bridgectl key encrypt --keystore {}`",
+            dir.display(),
+            dir.display()
+        )));
+    }
+
+    let secret = KeystoreSecret::from_env()?;
+    let keystore = if EncryptedKeyStore::is_encrypted(dir) {
+        EncryptedKeyStore::open(dir, &secret)?
+    } else {
+        EncryptedKeyStore::create(dir, &secret)?
+    };
+
+    let keystore = Arc::new(keystore);
+    unlocked.insert(dir.to_path_buf(), keystore.clone());
+    Ok(keystore)
+}
+
+fn key_label(pub_key: Word) -> String {
+    pub_key.to_hex().trim_start_matches("0x").to_string()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string()
+}
+
+fn sibling(dir: &Path, suffix: &str) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", suffix));
+    dir.with_file_name(name)
+}
+
+fn decode_hex(hex: &str, what: &str) -> BridgeResult<Vec<u8>> {
+    hex::decode(hex.trim())
+        .map_err(|e| BridgeError::Internal(format!("Invalid hex in keystore entry {}: {}", what, e)))
+}
+
+fn list_dir(dir: &Path) -> BridgeResult<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| BridgeError::Internal(format!("Failed to read keystore directory {}: {}", dir.display(), e)))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| BridgeError::Internal(format!("Failed to read keystore directory {}: {}", dir.display(), e)))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_file(path: &Path) -> BridgeResult<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| BridgeError::Internal(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// Written to a temporary file and renamed, so a crash never leaves half a key
+fn write_file(path: &Path, contents: &[u8]) -> BridgeResult<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents)
+        .map_err(|e| BridgeError::Internal(format!("Failed to write {}: {}", tmp.display(), e)))?;
+    rename(&tmp, path)
+}
+
+fn rename(from: &Path, to: &Path) -> BridgeResult<()> {
+    std::fs::rename(from, to)
+        .map_err(|e| BridgeError::Internal(format!("Failed to rename {} to {}: {}", from.display(), to.display(), e)))
+}
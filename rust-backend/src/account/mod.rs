@@ -1,3 +1,7 @@
 pub mod create;
+pub mod faucet_auth;
+pub mod keystore;
+#[cfg(unix)]
+pub mod remote_signer;
+pub mod signer;
 pub mod token_wrapper;
-
@@ -0,0 +1,409 @@
+use crate::account::keystore::EncryptedKeyStore;
+use crate::db::audit::GENESIS_HASH;
+use crate::error::{BridgeError, BridgeResult};
+use miden_client::auth::{
+    AuthenticationError, PublicKeyCommitment, Signature, SigningInputs, TransactionAuthenticator,
+};
+use miden_objects::account::AccountId;
+use miden_objects::asset::Asset;
+use miden_objects::transaction::TransactionSummary;
+use miden_objects::utils::{Deserializable, Serializable};
+use miden_objects::Word;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader as AsyncBufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{info, warn};
+
+/// Most a connection may send the signer
+const MAX_REQUEST_BYTES: u64 = 1024 * 1024;
+
+/// Signing request, one JSON line on the socket
+///
+/// Only transaction summaries are signed: the signer's policy is checked against the
+/// summary itself, not against anything the caller claims about it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub pub_key: String, // public key commitment, hex
+    pub summary: String, // serialized TransactionSummary, hex
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignResponse {
+    pub success: bool,
+    pub signature: Option<String>, // serialized Signature, hex
+    pub error: Option<String>,
+}
+
+/// `TransactionAuthenticator` that forwards signing to a signer process over a Unix socket
+///
+/// The keys stay with the signer process (`bridgectl signer serve`), so the host running
+/// the API and relayers holds no faucet key material.
+pub struct RemoteSigner {
+    socket: PathBuf,
+    timeout: Duration,
+}
+
+impl RemoteSigner {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket, timeout: Duration::from_secs(30) }
+    }
+
+    async fn call(&self, request: &SignRequest) -> BridgeResult<SignResponse> {
+        let exchange = async {
+            let mut stream = UnixStream::connect(&self.socket).await
+                .map_err(|e| BridgeError::Internal(format!("Failed to connect to signer at {}: {}", self.socket.display(), e)))?;
+
+            let mut line = serde_json::to_vec(request)
+                .map_err(|e| BridgeError::Internal(format!("Failed to serialize signing request: {}", e)))?;
+            line.push(b'\n');
+            stream.write_all(&line).await
+                .map_err(|e| BridgeError::Internal(format!("Failed to send signing request: {}", e)))?;
+
+            let mut response = String::new();
+            AsyncBufReader::new(stream).read_line(&mut response).await
+                .map_err(|e| BridgeError::Internal(format!("Failed to read signer response: {}", e)))?;
+            serde_json::from_str(&response)
+                .map_err(|e| BridgeError::Internal(format!("Invalid signer response: {}", e)))
+        };
+
+        tokio::time::timeout(self.timeout, exchange).await
+            .map_err(|_| BridgeError::Internal(format!("Signer at {} did not answer in time", self.socket.display())))?
+    }
+}
+
+impl TransactionAuthenticator for RemoteSigner {
+    async fn get_signature(
+        &self,
+        pub_key: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        let SigningInputs::TransactionSummary(summary) = signing_inputs else {
+            return Err(AuthenticationError::other("the remote signer only signs transaction summaries".to_string()));
+        };
+        let request = SignRequest {
+            pub_key: Word::from(pub_key).to_hex(),
+            summary: hex::encode(summary.to_bytes()),
+        };
+
+        let response = self.call(&request).await
+            .map_err(|e| AuthenticationError::other(e.to_string()))?;
+        if !response.success {
+            return Err(AuthenticationError::other(format!(
+                "signer refused: {}",
+                response.error.unwrap_or_default()
+            )));
+        }
+
+        let signature = response.signature
+            .ok_or_else(|| AuthenticationError::other("signer returned no signature".to_string()))?;
+        let bytes = hex::decode(&signature)
+            .map_err(|e| AuthenticationError::other(format!("invalid signature from signer: {}", e)))?;
+        Signature::read_from_bytes(&bytes)
+            .map_err(|e| AuthenticationError::other(format!("invalid signature from signer: {}", e)))
+    }
+}
+
+/// What the signer process agrees to sign, loaded from the environment
+///
+/// SIGNER_ALLOWED_ACCOUNTS  comma-separated account ids (hex) whose transactions it signs
+///                          (`bridgectl signer serve` defaults to the bridge's wTAZ faucet)
+/// SIGNER_MAX_AMOUNT        most a transaction may send out in notes, summed over fungible
+///                          assets in base units (default: no limit)
+/// SIGNER_MAX_PER_HOUR      signatures per rolling hour (default 60)
+#[derive(Debug, Clone)]
+pub struct SignerPolicy {
+    pub allowed_accounts: Vec<AccountId>,
+    pub max_amount: Option<u64>,
+    pub max_per_hour: u32,
+}
+
+impl SignerPolicy {
+    pub fn from_env() -> BridgeResult<Self> {
+        let allowed_accounts = std::env::var("SIGNER_ALLOWED_ACCOUNTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|account| !account.is_empty())
+            .map(|account| {
+                let hex = account.strip_prefix("0x").unwrap_or(account);
+                AccountId::from_hex(&format!("0x{}", hex))
+                    .map_err(|e| BridgeError::InvalidInput(format!("Invalid account in SIGNER_ALLOWED_ACCOUNTS {}: {}", account, e)))
+            })
+            .collect::<BridgeResult<Vec<_>>>()?;
+
+        let max_amount = std::env::var("SIGNER_MAX_AMOUNT").ok()
+            .map(|amount| amount.trim().parse::<u64>()
+                .map_err(|e| BridgeError::InvalidInput(format!("Invalid SIGNER_MAX_AMOUNT: {}", e))))
+            .transpose()?;
+
+        let max_per_hour = std::env::var("SIGNER_MAX_PER_HOUR").ok()
+            .map(|count| count.trim().parse::<u32>()
+                .map_err(|e| BridgeError::InvalidInput(format!("Invalid SIGNER_MAX_PER_HOUR: {}", e))))
+            .transpose()?
+            .unwrap_or(60);
+
+        Ok(Self { allowed_accounts, max_amount, max_per_hour })
+    }
+}
+
+/// Applies the policy, remembering recent signatures for the rate limit
+pub struct PolicyGuard {
+    policy: SignerPolicy,
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl PolicyGuard {
+    pub fn new(policy: SignerPolicy) -> Self {
+        Self { policy, recent: Mutex::new(VecDeque::new()) }
+    }
+
+    /// Admit a transaction of `account` sending out `amount`, or say why not
+    ///
+    /// Admitted transactions count against the hourly limit.
+    pub fn admit(&self, account: AccountId, amount: u64) -> Result<(), String> {
+        if !self.policy.allowed_accounts.contains(&account) {
+            return Err(format!("account {} is not allowed", account.to_hex()));
+        }
+        if let Some(max_amount) = self.policy.max_amount
+            && amount > max_amount
+        {
+            return Err(format!("amount {} is over the limit of {}", amount, max_amount));
+        }
+
+        let mut recent = self.recent.lock().unwrap();
+        let now = Instant::now();
+        while recent.front().is_some_and(|at| now.duration_since(*at) >= Duration::from_secs(60 * 60)) {
+            recent.pop_front();
+        }
+        if recent.len() >= self.policy.max_per_hour as usize {
+            return Err(format!("over the limit of {} signatures per hour", self.policy.max_per_hour));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+/// One line of the signer's audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignerAuditEntry {
+    pub at: i64,
+    pub pub_key: String,
+    pub account: Option<String>,
+    pub amount: Option<u64>,
+    pub message: Option<String>, // summary commitment that was signed or refused
+    pub signed: bool,
+    pub reason: Option<String>,
+    pub prev_hash: String, // sha256 of the previous line
+}
+
+/// Append-only JSON lines log of every signing request, chained by hash
+///
+/// Each line carries the hash of the line before it, so edits and deletions show up in
+/// `SignerAudit::verify`.
+pub struct SignerAudit {
+    path: PathBuf,
+    last_hash: Mutex<String>,
+}
+
+impl SignerAudit {
+    /// Open the log at `path`, checking the existing entries
+    pub fn open(path: &Path) -> BridgeResult<Self> {
+        let last_hash = if path.exists() {
+            Self::verify(path)?.1
+        } else {
+            GENESIS_HASH.to_string()
+        };
+        Ok(Self { path: path.to_path_buf(), last_hash: Mutex::new(last_hash) })
+    }
+
+    /// Append an entry, filling in `prev_hash`
+    pub fn append(&self, mut entry: SignerAuditEntry) -> BridgeResult<()> {
+        let mut last_hash = self.last_hash.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock signer audit log: {}", e)))?;
+        entry.prev_hash = last_hash.clone();
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| BridgeError::Internal(format!("Failed to serialize audit entry: {}", e)))?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| BridgeError::Internal(format!("Failed to open {}: {}", self.path.display(), e)))?;
+        writeln!(file, "{}", line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| BridgeError::Internal(format!("Failed to write {}: {}", self.path.display(), e)))?;
+
+        *last_hash = hex::encode(Sha256::digest(line.as_bytes()));
+        Ok(())
+    }
+
+    /// Check the hash chain of the log at `path`, returning its entry count and last hash
+    pub fn verify(path: &Path) -> BridgeResult<(usize, String)> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| BridgeError::Internal(format!("Failed to open {}: {}", path.display(), e)))?;
+
+        let mut count = 0;
+        let mut last_hash = GENESIS_HASH.to_string();
+        for line in BufReader::new(file).lines() {
+            let line = line
+                .map_err(|e| BridgeError::Internal(format!("Failed to read {}: {}", path.display(), e)))?;
+            let entry: SignerAuditEntry = serde_json::from_str(&line)
+                .map_err(|e| BridgeError::Internal(format!("Invalid entry {} in {}: {}", count + 1, path.display(), e)))?;
+            if entry.prev_hash != last_hash {
+                return Err(BridgeError::Internal(format!(
+                    "Signer audit log {} is broken at entry {}: it doesn't follow the entry before it",
+                    path.display(),
+                    count + 1
+                )));
+            }
+            last_hash = hex::encode(Sha256::digest(line.as_bytes()));
+            count += 1;
+        }
+        Ok((count, last_hash))
+    }
+}
+
+/// The signer process: signs transaction summaries its policy admits, logging every request
+pub struct SignerServer {
+    keystore: Arc<EncryptedKeyStore>,
+    guard: PolicyGuard,
+    audit: SignerAudit,
+}
+
+impl SignerServer {
+    pub fn new(keystore: Arc<EncryptedKeyStore>, policy: SignerPolicy, audit: SignerAudit) -> Self {
+        Self { keystore, guard: PolicyGuard::new(policy), audit }
+    }
+
+    /// Accept connections on `socket` until the process stops
+    ///
+    /// The socket is only accessible to the signer's own user; give the API and relayers
+    /// access through a shared directory or group as needed.
+    pub async fn serve(self: Arc<Self>, socket: &Path) -> BridgeResult<()> {
+        // A socket left behind by an earlier run would make bind fail
+        if socket.exists() {
+            std::fs::remove_file(socket)
+                .map_err(|e| BridgeError::Internal(format!("Failed to remove stale socket {}: {}", socket.display(), e)))?;
+        }
+        let listener = UnixListener::bind(socket)
+            .map_err(|e| BridgeError::Internal(format!("Failed to bind {}: {}", socket.display(), e)))?;
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| BridgeError::Internal(format!("Failed to restrict {}: {}", socket.display(), e)))?;
+        }
+        info!(socket = %socket.display(), "Signer listening");
+
+        loop {
+            let (stream, _) = listener.accept().await
+                .map_err(|e| BridgeError::Internal(format!("Failed to accept connection: {}", e)))?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.serve_connection(stream).await {
+                    warn!(error = %e, "Signer connection failed");
+                }
+            });
+        }
+    }
+
+    async fn serve_connection(&self, stream: UnixStream) -> BridgeResult<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = AsyncBufReader::new(reader.take(MAX_REQUEST_BYTES)).lines();
+
+        while let Some(line) = lines.next_line().await
+            .map_err(|e| BridgeError::Internal(format!("Failed to read request: {}", e)))?
+        {
+            let response = match serde_json::from_str::<SignRequest>(&line) {
+                Ok(request) => self.handle(&request).await,
+                Err(e) => SignResponse { success: false, signature: None, error: Some(format!("invalid request: {}", e)) },
+            };
+            let mut response = serde_json::to_vec(&response)
+                .map_err(|e| BridgeError::Internal(format!("Failed to serialize response: {}", e)))?;
+            response.push(b'\n');
+            writer.write_all(&response).await
+                .map_err(|e| BridgeError::Internal(format!("Failed to write response: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Sign a request if the policy admits it; every request is logged, and nothing is
+    /// signed if logging fails
+    pub async fn handle(&self, request: &SignRequest) -> SignResponse {
+        let mut entry = SignerAuditEntry {
+            at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            pub_key: request.pub_key.clone(),
+            account: None,
+            amount: None,
+            message: None,
+            signed: false,
+            reason: None,
+            prev_hash: String::new(),
+        };
+
+        let result = self.sign(request, &mut entry).await;
+        entry.signed = result.is_ok();
+        entry.reason = result.as_ref().err().cloned();
+        if let Err(e) = self.audit.append(entry) {
+            warn!(error = %e, "Refusing to sign without an audit entry");
+            return SignResponse { success: false, signature: None, error: Some("signer audit log unavailable".to_string()) };
+        }
+
+        match result {
+            Ok(signature) => SignResponse { success: true, signature: Some(signature), error: None },
+            Err(reason) => {
+                info!(reason = %reason, "Refused signing request");
+                SignResponse { success: false, signature: None, error: Some(reason) }
+            }
+        }
+    }
+
+    async fn sign(&self, request: &SignRequest, entry: &mut SignerAuditEntry) -> Result<String, String> {
+        let pub_key = Word::try_from(format!("0x{}", request.pub_key.trim_start_matches("0x")).as_str())
+            .map_err(|e| format!("invalid public key commitment: {}", e))?;
+        let summary = hex::decode(&request.summary)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| TransactionSummary::read_from_bytes(&bytes).map_err(|e| e.to_string()))
+            .map_err(|e| format!("invalid transaction summary: {}", e))?;
+
+        let account = summary.account_delta().id();
+        let amount = outgoing_amount(&summary)?;
+        entry.account = Some(account.to_hex());
+        entry.amount = Some(amount);
+        entry.message = Some(summary.to_commitment().to_hex());
+
+        self.guard.admit(account, amount)?;
+
+        let signature = self.keystore
+            .get_signature(PublicKeyCommitment::from(pub_key), &SigningInputs::TransactionSummary(Box::new(summary)))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(hex::encode(signature.to_bytes()))
+    }
+}
+
+/// Fungible amount the transaction sends out in notes
+///
+/// Output notes whose assets the summary doesn't carry can't be checked, so they are refused.
+fn outgoing_amount(summary: &TransactionSummary) -> Result<u64, String> {
+    let mut amount = 0u64;
+    for note in summary.output_notes().iter() {
+        let assets = note.assets()
+            .ok_or_else(|| format!("output note {} has no assets to check", note.id().to_hex()))?;
+        for asset in assets.iter() {
+            if let Asset::Fungible(asset) = asset {
+                amount = amount.checked_add(asset.amount())
+                    .ok_or_else(|| "output amount overflows".to_string())?;
+            }
+        }
+    }
+    Ok(amount)
+}
@@ -0,0 +1,55 @@
+use crate::account::keystore::{self, EncryptedKeyStore};
+#[cfg(unix)]
+use crate::account::remote_signer::RemoteSigner;
+use crate::error::BridgeResult;
+use miden_client::auth::{
+    AuthenticationError, PublicKeyCommitment, Signature, SigningInputs, TransactionAuthenticator,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+/// What signs the bridge's faucet transactions
+///
+/// SIGNER_SOCKET  Unix socket of a signer process (`bridgectl signer serve`) holding the
+///                faucet keys; unset signs with the local encrypted keystore
+pub enum BridgeSigner {
+    Local(Arc<EncryptedKeyStore>),
+    #[cfg(unix)]
+    Remote(RemoteSigner),
+}
+
+impl BridgeSigner {
+    /// SIGNER_SOCKET, if set; this process then holds no keys of its own
+    pub fn remote_socket() -> Option<String> {
+        std::env::var("SIGNER_SOCKET")
+            .ok()
+            .map(|socket| socket.trim().to_string())
+            .filter(|socket| !socket.is_empty())
+    }
+
+    /// The signer process if SIGNER_SOCKET is set, otherwise the keystore in `keystore_dir`
+    pub fn open(keystore_dir: &Path) -> BridgeResult<Arc<Self>> {
+        match Self::remote_socket() {
+            #[cfg(unix)]
+            Some(socket) => Ok(Arc::new(BridgeSigner::Remote(RemoteSigner::new(socket.into())))),
+            #[cfg(not(unix))]
+            Some(_) => Err(crate::error::BridgeError::Internal("SIGNER_SOCKET needs Unix sockets, which this platform lacks".to_string())),
+            // A missing keystore is started empty, like keystore::unlock does for every caller
+            None => Ok(Arc::new(BridgeSigner::Local(keystore::unlock(keystore_dir)?))),
+        }
+    }
+}
+
+impl TransactionAuthenticator for BridgeSigner {
+    async fn get_signature(
+        &self,
+        pub_key: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        match self {
+            BridgeSigner::Local(keystore) => keystore.get_signature(pub_key, signing_inputs).await,
+            #[cfg(unix)]
+            BridgeSigner::Remote(signer) => signer.get_signature(pub_key, signing_inputs).await,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+
+/// Who is calling the API
+///
+/// public: anyone (deposits, withdrawals, balances)
+/// operator: bridge operators (fee ledger and other operational endpoints)
+/// admin: faucet creation and minting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Public,
+    Operator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Public => "public",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+/// API keys allowed to call operator/admin endpoints
+///
+/// Loaded from BRIDGE_OPERATOR_API_KEYS and BRIDGE_ADMIN_API_KEYS (comma-separated).
+/// Keys are sent as `X-API-Key: <key>` or `Authorization: Bearer <key>`.
+pub struct ApiKeys {
+    keys: Vec<(String, Role)>,
+}
+
+impl ApiKeys {
+    pub fn from_env() -> Self {
+        let mut keys = Vec::new();
+
+        for (var, role) in [
+            ("BRIDGE_OPERATOR_API_KEYS", Role::Operator),
+            ("BRIDGE_ADMIN_API_KEYS", Role::Admin),
+        ] {
+            if let Ok(value) = std::env::var(var) {
+                for key in value.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+                    keys.push((key.to_string(), role));
+                }
+            }
+        }
+
+        Self { keys }
+    }
+
+    /// Number of keys configured for a role
+    pub fn count(&self, role: Role) -> usize {
+        self.keys.iter().filter(|(_, r)| *r == role).count()
+    }
+
+    /// Role granted by an API key (public if the key is unknown)
+    pub fn role_for(&self, key: &str) -> Role {
+        self.keys
+            .iter()
+            .filter(|(known, _)| constant_time_eq(known.as_bytes(), key.as_bytes()))
+            .map(|(_, role)| *role)
+            .max()
+            .unwrap_or(Role::Public)
+    }
+}
+
+/// Compare keys without leaking how much of them matched through timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Production mode (BRIDGE_MODE=production) leaves operator and admin endpoints unmounted
+pub fn production_mode() -> bool {
+    std::env::var("BRIDGE_MODE")
+        .map(|mode| mode.eq_ignore_ascii_case("production"))
+        .unwrap_or(false)
+}
+
+/// Extract the API key from the request headers
+fn request_api_key<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    request
+        .headers()
+        .get_one("X-API-Key")
+        .or_else(|| {
+            request
+                .headers()
+                .get_one("Authorization")
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+}
+
+/// Why the auth guards rejected a request, cached on the request for the error catchers
+pub struct AuthFailure(pub Option<String>);
+
+fn reject<T>(request: &Request<'_>, status: Status, message: String) -> Outcome<T, String> {
+    request.local_cache(|| AuthFailure(Some(message.clone())));
+    Outcome::Error((status, message))
+}
+
+/// Authenticate a request against the managed `ApiKeys` and require at least `required`
+fn authorize(request: &Request<'_>, required: Role) -> Outcome<Role, String> {
+    let Some(api_keys) = request.rocket().state::<ApiKeys>() else {
+        return reject(request, Status::InternalServerError, "API keys are not configured".to_string());
+    };
+
+    let Some(key) = request_api_key(request) else {
+        return reject(request, Status::Unauthorized, "Missing API key".to_string());
+    };
+
+    let role = api_keys.role_for(key);
+    if role >= required {
+        Outcome::Success(role)
+    } else if role == Role::Public {
+        reject(request, Status::Unauthorized, "Invalid API key".to_string())
+    } else {
+        reject(
+            request,
+            Status::Forbidden,
+            format!("This endpoint requires the {} role", required.as_str()),
+        )
+    }
+}
+
+/// Request guard for operator endpoints (admins are operators too)
+pub struct OperatorAuth(pub Role);
+
+/// Request guard for admin endpoints
+pub struct AdminAuth;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for OperatorAuth {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Role::Operator).map(OperatorAuth)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminAuth {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Role::Admin).map(|_| AdminAuth)
+    }
+}
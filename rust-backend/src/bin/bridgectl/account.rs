@@ -0,0 +1,37 @@
+use crate::{parse_account_id, CliResult, Config};
+use clap::Subcommand;
+use miden_objects::address::NetworkId;
+use rust_backend::account::create::create_wallet_account;
+
+#[derive(Subcommand)]
+pub enum AccountCommand {
+    /// Create a wallet account in the user wallet store
+    Create,
+}
+
+pub async fn run(command: AccountCommand, config: &Config) -> CliResult<()> {
+    match command {
+        AccountCommand::Create => {
+            let (keystore_path, store_path) = config.wallet_paths();
+            if let Some(parent) = store_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            config.progress("Creating wallet account...");
+            let bech32 = create_wallet_account(&keystore_path, &store_path, &config.rpc_url).await?;
+            let account_id = parse_account_id(&bech32)?;
+
+            config.output(
+                serde_json::json!({
+                    "account_id": account_id.to_hex(),
+                    "bech32": account_id.to_bech32(NetworkId::Testnet),
+                }),
+                || {
+                    println!("✅ Wallet account created!");
+                    println!("   Hex: {}", account_id.to_hex());
+                    println!("   Bech32: {}", bech32);
+                },
+            )
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::db::audit::{AuditEntry, ChainVerifier};
+use rust_backend::db::BridgeStore;
+use std::io::Write;
+
+/// Entries read from the ledger per query
+const PAGE_SIZE: u32 = 500;
+
+#[derive(Subcommand)]
+pub enum AuditCommand {
+    /// Check the hash chain of the whole ledger; exits non-zero if it was tampered with
+    Verify {
+        /// Head hash recorded earlier (e.g. off-site), to also catch entries cut from the end
+        #[arg(long)]
+        head: Option<String>,
+    },
+    /// Write the verified ledger to stdout
+    Export {
+        /// json or csv
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+}
+
+pub fn run(command: AuditCommand, config: &Config) -> CliResult<()> {
+    let db = config.db()?;
+
+    match command {
+        AuditCommand::Verify { head } => {
+            let verifier = verify_chain(db.as_ref(), |_| Ok(()))
+                .map_err(|e| format!("Audit ledger has been tampered with: {}", e))?;
+
+            if let Some(expected) = &head
+                && !head_reached(db.as_ref(), expected)?
+            {
+                return Err(format!("Audit ledger no longer contains head {}; entries were removed", expected).into());
+            }
+
+            config.output(
+                serde_json::json!({ "entries": verifier.len(), "head": verifier.head() }),
+                || {
+                    println!("✅ Audit ledger intact: {} entries", verifier.len());
+                    println!("   Head: {}", verifier.head());
+                },
+            )
+        }
+        AuditCommand::Export { format } => {
+            let mut out = std::io::stdout().lock();
+
+            // The chain is verified as it is written, so a broken export fails instead of looking complete
+            let verifier = match format.as_str() {
+                "json" => {
+                    let mut entries = Vec::new();
+                    let verifier = verify_chain(db.as_ref(), |entry| {
+                        entries.push(entry.clone());
+                        Ok(())
+                    });
+                    if let Ok(verifier) = &verifier {
+                        serde_json::to_writer_pretty(&mut out, &serde_json::json!({
+                            "head": verifier.head(),
+                            "entries": entries,
+                        }))?;
+                        writeln!(out)?;
+                    }
+                    verifier
+                }
+                "csv" => {
+                    writeln!(out, "id,action,inputs_digest,result,actor,created_at,prev_hash,hash")?;
+                    verify_chain(db.as_ref(), |entry| {
+                        writeln!(
+                            out,
+                            "{},{},{},{},{},{},{},{}",
+                            entry.id,
+                            entry.action.as_str(),
+                            entry.inputs_digest,
+                            csv_field(entry.result.as_deref().unwrap_or("")),
+                            csv_field(&entry.actor),
+                            entry.created_at,
+                            entry.prev_hash,
+                            entry.hash,
+                        )
+                        .map_err(|e| e.to_string())
+                    })
+                }
+                other => return Err(format!("Unknown export format: {} (use json or csv)", other).into()),
+            }
+            .map_err(|e| format!("Audit ledger has been tampered with: {}", e))?;
+
+            // stdout carries the export, so the summary goes to stderr
+            if !config.json {
+                eprintln!("✅ Exported {} entries, head {}", verifier.len(), verifier.head());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check every entry in order, handing each one to `visit` once it has been verified
+fn verify_chain(
+    db: &dyn BridgeStore,
+    mut visit: impl FnMut(&AuditEntry) -> Result<(), String>,
+) -> Result<ChainVerifier, String> {
+    let mut verifier = ChainVerifier::new();
+    let mut after_id = 0;
+
+    loop {
+        let page = db.audit().entries(after_id, PAGE_SIZE).map_err(|e| e.to_string())?;
+        for entry in &page {
+            verifier.check(entry)?;
+            visit(entry)?;
+            after_id = entry.id;
+        }
+        if page.len() < PAGE_SIZE as usize {
+            return Ok(verifier);
+        }
+    }
+}
+
+/// Whether an entry with this hash is still in the ledger
+fn head_reached(db: &dyn BridgeStore, expected: &str) -> CliResult<bool> {
+    let mut after_id = 0;
+
+    loop {
+        let page = db.audit().entries(after_id, PAGE_SIZE)?;
+        if page.iter().any(|entry| entry.hash == expected) {
+            return Ok(true);
+        }
+        match page.last() {
+            Some(last) if page.len() == PAGE_SIZE as usize => after_id = last.id,
+            _ => return Ok(false),
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
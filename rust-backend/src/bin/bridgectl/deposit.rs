@@ -0,0 +1,76 @@
+use crate::refund::{self, RefundCommand};
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::db::events::BridgeEvent;
+
+#[derive(Subcommand)]
+pub enum DepositCommand {
+    /// List claimed deposit outputs
+    List {
+        /// Only reservations whose mint never finished (a process stopped mid-mint)
+        #[arg(long)]
+        pending: bool,
+    },
+    /// Show the lifecycle events of a deposit
+    Events {
+        /// Deposit output ("txid:pool:index")
+        key: String,
+    },
+    /// Rejected deposits and their refunds
+    #[command(subcommand)]
+    Refund(RefundCommand),
+}
+
+pub fn run(command: DepositCommand, config: &Config) -> CliResult<()> {
+    match command {
+        DepositCommand::List { pending } => {
+            let db = config.db()?;
+            let (claims, label) = if pending {
+                (db.deposits().pending_claims()?, "reserved")
+            } else {
+                (db.deposits().list_claims()?, "claimed")
+            };
+            let deposits: Vec<_> = claims.iter()
+                .map(|claim| serde_json::json!({
+                    "txid": claim.txid,
+                    "pool": claim.pool,
+                    "output_index": claim.output_index,
+                    "amount": claim.amount,
+                    "recipient_hash": claim.recipient_hash,
+                    "claimed_at": claim.claimed_at,
+                }))
+                .collect();
+
+            config.output(serde_json::json!({ "deposits": deposits }), || {
+                println!("{} {} deposit output(s)", claims.len(), label);
+                for claim in &claims {
+                    let output = match (&claim.pool, claim.output_index) {
+                        (Some(pool), Some(index)) => format!("{}:{}:{}", claim.txid, pool, index),
+                        _ => claim.txid.clone(),
+                    };
+                    println!("  {}  {} zatoshis  {} {}", output, claim.amount, label, format_time(claim.claimed_at));
+                }
+            })
+        }
+        DepositCommand::Events { key } => output_events(config, &config.db()?.events().deposit_events(&key, 0)?),
+        DepositCommand::Refund(command) => refund::run(command, config),
+    }
+}
+
+/// Print events oldest first
+pub(crate) fn output_events(config: &Config, events: &[BridgeEvent]) -> CliResult<()> {
+    config.output(serde_json::json!({ "events": events }), || {
+        if events.is_empty() {
+            println!("No events");
+        }
+        for event in events {
+            println!("  #{:<6} {}  {:<24} {}", event.id, format_time(event.created_at), event.event_type(), event.detail.as_deref().unwrap_or(""));
+        }
+    })
+}
+
+pub(crate) fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
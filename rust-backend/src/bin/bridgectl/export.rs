@@ -0,0 +1,82 @@
+use crate::{CliResult, Config};
+use clap::{Args, ValueEnum};
+use rust_backend::bridge::accounting::{export, ExportRange, ExportTable};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// First day, YYYY-MM-DD (UTC)
+    #[arg(long)]
+    from: String,
+    /// Last day, YYYY-MM-DD (UTC)
+    #[arg(long)]
+    to: String,
+    #[arg(long, value_enum, default_value = "json")]
+    format: Format,
+    /// Print only this table as CSV (deposits, withdrawals or daily)
+    #[arg(long)]
+    table: Option<String>,
+    /// Write the export into this directory instead of printing it (all three tables for CSV)
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Add recipient hashes and account ids of deposits
+    #[arg(long)]
+    include_private: bool,
+}
+
+pub fn run(args: ExportArgs, config: &Config) -> CliResult<()> {
+    let range = ExportRange::parse(&args.from, &args.to)?;
+    let ledger = export(&*config.db()?, range, args.include_private)?;
+
+    let Some(out) = args.out else {
+        match args.format {
+            Format::Json => println!("{}", serde_json::to_string_pretty(&ledger)?),
+            Format::Csv => {
+                let table = args.table.ok_or("Pick a table with --table, or write all of them with --out")?;
+                print!("{}", ledger.to_csv(ExportTable::parse(&table)?));
+            }
+        }
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(&out)?;
+    let mut files = Vec::new();
+    match args.format {
+        Format::Json => {
+            let path = out.join(format!("accounting_{}_{}.json", ledger.from, ledger.to));
+            std::fs::write(&path, serde_json::to_string_pretty(&ledger)?)?;
+            files.push(path);
+        }
+        Format::Csv => {
+            let tables = match &args.table {
+                Some(table) => vec![ExportTable::parse(table)?],
+                None => ExportTable::ALL.to_vec(),
+            };
+            for table in tables {
+                let path = out.join(format!("{}_{}_{}.csv", table.as_str(), ledger.from, ledger.to));
+                std::fs::write(&path, ledger.to_csv(table))?;
+                files.push(path);
+            }
+        }
+    }
+
+    config.output(
+        serde_json::json!({
+            "files": files,
+            "deposits": ledger.deposits.len(),
+            "withdrawals": ledger.withdrawals.len(),
+        }),
+        || {
+            println!("✅ Exported {} deposit(s) and {} withdrawal(s) from {} to {}", ledger.deposits.len(), ledger.withdrawals.len(), ledger.from, ledger.to);
+            for file in &files {
+                println!("   {}", file.display());
+            }
+        },
+    )
+}
@@ -0,0 +1,100 @@
+use crate::{parse_account_id, CliResult, Config};
+use clap::Subcommand;
+use miden_lib::account::faucets::FungibleFaucetExt;
+use miden_objects::address::NetworkId;
+use rust_backend::account::create::create_faucet_account;
+use rust_backend::bridge::deposit::get_or_create_zcash_faucet;
+use std::sync::Mutex;
+
+#[derive(Subcommand)]
+pub enum FaucetCommand {
+    /// Create a faucet account in the bridge store
+    Create {
+        /// Create the bridge's wTAZ faucet and register it in the bridge database (no-op if it exists)
+        #[arg(long, conflicts_with_all = ["symbol", "decimals", "max_supply"])]
+        bridge: bool,
+        #[arg(long, default_value = "MID")]
+        symbol: String,
+        #[arg(long, default_value_t = 8)]
+        decimals: u8,
+        #[arg(long, default_value_t = 1_000_000)]
+        max_supply: u64,
+    },
+    /// Show the bridge's wTAZ faucet and its issued supply
+    Show,
+}
+
+pub async fn run(command: FaucetCommand, config: &Config) -> CliResult<()> {
+    match command {
+        FaucetCommand::Create { bridge: true, .. } => {
+            let (keystore_path, store_path) = config.bridge_paths();
+            let db = Mutex::new(config.db()?);
+
+            config.progress("Getting or creating the wTAZ faucet...");
+            let faucet_id = get_or_create_zcash_faucet(keystore_path, store_path, &config.rpc_url, &db).await?;
+
+            config.output(
+                serde_json::json!({
+                    "faucet_id": faucet_id.to_hex(),
+                    "bech32": faucet_id.to_bech32(NetworkId::Testnet),
+                }),
+                || {
+                    println!("✅ Bridge faucet ready!");
+                    println!("   Hex: {}", faucet_id.to_hex());
+                    println!("   Bech32: {}", faucet_id.to_bech32(NetworkId::Testnet));
+                },
+            )
+        }
+        FaucetCommand::Create { bridge: false, symbol, decimals, max_supply } => {
+            let (keystore_path, store_path) = config.bridge_paths();
+
+            config.progress(format!("Creating faucet account (symbol {}, decimals {}, max supply {})...", symbol, decimals, max_supply));
+            let bech32 = create_faucet_account(&keystore_path, &store_path, &config.rpc_url, &symbol, decimals, max_supply, None).await?;
+            let faucet_id = parse_account_id(&bech32)?;
+
+            config.output(
+                serde_json::json!({
+                    "faucet_id": faucet_id.to_hex(),
+                    "bech32": bech32,
+                    "symbol": symbol,
+                    "decimals": decimals,
+                    "max_supply": max_supply,
+                }),
+                || {
+                    println!("✅ Faucet account created!");
+                    println!("   Hex: {}", faucet_id.to_hex());
+                    println!("   Bech32: {}", bech32);
+                },
+            )
+        }
+        FaucetCommand::Show => {
+            let faucet_id = config.faucet_id(None)?;
+
+            config.progress("Syncing the bridge store...");
+            let (mut client, _) = config.client(config.bridge_paths()).await?;
+            client.sync_state().await
+                .map_err(|e| format!("Failed to sync state: {}", e))?;
+
+            let faucet = client.get_account(faucet_id).await
+                .map_err(|e| format!("Failed to get faucet account: {}", e))?
+                .ok_or("Faucet account is not in the bridge store")?;
+            let issued = faucet.account().get_token_issuance()
+                .map_err(|e| format!("Failed to read faucet issuance: {}", e))?
+                .as_int();
+
+            config.output(
+                serde_json::json!({
+                    "faucet_id": faucet_id.to_hex(),
+                    "bech32": faucet_id.to_bech32(NetworkId::Testnet),
+                    "issued": issued,
+                }),
+                || {
+                    println!("wTAZ faucet");
+                    println!("   Hex: {}", faucet_id.to_hex());
+                    println!("   Bech32: {}", faucet_id.to_bech32(NetworkId::Testnet));
+                    println!("   Issued: {}", issued);
+                },
+            )
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{parse_account_id, CliResult, Config};
+use clap::Subcommand;
+use miden_objects::address::NetworkId;
+
+#[derive(Subcommand)]
+pub enum IdCommand {
+    /// Print an account id in hex and bech32
+    Convert {
+        /// Hex (with or without 0x) or bech32 account id
+        id: String,
+    },
+}
+
+pub fn run(command: IdCommand, config: &Config) -> CliResult<()> {
+    match command {
+        IdCommand::Convert { id } => {
+            let account_id = parse_account_id(&id)?;
+            let hex = account_id.to_hex();
+            let bech32 = account_id.to_bech32(NetworkId::Testnet);
+
+            config.output(
+                serde_json::json!({
+                    "hex": hex,
+                    "bech32": bech32,
+                    "account_type": format!("{:?}", account_id.account_type()),
+                    "storage_mode": format!("{:?}", account_id.storage_mode()),
+                }),
+                || {
+                    println!("Hex:     {}", hex);
+                    println!("Bech32:  {}", bech32);
+                    println!("Type:    {:?}, {:?}", account_id.account_type(), account_id.storage_mode());
+                },
+            )
+        }
+    }
+}
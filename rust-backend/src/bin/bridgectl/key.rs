@@ -0,0 +1,193 @@
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use miden_client::auth::AuthSecretKey;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_objects::{address::NetworkId, Word};
+use rust_backend::account::faucet_auth::{faucet_auth_library, RotatableFalconAuth, PUBLIC_KEY_SLOT};
+use rust_backend::account::keystore::{EncryptedKeyStore, KeystoreSecret};
+use rust_backend::db::audit::{inputs_digest, operator_actor, AuditAction};
+
+/// Stages the key passed as the script argument; the faucet's auth procedure makes it current
+const ROTATE_SCRIPT: &str = "
+use.bridge::faucet_auth
+
+begin
+    call.faucet_auth::rotate_key
+end
+";
+
+#[derive(Subcommand)]
+pub enum KeyCommand {
+    /// Encrypt the plain FilesystemKeyStore keys in the keystore directory and delete them
+    Encrypt {
+        /// Leave the plain key files in place
+        #[arg(long)]
+        keep_plaintext: bool,
+    },
+    /// List the public key commitments in the keystore
+    List,
+    /// Re-encrypt the keystore under KEYSTORE_NEW_PASSPHRASE or KEYSTORE_NEW_KEY
+    Rekey,
+    /// Replace the faucet's signing key on-chain with a new key from the keystore
+    Rotate {
+        /// Faucet to rotate (default: the bridge's wTAZ faucet)
+        #[arg(long)]
+        faucet: Option<String>,
+    },
+}
+
+pub async fn run(command: KeyCommand, config: &Config) -> CliResult<()> {
+    let (dir, _) = config.bridge_paths();
+
+    match command {
+        KeyCommand::Encrypt { keep_plaintext } => {
+            let secret = KeystoreSecret::from_env()?;
+            let keystore = if EncryptedKeyStore::is_encrypted(&dir) {
+                EncryptedKeyStore::open(&dir, &secret)?
+            } else {
+                EncryptedKeyStore::create(&dir, &secret)?
+            };
+            let imported = keystore.import_plaintext(&dir)?;
+
+            // The plain files only go once the encrypted copies are known to open
+            let keys = keystore.public_keys()?;
+            if EncryptedKeyStore::open(&dir, &secret)?.public_keys()? != keys {
+                return Err("Encrypted keystore does not read back the keys just written; plain files kept".into());
+            }
+            if !keep_plaintext {
+                for path in &imported {
+                    std::fs::remove_file(path)
+                        .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+                }
+            }
+
+            config.output(
+                serde_json::json!({
+                    "keystore": dir.display().to_string(),
+                    "imported": imported.len(),
+                    "keys": keys.len(),
+                    "plaintext_removed": !keep_plaintext,
+                }),
+                || {
+                    println!("✅ Encrypted {} key(s) into {}", imported.len(), dir.display());
+                    println!("   Keys in keystore: {}", keys.len());
+                    if keep_plaintext {
+                        println!("⚠️  Plain key files kept; the API and relayers refuse to start until they are removed");
+                    }
+                    println!("💡 Keys that were ever committed to git should be treated as exposed and replaced");
+                },
+            )
+        }
+        KeyCommand::List => {
+            let secret = KeystoreSecret::from_env()?;
+            let keys = EncryptedKeyStore::open(&dir, &secret)?.public_keys()?;
+            config.output(
+                serde_json::json!({ "keystore": dir.display().to_string(), "public_keys": keys }),
+                || {
+                    println!("Keystore {} ({} key(s))", dir.display(), keys.len());
+                    for key in &keys {
+                        println!("   {}", key);
+                    }
+                },
+            )
+        }
+        KeyCommand::Rekey => {
+            let secret = KeystoreSecret::from_env()?;
+            let new_secret = KeystoreSecret::from_vars("KEYSTORE_NEW_KEY", "KEYSTORE_NEW_PASSPHRASE")?;
+            let keystore = EncryptedKeyStore::open(&dir, &secret)?.rekey(&new_secret)?;
+            let keys = keystore.public_keys()?.len();
+
+            config.output(
+                serde_json::json!({ "keystore": dir.display().to_string(), "keys": keys }),
+                || {
+                    println!("✅ Re-encrypted {} key(s) in {}", keys, dir.display());
+                    println!("💡 Set KEYSTORE_PASSPHRASE or KEYSTORE_KEY to the new secret before restarting the API and relayers");
+                },
+            )
+        }
+        KeyCommand::Rotate { faucet } => rotate(faucet.as_deref(), config).await,
+    }
+}
+
+/// Swap the faucet's Falcon key: the new key goes into the keystore first, then a transaction
+/// signed with the old key installs it
+async fn rotate(faucet: Option<&str>, config: &Config) -> CliResult<()> {
+    let faucet_id = config.faucet_id(faucet)?;
+    let faucet_bech32 = faucet_id.to_bech32(NetworkId::Testnet);
+
+    config.progress("Syncing the bridge store...");
+    let (mut client, keystore) = config.client(config.bridge_paths()).await?;
+    client.sync_state().await
+        .map_err(|e| format!("Failed to sync state: {}", e))?;
+
+    let account = client.get_account(faucet_id).await
+        .map_err(|e| format!("Failed to get faucet account: {}", e))?
+        .ok_or_else(|| format!("Faucet {} is not in the bridge store", faucet_bech32))?;
+    let account = account.account();
+
+    // Faucet code is immutable, so only faucets created with the rotatable auth component qualify
+    if !RotatableFalconAuth::is_installed(account.code()) {
+        return Err(format!(
+            "Faucet {} was not created with the rotatable key component (it predates it, or is a multisig faucet), \
+             so its key can't be replaced on-chain",
+            faucet_bech32
+        ).into());
+    }
+
+    let old_key = account.storage().get_item(PUBLIC_KEY_SLOT)
+        .map_err(|e| format!("Failed to read the faucet's public key: {}", e))?;
+    if keystore.get_key(old_key)?.is_none() {
+        return Err(format!("The faucet's current key {} is not in the keystore, so it can't sign the rotation", old_key.to_hex()).into());
+    }
+
+    // Stored before the transaction, so the faucet can't end up with a key nobody holds
+    let new_key = AuthSecretKey::new_rpo_falcon512();
+    let new_key_commitment = Word::from(new_key.public_key().to_commitment());
+    keystore.add_key(&new_key)?;
+
+    config.progress(format!("Rotating the key of {}...", faucet_bech32));
+    let script = client.script_builder()
+        .with_dynamically_linked_library(&faucet_auth_library())
+        .map_err(|e| format!("Failed to link the faucet auth library: {}", e))?
+        .compile_tx_script(ROTATE_SCRIPT)
+        .map_err(|e| format!("Failed to compile the rotation script: {}", e))?;
+    let transaction_request = TransactionRequestBuilder::new()
+        .custom_script(script)
+        .script_arg(new_key_commitment)
+        .build()
+        .map_err(|e| format!("Failed to build rotation transaction: {}", e))?;
+    let tx_id = client
+        .submit_new_transaction(faucet_id, transaction_request)
+        .await
+        .map_err(|e| format!("Failed to submit rotation transaction: {}", e))?;
+
+    let inputs = serde_json::json!({
+        "faucet_id": faucet_id.to_hex(),
+        "old_key": old_key.to_hex(),
+        "new_key": new_key_commitment.to_hex(),
+    });
+    let audited = config.db()?
+        .audit()
+        .append(AuditAction::KeyRotate, &inputs_digest(&inputs), Some(&tx_id.to_hex()), &operator_actor("bridgectl"));
+    if let Err(e) = &audited {
+        eprintln!("⚠️  Failed to append audit entry: {}", e);
+    }
+
+    config.output(
+        serde_json::json!({
+            "tx_id": tx_id.to_hex(),
+            "faucet_id": faucet_id.to_hex(),
+            "old_key": old_key.to_hex(),
+            "new_key": new_key_commitment.to_hex(),
+            "audited": audited.is_ok(),
+        }),
+        || {
+            println!("✅ Faucet key rotated");
+            println!("   Faucet: {}", faucet_bech32);
+            println!("   Old key: {}", old_key.to_hex());
+            println!("   New key: {}", new_key_commitment.to_hex());
+            println!("   Transaction ID: {}", tx_id.to_hex());
+            println!("💡 Restart the signer process (`bridgectl signer serve`) so it loads the new key");
+        },
+    )
+}
@@ -0,0 +1,293 @@
+//! Admin CLI for the bridge: accounts, the wTAZ faucet, notes, deposits and refunds, withdrawals,
+//! offline-signed payouts, accounting, the audit ledger, reconciliation, webhooks, keys and the signer process
+
+mod account;
+mod audit;
+mod deposit;
+mod export;
+mod faucet;
+mod id;
+mod key;
+mod mint;
+mod note;
+mod payout;
+mod reconcile;
+mod refund;
+#[cfg(unix)]
+mod signer;
+mod webhooks;
+mod withdrawal;
+
+use clap::{Parser, Subcommand};
+use miden_client::{
+    builder::ClientBuilder,
+    rpc::{Endpoint, GrpcClient},
+    Client,
+};
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use miden_objects::account::AccountId;
+use rust_backend::account::keystore::{self, EncryptedKeyStore};
+use rust_backend::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use rust_backend::db::{self, BridgeStore};
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub type CliResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+#[derive(Parser)]
+#[command(name = "bridgectl", about = "Operate the Miden-Zcash bridge")]
+struct Cli {
+    /// Print the result as JSON, without progress output
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Miden node RPC endpoint
+    #[arg(long, global = true, env = "RPC_URL", default_value = "https://rpc.testnet.miden.io")]
+    rpc_url: String,
+
+    /// Keystore directory (default: the bridge's, or test_wallet/keystore for user wallets)
+    #[arg(long, global = true)]
+    keystore: Option<PathBuf>,
+
+    /// Miden client store (default: bridge_store.sqlite3, or test_wallet/test_store.sqlite3 for user wallets)
+    #[arg(long, global = true)]
+    store: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Miden wallet accounts
+    #[command(subcommand)]
+    Account(account::AccountCommand),
+    /// Faucet accounts, including the bridge's wTAZ faucet
+    #[command(subcommand)]
+    Faucet(faucet::FaucetCommand),
+    /// Mint faucet tokens by hand, outside the deposit flow
+    Mint(mint::MintArgs),
+    /// Find, rebuild and consume notes
+    #[command(subcommand)]
+    Note(note::NoteCommand),
+    /// Claimed deposits, their events and refunds of rejected ones
+    #[command(subcommand)]
+    Deposit(deposit::DepositCommand),
+    /// Withdrawal commitments and their payouts
+    #[command(subcommand)]
+    Withdrawal(withdrawal::WithdrawalCommand),
+    /// Zcash payouts waiting on the offline signer
+    #[command(subcommand)]
+    Payout(payout::PayoutCommand),
+    /// Ledger of deposits, withdrawals and fees over a date range, for accounting
+    Export(export::ExportArgs),
+    /// Hash-chained audit ledger of privileged actions
+    #[command(subcommand)]
+    Audit(audit::AuditCommand),
+    /// Cross-check the Zcash wallet, the bridge database and the Miden store
+    #[command(subcommand)]
+    Reconcile(reconcile::ReconcileCommand),
+    /// Webhook deliveries and the dead-letter list
+    #[command(subcommand)]
+    Webhooks(webhooks::WebhooksCommand),
+    /// Account id formats
+    #[command(subcommand)]
+    Id(id::IdCommand),
+    /// The encrypted keystore
+    #[command(subcommand)]
+    Key(key::KeyCommand),
+    /// The signer process holding the faucet keys
+    #[cfg(unix)]
+    #[command(subcommand)]
+    Signer(signer::SignerCommand),
+}
+
+/// Settings shared by every subcommand
+pub struct Config {
+    pub project_root: PathBuf,
+    pub rpc_url: String,
+    pub json: bool,
+    keystore: Option<PathBuf>,
+    store: Option<PathBuf>,
+}
+
+impl Config {
+    /// The bridge's own keystore and Miden store, holding the faucet
+    pub fn bridge_paths(&self) -> (PathBuf, PathBuf) {
+        (
+            self.keystore.clone().unwrap_or_else(|| self.project_root.join("rust-backend").join("keystore")),
+            self.store.clone().unwrap_or_else(|| self.project_root.join("bridge_store.sqlite3")),
+        )
+    }
+
+    /// A separate keystore and store for user-side wallets, so they stay out of the bridge's
+    pub fn wallet_paths(&self) -> (PathBuf, PathBuf) {
+        let test_dir = self.project_root.join("rust-backend").join("test_wallet");
+        (
+            self.keystore.clone().unwrap_or_else(|| test_dir.join("keystore")),
+            self.store.clone().unwrap_or_else(|| test_dir.join("test_store.sqlite3")),
+        )
+    }
+
+    pub fn db(&self) -> CliResult<Box<dyn BridgeStore>> {
+        Ok(db::open(&self.project_root)
+            .map_err(|e| format!("Failed to open bridge database: {}", e))?)
+    }
+
+    /// Miden client over the given keystore and store
+    pub async fn client(
+        &self,
+        (keystore_path, store_path): (PathBuf, PathBuf),
+    ) -> CliResult<(Client<EncryptedKeyStore>, Arc<EncryptedKeyStore>)> {
+        if let Some(parent) = keystore_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let endpoint = Endpoint::try_from(self.rpc_url.as_str())
+            .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
+
+        let keystore = keystore::unlock(&keystore_path)?;
+
+        let client = ClientBuilder::new()
+            .rpc(rpc_client)
+            .sqlite_store(store_path)
+            .authenticator(keystore.clone())
+            .in_debug_mode(true.into())
+            .build()
+            .await
+            .map_err(|e| format!("Failed to build client: {}", e))?;
+
+        Ok((client, keystore))
+    }
+
+    /// The faucet given on the command line, or else the bridge's wTAZ faucet
+    pub fn faucet_id(&self, faucet: Option<&str>) -> CliResult<AccountId> {
+        match faucet {
+            Some(faucet) => parse_account_id(faucet),
+            None => Ok(self.db()?
+                .faucets()
+                .get_faucet_id(ZCASH_ORIGIN_NETWORK)?
+                .ok_or("No faucet found in the bridge database. Create one with `bridgectl faucet create --bridge`.")?),
+        }
+    }
+
+    /// Progress line, left out of JSON output
+    pub fn progress(&self, message: impl std::fmt::Display) {
+        if !self.json {
+            println!("{}", message);
+        }
+    }
+
+    /// Print the result: `value` with --json, otherwise the text `text` prints
+    pub fn output(&self, mut value: serde_json::Value, text: impl FnOnce()) -> CliResult<()> {
+        if self.json {
+            if let Some(object) = value.as_object_mut() {
+                object.insert("success".to_string(), true.into());
+            }
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        } else {
+            text();
+        }
+        Ok(())
+    }
+}
+
+/// Account id as hex (with or without 0x, possibly short) or bech32 (mtst1...)
+pub fn parse_account_id(account_id: &str) -> CliResult<AccountId> {
+    let account_id = account_id.trim();
+
+    if account_id.starts_with("mtst") || account_id.starts_with("mm") {
+        // Addresses copied from wallets may carry a routing suffix after '_'
+        let parsed = AccountId::from_bech32(account_id)
+            .or_else(|e| match account_id.split_once('_') {
+                Some((id, _)) => AccountId::from_bech32(id),
+                None => Err(e),
+            })
+            .map_err(|e| format!("Invalid bech32 account id {}: {}", account_id, e))?;
+        return Ok(parsed.1);
+    }
+
+    let hex = account_id.strip_prefix("0x").unwrap_or(account_id);
+    // Account ids are 15 bytes; shorter hex is left-padded, longer keeps the last 30 digits
+    let hex = if hex.len() < 30 {
+        format!("{:0>30}", hex)
+    } else {
+        hex[hex.len() - 30..].to_string()
+    };
+    Ok(AccountId::from_hex(&format!("0x{}", hex))
+        .map_err(|e| format!("Invalid hex account id {}: {}", account_id, e))?)
+}
+
+fn main() {
+    // Get project root
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    let project_root = if current_dir.ends_with("rust-backend") {
+        current_dir.parent().expect("Failed to get parent directory").to_path_buf()
+    } else {
+        current_dir
+    };
+
+    // Load .env before parsing, so RPC_URL and friends can come from it
+    if dotenv::from_path(project_root.join(".env")).is_err() {
+        dotenv::dotenv().ok();
+    }
+
+    let cli = Cli::parse();
+    let config = Config {
+        project_root,
+        rpc_url: cli.rpc_url,
+        json: cli.json,
+        keystore: cli.keystore,
+        store: cli.store,
+    };
+    let command = cli.command;
+
+    // Proving needs a bigger stack than the main thread gets on Windows
+    let result = std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+            let result = rt.block_on(run(command, &config));
+            (config.json, result)
+        })
+        .expect("Failed to spawn thread")
+        .join();
+
+    match result {
+        Ok((_, Ok(()))) => {}
+        Ok((json, Err(e))) => {
+            if json {
+                println!("{}", serde_json::json!({ "success": false, "error": e.to_string() }));
+            } else {
+                eprintln!("❌ {}", e);
+            }
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("❌ Thread panicked: {:?}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn run(command: Command, config: &Config) -> CliResult<()> {
+    match command {
+        Command::Account(command) => account::run(command, config).await,
+        Command::Faucet(command) => faucet::run(command, config).await,
+        Command::Mint(args) => mint::run(args, config).await,
+        Command::Note(command) => note::run(command, config).await,
+        Command::Deposit(command) => deposit::run(command, config),
+        Command::Withdrawal(command) => withdrawal::run(command, config),
+        Command::Payout(command) => payout::run(command, config),
+        Command::Export(args) => export::run(args, config),
+        Command::Audit(command) => audit::run(command, config),
+        Command::Reconcile(command) => reconcile::run(command, config).await,
+        Command::Webhooks(command) => webhooks::run(command, config),
+        Command::Id(command) => id::run(command, config),
+        Command::Key(command) => key::run(command, config).await,
+        #[cfg(unix)]
+        Command::Signer(command) => signer::run(command, config).await,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{parse_account_id, CliResult, Config};
+use clap::Args;
+use miden_client::{note::NoteType, transaction::TransactionRequestBuilder};
+use miden_objects::{address::NetworkId, asset::FungibleAsset};
+use rust_backend::db::audit::{inputs_digest, operator_actor, AuditAction};
+
+#[derive(Args)]
+pub struct MintArgs {
+    /// Recipient account id (hex or bech32)
+    #[arg(long)]
+    recipient: String,
+    /// Amount in base units (8 decimals: 100000000 is one token)
+    #[arg(long)]
+    amount: u64,
+    /// Mint a private note instead of a public one
+    #[arg(long)]
+    private: bool,
+    /// Faucet to mint from (default: the bridge's wTAZ faucet)
+    #[arg(long)]
+    faucet: Option<String>,
+}
+
+pub async fn run(args: MintArgs, config: &Config) -> CliResult<()> {
+    let recipient_id = parse_account_id(&args.recipient)?;
+    let faucet_id = config.faucet_id(args.faucet.as_deref())?;
+    let note_type = if args.private { NoteType::Private } else { NoteType::Public };
+
+    config.progress("Syncing the bridge store...");
+    let (mut client, _) = config.client(config.bridge_paths()).await?;
+    client.sync_state().await
+        .map_err(|e| format!("Failed to sync state: {}", e))?;
+
+    config.progress(format!("Minting {} from {} to {}...", args.amount, faucet_id.to_bech32(NetworkId::Testnet), recipient_id.to_bech32(NetworkId::Testnet)));
+    let fungible_asset = FungibleAsset::new(faucet_id, args.amount)
+        .map_err(|e| format!("Failed to create asset: {}", e))?;
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(fungible_asset, recipient_id, note_type, client.rng())
+        .map_err(|e| format!("Failed to build mint transaction: {}", e))?;
+    let tx_id = client
+        .submit_new_transaction(faucet_id, transaction_request)
+        .await
+        .map_err(|e| format!("Failed to submit transaction: {}", e))?;
+
+    // Tokens minted outside the bridge flows still go on the audit ledger
+    let inputs = serde_json::json!({
+        "faucet_id": faucet_id.to_hex(),
+        "recipient": recipient_id.to_hex(),
+        "amount": args.amount,
+    });
+    let audited = config.db()?
+        .audit()
+        .append(AuditAction::ManualMint, &inputs_digest(&inputs), Some(&tx_id.to_hex()), &operator_actor("bridgectl"));
+    if let Err(e) = &audited {
+        eprintln!("⚠️  Failed to append audit entry: {}", e);
+    }
+
+    config.output(
+        serde_json::json!({
+            "tx_id": tx_id.to_hex(),
+            "faucet_id": faucet_id.to_hex(),
+            "recipient": recipient_id.to_hex(),
+            "amount": args.amount,
+            "note_type": if args.private { "private" } else { "public" },
+            "audited": audited.is_ok(),
+        }),
+        || {
+            println!("✅ Mint submitted!");
+            println!("   Recipient: {}", recipient_id.to_bech32(NetworkId::Testnet));
+            println!("   Amount: {}", args.amount);
+            println!("   Transaction ID: {}", tx_id.to_hex());
+            if !args.private {
+                println!("💡 Public note: the recipient can consume it after syncing.");
+            }
+        },
+    )
+}
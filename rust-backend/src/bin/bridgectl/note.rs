@@ -0,0 +1,287 @@
+use crate::{parse_account_id, CliResult, Config};
+use clap::Subcommand;
+use miden_client::{
+    account::component::{BasicFungibleFaucet, BasicWallet},
+    auth::AuthSecretKey,
+    transaction::TransactionRequestBuilder,
+};
+use miden_lib::account::auth::AuthRpoFalcon512;
+use miden_objects::{
+    account::{AccountBuilder, AccountStorageMode, AccountType},
+    address::NetworkId,
+    asset::TokenSymbol,
+    note::NoteTag,
+    Felt, Word,
+};
+use rand::RngCore;
+use rust_backend::bridge::deposit::mint_deposit_note;
+use rust_backend::miden::notes::{reconstruct_deposit_note, BRIDGE_USECASE};
+use rust_backend::miden::recipient::{build_deposit_recipient, generate_secret};
+
+#[derive(Subcommand)]
+pub enum NoteCommand {
+    /// List the public notes a wallet can consume
+    Check {
+        /// Wallet account id (hex or bech32)
+        wallet: String,
+        /// Deposit secret, to also print the P2ID recipient hash of a private deposit note
+        #[arg(long)]
+        secret: Option<String>,
+    },
+    /// Rebuild a deposit note from the wallet id and secret, and print its id
+    Reconstruct {
+        wallet: String,
+        secret: String,
+        /// Amount in base units
+        amount: u64,
+        /// Faucet the note was minted from (default: the bridge's wTAZ faucet)
+        #[arg(long)]
+        faucet: Option<String>,
+    },
+    /// Consume a private deposit note into a wallet of the user wallet store
+    Consume {
+        wallet: String,
+        secret: String,
+        /// Amount in base units
+        amount: u64,
+        /// Faucet the note was minted from (default: the bridge's wTAZ faucet)
+        #[arg(long)]
+        faucet: Option<String>,
+    },
+    /// Mint a deposit note from a throwaway faucet to a throwaway wallet and check it reconstructs
+    Roundtrip {
+        /// Amount in base units
+        #[arg(long, default_value_t = 1000)]
+        amount: u64,
+    },
+}
+
+pub async fn run(command: NoteCommand, config: &Config) -> CliResult<()> {
+    match command {
+        NoteCommand::Check { wallet, secret } => check(config, &wallet, secret.as_deref()).await,
+        NoteCommand::Reconstruct { wallet, secret, amount, faucet } => {
+            let wallet_id = parse_account_id(&wallet)?;
+            let faucet_id = config.faucet_id(faucet.as_deref())?;
+            let secret = parse_secret(&secret)?;
+
+            let note = reconstruct_deposit_note(wallet_id, secret, faucet_id, amount)?;
+            let recipient_hash = note.recipient().digest().to_hex();
+            config.output(
+                serde_json::json!({
+                    "note_id": note.id().to_hex(),
+                    "recipient_hash": recipient_hash,
+                }),
+                || {
+                    println!("Note ID: {}", note.id().to_hex());
+                    println!("Recipient hash: {}", recipient_hash);
+                },
+            )
+        }
+        NoteCommand::Consume { wallet, secret, amount, faucet } => {
+            let wallet_id = parse_account_id(&wallet)?;
+            let faucet_id = config.faucet_id(faucet.as_deref())?;
+            let secret = parse_secret(&secret)?;
+
+            let (mut client, _) = config.client(config.wallet_paths()).await?;
+            client.add_note_tag(bridge_tag()?).await?;
+
+            let note = reconstruct_deposit_note(wallet_id, secret, faucet_id, amount)?;
+            let note_id = note.id().to_hex();
+            config.progress(format!("Reconstructed note {}", note_id));
+
+            if client.get_account(wallet_id).await
+                .map_err(|e| format!("Failed to get account: {}", e))?
+                .is_none()
+            {
+                return Err("Wallet account not found in the user wallet store. Add it first.".into());
+            }
+
+            // Private notes aren't in the store, so the note goes in as an unauthenticated input
+            config.progress("Submitting consume transaction...");
+            let tx_request = TransactionRequestBuilder::new()
+                .unauthenticated_input_notes([(note, Some(secret.into()))])
+                .build()
+                .map_err(|e| format!("Failed to build transaction: {:?}", e))?;
+            let tx_id = client
+                .submit_new_transaction(wallet_id, tx_request)
+                .await
+                .map_err(|e| format!("Failed to submit transaction: {}", e))?;
+
+            config.output(
+                serde_json::json!({ "note_id": note_id, "tx_id": tx_id.to_hex() }),
+                || {
+                    println!("✅ Note consumed!");
+                    println!("   Note ID: {}", note_id);
+                    println!("   Transaction ID: {}", tx_id.to_hex());
+                },
+            )
+        }
+        NoteCommand::Roundtrip { amount } => roundtrip(config, amount).await,
+    }
+}
+
+async fn check(config: &Config, wallet: &str, secret: Option<&str>) -> CliResult<()> {
+    let wallet_id = parse_account_id(wallet)?;
+
+    let (mut client, _) = config.client(config.wallet_paths()).await?;
+    client.add_note_tag(bridge_tag()?).await
+        .map_err(|e| format!("Failed to add note tag: {}", e))?;
+
+    config.progress("Syncing state...");
+    client.sync_state().await.map_err(|e| format!("Failed to sync: {}", e))?;
+
+    let consumable_notes = client
+        .get_consumable_notes(Some(wallet_id))
+        .await
+        .map_err(|e| format!("Failed to get consumable notes: {}", e))?;
+
+    // Private P2ID notes can't be found by sync; the recipient hash is what the bridge minted to
+    let recipient_hash = match secret {
+        Some(secret) => Some(build_deposit_recipient(wallet_id, parse_secret(secret)?)?.digest().to_hex()),
+        None => None,
+    };
+
+    let notes: Vec<_> = consumable_notes.iter()
+        .map(|(note, _)| {
+            let metadata = note.metadata();
+            serde_json::json!({
+                "note_id": note.id().to_hex(),
+                "sender": metadata.map(|metadata| metadata.sender().to_hex()),
+                "note_type": metadata.map(|metadata| format!("{:?}", metadata.note_type())),
+                "assets": note.assets().num_assets(),
+            })
+        })
+        .collect();
+
+    config.output(
+        serde_json::json!({
+            "account_id": wallet_id.to_hex(),
+            "notes": notes,
+            "recipient_hash": recipient_hash,
+        }),
+        || {
+            println!("Wallet {} ({})", wallet_id.to_bech32(NetworkId::Testnet), wallet_id.to_hex());
+            if let Some(recipient_hash) = &recipient_hash {
+                println!("   P2ID recipient hash: {}", recipient_hash);
+                println!("   Private notes aren't listed below; consume with `bridgectl note consume`.");
+            }
+            if consumable_notes.is_empty() {
+                println!("⚠️  No public consumable notes found (bridge tag use case {})", BRIDGE_USECASE);
+            }
+            for (note, _) in &consumable_notes {
+                println!();
+                println!("   Note ID: {}", note.id().to_hex());
+                if let Some(metadata) = note.metadata() {
+                    println!("   - Sender: {}", metadata.sender().to_hex());
+                    println!("   - Type: {:?}", metadata.note_type());
+                }
+                println!("   - Assets: {}", note.assets().num_assets());
+            }
+        },
+    )
+}
+
+/// End-to-end check of the deposit note pattern against the live network
+async fn roundtrip(config: &Config, amount: u64) -> CliResult<()> {
+    let (keystore_path, store_path) = config.wallet_paths();
+    let (mut client, keystore) = config.client((keystore_path.clone(), store_path.clone())).await?;
+    client.add_note_tag(bridge_tag()?).await
+        .map_err(|e| format!("Failed to add note tag: {}", e))?;
+
+    // The wallet stays out of the store until after the mint, like the bridge never has it
+    config.progress("[1] Creating private wallet account...");
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    let wallet_key = AuthSecretKey::new_rpo_falcon512();
+    let wallet_account = AccountBuilder::new(seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(AuthRpoFalcon512::new(wallet_key.public_key().to_commitment()))
+        .with_component(BasicWallet)
+        .build()
+        .map_err(|e| format!("Failed to build wallet: {}", e))?;
+    keystore.add_key(&wallet_key)
+        .map_err(|e| format!("Failed to add key: {}", e))?;
+    let wallet_id = wallet_account.id();
+
+    config.progress("[2] Creating test faucet...");
+    rand::rng().fill_bytes(&mut seed);
+    let faucet_key = AuthSecretKey::new_rpo_falcon512();
+    let faucet_account = AccountBuilder::new(seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(faucet_key.public_key().to_commitment()))
+        .with_component(
+            BasicFungibleFaucet::new(TokenSymbol::new("TEST").map_err(|e| format!("Invalid token symbol: {}", e))?, 8, Felt::new(1_000_000))
+                .map_err(|e| format!("Failed to build faucet component: {}", e))?,
+        )
+        .build()
+        .map_err(|e| format!("Failed to build faucet: {}", e))?;
+    client.add_account(&faucet_account, false).await
+        .map_err(|e| format!("Failed to add faucet: {}", e))?;
+    keystore.add_key(&faucet_key)
+        .map_err(|e| format!("Failed to add faucet key: {}", e))?;
+    let faucet_id = faucet_account.id();
+    client.sync_state().await.map_err(|e| format!("Failed to sync: {}", e))?;
+
+    config.progress("[3] Minting deposit note...");
+    let secret = generate_secret();
+    let (note_id, tx_id) = mint_deposit_note(wallet_id, secret, faucet_id, amount, keystore_path, store_path, &config.rpc_url).await?;
+
+    let reconstructed_id = reconstruct_deposit_note(wallet_id, secret, faucet_id, amount)?.id().to_hex();
+
+    config.progress("[4] Waiting for the note to land on-chain...");
+    client.add_account(&wallet_account, false).await
+        .map_err(|e| format!("Failed to add wallet: {}", e))?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+    client.sync_state().await.map_err(|e| format!("Failed to sync: {}", e))?;
+    let found = client.get_consumable_notes(Some(wallet_id)).await
+        .map_err(|e| format!("Failed to get consumable notes: {}", e))?
+        .iter()
+        .any(|(note, _)| note.id().to_hex() == note_id);
+
+    let matches = note_id == reconstructed_id;
+    config.output(
+        serde_json::json!({
+            "wallet_id": wallet_id.to_hex(),
+            "faucet_id": faucet_id.to_hex(),
+            "secret": secret.to_hex(),
+            "tx_id": tx_id,
+            "note_id": note_id,
+            "reconstructed_note_id": reconstructed_id,
+            "matches": matches,
+            "found": found,
+        }),
+        || {
+            println!("   Wallet: {}", wallet_id.to_bech32(NetworkId::Testnet));
+            println!("   Secret: {}", secret.to_hex());
+            println!("   Transaction ID: {}", tx_id);
+            println!("   Note ID: {}", note_id);
+            println!("   Reconstructed note ID: {}", reconstructed_id);
+            if matches {
+                println!("✅ Reconstructed note matches the minted one");
+            } else {
+                println!("❌ Reconstructed note differs from the minted one");
+            }
+            if !found {
+                println!("⚠️  Note not in the wallet's consumable notes yet; it may need more time to sync");
+            }
+        },
+    )?;
+
+    if !matches {
+        return Err("Reconstructed note id does not match the minted note".into());
+    }
+    Ok(())
+}
+
+fn parse_secret(secret: &str) -> CliResult<Word> {
+    let secret = if secret.starts_with("0x") { secret.to_string() } else { format!("0x{}", secret) };
+    Ok(Word::try_from(secret.as_str())
+        .map_err(|e| format!("Failed to parse secret: {}", e))?)
+}
+
+fn bridge_tag() -> CliResult<NoteTag> {
+    Ok(NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
+        .map_err(|e| format!("Failed to create bridge tag: {:?}", e))?)
+}
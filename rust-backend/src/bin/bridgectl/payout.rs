@@ -0,0 +1,214 @@
+use crate::deposit::format_time;
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::bridge::payouts::{self, format_taz};
+use rust_backend::db::payouts::{Payout, PayoutStatus};
+use rust_backend::zcash::bridge_wallet::BridgeWallet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const UNSIGNED_SUFFIX: &str = ".pczt";
+const SIGNED_SUFFIX: &str = ".signed.pczt";
+
+/// Payouts queued for the offline signer (ZCASH_PAYOUT_SIGNING=offline)
+///
+/// export (API host) -> sign (signer host, holding key.txt) -> import -> broadcast (API host).
+/// PCZTs travel as `<id>.pczt` and come back as `<id>.signed.pczt` in one directory.
+#[derive(Subcommand)]
+pub enum PayoutCommand {
+    /// List queued payouts
+    List {
+        /// Only payouts in this status: queued, building, unsigned, signed, broadcasting, broadcast or failed
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Write the PCZTs waiting for a signature to a directory, for the signer host
+    ///
+    /// Builds the next queued payout's PCZT first if none is out.
+    Export {
+        #[arg(long)]
+        dir: PathBuf,
+    },
+    /// Sign every exported PCZT in a directory with the bridge spending key (run on the signer host)
+    Sign {
+        #[arg(long)]
+        dir: PathBuf,
+        /// Wallet holding the spending key and key.txt (default: wallet/bridge_wallet)
+        #[arg(long)]
+        wallet_dir: Option<PathBuf>,
+    },
+    /// Attach the signed PCZTs in a directory to their payouts
+    Import {
+        #[arg(long)]
+        dir: PathBuf,
+    },
+    /// Send signed payouts and record them as paid
+    Broadcast {
+        /// Only this payout (default: every signed one)
+        id: Option<String>,
+    },
+    /// Build a new PCZT for a failed payout, after checking its transaction never landed
+    Rebuild {
+        id: String,
+    },
+}
+
+pub fn run(command: PayoutCommand, config: &Config) -> CliResult<()> {
+    match command {
+        PayoutCommand::List { status } => {
+            let db = config.db()?;
+            let payouts = match status {
+                Some(status) => db.payouts().with_status(PayoutStatus::parse(&status)?)?,
+                None => db.payouts().list()?,
+            };
+            let json: Vec<_> = payouts.iter().map(payout_json).collect();
+
+            config.output(serde_json::json!({ "payouts": json }), || {
+                println!("{} payout(s)", payouts.len());
+                for payout in &payouts {
+                    let status = match (&payout.txid, &payout.detail) {
+                        (Some(txid), _) => format!("{} in {}", payout.status.as_str(), txid),
+                        (None, Some(detail)) => format!("{}: {}", payout.status.as_str(), detail),
+                        (None, None) => payout.status.as_str().to_string(),
+                    };
+                    println!("  {}  {} TAZ to {}  queued {}  {}", payout.id, format_taz(payout.net_amount), payout.zcash_address, format_time(payout.created_at), status);
+                }
+            })
+        }
+        PayoutCommand::Export { dir } => {
+            std::fs::create_dir_all(&dir)?;
+            let db = Mutex::new(config.db()?);
+            let wallet = BridgeWallet::new(config.project_root.clone());
+            if let Some(id) = payouts::build_next(&db, &wallet)? {
+                config.progress(format!("  built {}", id));
+            }
+            let payouts = db.lock().unwrap().payouts().with_status(PayoutStatus::Unsigned)?;
+
+            for payout in &payouts {
+                // A signature left over from before a rebuild belongs to the old PCZT
+                let stale = dir.join(format!("{}{}", payout.id, SIGNED_SUFFIX));
+                if stale.exists() {
+                    std::fs::remove_file(stale)?;
+                }
+                std::fs::write(dir.join(format!("{}{}", payout.id, UNSIGNED_SUFFIX)), hex::decode(&payout.pczt)?)?;
+                config.progress(format!("  {}  {} TAZ to {}", payout.id, format_taz(payout.net_amount), payout.zcash_address));
+            }
+
+            let ids: Vec<_> = payouts.iter().map(|payout| payout.id.as_str()).collect();
+            config.output(serde_json::json!({ "exported": ids, "dir": dir }), || {
+                println!("✅ Exported {} PCZT(s) to {}", payouts.len(), dir.display());
+            })
+        }
+        PayoutCommand::Sign { dir, wallet_dir } => {
+            let wallet_dir = wallet_dir.unwrap_or_else(|| config.project_root.join("wallet").join("bridge_wallet"));
+            let wallet = BridgeWallet::with_wallet_dir(config.project_root.clone(), wallet_dir);
+
+            let mut signed = Vec::new();
+            for (id, path) in pczt_files(&dir, UNSIGNED_SUFFIX)? {
+                let signed_path = dir.join(format!("{}{}", id, SIGNED_SUFFIX));
+                if signed_path.exists() {
+                    continue;
+                }
+
+                let pczt = wallet.sign_pczt(&std::fs::read(&path)?)
+                    .map_err(|e| format!("Failed to sign {}: {}", id, e))?;
+                std::fs::write(&signed_path, pczt)?;
+                config.progress(format!("  signed {}", id));
+                signed.push(id);
+            }
+
+            config.output(serde_json::json!({ "signed": signed }), || {
+                println!("✅ Signed {} PCZT(s) in {}", signed.len(), dir.display());
+            })
+        }
+        PayoutCommand::Import { dir } => {
+            let db = config.db()?;
+
+            let mut imported = Vec::new();
+            for (id, path) in pczt_files(&dir, SIGNED_SUFFIX)? {
+                if db.payouts().attach_signed(&id, &hex::encode(std::fs::read(&path)?))? {
+                    config.progress(format!("  imported {}", id));
+                    imported.push(id);
+                } else {
+                    config.progress(format!("  skipped {} (not waiting for a signature)", id));
+                }
+            }
+
+            config.output(serde_json::json!({ "imported": imported }), || {
+                println!("✅ Imported {} signed PCZT(s)", imported.len());
+            })
+        }
+        PayoutCommand::Broadcast { id } => {
+            let db = Mutex::new(config.db()?);
+            let wallet = BridgeWallet::new(config.project_root.clone());
+
+            let ids = match id {
+                Some(id) => vec![id],
+                None => db.lock().unwrap()
+                    .payouts()
+                    .with_status(PayoutStatus::Signed)?
+                    .into_iter()
+                    .map(|payout| payout.id)
+                    .collect(),
+            };
+
+            let mut sent = Vec::new();
+            for id in ids {
+                let txid = payouts::broadcast(&db, &wallet, &id, "bridgectl")?;
+                config.progress(format!("  {}  sent in {}", id, txid));
+                sent.push(serde_json::json!({ "id": id, "zcash_txid": txid }));
+            }
+
+            config.output(serde_json::json!({ "broadcast": sent }), || {
+                println!("✅ Broadcast {} payout(s)", sent.len());
+            })
+        }
+        PayoutCommand::Rebuild { id } => {
+            let db = Mutex::new(config.db()?);
+            let wallet = BridgeWallet::new(config.project_root.clone());
+
+            payouts::rebuild(&db, &wallet, &id)?;
+
+            config.output(serde_json::json!({ "id": id }), || {
+                println!("✅ Rebuilt payout {}; export it for signing again", id);
+            })
+        }
+    }
+}
+
+fn payout_json(payout: &Payout) -> serde_json::Value {
+    serde_json::json!({
+        "id": payout.id,
+        "kind": payout.kind,
+        "note_id": payout.note_id,
+        "zcash_address": payout.zcash_address,
+        "amount": payout.amount,
+        "fee": payout.fee,
+        "net_amount": payout.net_amount,
+        "status": payout.status,
+        "zcash_txid": payout.txid,
+        "detail": payout.detail,
+        "created_at": payout.created_at,
+        "updated_at": payout.updated_at,
+    })
+}
+
+// Payout ids and paths of the PCZT files with this suffix, in name order
+fn pczt_files(dir: &Path, suffix: &str) -> CliResult<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // An unsigned listing mustn't pick up the signed files next to it
+        if suffix == UNSIGNED_SUFFIX && name.ends_with(SIGNED_SUFFIX) {
+            continue;
+        }
+        if let Some(id) = name.strip_suffix(suffix) {
+            files.push((id.to_string(), path));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
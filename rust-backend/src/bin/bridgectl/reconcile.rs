@@ -0,0 +1,71 @@
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::bridge::reconcile::{ReconcileReport, Reconciler};
+use std::env;
+
+#[derive(Subcommand)]
+pub enum ReconcileCommand {
+    /// Reconcile once; exits non-zero on discrepancies
+    Run,
+    /// Reconcile every RECONCILE_INTERVAL_SECS (default 3600), serving metrics on RECONCILE_METRICS_PORT
+    Watch,
+}
+
+pub async fn run(command: ReconcileCommand, config: &Config) -> CliResult<()> {
+    match command {
+        ReconcileCommand::Run => {
+            let report = Reconciler::new(config.project_root.clone(), 0).run().await?;
+            config.output(serde_json::to_value(&report)?, || print_report(&report))?;
+
+            // Non-zero exit so cron jobs and CI notice discrepancies
+            if !report.is_clean() {
+                std::process::exit(1);
+            }
+        }
+        ReconcileCommand::Watch => {
+            rust_backend::logging::init();
+
+            // Get reconcile interval from env (default 1 hour)
+            let interval = env::var("RECONCILE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<u64>()
+                .unwrap_or(3600);
+            println!("Reconcile interval: {} seconds", interval);
+
+            // Serve Prometheus metrics if a port is configured
+            rust_backend::metrics::serve_from_env("RECONCILE_METRICS_PORT");
+
+            println!("✅ Reconciliation scheduled");
+            println!("Press Ctrl+C to stop");
+            println!();
+
+            Reconciler::new(config.project_root.clone(), interval).start().await;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &ReconcileReport) {
+    let totals = &report.totals;
+    println!("Totals");
+    println!("  Zcash deposited:      {} zatoshis", totals.zcash_deposited);
+    println!("  Miden minted:         {}", totals.miden_minted);
+    println!("  Ledger minted (net):  {}", totals.ledger_minted);
+    println!("  Miden burned:         {}", totals.miden_burned);
+    println!("  Zcash paid out:       {} zatoshis", totals.zcash_paid_out);
+    println!("  Ledger paid out:      {}", totals.ledger_paid_out);
+    println!("  Pending withdrawals:  {}", totals.pending_withdrawals);
+    println!();
+
+    if report.is_clean() {
+        println!("✅ Wallet, bridge database and Miden store agree");
+        return;
+    }
+
+    println!("❌ {} discrepancies", report.issues.len());
+    for issue in &report.issues {
+        println!("  {:<16} {}", issue.kind.as_str(), issue.key);
+        println!("  {:<16} {}", "", issue.detail);
+    }
+}
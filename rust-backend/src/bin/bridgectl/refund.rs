@@ -0,0 +1,204 @@
+use crate::deposit::format_time;
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use rust_backend::bridge::fees::{FeeDirection, FeeSchedule};
+use rust_backend::bridge::payouts::format_taz;
+use rust_backend::db::audit::{inputs_digest, operator_actor, AuditAction};
+use rust_backend::db::events::EventState;
+use rust_backend::db::refunds::{RefundStatus, RejectedDeposit};
+use rust_backend::zcash::bridge_wallet::BridgeWallet;
+
+/// Rejected deposits and their refunds
+///
+/// rejected -> requested (by the user, over the API) -> approve -> send.
+#[derive(Subcommand)]
+pub enum RefundCommand {
+    /// List rejected deposits
+    List {
+        /// Only deposits in this status: rejected, requested, approved, refunded or unrefundable
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Approve a requested refund
+    Approve {
+        txid: String,
+        /// Output of the transaction, needed if several of its outputs requested refunds
+        pool: Option<String>,
+        output_index: Option<u32>,
+    },
+    /// Send approved refunds from the bridge wallet
+    Send {
+        /// Only refunds of this transaction (default: every approved one)
+        txid: Option<String>,
+    },
+}
+
+pub fn run(command: RefundCommand, config: &Config) -> CliResult<()> {
+    let db = config.db()?;
+
+    match command {
+        RefundCommand::List { status } => {
+            let status = status.map(|status| RefundStatus::parse(&status)).transpose()?;
+            let deposits = db.refunds().list(status)?;
+            let json: Vec<_> = deposits.iter().map(rejected_json).collect();
+
+            config.output(serde_json::json!({ "deposits": json }), || {
+                println!("{} rejected deposit output(s)", deposits.len());
+                for deposit in &deposits {
+                    println!("  {}:{}:{}", deposit.txid, deposit.pool, deposit.output_index);
+                    println!("     Status:         {}", deposit.status.as_str());
+                    println!("     Amount:         {} zatoshis", deposit.amount);
+                    println!("     Reason:         {}", deposit.reason);
+                    println!("     Rejected:       {}", format_time(deposit.rejected_at));
+                    println!("     Refund address: {}", deposit.refund_address.as_deref().unwrap_or("-"));
+                    if let Some(refund_txid) = &deposit.refund_txid {
+                        println!("     Refund txid:    {}", refund_txid);
+                    }
+                }
+            })
+        }
+        RefundCommand::Approve { txid, pool, output_index } => {
+            // Without an explicit output, the transaction must have exactly one requested refund
+            let (pool, output_index) = match (pool, output_index) {
+                (Some(pool), Some(index)) => (pool.to_lowercase(), index),
+                _ => {
+                    let requested: Vec<_> = db.refunds().get_rejected_for_tx(&txid)?
+                        .into_iter()
+                        .filter(|deposit| deposit.status == RefundStatus::Requested)
+                        .collect();
+                    match requested.as_slice() {
+                        [deposit] => (deposit.pool.clone(), deposit.output_index),
+                        [] => return Err(format!("Refund for {} is not in the requested state", txid).into()),
+                        _ => return Err(format!("Several outputs of {} have requested refunds, specify pool and output_index", txid).into()),
+                    }
+                }
+            };
+            let deposit_key = format!("{}:{}:{}", txid, pool, output_index);
+
+            let approved = db.transaction(|db| {
+                if !db.refunds().approve(&txid, &pool, output_index)? {
+                    return Ok(false);
+                }
+                let inputs = serde_json::json!({ "deposit": deposit_key });
+                db.audit().append(AuditAction::RefundApprove, &inputs_digest(&inputs), None, &operator_actor("bridgectl"))?;
+                Ok(true)
+            })?;
+            if !approved {
+                return Err(format!("Refund for {} is not in the requested state", deposit_key).into());
+            }
+
+            if let Err(e) = db.events().record_deposit(&txid, &pool, output_index, None, EventState::RefundApproved, None, None) {
+                config.progress(format!("⚠️  Failed to record refund event: {}", e));
+            }
+
+            config.output(serde_json::json!({ "approved": deposit_key }), || {
+                println!("✅ Refund for {} approved", deposit_key);
+            })
+        }
+        RefundCommand::Send { txid } => {
+            let fee_schedule = FeeSchedule::load(&config.project_root)?;
+            let bridge_wallet = BridgeWallet::new(config.project_root.clone());
+
+            let to_send: Vec<_> = db.refunds().list(Some(RefundStatus::Approved))?
+                .into_iter()
+                .filter(|deposit| txid.as_ref().is_none_or(|txid| &deposit.txid == txid))
+                .collect();
+
+            let mut sent = Vec::new();
+            let mut unrefundable = Vec::new();
+            let mut failed = Vec::new();
+            for deposit in to_send {
+                let deposit_key = format!("{}:{}:{}", deposit.txid, deposit.pool, deposit.output_index);
+                let Some(refund_address) = deposit.refund_address.as_deref() else {
+                    config.progress(format!("❌ Refund for {} has no refund address, skipping", deposit_key));
+                    failed.push(serde_json::json!({ "deposit": deposit_key, "error": "no refund address" }));
+                    continue;
+                };
+
+                // Refunds have their own fee rule, so a deposit below the deposit fee can still go back.
+                // One the refund fee would swallow is closed instead of being retried on every send.
+                let quote = match fee_schedule.quote(FeeDirection::Refund, ZCASH_ORIGIN_NETWORK, deposit.amount) {
+                    Ok(quote) => quote,
+                    Err(e) => {
+                        db.refunds().mark_unrefundable(&deposit.txid, &deposit.pool, deposit.output_index)?;
+                        config.progress(format!("❌ Refund for {} marked unrefundable - {}", deposit_key, e));
+                        unrefundable.push(serde_json::json!({ "deposit": deposit_key, "error": e.to_string() }));
+                        continue;
+                    }
+                };
+
+                let amount = format_taz(quote.net_amount);
+                config.progress(format!("Sending {} TAZ to {} (refund of {})...", amount, refund_address, deposit_key));
+
+                let refund_txid = match bridge_wallet.send(refund_address, &amount, None, None) {
+                    Ok(refund_txid) => refund_txid.trim().to_string(),
+                    Err(e) => {
+                        config.progress(format!("❌ Failed to send refund for {}: {}", deposit_key, e));
+                        failed.push(serde_json::json!({ "deposit": deposit_key, "error": e.to_string() }));
+                        continue;
+                    }
+                };
+
+                db.transaction(|db| {
+                    db.refunds().mark_refunded(&deposit.txid, &deposit.pool, deposit.output_index, &refund_txid)?;
+                    db.fees().record_fee(&quote, &format!("refund:{}", deposit_key))?;
+                    let inputs = serde_json::json!({
+                        "deposit": deposit_key,
+                        "refund_address": refund_address,
+                        "amount": deposit.amount,
+                        "fee": quote.fee,
+                    });
+                    db.audit().append(AuditAction::RefundPayout, &inputs_digest(&inputs), Some(&refund_txid), &operator_actor("bridgectl"))?;
+                    Ok(())
+                })?;
+                config.progress(format!("  {}  refunded in {}", deposit_key, refund_txid));
+
+                let recorded = db.events().record_deposit(
+                    &deposit.txid,
+                    &deposit.pool,
+                    deposit.output_index,
+                    None,
+                    EventState::Refunded,
+                    None,
+                    Some(&refund_txid),
+                );
+                if let Err(e) = recorded {
+                    config.progress(format!("⚠️  Failed to record refund event: {}", e));
+                }
+                sent.push(serde_json::json!({ "deposit": deposit_key, "refund_txid": refund_txid, "amount": quote.net_amount }));
+            }
+
+            config.output(
+                serde_json::json!({ "refunded": sent, "unrefundable": unrefundable, "failed": failed }),
+                || {
+                    println!("✅ Sent {} refund(s)", sent.len());
+                    if !unrefundable.is_empty() {
+                        println!("   {} marked unrefundable", unrefundable.len());
+                    }
+                    if !failed.is_empty() {
+                        println!("❌ {} failed; they stay approved and are retried on the next send", failed.len());
+                    }
+                },
+            )
+        }
+    }
+}
+
+fn rejected_json(deposit: &RejectedDeposit) -> serde_json::Value {
+    serde_json::json!({
+        "txid": deposit.txid,
+        "pool": deposit.pool,
+        "output_index": deposit.output_index,
+        "amount": deposit.amount,
+        "reason": deposit.reason,
+        "status": deposit.status,
+        "refund_address": deposit.refund_address,
+        "memo_refund_address": deposit.memo_refund_address,
+        "rejected_at": deposit.rejected_at,
+        "requested_at": deposit.requested_at,
+        "approved_at": deposit.approved_at,
+        "refund_txid": deposit.refund_txid,
+        "refunded_at": deposit.refunded_at,
+    })
+}
@@ -0,0 +1,71 @@
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::account::keystore;
+use rust_backend::account::remote_signer::{SignerAudit, SignerPolicy, SignerServer};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Subcommand)]
+pub enum SignerCommand {
+    /// Sign faucet transactions for the API and relayers over a Unix socket, within SIGNER_* policy
+    Serve {
+        /// Socket to listen on; the API and relayers point SIGNER_SOCKET at it
+        #[arg(long, env = "SIGNER_LISTEN")]
+        socket: PathBuf,
+        /// Audit log of every signing request (default: signer_audit.jsonl in the project root)
+        #[arg(long, env = "SIGNER_AUDIT_LOG")]
+        audit_log: Option<PathBuf>,
+    },
+    /// Check the hash chain of the signer's audit log
+    VerifyLog {
+        #[arg(long, env = "SIGNER_AUDIT_LOG")]
+        audit_log: Option<PathBuf>,
+    },
+}
+
+pub async fn run(command: SignerCommand, config: &Config) -> CliResult<()> {
+    match command {
+        SignerCommand::Serve { socket, audit_log } => {
+            rust_backend::logging::init();
+
+            let mut policy = SignerPolicy::from_env()?;
+            if policy.allowed_accounts.is_empty() {
+                policy.allowed_accounts.push(config.faucet_id(None)?);
+            }
+            let (keystore_path, _) = config.bridge_paths();
+            let keystore = keystore::unlock(&keystore_path)?;
+            let audit_log = audit_log.unwrap_or_else(|| config.project_root.join("signer_audit.jsonl"));
+            let audit = SignerAudit::open(&audit_log)?;
+
+            println!("Signer keys: {}", keystore.public_keys()?.len());
+            println!("Allowed accounts: {}", policy.allowed_accounts.iter().map(|id| id.to_hex()).collect::<Vec<_>>().join(", "));
+            match policy.max_amount {
+                Some(max_amount) => println!("Max amount per transaction: {}", max_amount),
+                None => println!("⚠️  No SIGNER_MAX_AMOUNT; transactions of any amount are signed"),
+            }
+            println!("Max signatures per hour: {}", policy.max_per_hour);
+            println!("Audit log: {}", audit_log.display());
+            println!("Press Ctrl+C to stop");
+            println!();
+
+            Arc::new(SignerServer::new(keystore, policy, audit)).serve(&socket).await?;
+            Ok(())
+        }
+        SignerCommand::VerifyLog { audit_log } => {
+            let audit_log = audit_log.unwrap_or_else(|| config.project_root.join("signer_audit.jsonl"));
+            let (entries, last_hash) = SignerAudit::verify(&audit_log)?;
+
+            config.output(
+                serde_json::json!({
+                    "audit_log": audit_log.display().to_string(),
+                    "entries": entries,
+                    "last_hash": last_hash,
+                }),
+                || {
+                    println!("✅ Signer audit log intact: {} entries", entries);
+                    println!("   Last hash: {}", last_hash);
+                },
+            )
+        }
+    }
+}
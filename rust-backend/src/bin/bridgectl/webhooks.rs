@@ -0,0 +1,165 @@
+use crate::deposit::format_time;
+use crate::{CliResult, Config};
+use clap::Subcommand;
+use rust_backend::bridge::webhooks::{verify_signature, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use rust_backend::db::webhooks::{DeliveryStatus, WebhookDelivery};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+/// Signed deliveries older than this are rejected by the stand-in receiver
+const MAX_SIGNATURE_AGE_SECS: i64 = 5 * 60;
+
+#[derive(Subcommand)]
+pub enum WebhooksCommand {
+    /// List the latest webhook deliveries
+    List {
+        /// Only deliveries in this status: pending, delivered or dead
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long, default_value_t = 100)]
+        limit: u32,
+    },
+    /// Queue a dead-lettered delivery again
+    Retry {
+        id: i64,
+    },
+    /// Run a local stand-in subscriber that checks signatures and prints deliveries
+    Listen {
+        #[arg(long, default_value_t = 9200)]
+        port: u16,
+        /// The subscription's shared secret
+        #[arg(long)]
+        secret: String,
+        /// Answer the first N deliveries with HTTP 500, to exercise retries
+        #[arg(long, default_value_t = 0)]
+        fail_first: u32,
+    },
+}
+
+pub fn run(command: WebhooksCommand, config: &Config) -> CliResult<()> {
+    match command {
+        WebhooksCommand::List { status, limit } => {
+            let status = status.map(|status| DeliveryStatus::parse(&status)).transpose()?;
+            let deliveries = config.db()?.webhooks().list(status, limit)?;
+            let json: Vec<_> = deliveries.iter().map(delivery_json).collect();
+
+            config.output(serde_json::json!({ "deliveries": json }), || {
+                println!("{} webhook deliveries", deliveries.len());
+                for delivery in &deliveries {
+                    println!("  #{} {} -> {}", delivery.id, delivery.event_type, delivery.subscription);
+                    println!("     Status:     {}", delivery.status.as_str());
+                    println!("     Event:      {}", delivery.event_id);
+                    println!("     Attempts:   {}", delivery.attempts);
+                    if delivery.status == DeliveryStatus::Pending {
+                        println!("     Next try:   {}", format_time(delivery.next_attempt_at));
+                    }
+                    if let Some(error) = &delivery.last_error {
+                        println!("     Last error: {}", error);
+                    }
+                }
+            })
+        }
+        WebhooksCommand::Retry { id } => {
+            if !config.db()?.webhooks().retry(id)? {
+                return Err(format!("Delivery #{} is not in the dead-letter list", id).into());
+            }
+            config.output(serde_json::json!({ "id": id }), || {
+                println!("✅ Delivery #{} queued again", id);
+            })
+        }
+        WebhooksCommand::Listen { port, secret, fail_first } => listen(config, port, &secret, fail_first),
+    }
+}
+
+fn delivery_json(delivery: &WebhookDelivery) -> serde_json::Value {
+    serde_json::json!({
+        "id": delivery.id,
+        "subscription": delivery.subscription,
+        "event_id": delivery.event_id,
+        "event_type": delivery.event_type,
+        "status": delivery.status,
+        "attempts": delivery.attempts,
+        "next_attempt_at": delivery.next_attempt_at,
+        "last_error": delivery.last_error,
+        "created_at": delivery.created_at,
+        "delivered_at": delivery.delivered_at,
+    })
+}
+
+/// Local stand-in for a subscriber; prints one line (one JSON object with --json) per delivery
+fn listen(config: &Config, port: u16, secret: &str, fail_first: u32) -> CliResult<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    config.progress(format!("Listening for webhooks on http://127.0.0.1:{}/", port));
+
+    let mut received = 0u32;
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+
+        // Request line, then headers up to the blank line
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let content_length = headers.get("content-length")
+            .and_then(|length| length.parse::<usize>().ok())
+            .unwrap_or(0);
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+
+        let header = |name: &str| headers.get(&name.to_lowercase()).cloned().unwrap_or_default();
+        let timestamp = header(TIMESTAMP_HEADER);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+        let fresh = timestamp.parse::<i64>()
+            .map(|signed_at| (now - signed_at).abs() <= MAX_SIGNATURE_AGE_SECS)
+            .unwrap_or(false);
+        let valid = fresh && verify_signature(secret, &timestamp, &body, &header(SIGNATURE_HEADER));
+
+        received += 1;
+        let status = if !valid {
+            "401 Unauthorized"
+        } else if received <= fail_first {
+            "500 Internal Server Error"
+        } else {
+            "200 OK"
+        };
+
+        let body = String::from_utf8_lossy(&body);
+        if config.json {
+            println!("{}", serde_json::json!({
+                "request": request_line.trim(),
+                "delivery": header(DELIVERY_HEADER),
+                "event": header(EVENT_HEADER),
+                "signature_valid": valid,
+                "status": status,
+                "body": body,
+            }));
+        } else {
+            println!(
+                "{} {} delivery={} event={} signature={} -> {}",
+                request_line.trim(),
+                body,
+                header(DELIVERY_HEADER),
+                header(EVENT_HEADER),
+                if valid { "valid" } else { "INVALID" },
+                status
+            );
+        }
+
+        write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::deposit::{format_time, output_events};
+use crate::{CliResult, Config};
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum WithdrawalCommand {
+    /// List withdrawal commitments
+    List {
+        /// Only those not paid out yet
+        #[arg(long)]
+        pending: bool,
+    },
+    /// Show the lifecycle events of a withdrawal
+    Events {
+        /// Withdrawal commitment (hex)
+        commitment: String,
+    },
+}
+
+pub fn run(command: WithdrawalCommand, config: &Config) -> CliResult<()> {
+    let db = config.db()?;
+
+    match command {
+        WithdrawalCommand::List { pending } => {
+            let withdrawals: Vec<_> = db.withdrawals().list_withdrawals()?
+                .into_iter()
+                .filter(|withdrawal| !pending || withdrawal.zcash_txid.is_none())
+                .collect();
+            let json: Vec<_> = withdrawals.iter()
+                .map(|withdrawal| serde_json::json!({
+                    "commitment": withdrawal.commitment,
+                    "note_id": withdrawal.note_id,
+                    "amount": withdrawal.amount,
+                    "block_number": withdrawal.block_number,
+                    "created_at": withdrawal.created_at,
+                    "claimed_at": withdrawal.claimed_at,
+                    "zcash_txid": withdrawal.zcash_txid,
+                }))
+                .collect();
+
+            config.output(serde_json::json!({ "withdrawals": json }), || {
+                println!("{} withdrawal(s)", withdrawals.len());
+                for withdrawal in &withdrawals {
+                    let status = match (&withdrawal.zcash_txid, withdrawal.claimed_at) {
+                        (Some(txid), _) => format!("paid in {}", txid),
+                        (None, Some(_)) => "claimed, payout pending".to_string(),
+                        (None, None) => "unclaimed".to_string(),
+                    };
+                    println!("  {}  {}  block {}  created {}  {}", withdrawal.commitment, withdrawal.amount, withdrawal.block_number, format_time(withdrawal.created_at), status);
+                }
+            })
+        }
+        WithdrawalCommand::Events { commitment } => output_events(config, &db.events().withdrawal_events(&commitment, 0)?),
+    }
+}
@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 #[tokio::main]
 async fn main() {
+    rust_backend::logging::init();
+
     println!("=== Miden → Zcash Exit Relayer ===");
     println!("Scans Miden for burn notes and sends Zcash transactions");
     println!();
@@ -31,6 +33,9 @@ async fn main() {
         .unwrap_or(10);
 
     println!("Scan interval: {} seconds", scan_interval);
+
+    // Serve Prometheus metrics if a port is configured
+    rust_backend::metrics::serve_from_env("MIDEN_RELAYER_METRICS_PORT");
     println!();
 
     // Initialize bridge wallet
@@ -0,0 +1,172 @@
+//! Independent check that the bridge's Zcash reserves cover the wTAZ it issued
+//!
+//! Kept apart from bridgectl on purpose: it is run by third parties, who have neither the
+//! bridge database nor its keystore and configuration, and only needs a published viewing key
+//! (or the API serving it) plus a public Miden node.
+
+use clap::{Parser, Subcommand};
+use miden_client::rpc::{Endpoint, GrpcClient, NodeRpcClient};
+use miden_lib::account::faucets::FungibleFaucetExt;
+use miden_objects::account::AccountId;
+use rust_backend::bridge::solvency::{assess, reserves_by_pool, ReservesDisclosure, SolvencyReport};
+use rust_backend::zcash::bridge_wallet::BridgeWallet;
+use sha2::{Digest, Sha256};
+use std::env;
+
+#[derive(Parser)]
+#[command(name = "verify_reserves", about = "Check that the bridge's Zcash reserves cover the wTAZ it issued")]
+struct Cli {
+    /// Miden node RPC endpoint, to read the faucet supply from
+    #[arg(long, env = "RPC_URL", default_value = "https://rpc.testnet.miden.io")]
+    rpc_url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fetch the viewing key and faucet from a bridge API that publishes them, and compare with its claims
+    Url {
+        /// Bridge API base URL
+        url: String,
+    },
+    /// Scan with a viewing key handed out some other way
+    Key {
+        /// Unified full viewing key of the bridge wallet
+        ufvk: String,
+        /// Height the bridge wallet was created at, to scan from
+        birthday_height: u64,
+        /// wTAZ faucet account ID (hex or bech32)
+        faucet_id: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+
+    let cli = Cli::parse();
+
+    // Get project root
+    let current_dir = env::current_dir()?;
+    let project_root = if current_dir.ends_with("rust-backend") {
+        current_dir.parent()
+            .ok_or("Failed to get parent directory")?
+            .to_path_buf()
+    } else {
+        current_dir
+    };
+
+    // What the operator discloses, and what it claims about its reserves (if asked over the API)
+    let (disclosure, faucet_id, claimed) = match cli.command {
+        Command::Url { url } => {
+            let url = url.trim_end_matches('/');
+            let response: serde_json::Value = reqwest::get(format!("{}/reserves/disclosure", url))
+                .await?
+                .error_for_status()
+                .map_err(|e| format!("Bridge does not publish its viewing key: {}", e))?
+                .json()
+                .await?;
+            let disclosure: ReservesDisclosure = serde_json::from_value(response["disclosure"].clone())?;
+            let faucet_id = response["faucet_id"].as_str()
+                .ok_or("Bridge has no wTAZ faucet yet")?
+                .to_string();
+
+            let claimed = match reqwest::get(format!("{}/reserves", url)).await {
+                Ok(response) => response.json::<serde_json::Value>().await.ok()
+                    .and_then(|response| serde_json::from_value::<SolvencyReport>(response["reserves"].clone()).ok()),
+                Err(_) => None,
+            };
+            (disclosure, faucet_id, claimed)
+        }
+        Command::Key { ufvk, birthday_height, faucet_id } => {
+            let disclosure = ReservesDisclosure {
+                network: "test".to_string(),
+                ufvk,
+                birthday_height,
+            };
+            (disclosure, faucet_id, None)
+        }
+    };
+
+    if disclosure.network != "test" {
+        return Err(format!("Viewing key is for the {} network; only testnet is supported", disclosure.network).into());
+    }
+
+    // Scan the chain with the viewing key in a view-only wallet of our own (one per key)
+    println!("🔍 Scanning the bridge wallet from height {}...", disclosure.birthday_height);
+    let key_hash = hex::encode(Sha256::digest(disclosure.ufvk.as_bytes()));
+    let wallet_dir = project_root.join("wallet").join("reserves_verifier").join(&key_hash[..16]);
+    let wallet = BridgeWallet::with_wallet_dir(project_root.clone(), wallet_dir.clone());
+    if !wallet_dir.exists() {
+        std::fs::create_dir_all(wallet_dir.parent().ok_or("Invalid wallet directory")?)?;
+        wallet.init_view_only(&disclosure.ufvk, disclosure.birthday_height)?;
+    }
+    wallet.sync()?;
+    let reserves = reserves_by_pool(&wallet.get_balance()?)?;
+
+    // The faucet is a public account, so its issued supply can be read from any node
+    println!("🔍 Reading the wTAZ supply of faucet {}...", faucet_id);
+    let issued = faucet_issuance(&faucet_id, &cli.rpc_url).await?;
+
+    // Burns aren't visible from outside the bridge, so the whole issued supply has to be covered
+    let report = assess(issued, 0, reserves, 0);
+
+    println!();
+    println!("Verified");
+    println!("  wTAZ issued:          {}", report.issued);
+    for (pool, value) in &report.reserves_by_pool {
+        println!("  Reserves ({:<11}) {} zatoshis", format!("{}):", pool), value);
+    }
+    println!("  Reserves (total):     {} zatoshis", report.reserves);
+    match report.ratio {
+        Some(ratio) => println!("  Ratio:                {:.4}", ratio),
+        None => println!("  Ratio:                - (nothing issued)"),
+    }
+
+    if let Some(claimed) = &claimed {
+        println!();
+        println!("Claimed by the bridge");
+        println!("  Outstanding:          {} (issued {}, burned {})", claimed.outstanding, claimed.issued, claimed.burned);
+        println!("  Reserves:             {} zatoshis", claimed.reserves);
+        if claimed.reserves != report.reserves || claimed.issued != report.issued {
+            println!("  ⚠️  Differs from the verified figures (the bridge may have checked at another height)");
+        }
+    }
+
+    println!();
+    if report.deficit == 0 {
+        println!("✅ Reserves cover the issued wTAZ supply");
+    } else {
+        println!("❌ Reserves are {} zatoshis short of the issued wTAZ supply", report.deficit);
+        if claimed.is_some_and(|claimed| claimed.burned > 0) {
+            println!("   The bridge reports burns the faucet supply doesn't reflect; compare with its outstanding figure");
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Tokens the faucet has issued, read from the public account's storage on the node
+async fn faucet_issuance(faucet_id: &str, rpc_url: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    let faucet_id = if faucet_id.starts_with("0x") {
+        AccountId::from_hex(faucet_id)?
+    } else {
+        AccountId::from_bech32(faucet_id)?.1
+    };
+
+    let endpoint = Endpoint::try_from(rpc_url)
+        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+    let rpc = GrpcClient::new(&endpoint, 10_000);
+
+    let fetched = rpc.get_account_details(faucet_id).await
+        .map_err(|e| format!("Failed to fetch faucet account: {}", e))?;
+    let faucet = fetched.account()
+        .ok_or("Faucet account is private; its supply can't be verified")?;
+
+    Ok(faucet.get_token_issuance()
+        .map_err(|e| format!("Failed to read faucet issuance: {}", e))?
+        .as_int())
+}
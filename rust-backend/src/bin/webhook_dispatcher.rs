@@ -0,0 +1,46 @@
+use rust_backend::bridge::webhooks::WebhookDispatcher;
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+    rust_backend::logging::init();
+
+    println!("=== Webhook Dispatcher ===");
+    println!("Delivers signed bridge events to the subscriptions in webhooks.json");
+    println!();
+
+    // Get project root
+    let current_dir = std::env::current_dir()
+        .expect("Failed to get current directory");
+
+    let project_root = if current_dir.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n == "rust-backend")
+        .unwrap_or(false) {
+        current_dir.parent().unwrap().to_path_buf()
+    } else {
+        current_dir
+    };
+
+    println!("Project root: {:?}", project_root);
+
+    // Get poll interval from env (default 5 seconds)
+    let poll_interval = std::env::var("WEBHOOK_DISPATCH_INTERVAL_SECS")
+        .unwrap_or_else(|_| "5".to_string())
+        .parse::<u64>()
+        .unwrap_or(5);
+
+    println!("Poll interval: {} seconds", poll_interval);
+
+    // Serve Prometheus metrics if a port is configured
+    rust_backend::metrics::serve_from_env("WEBHOOK_DISPATCHER_METRICS_PORT");
+    println!();
+
+    let dispatcher = WebhookDispatcher::new(project_root, poll_interval);
+
+    println!("✅ Webhook dispatcher started!");
+    println!("Press Ctrl+C to stop");
+    println!();
+
+    dispatcher.start().await;
+}
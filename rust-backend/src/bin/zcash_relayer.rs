@@ -4,6 +4,8 @@ use std::sync::Arc;
 
 #[tokio::main]
 async fn main() {
+    rust_backend::logging::init();
+
     println!("=== Zcash → Miden Relayer ===");
     println!("Scans Zcash wallet for deposits and mints Miden notes");
     println!();
@@ -30,6 +32,9 @@ async fn main() {
         .unwrap_or(5);
 
     println!("Scan interval: {} seconds", scan_interval);
+
+    // Serve Prometheus metrics if a port is configured
+    rust_backend::metrics::serve_from_env("ZCASH_RELAYER_METRICS_PORT");
     println!();
 
     // Initialize bridge wallet
@@ -235,14 +235,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("[7] Consuming note...");
                 let consume_url = format!("{}/note/consume", BACKEND_URL);
                 
-                // Get faucet_id - backend will auto-detect from faucets.db, but we can pass empty string
+                // Get faucet_id - backend will auto-detect from the bridge database, but we can pass empty string
                 // Amount in base units (zatoshis)
                 let amount_base = (AMOUNT * 1e8) as u64;
                 
                 let consume_body = json!({
                     "account_id": account_id,
                     "secret": secret_hex,
-                    "faucet_id": "", // Backend will auto-detect from faucets.db
+                    "faucet_id": "", // Backend will auto-detect from the bridge database
                     "amount": amount_base
                 });
                 
@@ -0,0 +1,430 @@
+use crate::bridge::memo::normalize_hex;
+use crate::db::events::{EventState, EventSubject};
+use crate::db::fees::LedgerTotals;
+use crate::db::BridgeStore;
+use crate::error::{BridgeError, BridgeResult};
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+
+/// Longest range one export covers
+pub const MAX_EXPORT_DAYS: u64 = 366;
+
+/// How long before its claim is confirmed a deposit's Minted event may be published
+const MINTED_EVENT_LEAD_SECS: i64 = 60 * 60;
+
+/// Days covered by an export, both ends inclusive (UTC)
+#[derive(Debug, Clone, Copy)]
+pub struct ExportRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl ExportRange {
+    /// Range from two YYYY-MM-DD dates
+    pub fn parse(from: &str, to: &str) -> BridgeResult<Self> {
+        let date = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|_| BridgeError::InvalidInput(format!("Invalid date {:?} (use YYYY-MM-DD)", value)))
+        };
+        let (from, to) = (date(from)?, date(to)?);
+
+        if from > to {
+            return Err(BridgeError::InvalidInput(format!("Export range starts after it ends: {} > {}", from, to)));
+        }
+        if (to - from).num_days() as u64 >= MAX_EXPORT_DAYS {
+            return Err(BridgeError::InvalidInput(format!("Export range is longer than {} days", MAX_EXPORT_DAYS)));
+        }
+        Ok(Self { from, to })
+    }
+
+    fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        self.from.iter_days().take_while(|day| *day <= self.to)
+    }
+
+    /// Unix time of the start of `from`
+    fn since(&self) -> i64 {
+        self.from.and_time(NaiveTime::MIN).and_utc().timestamp()
+    }
+
+    /// Unix time of the end of `to`, exclusive
+    fn until(&self) -> i64 {
+        self.to.and_time(NaiveTime::MIN).and_utc().timestamp() + 24 * 60 * 60
+    }
+}
+
+/// Which table of an export to write as CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    Deposits,
+    Withdrawals,
+    Daily,
+}
+
+impl ExportTable {
+    pub const ALL: [ExportTable; 3] = [ExportTable::Deposits, ExportTable::Withdrawals, ExportTable::Daily];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportTable::Deposits => "deposits",
+            ExportTable::Withdrawals => "withdrawals",
+            ExportTable::Daily => "daily",
+        }
+    }
+
+    pub fn parse(table: &str) -> BridgeResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|known| known.as_str() == table)
+            .ok_or_else(|| BridgeError::InvalidInput(format!("Unknown export table: {} (use deposits, withdrawals or daily)", table)))
+    }
+}
+
+/// A claimed Zcash deposit output and the wTAZ minted for it
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DepositEntry {
+    pub claimed_at: String,
+    pub txid: String,
+    pub pool: Option<String>,
+    pub output_index: Option<u32>,
+    /// Zatoshis received
+    pub amount: u64,
+    pub fee: Option<u64>,
+    /// wTAZ minted (amount minus fee)
+    pub minted: Option<u64>,
+    pub note_id: Option<String>,
+    /// Only with private columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_hash: Option<String>,
+    /// Only with private columns, and only for deposits the relayer minted automatically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+}
+
+/// A wTAZ burn and its Zcash payout
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WithdrawalEntry {
+    pub burned_at: String,
+    /// None for exits paid by the exit relayer, which have no commitment
+    pub commitment: Option<String>,
+    pub burn_note_id: String,
+    /// wTAZ burned
+    pub amount: u64,
+    pub fee: Option<u64>,
+    /// Zatoshis paid out (amount minus fee)
+    pub payout: Option<u64>,
+    pub payout_txid: Option<String>,
+    pub paid_at: Option<String>,
+}
+
+/// Flows of one day, with running totals since the bridge started
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct DailyTotals {
+    pub date: String,
+    pub deposits: u64,
+    pub deposited: u64,
+    pub deposit_fees: u64,
+    pub withdrawals: u64,
+    pub withdrawn: u64,
+    pub withdrawal_fees: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_fees: u64,
+}
+
+/// Ledger of bridge flows over a date range
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountingExport {
+    pub from: String,
+    pub to: String,
+    pub generated_at: String,
+    pub deposits: Vec<DepositEntry>,
+    pub withdrawals: Vec<WithdrawalEntry>,
+    pub daily: Vec<DailyTotals>,
+    #[serde(skip)]
+    include_private: bool,
+}
+
+/// Build the export from the bridge database
+///
+/// Recipient hashes and account ids link deposits to Miden accounts, so they are left out
+/// unless `include_private` is set.
+pub fn export(db: &dyn BridgeStore, range: ExportRange, include_private: bool) -> BridgeResult<AccountingExport> {
+    let (since, until) = (range.since(), range.until());
+
+    // Minted notes and payouts, from the event log. Both come after the deposit or burn they
+    // belong to (give or take the relayer publishing Minted before it confirms the claim), and
+    // fees accrue at or after it too, so nothing recorded before the range is needed.
+    let mut minted: HashMap<String, String> = HashMap::new();
+    let mut paid: HashMap<String, (String, i64)> = HashMap::new();
+    let mut after_id = 0;
+    loop {
+        let page = db.events().events_since(since - MINTED_EVENT_LEAD_SECS, after_id)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after_id = last.id;
+
+        for event in page {
+            match (event.subject, event.state, event.note_id, event.detail) {
+                (EventSubject::Deposit, EventState::Minted, Some(note_id), _) => {
+                    minted.insert(event.key, note_id);
+                }
+                (EventSubject::Withdrawal, EventState::Paid, _, Some(txid)) => {
+                    paid.insert(normalize_hex(&event.key), (txid, event.created_at));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut deposit_fees = HashMap::new();
+    let mut withdrawal_fees = HashMap::new();
+    for accrual in db.fees().accruals_since(since)? {
+        match accrual.direction.as_str() {
+            "deposit" => {
+                deposit_fees.insert(accrual.reference.clone(), accrual);
+            }
+            "withdrawal" if !accrual.reference.starts_with("refund:") => {
+                withdrawal_fees.insert(normalize_hex(&accrual.reference), accrual);
+            }
+            _ => {}
+        }
+    }
+
+    let mut deposits = Vec::new();
+    for claim in db.deposits().list_claims_between(since, until)? {
+        let key = match (&claim.pool, claim.output_index) {
+            (Some(pool), Some(index)) => format!("{}:{}:{}", claim.txid, pool, index),
+            _ => claim.txid.clone(),
+        };
+        let accrual = deposit_fees.get(&key);
+        let account_id = if include_private { account_id(db, &claim.recipient_hash)? } else { None };
+
+        deposits.push((claim.claimed_at, DepositEntry {
+            claimed_at: format_time(claim.claimed_at),
+            txid: claim.txid,
+            pool: claim.pool,
+            output_index: claim.output_index,
+            amount: claim.amount,
+            fee: accrual.map(|accrual| accrual.fee),
+            minted: accrual.map(|accrual| accrual.net_amount),
+            note_id: minted.get(&key).cloned(),
+            recipient_hash: include_private.then_some(claim.recipient_hash),
+            account_id,
+        }));
+    }
+
+    let mut withdrawals = Vec::new();
+    for withdrawal in db.withdrawals().list_withdrawals_between(since, until)? {
+        let commitment = normalize_hex(&withdrawal.commitment);
+        let note_id = normalize_hex(&withdrawal.note_id);
+        let accrual = withdrawal_fees.remove(&commitment).or_else(|| withdrawal_fees.remove(&note_id));
+        let payout = paid.remove(&commitment).or_else(|| paid.remove(&note_id));
+
+        withdrawals.push((withdrawal.created_at, WithdrawalEntry {
+            burned_at: format_time(withdrawal.created_at),
+            commitment: Some(withdrawal.commitment),
+            burn_note_id: withdrawal.note_id,
+            amount: withdrawal.amount,
+            fee: accrual.as_ref().map(|accrual| accrual.fee),
+            payout: accrual.as_ref().map(|accrual| accrual.net_amount),
+            payout_txid: withdrawal.zcash_txid.or_else(|| payout.as_ref().map(|(txid, _)| txid.clone())),
+            paid_at: payout.map(|(_, at)| format_time(at)),
+        }));
+    }
+
+    // Exits paid by the exit relayer are only in the fee ledger and the event log, keyed by burn note.
+    // Fees left over for withdrawals burned before the range aren't exits.
+    for (note_id, accrual) in withdrawal_fees {
+        if accrual.accrued_at >= until
+            || db.withdrawals().get_withdrawal(&accrual.reference)?.is_some()
+            || db.withdrawals().get_withdrawal_by_note_id(&accrual.reference)?.is_some()
+        {
+            continue;
+        }
+        let payout = paid.remove(&note_id);
+        withdrawals.push((accrual.accrued_at, WithdrawalEntry {
+            burned_at: format_time(accrual.accrued_at),
+            commitment: None,
+            burn_note_id: accrual.reference,
+            amount: accrual.gross_amount,
+            fee: Some(accrual.fee),
+            payout: Some(accrual.net_amount),
+            payout_txid: payout.as_ref().map(|(txid, _)| txid.clone()),
+            paid_at: payout.map(|(_, at)| format_time(at)),
+        }));
+    }
+    withdrawals.sort_by_key(|(at, _)| *at);
+
+    let daily = daily_totals(range, db.fees().totals_before(since)?, &deposits, &withdrawals);
+
+    Ok(AccountingExport {
+        from: range.from.to_string(),
+        to: range.to.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        deposits: deposits.into_iter().map(|(_, entry)| entry).collect(),
+        withdrawals: withdrawals.into_iter().map(|(_, entry)| entry).collect(),
+        daily,
+        include_private,
+    })
+}
+
+/// One row per day of the range; running totals start from `before`, the totals up to it
+fn daily_totals(
+    range: ExportRange,
+    before: LedgerTotals,
+    deposits: &[(i64, DepositEntry)],
+    withdrawals: &[(i64, WithdrawalEntry)],
+) -> Vec<DailyTotals> {
+    let mut days: BTreeMap<NaiveDate, DailyTotals> = BTreeMap::new();
+    for (at, deposit) in deposits {
+        let Some(day) = day_of(*at) else { continue };
+        let totals = days.entry(day).or_default();
+        totals.deposits += 1;
+        totals.deposited += deposit.amount;
+        totals.deposit_fees += deposit.fee.unwrap_or(0);
+    }
+    for (at, withdrawal) in withdrawals {
+        let Some(day) = day_of(*at) else { continue };
+        let totals = days.entry(day).or_default();
+        totals.withdrawals += 1;
+        totals.withdrawn += withdrawal.amount;
+        totals.withdrawal_fees += withdrawal.fee.unwrap_or(0);
+    }
+
+    let mut running = before;
+    range.days()
+        .map(|date| {
+            let mut day = days.remove(&date).unwrap_or_default();
+            running.deposited += day.deposited;
+            running.withdrawn += day.withdrawn;
+            running.fees += day.deposit_fees + day.withdrawal_fees;
+            day.date = date.to_string();
+            day.total_deposited = running.deposited;
+            day.total_withdrawn = running.withdrawn;
+            day.total_fees = running.fees;
+            day
+        })
+        .collect()
+}
+
+/// Miden account a deposit was minted to, if the user left it with the relayer for automatic minting
+fn account_id(db: &dyn BridgeStore, recipient_hash: &str) -> BridgeResult<Option<String>> {
+    for address in db.deposit_addresses().addresses_for_recipient(recipient_hash)? {
+        if let Some(account_id) = db.deposit_addresses().get(&address)?.and_then(|address| address.account_id) {
+            return Ok(Some(account_id));
+        }
+    }
+    for code in db.intents().codes_for_recipient(recipient_hash)? {
+        if let Some(account_id) = db.intents().get(&code)?.and_then(|intent| intent.account_id) {
+            return Ok(Some(account_id));
+        }
+    }
+    Ok(None)
+}
+
+fn day_of(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|time| time.date_naive())
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+impl AccountingExport {
+    /// One table as CSV, with a header row
+    pub fn to_csv(&self, table: ExportTable) -> String {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let number = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+        match table {
+            ExportTable::Deposits => {
+                let mut header = vec!["claimed_at", "txid", "pool", "output_index", "amount", "fee", "minted", "note_id"];
+                if self.include_private {
+                    header.extend(["recipient_hash", "account_id"]);
+                }
+                rows.push(header.into_iter().map(String::from).collect());
+
+                for deposit in &self.deposits {
+                    let mut row = vec![
+                        deposit.claimed_at.clone(),
+                        deposit.txid.clone(),
+                        text(&deposit.pool),
+                        number(deposit.output_index.map(u64::from)),
+                        deposit.amount.to_string(),
+                        number(deposit.fee),
+                        number(deposit.minted),
+                        text(&deposit.note_id),
+                    ];
+                    if self.include_private {
+                        row.extend([text(&deposit.recipient_hash), text(&deposit.account_id)]);
+                    }
+                    rows.push(row);
+                }
+            }
+            ExportTable::Withdrawals => {
+                rows.push(
+                    ["burned_at", "commitment", "burn_note_id", "amount", "fee", "payout", "payout_txid", "paid_at"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect(),
+                );
+                for withdrawal in &self.withdrawals {
+                    rows.push(vec![
+                        withdrawal.burned_at.clone(),
+                        text(&withdrawal.commitment),
+                        withdrawal.burn_note_id.clone(),
+                        withdrawal.amount.to_string(),
+                        number(withdrawal.fee),
+                        number(withdrawal.payout),
+                        text(&withdrawal.payout_txid),
+                        text(&withdrawal.paid_at),
+                    ]);
+                }
+            }
+            ExportTable::Daily => {
+                rows.push(
+                    [
+                        "date", "deposits", "deposited", "deposit_fees", "withdrawals", "withdrawn", "withdrawal_fees",
+                        "total_deposited", "total_withdrawn", "total_fees",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                );
+                for day in &self.daily {
+                    rows.push(vec![
+                        day.date.clone(),
+                        day.deposits.to_string(),
+                        day.deposited.to_string(),
+                        day.deposit_fees.to_string(),
+                        day.withdrawals.to_string(),
+                        day.withdrawn.to_string(),
+                        day.withdrawal_fees.to_string(),
+                        day.total_deposited.to_string(),
+                        day.total_withdrawn.to_string(),
+                        day.total_fees.to_string(),
+                    ]);
+                }
+            }
+        }
+
+        rows.iter()
+            .map(|row| row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",") + "\n")
+            .collect()
+    }
+}
+
+/// Quote a field if it holds a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -1,10 +1,15 @@
 use crate::account::create::create_faucet_account;
-use crate::db::faucets::FaucetStore;
+use crate::account::signer::BridgeSigner;
+use crate::bridge::memo;
+use crate::bridge::multisig::MultisigConfig;
+use crate::db::BridgeStore;
+use crate::db::audit::{inputs_digest, AuditAction};
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
 use crate::miden::recipient::build_deposit_recipient;
-use crate::zcash::bridge_wallet::BridgeWallet;
+use crate::zcash::bridge_wallet::{BridgeWallet, DepositOutput};
 use miden_client::{
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
     rpc::{Endpoint, GrpcClient},
     transaction::{OutputNote, TransactionRequestBuilder},
 };
@@ -15,18 +20,23 @@ use miden_objects::{
     note::{Note, NoteAssets, NoteExecutionHint, NoteMetadata, NoteTag, NoteType},
     FieldElement, Felt, Word,
 };
-use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+use std::collections::HashSet;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Origin network key for the wTAZ faucet (faucets table) and the fee schedule
+pub const ZCASH_ORIGIN_NETWORK: &str = "zcash_testnet";
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ClaimDepositRequest {
     pub account_id: String,
     pub secret: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ClaimDepositResponse {
     pub success: bool,
     pub note_id: Option<String>,
@@ -35,38 +45,63 @@ pub struct ClaimDepositResponse {
 }
 
 /// Scan bridge Zcash testnet wallet for deposits with a specific memo (recipient hash)
+///
+/// Outputs paid to one of `deposit_addresses` (diversified addresses handed out for this
+/// recipient hash) match without a memo, and memos carrying one of `reference_codes`
+/// (deposit intents registered for this recipient hash) match as well.
 pub async fn scan_zcash_deposits(
     bridge_wallet: &BridgeWallet,
     recipient_hash: &str,
     bridge_address: &str,
-) -> Result<Option<(String, u64)>, String> {
+    deposit_addresses: &HashSet<String>,
+    reference_codes: &HashSet<String>,
+) -> BridgeResult<Option<DepositOutput>> {
     // First, enhance transactions to get memo data
     bridge_wallet.enhance_transactions()
-        .map_err(|e| format!("Failed to enhance transactions: {}", e))?;
+        .map_err(|e| e.context("Failed to enhance transactions"))?;
     
     // List transactions from bridge wallet
     let tx_output = bridge_wallet.list_transactions(None)
-        .map_err(|e| format!("Failed to list transactions: {}", e))?;
+        .map_err(|e| e.context("Failed to list transactions"))?;
     
     // Parse transactions
     let transactions = bridge_wallet.parse_transactions(&tx_output)
-        .map_err(|e| format!("Failed to parse transactions: {}", e))?;
+        .map_err(|e| e.context("Failed to parse transactions"))?;
     
-    // Find transaction with matching memo and to bridge address
+    // Find the output with matching memo and to bridge address
+    // Each output is matched on its own, so one transaction can carry several deposits
     for tx in transactions {
-        // Check if memo matches recipient_hash
-        if let Some(memo) = &tx.memo {
-            if memo.trim() == recipient_hash.trim() {
-                // Check if it's to the bridge address
-                if let Some(to_addr) = &tx.to_address {
-                    if to_addr == bridge_address {
-                        return Ok(Some((tx.txid, tx.amount)));
-                    }
-                }
-                // Also check if amount > 0 (valid deposit)
-                if tx.amount > 0 {
-                    return Ok(Some((tx.txid, tx.amount)));
-                }
+        if !tx.is_incoming() {
+            continue;
+        }
+        for output in tx.outputs {
+            // Check if it was paid to a deposit address of this recipient, or the memo matches recipient_hash
+            let to_deposit_address = output.to_address.as_ref()
+                .map(|to_addr| deposit_addresses.contains(to_addr))
+                .unwrap_or(false);
+            let memo_matches = output.memo.as_ref()
+                .map(|memo| {
+                    memo.trim() == recipient_hash.trim()
+                        || memo::parse_reference_code(memo::memo_content(memo))
+                            .map(|code| reference_codes.contains(&code))
+                            .unwrap_or(false)
+                })
+                .unwrap_or(false);
+            if !to_deposit_address && !memo_matches {
+                continue;
+            }
+            
+            // Check if it's to the bridge address, or at least a valid deposit (value > 0)
+            let to_bridge = output.to_address.as_deref() == Some(bridge_address);
+            if to_bridge || output.value > 0 {
+                return Ok(Some(DepositOutput {
+                    txid: tx.txid.clone(),
+                    pool: output.pool,
+                    index: output.index,
+                    value: output.value,
+                    to_address: output.to_address,
+                    memo: output.memo,
+                }));
             }
         }
     }
@@ -80,22 +115,23 @@ pub async fn get_or_create_zcash_faucet(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-    faucet_store_path: PathBuf,
-) -> Result<AccountId, String> {
-    // Open faucet store
-    let faucet_store = FaucetStore::new(faucet_store_path)
-        .map_err(|e| format!("Failed to open faucet store: {}", e))?;
+    db: &Mutex<Box<dyn BridgeStore>>,
+) -> BridgeResult<AccountId> {
+    let lock_db = || db.lock()
+        .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)));
     
     // Check if faucet exists
-    const ZCASH_ORIGIN_NETWORK: &str = "zcash_testnet";
-    if let Some(faucet_id) = faucet_store.get_faucet_id(ZCASH_ORIGIN_NETWORK)
-        .map_err(|e| format!("Failed to query faucet store: {}", e))? {
+    if let Some(faucet_id) = lock_db()?
+        .faucets()
+        .get_faucet_id(ZCASH_ORIGIN_NETWORK)
+        .map_err(|e| e.context("Failed to query faucet store"))? {
         return Ok(faucet_id);
     }
     
-    // Faucet doesn't exist in faucets.db, create a new one
-    println!("[Bridge] Creating NEW Zcash testnet faucet (wTAZ)...");
-    println!("[Bridge] Note: If an old faucet exists in bridge_store.sqlite3, it will remain but a NEW one will be created.");
+    // Faucet doesn't exist in the bridge database, create a new one
+    // With MINT_APPROVERS set, mints need M-of-N approver signatures instead of the bridge's key
+    let multisig = MultisigConfig::from_env()?;
+    info!("Creating new Zcash testnet faucet (wTAZ); any older faucet in bridge_store.sqlite3 is left as is");
     let faucet_id_bech32 = create_faucet_account(
         &keystore_path,
         &store_path,
@@ -103,15 +139,14 @@ pub async fn get_or_create_zcash_faucet(
         "TAZ",  // Symbol
         8,      // Decimals (same as Zcash)
         1_000_000_000_000_000_000u64, // Max supply (1 billion TAZ)
+        multisig.as_ref(),
     )
     .await
-    .map_err(|e| format!("Failed to create faucet: {}", e))?;
-    
-    println!("[Bridge] ✅ Created new faucet account: {}", faucet_id_bech32);
+    .map_err(|e| e.context("Failed to create faucet"))?;
     
     // Parse faucet_id from bech32
     let faucet_id = AccountId::from_bech32(&faucet_id_bech32)
-        .map_err(|e| format!("Failed to parse faucet_id: {}", e))?
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse faucet_id: {}", e)))?
         .1;
     
     // Get hex representation for logging
@@ -124,14 +159,31 @@ pub async fn get_or_create_zcash_faucet(
         faucet_hex
     };
     
-    // Store faucet_id in database
-    faucet_store.store_faucet_id(ZCASH_ORIGIN_NETWORK, &faucet_id)
-        .map_err(|e| format!("Failed to store faucet_id: {}", e))?;
-    
-    println!("[Bridge] ✅ Created and stored Zcash testnet faucet:");
-    println!("[Bridge]    Bech32: {}", faucet_id_bech32);
-    println!("[Bridge]    Hex:    0x{}", faucet_hex_padded);
-    println!("[Bridge]    Database: faucets.db");
+    // Store faucet_id in database, together with its audit entry
+    lock_db()?.transaction(|db| {
+        db.faucets()
+            .store_faucet_id(ZCASH_ORIGIN_NETWORK, &faucet_id)
+            .map_err(|e| e.context("Failed to store faucet_id"))?;
+        
+        let inputs = serde_json::json!({
+            "origin_network": ZCASH_ORIGIN_NETWORK,
+            "symbol": "TAZ",
+            "decimals": 8,
+            "max_supply": 1_000_000_000_000_000_000u64,
+            "approvers": multisig.as_ref().map(|multisig| multisig.approvers.iter().map(|approver| approver.to_hex()).collect::<Vec<_>>()),
+            "threshold": multisig.as_ref().map(|multisig| multisig.threshold),
+        });
+        db.audit()
+            .append(AuditAction::FaucetCreate, &inputs_digest(&inputs), Some(&faucet_id_bech32), "bridge")
+            .map_err(|e| e.context("Failed to append audit entry"))?;
+        Ok(())
+    })?;
+    
+    info!(
+        faucet_id = %faucet_id_bech32,
+        faucet_hex = %format!("0x{}", faucet_hex_padded),
+        "Created and stored Zcash testnet faucet in the bridge database"
+    );
     Ok(faucet_id)
 }
 
@@ -146,32 +198,29 @@ pub async fn mint_deposit_note_from_hash(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-) -> Result<(String, String), String> {
+) -> BridgeResult<(String, String)> {
     // Initialize Miden client
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path)
-            .map_err(|e| format!("Failed to create keystore: {}", e))?,
-    );
+    let signer = BridgeSigner::open(&keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path)
-        .authenticator(keystore.clone())
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Create asset (wTAZ tokens)
     let asset = FungibleAsset::new(faucet_id, amount)
-        .map_err(|e| format!("Failed to create asset: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create asset: {}", e)))?;
     
     let assets = NoteAssets::new(vec![asset.into()])
-        .map_err(|e| format!("Failed to create note assets: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create note assets: {}", e)))?;
     
     // Create note metadata
     use crate::miden::notes::BRIDGE_USECASE;
@@ -179,11 +228,11 @@ pub async fn mint_deposit_note_from_hash(
         faucet_id, // Sender is the faucet
         NoteType::Private,
         NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
-            .map_err(|e| format!("Invalid tag: {:?}", e))?,
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid tag: {:?}", e)))?,
         NoteExecutionHint::always(),
         Felt::ZERO,
     )
-    .map_err(|e| format!("Failed to create metadata: {}", e))?;
+    .map_err(|e| BridgeError::InvalidInput(format!("Failed to create metadata: {}", e)))?;
     
     // Create transaction to mint note with recipient hash
     // Following mono bridge pattern exactly: use recipient_hash.into() to match their code
@@ -193,31 +242,31 @@ pub async fn mint_deposit_note_from_hash(
             miden_objects::note::PartialNote::new(metadata, recipient_hash.into(), assets),
         )])
         .build()
-        .map_err(|e| format!("Failed to build transaction: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build transaction: {}", e)))?;
     
     // Execute transaction
     let tx_result = client
         .execute_transaction(faucet_id, tx_request)
         .await
-        .map_err(|e| format!("Failed to execute transaction: {}", e))?;
+        .map_err(|e| BridgeError::ProvingFailed(format!("Failed to execute transaction: {}", e)))?;
     
     // Prove transaction
     let proven_tx = client
         .prove_transaction(&tx_result)
         .await
-        .map_err(|e| format!("Failed to prove transaction: {}", e))?;
+        .map_err(|e| BridgeError::ProvingFailed(format!("Failed to prove transaction: {}", e)))?;
     
     // Submit transaction
     let submission_height = client
         .submit_proven_transaction(proven_tx, &tx_result)
         .await
-        .map_err(|e| format!("Failed to submit transaction: {}", e))?;
+        .map_err(|e| BridgeError::TransactionFailed(format!("Failed to submit transaction: {}", e)))?;
     
     // Apply transaction
     client
         .apply_transaction(&tx_result, submission_height)
         .await
-        .map_err(|e| format!("Failed to apply transaction: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to apply transaction: {}", e)))?;
     
     // Get note ID and transaction ID
     let note_id = tx_result
@@ -237,6 +286,7 @@ pub async fn mint_deposit_note_from_hash(
 /// 
 /// NOTE: account_id is required to build the P2ID recipient, but it's
 /// NOT stored in the database for privacy.
+#[tracing::instrument(name = "mint", skip_all, fields(faucet_id = %faucet_id, amount = amount, note_id = tracing::field::Empty))]
 pub async fn mint_deposit_note(
     account_id: AccountId,
     secret: Word,
@@ -245,69 +295,58 @@ pub async fn mint_deposit_note(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-) -> Result<(String, String), String> {
+) -> BridgeResult<(String, String)> {
     // Initialize Miden client
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
     
-    // Check if keystore directory exists
-    if !keystore_path.exists() {
-        return Err(format!(
-            "Keystore directory does not exist: {:?}. The faucet key must be in the keystore.",
-            keystore_path
-        ));
-    }
-    
-    println!("[Bridge] Using keystore path: {:?}", keystore_path);
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore at {:?}: {}", keystore_path, e))?,
-    );
+    // The faucet key is in the keystore, or with the signer process if SIGNER_SOCKET is set
+    debug!(keystore_path = ?keystore_path, "Using keystore");
+    let signer = BridgeSigner::open(&keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path.clone())
-        .authenticator(keystore.clone())
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Sync client state to ensure faucet account is loaded and up-to-date
-    println!("[Bridge] Syncing client state...");
-    client.sync_state().await
-        .map_err(|e| format!("Failed to sync client state: {}", e))?;
+    debug!("Syncing client state");
+    let sync_summary = client.sync_state().await
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+    metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
     
     // Check if faucet account exists in client
-    println!("[Bridge] Checking if faucet account exists in client...");
     match client.get_account(faucet_id).await {
         Ok(Some(_)) => {
-            println!("[Bridge] ✅ Faucet account found in client");
+            debug!("Faucet account found in client");
         }
         Ok(None) => {
-            // Account not in client - try to load it from store
-            // The account should be in the SQLite store if it was created
-            println!("[Bridge] ⚠️  Faucet account not in client, but should be in store");
-            println!("[Bridge] 💡 The account will be loaded when needed, but the key must be in keystore");
-            println!("[Bridge]    Keystore path: {:?}", keystore_path);
+            // Created on another host, e.g. the signer's: the faucet is public, so import it
+            warn!("Faucet account not in client; importing it from the chain");
+            client.import_account_by_id(faucet_id).await
+                .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to import faucet account: {}", e)))?;
         }
         Err(e) => {
-            return Err(format!("Failed to check faucet account: {}", e));
+            return Err(BridgeError::Internal(format!("Failed to check faucet account: {}", e)));
         }
     }
     
-    // The key must be in the keystore for the transaction to work
-    // FilesystemKeyStore should auto-load keys from the directory
+    // The key must be in the keystore (or the signer process) for the transaction to work
+    // The keystore decrypts every key in the directory when it is unlocked
     // If the key is missing, the transaction will fail with "missing secret key" error
     
     // Create asset (wTAZ tokens)
     let asset = FungibleAsset::new(faucet_id, amount)
-        .map_err(|e| format!("Failed to create asset: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create asset: {}", e)))?;
     
     let assets = NoteAssets::new(vec![asset.into()])
-        .map_err(|e| format!("Failed to create note assets: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create note assets: {}", e)))?;
     
     // Create note metadata
     // Use BRIDGE_USECASE tag (20050519) for our bridge
@@ -316,51 +355,45 @@ pub async fn mint_deposit_note(
         faucet_id, // Sender is the faucet
         NoteType::Private,
         NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
-            .map_err(|e| format!("Invalid tag: {:?}", e))?,
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid tag: {:?}", e)))?,
         NoteExecutionHint::always(),
         Felt::ZERO,
     )
-    .map_err(|e| format!("Failed to create metadata: {}", e))?;
+    .map_err(|e| BridgeError::InvalidInput(format!("Failed to create metadata: {}", e)))?;
     
     // Build recipient - P2ID note (requires account_id + secret)
-    // Log account_id for debugging
-    use miden_objects::utils::Serializable;
-    let account_bytes = account_id.to_bytes();
-    let account_hex: String = account_bytes.iter().map(|b| format!("{:02x}", b)).collect();
-    println!("[Bridge] Minting note for account_id:");
-    println!("[Bridge]   Hex: 0x{}", account_hex);
-    println!("[Bridge]   Bech32: {}", account_id.to_bech32(miden_objects::address::NetworkId::Testnet));
-    
-    let recipient = build_deposit_recipient(account_id, secret)
-        .map_err(|e| format!("Failed to build recipient: {}", e))?;
+    let recipient = build_deposit_recipient(account_id, secret)?;
     
-    println!("[Bridge]   Recipient digest: {}", recipient.digest().to_hex());
+    debug!(
+        account_id = %account_id.to_bech32(miden_objects::address::NetworkId::Testnet),
+        recipient_digest = %recipient.digest().to_hex(),
+        "Minting note"
+    );
     
     // Create a complete note with full recipient (as per test script - uses OutputNote::Full)
     let note = Note::new(assets, metadata, recipient);
     let note_id = note.id().to_hex();
+    tracing::Span::current().record("note_id", note_id.as_str());
     
     // Create transaction to mint note using OutputNote::Full (complete note)
     let tx_request = TransactionRequestBuilder::new()
         .own_output_notes(vec![OutputNote::Full(note)])
         .build()
-        .map_err(|e| format!("Failed to build transaction: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build transaction: {}", e)))?;
     
     // Submit transaction (like other mint scripts - simpler than execute/prove/submit)
-    println!("[Bridge] Submitting transaction...");
+    debug!("Submitting transaction");
     let tx_id = client
         .submit_new_transaction(faucet_id, tx_request)
         .await
         .map_err(|e| {
             let error_msg = format!("Failed to submit transaction: {}", e);
-            let error_debug = format!("{:?}", e);
-            println!("[Bridge] ❌ Transaction submission error: {}", error_msg);
-            println!("[Bridge] ❌ Error details: {}", error_debug);
-            println!("[Bridge] 💡 Make sure:");
-            println!("[Bridge]    1. The faucet account exists and is deployed on-chain");
-            println!("[Bridge]    2. The faucet key is in the keystore");
-            println!("[Bridge]    3. The faucet account has been synced to the latest block");
-            error_msg
+            error!(
+                error = %e,
+                details = ?e,
+                "Transaction submission failed; check the faucet is deployed on-chain, its key is in the keystore and it is synced"
+            );
+            BridgeError::TransactionFailed(error_msg)
         })?;
     
     let tx_id_str = format!("{:?}", tx_id);
@@ -0,0 +1,169 @@
+use crate::error::{BridgeError, BridgeResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
+
+/// Direction of a bridge transfer, used to pick the fee rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeDirection {
+    /// Zcash → Miden (fee is taken out of the minted wTAZ)
+    Deposit,
+    /// Miden → Zcash (fee is taken out of the TAZ payout)
+    Withdrawal,
+    /// Rejected deposit sent back to Zcash (fee is taken out of the refund)
+    Refund,
+}
+
+impl FeeDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeDirection::Deposit => "deposit",
+            FeeDirection::Withdrawal => "withdrawal",
+            FeeDirection::Refund => "refund",
+        }
+    }
+
+    pub fn parse(direction: &str) -> BridgeResult<Self> {
+        match direction.trim().to_lowercase().as_str() {
+            "deposit" => Ok(FeeDirection::Deposit),
+            "withdrawal" => Ok(FeeDirection::Withdrawal),
+            "refund" => Ok(FeeDirection::Refund),
+            other => Err(BridgeError::InvalidInput(format!(
+                "Unknown fee direction '{}' (expected deposit, withdrawal or refund)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Fee rule for one token and direction
+///
+/// fee = clamp(flat + amount * bps / 10_000, min, max)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeRule {
+    /// Flat fee in base units
+    #[serde(default)]
+    pub flat: u64,
+    /// Proportional fee in basis points (1 bps = 0.01%)
+    #[serde(default)]
+    pub bps: u32,
+    /// Minimum fee in base units
+    #[serde(default)]
+    pub min: u64,
+    /// Maximum fee in base units (no cap if not set)
+    #[serde(default)]
+    pub max: Option<u64>,
+}
+
+impl FeeRule {
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        // u128 so large amounts can't overflow before the division
+        let proportional = (amount as u128 * self.bps as u128 / 10_000) as u64;
+        let fee = self.flat.saturating_add(proportional).max(self.min);
+        match self.max {
+            Some(max) => fee.min(max),
+            None => fee,
+        }
+    }
+}
+
+/// Fee rules for each direction of one token
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenFees {
+    #[serde(default)]
+    pub deposit: FeeRule,
+    #[serde(default)]
+    pub withdrawal: FeeRule,
+    #[serde(default)]
+    pub refund: FeeRule,
+}
+
+/// Fee schedule keyed by token (origin network, e.g. "zcash_testnet")
+///
+/// Loaded from the JSON file at `BRIDGE_FEE_SCHEDULE` (default: `fee_schedule.json`
+/// in the project root):
+///
+/// ```json
+/// {
+///   "zcash_testnet": {
+///     "deposit":    { "flat": 10000, "bps": 10, "min": 10000, "max": 1000000 },
+///     "withdrawal": { "flat": 15000 }
+///   }
+/// }
+/// ```
+///
+/// Tokens or directions missing from the file are charged no fee. Refunds have their own rule
+/// so a deposit too small to cover the deposit fee can still be sent back; leave it out, or set
+/// just the network fee, to refund every rejected deposit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    #[serde(flatten)]
+    tokens: HashMap<String, TokenFees>,
+}
+
+/// Fee quote for a transfer: what the user sends, what the bridge keeps, what arrives
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeQuote {
+    pub direction: FeeDirection,
+    pub token: String,
+    pub gross_amount: u64,
+    pub fee: u64,
+    pub net_amount: u64,
+}
+
+impl FeeSchedule {
+    /// Load the fee schedule, falling back to an empty (zero-fee) schedule if no file exists
+    pub fn load(project_root: &Path) -> BridgeResult<Self> {
+        let path = std::env::var("BRIDGE_FEE_SCHEDULE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| project_root.join("fee_schedule.json"));
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| BridgeError::Internal(format!("Failed to read fee schedule {:?}: {}", path, e)))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse fee schedule {:?}: {}", path, e)))
+    }
+
+    /// Get the fee rule for a token and direction
+    pub fn rule(&self, direction: FeeDirection, token: &str) -> FeeRule {
+        self.tokens
+            .get(token)
+            .map(|fees| match direction {
+                FeeDirection::Deposit => fees.deposit.clone(),
+                FeeDirection::Withdrawal => fees.withdrawal.clone(),
+                FeeDirection::Refund => fees.refund.clone(),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Quote the fee for a transfer
+    ///
+    /// Fails if the fee would consume the whole amount.
+    pub fn quote(&self, direction: FeeDirection, token: &str, amount: u64) -> BridgeResult<FeeQuote> {
+        let fee = self.rule(direction, token).fee_for(amount);
+
+        if fee >= amount {
+            return Err(BridgeError::InvalidInput(format!(
+                "Amount {} does not cover the {} fee of {} base units",
+                amount,
+                direction.as_str(),
+                fee
+            )));
+        }
+
+        Ok(FeeQuote {
+            direction,
+            token: token.to_string(),
+            gross_amount: amount,
+            fee,
+            net_amount: amount - fee,
+        })
+    }
+}
@@ -0,0 +1,169 @@
+use miden_crypto::hash::rpo::Rpo256;
+use miden_objects::{account::AccountId, Word};
+use crate::error::{BridgeError, BridgeResult};
+
+/// Prefix of the optional memo segment carrying a Zcash refund address
+/// e.g. `<account_id>|<secret>|refund=utest1...`
+pub const REFUND_SEGMENT_PREFIX: &str = "refund=";
+
+/// Characters used in deposit intent reference codes (no 0/O, 1/I/L or U to avoid typos)
+pub const REFERENCE_CODE_ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Length of a deposit intent reference code
+pub const REFERENCE_CODE_LEN: usize = 8;
+
+/// Deposit memo parsed from an incoming Zcash transaction
+#[derive(Debug, Clone)]
+pub struct DepositMemo {
+    pub account_id: AccountId,
+    pub secret: Word,
+    pub refund_address: Option<String>,
+}
+
+/// Strip the `Memo::Text("...")` wrapper that zcash-devtool prints around text memos
+pub fn memo_content(memo: &str) -> &str {
+    memo.trim()
+        .strip_prefix("Memo::Text(\"")
+        .and_then(|s| s.strip_suffix("\")"))
+        .unwrap_or_else(|| memo.trim())
+}
+
+/// Split the `refund=<address>` segment off a memo
+///
+/// Returns the remaining memo payload and the refund address, if any.
+pub fn split_refund_address(memo_content: &str) -> (String, Option<String>) {
+    let mut refund_address = None;
+    let mut payload = Vec::new();
+
+    for segment in memo_content.split('|') {
+        match segment.trim().strip_prefix(REFUND_SEGMENT_PREFIX) {
+            Some(address) if !address.trim().is_empty() => {
+                refund_address = Some(address.trim().to_string());
+            }
+            _ => payload.push(segment),
+        }
+    }
+
+    (payload.join("|"), refund_address)
+}
+
+/// Parse an `account_id|secret` deposit memo
+///
+/// Fails with `InvalidInput` carrying the reason the deposit can't be minted. That reason
+/// is recorded in the refund queue so the user can see why their deposit was rejected,
+/// and logged, so it never quotes the memo: it's the user's only proof of ownership.
+pub fn parse_deposit_memo(memo_content: &str) -> BridgeResult<DepositMemo> {
+    let (payload, refund_address) = split_refund_address(memo_content);
+
+    let Some(pipe_pos) = payload.find('|') else {
+        return Err(BridgeError::InvalidInput(unsupported_memo_reason(&payload)));
+    };
+
+    let account_id_str = &payload[..pipe_pos];
+    let secret_str = &payload[pipe_pos + 1..];
+
+    // Validate account_id (should be 30 hex chars = 15 bytes, with or without 0x)
+    // AccountId::from_hex expects 0x + 30 hex chars = 32 total chars
+    let account_id_hex = account_id_str.strip_prefix("0x").unwrap_or(account_id_str);
+
+    if !account_id_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(BridgeError::InvalidInput("account_id contains non-hex characters".to_string()));
+    }
+
+    if account_id_hex.len() > 30 {
+        return Err(BridgeError::InvalidInput(format!(
+            "account_id too long (max 30 hex chars, got {})",
+            account_id_hex.len()
+        )));
+    }
+
+    // Pad with leading zeros to 30 chars if needed (AccountId expects 30 hex chars)
+    let account_id_for_parse = format!("0x{:0>30}", account_id_hex);
+
+    // Validate secret (should be 64 hex chars, with or without 0x)
+    let secret_hex = secret_str.strip_prefix("0x").unwrap_or(secret_str);
+
+    if secret_hex.len() != 64 || !secret_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(BridgeError::InvalidInput(format!(
+            "invalid secret format (expected 64 hex chars, got {} chars)",
+            secret_hex.len()
+        )));
+    }
+
+    let account_id = AccountId::from_hex(&account_id_for_parse)
+        .map_err(|e| BridgeError::InvalidInput(format!("invalid account_id: {}", e)))?;
+
+    let secret = Word::try_from(format!("0x{}", secret_hex).as_str())
+        .map_err(|e| BridgeError::InvalidInput(format!("invalid secret: {}", e)))?;
+
+    Ok(DepositMemo {
+        account_id,
+        secret,
+        refund_address,
+    })
+}
+
+/// Explain why a memo without an `account_id|secret` pair can't be minted
+fn unsupported_memo_reason(payload: &str) -> String {
+    let is_legacy_hash = payload.len() == 66
+        && payload.starts_with("0x")
+        && payload[2..].chars().all(|c| c.is_ascii_hexdigit());
+
+    if is_legacy_hash {
+        "memo uses the old recipient hash format, which can no longer be minted (use account_id|secret)".to_string()
+    } else {
+        format!(
+            "invalid memo format (expected account_id|secret or 0x + 64 hex chars, got {} chars)",
+            payload.len()
+        )
+    }
+}
+
+/// Generate a new deposit intent reference code
+pub fn generate_reference_code() -> String {
+    use rand::Rng;
+    let mut rng = rand::rng();
+    (0..REFERENCE_CODE_LEN)
+        .map(|_| REFERENCE_CODE_ALPHABET[rng.random_range(0..REFERENCE_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Parse a memo that only carries a deposit intent reference code
+///
+/// Codes are case-insensitive. The memo may still carry a `refund=<address>` segment.
+pub fn parse_reference_code(memo_content: &str) -> Option<String> {
+    let (payload, _) = split_refund_address(memo_content);
+    let code = payload.trim().to_uppercase();
+
+    let is_code = code.len() == REFERENCE_CODE_LEN
+        && code.bytes().all(|b| REFERENCE_CODE_ALPHABET.contains(&b));
+
+    is_code.then_some(code)
+}
+
+/// Extract the secret part of a memo, if it looks like one (64 hex chars after the first `|`)
+///
+/// Used to remember a digest of the secret for rejected deposits, even if the
+/// account_id part of the memo was unusable.
+pub fn memo_secret_hex(memo_content: &str) -> Option<String> {
+    let (payload, _) = split_refund_address(memo_content);
+    let secret_str = payload.split_once('|')?.1;
+    let secret_hex = normalize_hex(secret_str);
+
+    if secret_hex.len() == 64 && secret_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(secret_hex)
+    } else {
+        None
+    }
+}
+
+/// Lowercase hex without 0x prefix
+pub fn normalize_hex(value: &str) -> String {
+    let value = value.trim();
+    value.strip_prefix("0x").unwrap_or(value).to_lowercase()
+}
+
+/// Digest used to prove knowledge of a memo or secret without storing it
+pub fn knowledge_digest(value: &str) -> String {
+    Word::from(Rpo256::hash(value.as_bytes())).to_hex()
+}
@@ -1,7 +1,16 @@
+use crate::account::signer::BridgeSigner;
+use crate::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use crate::bridge::fees::{FeeDirection, FeeSchedule};
+use crate::bridge::payouts::{self, PayoutSigning};
+use crate::bridge::solvency::SolvencyGuard;
+use crate::db::{self, BridgeStore};
+use crate::db::audit::{inputs_digest, AuditAction};
+use crate::db::events::EventState;
+use crate::db::payouts::PayoutKind;
+use crate::metrics;
 use crate::zcash::bridge_wallet::BridgeWallet;
 use miden_client::{
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
     rpc::{Endpoint, GrpcClient},
     store::NoteFilter,
 };
@@ -9,13 +18,16 @@ use miden_client_sqlite_store::ClientBuilderSqliteExt;
 use miden_objects::{
     note::NoteTag,
 };
-use rand::rngs::StdRng;
+use crate::error::{BridgeError, BridgeResult};
 use crate::miden::notes::{BRIDGE_USECASE, decode_zcash_address};
-use crate::db::withdrawals::WithdrawalTracker;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::time::{Duration, interval};
+use tracing::{debug, error, info, info_span, warn};
+
+/// Destination chain id of exit notes paying out on Zcash testnet (matches withdrawal note creation in main.rs)
+pub(crate) const ZCASH_TESTNET_CHAIN_ID: u64 = 2;
 
 /// Miden exit relayer that polls for burn notes and sends Zcash transactions
 pub struct MidenExitRelayer {
@@ -24,7 +36,10 @@ pub struct MidenExitRelayer {
     scan_interval: Duration,
     processed_note_ids: Arc<Mutex<HashSet<String>>>,
     last_scanned_block: Arc<Mutex<u32>>,
-    withdrawal_tracker: Arc<Mutex<WithdrawalTracker>>,
+    fee_schedule: FeeSchedule,
+    db: Arc<Mutex<Box<dyn BridgeStore>>>,
+    solvency: SolvencyGuard,
+    payout_signing: PayoutSigning,
 }
 
 impl MidenExitRelayer {
@@ -33,9 +48,14 @@ impl MidenExitRelayer {
         project_root: PathBuf,
         scan_interval_secs: u64,
     ) -> Self {
-        let withdrawal_db_path = project_root.join("withdrawals.db");
-        let withdrawal_tracker = WithdrawalTracker::new(withdrawal_db_path)
-            .expect("Failed to initialize withdrawal tracker");
+        let fee_schedule = FeeSchedule::load(&project_root)
+            .expect("Failed to load fee schedule");
+        let db = db::open(&project_root)
+            .expect("Failed to open bridge database");
+        
+        let solvency = SolvencyGuard::new(bridge_wallet.clone(), project_root.clone());
+        let payout_signing = PayoutSigning::from_env()
+            .expect("Invalid payout signing configuration");
         
         Self {
             bridge_wallet,
@@ -43,85 +63,90 @@ impl MidenExitRelayer {
             scan_interval: Duration::from_secs(scan_interval_secs),
             processed_note_ids: Arc::new(Mutex::new(HashSet::new())),
             last_scanned_block: Arc::new(Mutex::new(0)),
-            withdrawal_tracker: Arc::new(Mutex::new(withdrawal_tracker)),
+            fee_schedule,
+            db: Arc::new(Mutex::new(db)),
+            solvency,
+            payout_signing,
         }
     }
 
     pub async fn start(&self) {
-        println!("[Miden Exit Relayer] Starting...");
+        info!(scan_interval_secs = self.scan_interval.as_secs(), "Starting Miden exit relayer");
         let mut interval = interval(self.scan_interval);
 
         loop {
             interval.tick().await;
+            let mut healthy = true;
             // Scan for old-style withdrawals (with zcash address in note)
             if let Err(e) = self.scan_and_process_exits().await {
-                eprintln!("[Miden Exit Relayer] Error scanning exits: {}", e);
+                error!(error = %e, "Error scanning exits");
+                healthy = false;
             }
             // Scan for commitment-based withdrawals (new method)
             if let Err(e) = self.scan_commitment_withdrawals().await {
-                eprintln!("[Miden Exit Relayer] Error scanning commitment withdrawals: {}", e);
+                error!(error = %e, "Error scanning commitment withdrawals");
+                healthy = false;
+            }
+            if healthy {
+                metrics::record_relayer_success("miden_exit");
             }
         }
     }
     
     /// Scan for public notes with withdrawal commitments
-    async fn scan_commitment_withdrawals(&self) -> Result<(), String> {
-        println!("[Miden Exit Relayer] Scanning for commitment-based withdrawals...");
+    async fn scan_commitment_withdrawals(&self) -> BridgeResult<()> {
+        debug!("Scanning for commitment-based withdrawals");
         
         // Initialize Miden client
         let endpoint = Endpoint::try_from("https://rpc.testnet.miden.io")
-            .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
         
         let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
         let keystore_path = self.project_root.join("rust-backend").join("keystore");
         let store_path = self.project_root.join("bridge_store.sqlite3");
 
-        if !keystore_path.exists() {
-            return Err("Keystore directory does not exist".to_string());
-        }
-
-        let keystore = Arc::new(
-            FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-                .map_err(|e| format!("Failed to create keystore: {}", e))?,
-        );
+        // The keystore, or the signer process if SIGNER_SOCKET is set
+        let signer = BridgeSigner::open(&keystore_path)?;
 
         let mut client = ClientBuilder::new()
             .rpc(rpc_client)
             .sqlite_store(store_path)
-            .authenticator(keystore)
+            .authenticator(signer)
             .in_debug_mode(true.into())
             .build()
             .await
-            .map_err(|e| format!("Failed to build client: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
 
         // Add withdrawal note tag (BRIDGE_USECASE, 1)
         let withdrawal_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 1)
-            .map_err(|e| format!("Failed to create withdrawal tag: {:?}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to create withdrawal tag: {:?}", e)))?;
         client.add_note_tag(withdrawal_tag).await
-            .map_err(|e| format!("Failed to add note tag: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to add note tag: {}", e)))?;
 
         // Sync state
-        client.sync_state().await
-            .map_err(|e| format!("Failed to sync client state: {}", e))?;
+        let sync_summary = client.sync_state().await
+            .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+        metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
 
         // Get output notes (public notes that were created)
         // These are notes that were output from transactions
         let notes = client.get_output_notes(NoteFilter::All).await
-            .map_err(|e| format!("Failed to get output notes: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to get output notes: {}", e)))?;
 
-        println!("[Miden Exit Relayer] Found {} output notes", notes.len());
+        debug!(notes = notes.len(), "Found output notes");
 
-        let withdrawal_tracker = self.withdrawal_tracker.lock()
-            .map_err(|e| format!("Failed to lock withdrawal tracker: {}", e))?;
+        let db = self.db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)))?;
 
         for note_record in notes.iter() {
             let note_id = note_record.id().to_hex();
+            let _span = info_span!("withdrawal", note_id = %note_id).entered();
             
             // Check if note has withdrawal tag
             let metadata = note_record.metadata();
             
             let withdrawal_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 1)
-                .map_err(|e| format!("Failed to create tag: {:?}", e))?;
+                .map_err(|e| BridgeError::Internal(format!("Failed to create tag: {:?}", e)))?;
             
             if metadata.tag() != withdrawal_tag {
                 continue;
@@ -135,11 +160,11 @@ impl MidenExitRelayer {
             // Extract commitment from note recipient inputs
             // For output notes, inputs are in the recipient
             let recipient = note_record.recipient()
-                .ok_or_else(|| "Note missing recipient".to_string())?;
+                .ok_or_else(|| BridgeError::Internal("Note missing recipient".to_string()))?;
             let inputs = recipient.inputs().values();
             
             if inputs.len() < 4 {
-                println!("[Miden Exit Relayer] Note {} has insufficient inputs for commitment, skipping", note_id);
+                debug!("Note has insufficient inputs for commitment, skipping");
                 continue;
             }
 
@@ -159,15 +184,24 @@ impl MidenExitRelayer {
             let commitment_hex = format!("0x{}", commitment_hex);
             
             // Check if already in database
-            if withdrawal_tracker.get_withdrawal(&commitment_hex)
-                .map_err(|e| format!("Failed to check withdrawal: {}", e))?
+            if db.withdrawals().get_withdrawal(&commitment_hex)
+                .map_err(|e| e.context("Failed to check withdrawal"))?
                 .is_some() {
+                // The note is on chain now, so the withdrawal can be claimed
+                if let Some(inclusion_proof) = note_record.inclusion_proof() {
+                    let block = inclusion_proof.location().block_num().as_u32().to_string();
+                    let recorded = db.events()
+                        .record_withdrawal(&commitment_hex, EventState::Committed, Some(&note_id), Some(&block));
+                    if let Err(e) = recorded {
+                        warn!(error = %e, "Failed to record withdrawal event");
+                    }
+                }
                 continue; // Already stored
             }
             
             // Get block number
             let inclusion_proof = note_record.inclusion_proof()
-                .ok_or_else(|| "Note missing inclusion proof".to_string())?;
+                .ok_or_else(|| BridgeError::Internal("Note missing inclusion proof".to_string()))?;
             let block_number = inclusion_proof.location().block_num().as_u32();
             
             // Amount is not in the note (it was sent to faucet separately)
@@ -176,10 +210,7 @@ impl MidenExitRelayer {
             // Actually, we should get amount from the private note that was consumed
             // But that's complex. Let's store it when the withdrawal is created.
             
-            println!("[Miden Exit Relayer] Found new commitment withdrawal:");
-            println!("  Note ID: {}", note_id);
-            println!("  Commitment: {}...", &commitment_hex[..30]);
-            println!("  Block: {}", block_number);
+            info!(commitment = %commitment_hex, block = block_number, "Found new commitment withdrawal");
             
             // Note: Amount should already be in database from withdrawal creation
             // This scan just updates block_number if needed
@@ -189,50 +220,45 @@ impl MidenExitRelayer {
         Ok(())
     }
 
-    async fn scan_and_process_exits(&self) -> Result<(), String> {
-        println!("[Miden Exit Relayer] Scanning for exit events...");
+    async fn scan_and_process_exits(&self) -> BridgeResult<()> {
+        debug!("Scanning for exit events");
 
         // Initialize Miden client
         let endpoint = Endpoint::try_from("https://rpc.testnet.miden.io")
-            .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
         
         let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
         let keystore_path = self.project_root.join("rust-backend").join("keystore");
         let store_path = self.project_root.join("bridge_store.sqlite3");
 
-        if !keystore_path.exists() {
-            return Err("Keystore directory does not exist".to_string());
-        }
-
-        let keystore = Arc::new(
-            FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-                .map_err(|e| format!("Failed to create keystore: {}", e))?,
-        );
+        // The keystore, or the signer process if SIGNER_SOCKET is set
+        let signer = BridgeSigner::open(&keystore_path)?;
 
         let mut client = ClientBuilder::new()
             .rpc(rpc_client)
             .sqlite_store(store_path)
-            .authenticator(keystore)
+            .authenticator(signer)
             .in_debug_mode(true.into())
             .build()
             .await
-            .map_err(|e| format!("Failed to build client: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
 
         // Add bridge note tag
         let bridge_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
-            .map_err(|e| format!("Failed to create bridge tag: {:?}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to create bridge tag: {:?}", e)))?;
         client.add_note_tag(bridge_tag).await
-            .map_err(|e| format!("Failed to add note tag: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to add note tag: {}", e)))?;
 
         // Sync state
-        client.sync_state().await
-            .map_err(|e| format!("Failed to sync client state: {}", e))?;
+        let sync_summary = client.sync_state().await
+            .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+        metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
 
         // Get last scanned block
         let last_block = {
             let mut last = self.last_scanned_block.lock().unwrap();
             let current = client.get_sync_height().await
-                .map_err(|e| format!("Failed to get sync height: {}", e))?
+                .map_err(|e| BridgeError::Internal(format!("Failed to get sync height: {}", e)))?
                 .as_u32();
             
             let start_block = if *last == 0 {
@@ -248,9 +274,9 @@ impl MidenExitRelayer {
 
         // Get committed input notes (these are notes that were consumed)
         let notes = client.get_input_notes(NoteFilter::Committed).await
-            .map_err(|e| format!("Failed to get input notes: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to get input notes: {}", e)))?;
 
-        println!("[Miden Exit Relayer] Found {} committed notes", notes.len());
+        debug!(notes = notes.len(), "Found committed notes");
 
         // Get list of already processed note IDs (clone to avoid holding lock during processing)
         let processed_ids: HashSet<String> = {
@@ -262,6 +288,8 @@ impl MidenExitRelayer {
         for note_record in notes.iter() {
             let note_id = note_record.id().to_hex();
             
+            let _span = info_span!("withdrawal", note_id = %note_id).entered();
+            
             // Skip if already processed
             if processed_ids.contains(&note_id) {
                 continue;
@@ -269,7 +297,7 @@ impl MidenExitRelayer {
 
             // Check if note has bridge tag
             let metadata = note_record.metadata()
-                .ok_or_else(|| "Note missing metadata".to_string())?;
+                .ok_or_else(|| BridgeError::Internal("Note missing metadata".to_string()))?;
             
             if metadata.tag() != bridge_tag {
                 continue;
@@ -277,7 +305,7 @@ impl MidenExitRelayer {
 
             // Check if note was consumed after our last scan
             let inclusion_proof = note_record.inclusion_proof()
-                .ok_or_else(|| "Note missing inclusion proof".to_string())?;
+                .ok_or_else(|| BridgeError::Internal("Note missing inclusion proof".to_string()))?;
             
             let block_num = inclusion_proof.location().block_num().as_u32();
             if block_num < last_block {
@@ -291,18 +319,15 @@ impl MidenExitRelayer {
             let inputs = details.inputs().values();
             
             if inputs.len() < 8 {
-                println!("[Miden Exit Relayer] Note {} has insufficient inputs, skipping", note_id);
+                debug!("Note has insufficient inputs, skipping");
                 continue;
             }
 
             // Extract destination chain (input[4])
             let dest_chain = inputs[4].as_int();
             
-            // Zcash testnet chain ID (matches withdrawal note creation in main.rs)
-            const ZCASH_TESTNET_CHAIN_ID: u64 = 2;
-            
             if dest_chain != ZCASH_TESTNET_CHAIN_ID {
-                println!("[Miden Exit Relayer] Note {} is for chain {}, not Zcash (expected {}), skipping", note_id, dest_chain, ZCASH_TESTNET_CHAIN_ID);
+                debug!(dest_chain, expected = ZCASH_TESTNET_CHAIN_ID, "Note is not for Zcash, skipping");
                 continue;
             }
 
@@ -318,7 +343,7 @@ impl MidenExitRelayer {
             // Note: We need to store the original address mapping or use deterministic encoding
             // For now, we'll use a hash-based approach (same as encode_zcash_address)
             let zcash_address = decode_zcash_address(zcash_address_felts)
-                .map_err(|e| format!("Failed to decode Zcash address: {}", e))?;
+                .map_err(|e| e.context("Failed to decode Zcash address"))?;
 
             // Extract amount from note assets (not inputs!)
             // The amount is in the fungible asset that was burned
@@ -334,28 +359,92 @@ impl MidenExitRelayer {
             }
             
             if amount == 0 {
-                println!("[Miden Exit Relayer] Note {} has no fungible assets or zero amount, skipping", note_id);
+                info!("Note has no fungible assets or zero amount, skipping");
                 continue;
             }
 
-            println!("[Miden Exit Relayer] Processing exit:");
-            println!("  Note ID: {}", note_id);
-            println!("  Zcash Address: {}", zcash_address);
-            println!("  Amount: {} (base units)", amount);
+            // Deduct the withdrawal fee - the payout is the net amount
+            let quote = match self.fee_schedule.quote(FeeDirection::Withdrawal, ZCASH_ORIGIN_NETWORK, amount) {
+                Ok(quote) => quote,
+                Err(e) => {
+                    info!(reason = %e, "Skipping exit");
+                    continue;
+                }
+            };
+
+            // Payouts halt while reserves don't cover the outstanding supply;
+            // rewind so this and later exits are picked up again on the next scan
+            if let Err(e) = self.solvency.ensure_solvent().await {
+                warn!(error = %e, "Not paying out exit");
+                *self.last_scanned_block.lock().unwrap() = last_block.saturating_sub(1);
+                return Ok(());
+            }
+
+            info!(zcash_address = %zcash_address, amount, fee = quote.fee, "Processing exit");
+
+            // Offline signing: queue the PCZT; bridgectl records the payout once it's broadcast
+            if self.payout_signing == PayoutSigning::Offline {
+                let queued = payouts::queue(
+                    &self.db,
+                    &self.bridge_wallet,
+                    PayoutKind::Exit,
+                    &note_id,
+                    &note_id,
+                    &zcash_address,
+                    &quote,
+                );
+                match queued {
+                    Ok(_) => {
+                        self.processed_note_ids.lock().unwrap().insert(note_id);
+                    }
+                    Err(e) => error!(error = %e, "Failed to queue Zcash payout"),
+                }
+                continue;
+            }
 
             // Send Zcash transaction
-            let amount_taz = amount as f64 / 1e8;
+            let amount_taz = quote.net_amount as f64 / 1e8;
             let amount_str = format!("{:.8}", amount_taz);
-            match self.bridge_wallet.send(&zcash_address, &amount_str, None, None) {
+            let payout_timer = metrics::PAYOUT_LATENCY.start_timer();
+            let send_result = self.bridge_wallet.send(&zcash_address, &amount_str, None, None);
+            payout_timer.observe_duration();
+            match send_result {
                 Ok(txid) => {
-                    println!("[Miden Exit Relayer] ✅ Sent {} TAZ to {}: {}", amount_taz, zcash_address, txid);
+                    metrics::WITHDRAWALS_PAID.inc();
+                    info!(zcash_txid = %txid, amount_taz, "Sent Zcash payout");
+                    
+                    let db = self.db.lock().unwrap();
+                    
+                    // Accrue the fee against the burn note
+                    if let Err(e) = db.fees().record_fee(&quote, &note_id) {
+                        warn!(error = %e, "Failed to record fee for exit");
+                    }
+                    
+                    let inputs = serde_json::json!({
+                        "note_id": note_id,
+                        "zcash_address": zcash_address,
+                        "amount": amount,
+                        "fee": quote.fee,
+                    });
+                    let audited = db.audit()
+                        .append(AuditAction::WithdrawalPayout, &inputs_digest(&inputs), Some(&txid), "exit_relayer");
+                    if let Err(e) = audited {
+                        warn!(error = %e, "Failed to append audit entry for exit");
+                    }
+                    
+                    // Legacy exits have no commitment, so subscribers follow them by burn note id
+                    let recorded = db.events()
+                        .record_withdrawal(&note_id, EventState::Paid, Some(&note_id), Some(&txid));
+                    if let Err(e) = recorded {
+                        warn!(error = %e, "Failed to record withdrawal event");
+                    }
                     
                     // Mark as processed
                     let mut processed = self.processed_note_ids.lock().unwrap();
                     processed.insert(note_id);
                 }
                 Err(e) => {
-                    eprintln!("[Miden Exit Relayer] ❌ Failed to send Zcash: {}", e);
+                    error!(error = %e, "Failed to send Zcash payout");
                     // Don't mark as processed so we can retry
                 }
             }
@@ -1,4 +1,12 @@
+pub mod accounting;
 pub mod deposit;
+pub mod fees;
+pub mod memo;
 pub mod relayer;
 pub mod miden_exit_relayer;
+pub mod multisig;
+pub mod payouts;
+pub mod reconcile;
+pub mod solvency;
 
+pub mod webhooks;
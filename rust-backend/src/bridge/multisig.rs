@@ -0,0 +1,495 @@
+use crate::account::signer::BridgeSigner;
+use crate::db::BridgeStore;
+use crate::db::mint_approvals::{MintProposal, MintSignature, ProposalStatus};
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use crate::miden::notes::{withdrawal_commitment_note, BRIDGE_USECASE};
+use miden_client::{
+    auth::{PublicKeyCommitment, Signature, SigningInputs, TransactionAuthenticator},
+    builder::ClientBuilder,
+    rpc::{Endpoint, GrpcClient},
+    transaction::{OutputNote, TransactionExecutorError, TransactionRequest, TransactionRequestBuilder},
+    Client, ClientError,
+};
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use miden_crypto::hash::rpo::Rpo256;
+use miden_objects::{
+    account::AccountId,
+    asset::FungibleAsset,
+    note::{NoteAssets, NoteExecutionHint, NoteMetadata, NoteTag, NoteType, PartialNote},
+    transaction::TransactionSummary,
+    utils::{Deserializable, Serializable},
+    FieldElement, Felt, Word,
+};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// M-of-N approval of wTAZ mints, loaded from the environment
+///
+/// MINT_APPROVERS   comma-separated public key commitments (hex) of the approvers; unset keeps
+///                  the single-key faucet
+/// MINT_THRESHOLD   signatures a mint needs (default: all approvers)
+/// MINT_SIGNER_KEY  this instance's approver commitment, whose key is in its keystore or signer
+///                  process; without it the instance proposes and submits mints but doesn't
+///                  sign them
+///
+/// The approvers are built into the faucet when it is created, so changing them needs a new faucet.
+#[derive(Debug, Clone)]
+pub struct MultisigConfig {
+    pub approvers: Vec<Word>,
+    pub threshold: u32,
+    pub signer: Option<Word>,
+}
+
+impl MultisigConfig {
+    /// The multisig setup, or None for a faucet controlled by one key
+    pub fn from_env() -> BridgeResult<Option<Self>> {
+        let Ok(approvers) = std::env::var("MINT_APPROVERS") else {
+            return Ok(None);
+        };
+        let mut approvers = approvers.split(',')
+            .map(str::trim)
+            .filter(|approver| !approver.is_empty())
+            .map(parse_commitment)
+            .collect::<BridgeResult<Vec<_>>>()?;
+        if approvers.is_empty() {
+            return Ok(None);
+        }
+        let count = approvers.len();
+        approvers.sort();
+        approvers.dedup();
+        if approvers.len() != count {
+            return Err(BridgeError::InvalidInput("MINT_APPROVERS lists an approver twice".to_string()));
+        }
+
+        let threshold = match std::env::var("MINT_THRESHOLD") {
+            Ok(threshold) => threshold.trim().parse::<u32>()
+                .map_err(|e| BridgeError::InvalidInput(format!("Invalid MINT_THRESHOLD: {}", e)))?,
+            Err(_) => approvers.len() as u32,
+        };
+        if threshold == 0 || threshold as usize > approvers.len() {
+            return Err(BridgeError::InvalidInput(format!(
+                "MINT_THRESHOLD must be between 1 and the {} approvers",
+                approvers.len()
+            )));
+        }
+
+        let signer = std::env::var("MINT_SIGNER_KEY").ok()
+            .map(|signer| parse_commitment(signer.trim()))
+            .transpose()?;
+        if let Some(signer) = signer
+            && !approvers.contains(&signer)
+        {
+            return Err(BridgeError::InvalidInput("MINT_SIGNER_KEY is not one of MINT_APPROVERS".to_string()));
+        }
+
+        Ok(Some(Self { approvers, threshold, signer }))
+    }
+}
+
+/// Public key commitment as hex, with or without 0x
+fn parse_commitment(commitment: &str) -> BridgeResult<Word> {
+    let hex = commitment.strip_prefix("0x").unwrap_or(commitment);
+    Word::try_from(format!("0x{}", hex).as_str())
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid public key commitment {}: {}", commitment, e)))
+}
+
+/// One instance's own view of a deposit, which a proposal has to match before it signs
+#[derive(Debug, Clone)]
+pub struct DepositView {
+    pub deposit: String, // Zcash output key (txid:pool:index)
+    pub recipient_hash: String,
+    pub amount: u64,
+    pub net_amount: u64, // after this instance's deposit fee quote
+}
+
+/// Proposal keys of withdrawal commitment notes start with this, next to deposits' txid:pool:index
+const WITHDRAWAL_PREFIX: &str = "withdrawal:";
+
+impl DepositView {
+    /// A withdrawal's commitment note as a proposal: the commitment stands in for the recipient,
+    /// and nothing is minted
+    pub fn withdrawal(commitment: &str, amount: u64) -> Self {
+        Self {
+            deposit: format!("{}{}", WITHDRAWAL_PREFIX, commitment),
+            recipient_hash: commitment.to_string(),
+            amount,
+            net_amount: 0,
+        }
+    }
+}
+
+/// Builds the faucet transaction a proposal stands for, with the approvers' signatures as advice
+type ProposalRequest = fn(AccountId, &DepositView, Vec<(Word, Vec<Felt>)>) -> BridgeResult<TransactionRequest>;
+
+/// Where a multisig mint stands after an instance contributed to it
+#[derive(Debug, Clone)]
+pub enum MintProgress {
+    /// Still short of the threshold
+    Pending { signatures: usize, threshold: u32 },
+    /// Submitted by this instance
+    Submitted { note_id: String, tx_id: String },
+    /// Another instance is submitting it, or already did
+    SubmittedElsewhere,
+}
+
+/// Coordinates multisig mints through the proposals in the bridge database
+///
+/// Every relayer and API instance verifies a deposit against its own Zcash wallet before
+/// contributing its signature, so a compromised host or database can propose mints but not
+/// get them signed. The message signed is the commitment of the mint's transaction summary,
+/// which depends on the faucet's state: once another mint lands, open proposals go stale and
+/// are proposed again.
+///
+/// Withdrawal commitment notes go through the same proposals, keyed by commitment: relayers
+/// sign them once they find the withdrawal in the database.
+///
+/// Instances other than the one that created the faucet import it from the chain, which only
+/// works once its first transaction is on-chain.
+pub struct MintCoordinator {
+    config: MultisigConfig,
+    keystore_path: PathBuf,
+    store_path: PathBuf,
+    rpc_url: String,
+    actor: String,
+}
+
+impl MintCoordinator {
+    pub fn new(config: MultisigConfig, keystore_path: PathBuf, store_path: PathBuf, rpc_url: &str, actor: &str) -> Self {
+        Self {
+            config,
+            keystore_path,
+            store_path,
+            rpc_url: rpc_url.to_string(),
+            actor: actor.to_string(),
+        }
+    }
+
+    /// Sign the mint of a deposit, and submit it once enough approvers have
+    ///
+    /// With `propose` set, a deposit without a proposal gets one; otherwise only an existing
+    /// proposal (e.g. opened by a /deposit/claim) is signed.
+    pub async fn contribute(
+        &self,
+        db: &Mutex<Box<dyn BridgeStore>>,
+        faucet_id: AccountId,
+        view: &DepositView,
+        propose: bool,
+    ) -> BridgeResult<MintProgress> {
+        self.contribute_to(db, faucet_id, view, propose, mint_request).await
+    }
+
+    /// Sign the commitment note of a withdrawal (see `DepositView::withdrawal`), and submit it
+    /// once enough approvers have
+    pub async fn contribute_withdrawal_note(
+        &self,
+        db: &Mutex<Box<dyn BridgeStore>>,
+        faucet_id: AccountId,
+        view: &DepositView,
+        propose: bool,
+    ) -> BridgeResult<MintProgress> {
+        self.contribute_to(db, faucet_id, view, propose, withdrawal_note_request).await
+    }
+
+    /// Sign the withdrawal commitment notes other instances proposed
+    ///
+    /// A note is only signed for a withdrawal this instance finds in the bridge database with the
+    /// same amount; the note moves no funds, but the exit relayer marks its withdrawal committed.
+    pub async fn approve_withdrawal_notes(&self, db: &Mutex<Box<dyn BridgeStore>>, faucet_id: AccountId) -> BridgeResult<()> {
+        let lock_db = || db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)));
+
+        let proposals = lock_db()?.mint_approvals().pending()?;
+        for proposal in proposals {
+            let Some(commitment) = proposal.deposit.strip_prefix(WITHDRAWAL_PREFIX) else {
+                continue;
+            };
+            let Some(withdrawal) = lock_db()?.withdrawals().get_withdrawal(commitment)? else {
+                warn!(commitment, "Withdrawal note proposed for an unknown withdrawal; not signing it");
+                continue;
+            };
+
+            let view = DepositView::withdrawal(commitment, withdrawal.amount);
+            match self.contribute_withdrawal_note(db, faucet_id, &view, false).await {
+                Ok(MintProgress::Submitted { note_id, tx_id }) => {
+                    info!(commitment, note_id = %note_id, tx_id = %tx_id, "Submitted withdrawal note");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(commitment, error = %e, "Failed to approve withdrawal note"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Propose, sign and submit one faucet transaction; `request` builds it from the view
+    async fn contribute_to(
+        &self,
+        db: &Mutex<Box<dyn BridgeStore>>,
+        faucet_id: AccountId,
+        view: &DepositView,
+        propose: bool,
+        request: ProposalRequest,
+    ) -> BridgeResult<MintProgress> {
+        let lock_db = || db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)));
+
+        let existing = lock_db()?.mint_approvals().get(&view.deposit)?;
+        match &existing {
+            Some(proposal) if matches!(proposal.status, ProposalStatus::Submitting | ProposalStatus::Submitted) => {
+                return Ok(MintProgress::SubmittedElsewhere);
+            }
+            Some(proposal) if proposal.status == ProposalStatus::Pending => check_matches(proposal, view)?,
+            None if !propose => {
+                return Ok(MintProgress::Pending { signatures: 0, threshold: self.config.threshold });
+            }
+            _ => {}
+        }
+
+        let (mut client, authenticator) = self.client(faucet_id).await?;
+        let summary = unauthorized_summary(&mut client, faucet_id, request(faucet_id, view, Vec::new())?).await?;
+        let message = summary.to_commitment();
+
+        // Open the proposal, or replace one that failed or whose message went stale
+        let stale = existing.as_ref()
+            .is_some_and(|proposal| proposal.status == ProposalStatus::Pending && proposal.message != message.to_hex());
+        if stale {
+            info!(deposit = %view.deposit, "Mint proposal is stale; proposing it again");
+            lock_db()?.mint_approvals().finish(&view.deposit, ProposalStatus::Failed, None, Some("stale message"))?;
+        }
+        if existing.is_none() || stale || existing.as_ref().is_some_and(|proposal| proposal.status == ProposalStatus::Failed) {
+            lock_db()?.mint_approvals().propose(&MintProposal {
+                deposit: view.deposit.clone(),
+                recipient_hash: view.recipient_hash.clone(),
+                amount: view.amount,
+                net_amount: view.net_amount,
+                message: message.to_hex(),
+                proposer: self.actor.clone(),
+                status: ProposalStatus::Pending,
+                tx_id: None,
+                detail: None,
+                created_at: 0,
+            })?;
+        }
+
+        // Another instance may have proposed at the same time; its message wins until it goes stale
+        let proposal = lock_db()?.mint_approvals().get(&view.deposit)?
+            .ok_or_else(|| BridgeError::Internal(format!("Mint proposal for {} disappeared", view.deposit)))?;
+        match proposal.status {
+            ProposalStatus::Pending => check_matches(&proposal, view)?,
+            ProposalStatus::Submitting | ProposalStatus::Submitted => return Ok(MintProgress::SubmittedElsewhere),
+            ProposalStatus::Failed => return Ok(MintProgress::Pending { signatures: 0, threshold: self.config.threshold }),
+        }
+
+        let mut signatures = self.approver_signatures(&lock_db()?.mint_approvals().signatures(&view.deposit)?);
+        if proposal.message == message.to_hex()
+            && let Some(signer) = self.config.signer
+            && !signatures.iter().any(|signature| signature.signer == signer.to_hex())
+        {
+            let signature = authenticator
+                .get_signature(PublicKeyCommitment::from(signer), &SigningInputs::TransactionSummary(Box::new(summary)))
+                .await
+                .map_err(|e| BridgeError::Internal(format!("Failed to sign mint of {}: {}", view.deposit, e)))?;
+            lock_db()?.mint_approvals().add_signature(&view.deposit, &signer.to_hex(), &hex::encode(signature.to_bytes()))?;
+            info!(deposit = %view.deposit, "Signed mint proposal");
+            signatures = self.approver_signatures(&lock_db()?.mint_approvals().signatures(&view.deposit)?);
+        }
+
+        if signatures.len() < self.config.threshold as usize || proposal.message != message.to_hex() {
+            return Ok(MintProgress::Pending { signatures: signatures.len(), threshold: self.config.threshold });
+        }
+        if !lock_db()?.mint_approvals().start_submission(&view.deposit)? {
+            return Ok(MintProgress::SubmittedElsewhere);
+        }
+
+        match self.submit(&mut client, faucet_id, view, message, &signatures, request).await {
+            Ok((note_id, tx_id)) => {
+                lock_db()?.mint_approvals().finish(&view.deposit, ProposalStatus::Submitted, Some(&tx_id), None)?;
+                Ok(MintProgress::Submitted { note_id, tx_id })
+            }
+            Err(e) => {
+                warn!(deposit = %view.deposit, error = %e, "Multisig mint failed; it will be proposed again");
+                lock_db()?.mint_approvals().finish(&view.deposit, ProposalStatus::Failed, None, Some(e.code()))?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Signatures by configured approvers, up to the threshold
+    fn approver_signatures(&self, signatures: &[MintSignature]) -> Vec<MintSignature> {
+        signatures.iter()
+            .filter(|signature| parse_commitment(&signature.signer).is_ok_and(|signer| self.config.approvers.contains(&signer)))
+            .take(self.config.threshold as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Execute the mint with the approvers' signatures in the advice map, then prove and submit it
+    async fn submit(
+        &self,
+        client: &mut Client<BridgeSigner>,
+        faucet_id: AccountId,
+        view: &DepositView,
+        message: Word,
+        signatures: &[MintSignature],
+        request: ProposalRequest,
+    ) -> BridgeResult<(String, String)> {
+        // The multisig auth procedure looks signatures up by hash(public key commitment, message)
+        let advice = signatures.iter()
+            .map(|signature| {
+                let signer = parse_commitment(&signature.signer)?;
+                let bytes = hex::decode(&signature.signature)
+                    .map_err(|e| BridgeError::Internal(format!("Invalid signature from {}: {}", signature.signer, e)))?;
+                let signature = Signature::read_from_bytes(&bytes)
+                    .map_err(|e| BridgeError::Internal(format!("Invalid signature from {}: {}", signature.signer, e)))?;
+                Ok((Rpo256::merge(&[signer, message]), signature.to_prepared_signature(message)))
+            })
+            .collect::<BridgeResult<Vec<_>>>()?;
+
+        let tx_result = client
+            .execute_transaction(faucet_id, request(faucet_id, view, advice)?)
+            .await
+            .map_err(|e| BridgeError::ProvingFailed(format!("Failed to execute transaction: {}", e)))?;
+
+        let proven_tx = client
+            .prove_transaction(&tx_result)
+            .await
+            .map_err(|e| BridgeError::ProvingFailed(format!("Failed to prove transaction: {}", e)))?;
+
+        let submission_height = client
+            .submit_proven_transaction(proven_tx, &tx_result)
+            .await
+            .map_err(|e| BridgeError::TransactionFailed(format!("Failed to submit transaction: {}", e)))?;
+
+        client
+            .apply_transaction(&tx_result, submission_height)
+            .await
+            .map_err(|e| BridgeError::Internal(format!("Failed to apply transaction: {}", e)))?;
+
+        let note_id = tx_result.created_notes().get_note(0).id().to_hex();
+        let tx_id = tx_result.executed_transaction().id().to_hex();
+        Ok((note_id, tx_id))
+    }
+
+    /// Synced client holding the faucet, imported from the chain if this store lacks it
+    async fn client(&self, faucet_id: AccountId) -> BridgeResult<(Client<BridgeSigner>, Arc<BridgeSigner>)> {
+        let endpoint = Endpoint::try_from(self.rpc_url.as_str())
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
+        let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
+        let signer = BridgeSigner::open(&self.keystore_path)?;
+
+        let mut client = ClientBuilder::new()
+            .rpc(rpc_client)
+            .sqlite_store(self.store_path.clone())
+            .authenticator(signer.clone())
+            .in_debug_mode(true.into())
+            .build()
+            .await
+            .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
+
+        let known = client.get_account(faucet_id).await
+            .map_err(|e| BridgeError::Internal(format!("Failed to check faucet account: {}", e)))?
+            .is_some();
+        if !known {
+            client.import_account_by_id(faucet_id).await
+                .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to import faucet account: {}", e)))?;
+        }
+        let sync_summary = client.sync_state().await
+            .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+        metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
+
+        Ok((client, signer))
+    }
+}
+
+/// Refuse to sign a proposal that doesn't match what this instance saw of the deposit
+fn check_matches(proposal: &MintProposal, view: &DepositView) -> BridgeResult<()> {
+    if proposal.recipient_hash != view.recipient_hash
+        || proposal.amount != view.amount
+        || proposal.net_amount != view.net_amount
+    {
+        return Err(BridgeError::Conflict(format!(
+            "Mint proposal for {} (recipient {}, {} minted of {}) doesn't match this instance's view (recipient {}, {} of {})",
+            view.deposit,
+            proposal.recipient_hash,
+            proposal.net_amount,
+            proposal.amount,
+            view.recipient_hash,
+            view.net_amount,
+            view.amount,
+        )));
+    }
+    Ok(())
+}
+
+/// Mint of the deposit's note as a partial note, so any instance can build it from the recipient hash
+///
+/// The salt comes from the deposit, so every instance computes the same transaction summary.
+fn mint_request(faucet_id: AccountId, view: &DepositView, signatures: Vec<(Word, Vec<Felt>)>) -> BridgeResult<TransactionRequest> {
+    let recipient_hash = Word::try_from(format!("0x{}", view.recipient_hash.trim_start_matches("0x")).as_str())
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid recipient hash: {}", e)))?;
+
+    let asset = FungibleAsset::new(faucet_id, view.net_amount)
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create asset: {}", e)))?;
+    let assets = NoteAssets::new(vec![asset.into()])
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to create note assets: {}", e)))?;
+
+    let metadata = NoteMetadata::new(
+        faucet_id,
+        NoteType::Private,
+        NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid tag: {:?}", e)))?,
+        NoteExecutionHint::always(),
+        Felt::ZERO,
+    )
+    .map_err(|e| BridgeError::InvalidInput(format!("Failed to create metadata: {}", e)))?;
+
+    TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Partial(PartialNote::new(metadata, recipient_hash, assets))])
+        .auth_arg(Word::from(Rpo256::hash(view.deposit.as_bytes())))
+        .extend_advice_map(signatures)
+        .build()
+        .map_err(|e| BridgeError::Internal(format!("Failed to build transaction: {}", e)))
+}
+
+/// Emission of a withdrawal's commitment note, keyed like the mint by the proposal's salt
+fn withdrawal_note_request(faucet_id: AccountId, view: &DepositView, signatures: Vec<(Word, Vec<Felt>)>) -> BridgeResult<TransactionRequest> {
+    let note = withdrawal_commitment_note(faucet_id, parse_withdrawal_commitment(&view.recipient_hash)?)?;
+
+    TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note)])
+        .auth_arg(Word::from(Rpo256::hash(view.deposit.as_bytes())))
+        .extend_advice_map(signatures)
+        .build()
+        .map_err(|e| BridgeError::Internal(format!("Failed to build transaction: {}", e)))
+}
+
+/// Withdrawal commitment as the API formats it: 0x and four 16-digit hex felts
+fn parse_withdrawal_commitment(commitment: &str) -> BridgeResult<Word> {
+    let hex = commitment.strip_prefix("0x").unwrap_or(commitment);
+    let invalid = || BridgeError::InvalidInput(format!("Invalid withdrawal commitment {}", commitment));
+    if hex.len() != 64 {
+        return Err(invalid());
+    }
+    let mut felts = [Felt::ZERO; 4];
+    for (i, felt) in felts.iter_mut().enumerate() {
+        let digits = hex.get(i * 16..(i + 1) * 16).ok_or_else(invalid)?;
+        *felt = Felt::new(u64::from_str_radix(digits, 16).map_err(|_| invalid())?);
+    }
+    Ok(Word::new(felts))
+}
+
+/// Execute the mint without signatures, for the summary the approvers sign
+async fn unauthorized_summary(
+    client: &mut Client<BridgeSigner>,
+    faucet_id: AccountId,
+    request: TransactionRequest,
+) -> BridgeResult<TransactionSummary> {
+    match client.execute_transaction(faucet_id, request).await {
+        Err(ClientError::TransactionExecutorError(TransactionExecutorError::Unauthorized(summary))) => Ok(*summary),
+        Err(e) => Err(BridgeError::ProvingFailed(format!("Failed to execute transaction: {}", e))),
+        Ok(_) => Err(BridgeError::Internal(
+            "Faucet mint executed without approvals; the faucet wasn't created with MINT_APPROVERS".to_string(),
+        )),
+    }
+}
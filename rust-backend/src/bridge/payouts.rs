@@ -0,0 +1,247 @@
+use crate::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use crate::bridge::fees::{FeeDirection, FeeQuote};
+use crate::db::audit::{inputs_digest, AuditAction};
+use crate::db::events::EventState;
+use crate::db::payouts::{Payout, PayoutKind, PayoutStatus};
+use crate::db::BridgeStore;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use crate::zcash::bridge_wallet::BridgeWallet;
+use std::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// How Zcash payouts get signed, loaded from the environment
+///
+/// ZCASH_PAYOUT_SIGNING  "online" (default): the API host and exit relayer send with the bridge
+///                       spending key in wallet/bridge_wallet/key.txt.
+///                       "offline": they only build and prove a PCZT from a view-only wallet and
+///                       queue it; `bridgectl payout` carries it to the signer host and back,
+///                       then broadcasts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutSigning {
+    Online,
+    Offline,
+}
+
+impl PayoutSigning {
+    pub fn from_env() -> BridgeResult<Self> {
+        match std::env::var("ZCASH_PAYOUT_SIGNING") {
+            Err(_) => Ok(PayoutSigning::Online),
+            Ok(mode) => match mode.trim().to_lowercase().as_str() {
+                "" | "online" => Ok(PayoutSigning::Online),
+                "offline" => Ok(PayoutSigning::Offline),
+                other => Err(BridgeError::Internal(format!(
+                    "Unknown ZCASH_PAYOUT_SIGNING '{}' (expected online or offline)",
+                    other
+                ))),
+            },
+        }
+    }
+}
+
+/// TAZ amount as zcash-devtool takes it
+pub fn format_taz(zatoshis: u64) -> String {
+    format!("{:.8}", zatoshis as f64 / 1e8)
+}
+
+/// Queue a payout for the offline signer, building its PCZT if no other payout's PCZT is out
+///
+/// PCZTs are built one at a time (see `Payouts::start_build`), so a payout queued behind
+/// another one gets its PCZT once that one is broadcast.
+/// Returns None if a payout with this id is already queued; the caller reports the existing one.
+pub fn queue(
+    db: &Mutex<Box<dyn BridgeStore>>,
+    wallet: &BridgeWallet,
+    kind: PayoutKind,
+    id: &str,
+    note_id: &str,
+    zcash_address: &str,
+    quote: &FeeQuote,
+) -> BridgeResult<Option<Payout>> {
+    if let Some(existing) = lock(db)?.payouts().get(id)? {
+        info!(payout = %id, status = existing.status.as_str(), "Payout already queued");
+        return Ok(None);
+    }
+
+    let payout = Payout {
+        id: id.to_string(),
+        kind,
+        note_id: note_id.to_string(),
+        zcash_address: zcash_address.to_string(),
+        amount: quote.gross_amount,
+        fee: quote.fee,
+        net_amount: quote.net_amount,
+        status: PayoutStatus::Queued,
+        pczt: String::new(),
+        signed_pczt: None,
+        txid: None,
+        detail: None,
+        created_at: 0,
+        updated_at: 0,
+    };
+
+    let queued = lock(db)?.transaction(|db| {
+        if !db.payouts().queue(&payout)? {
+            return Ok(false);
+        }
+        db.events().record_withdrawal(id, EventState::PayoutQueued, Some(note_id), None)?;
+        Ok(true)
+    })?;
+
+    if !queued {
+        return Ok(None);
+    }
+    info!(payout = %id, amount = quote.net_amount, "Queued payout for the offline signer");
+
+    // The payout is queued either way; a PCZT that fails to build is retried with the next one
+    if let Err(e) = build_next(db, wallet) {
+        warn!(error = %e, "Failed to build payout PCZT");
+    }
+    Ok(lock(db)?.payouts().get(id)?.or(Some(payout)))
+}
+
+/// Build the PCZT of the oldest queued payout, unless another payout's PCZT is out
+///
+/// Returns the id of the payout built, if any.
+pub fn build_next(db: &Mutex<Box<dyn BridgeStore>>, wallet: &BridgeWallet) -> BridgeResult<Option<String>> {
+    let Some(payout) = lock(db)?.payouts().with_status(PayoutStatus::Queued)?.into_iter().next() else {
+        return Ok(None);
+    };
+    if !build(db, wallet, &payout)? {
+        return Ok(None);
+    }
+    Ok(Some(payout.id))
+}
+
+// Build and prove the payout's PCZT; false if another payout's PCZT is out
+fn build(db: &Mutex<Box<dyn BridgeStore>>, wallet: &BridgeWallet, payout: &Payout) -> BridgeResult<bool> {
+    if !lock(db)?.payouts().start_build(&payout.id)? {
+        return Ok(false);
+    }
+
+    match wallet.create_pczt(&payout.zcash_address, &format_taz(payout.net_amount), None) {
+        Ok(pczt) => {
+            lock(db)?.payouts().attach_pczt(&payout.id, &hex::encode(pczt))?;
+            info!(payout = %payout.id, "Built payout PCZT");
+            Ok(true)
+        }
+        Err(e) => {
+            // Back to where it was, so the next attempt picks it up again
+            lock(db)?.payouts().finish(&payout.id, payout.status, None, Some(&e.to_string()))?;
+            Err(e.context("Failed to build payout PCZT"))
+        }
+    }
+}
+
+/// Build a fresh PCZT for a failed payout, once the operator has checked its transaction didn't land
+pub fn rebuild(db: &Mutex<Box<dyn BridgeStore>>, wallet: &BridgeWallet, id: &str) -> BridgeResult<()> {
+    let payout = lock(db)?.payouts().get(id)?
+        .ok_or_else(|| BridgeError::NotFound(format!("No payout {}", id)))?;
+    if payout.status != PayoutStatus::Failed {
+        return Err(BridgeError::InvalidInput(format!(
+            "Payout {} is {}, only failed payouts can be rebuilt",
+            id,
+            payout.status.as_str()
+        )));
+    }
+
+    if !build(db, wallet, &payout)? {
+        return Err(BridgeError::Conflict(format!(
+            "Another payout's PCZT is still out; rebuild {} once it is broadcast or failed",
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Broadcast a signed payout and record it as paid
+///
+/// The database lock isn't held while the transaction is sent. A failed send leaves the payout
+/// failed rather than retrying, since the transaction may have reached the network anyway.
+pub fn broadcast(
+    db: &Mutex<Box<dyn BridgeStore>>,
+    wallet: &BridgeWallet,
+    id: &str,
+    actor: &str,
+) -> BridgeResult<String> {
+    let payout = {
+        let db = lock(db)?;
+        if !db.payouts().start_broadcast(id)? {
+            return Err(BridgeError::InvalidInput(format!("Payout {} is not signed, or is already being broadcast", id)));
+        }
+        db.payouts().get(id)?
+            .ok_or_else(|| BridgeError::NotFound(format!("No payout {}", id)))?
+    };
+
+    let signed = payout.signed_pczt.as_deref()
+        .ok_or_else(|| BridgeError::Internal(format!("Payout {} is signed but has no signed PCZT", id)))
+        .and_then(|signed| hex::decode(signed)
+            .map_err(|e| BridgeError::Internal(format!("Signed PCZT of payout {} is not hex: {}", id, e))));
+
+    let payout_timer = metrics::PAYOUT_LATENCY.start_timer();
+    let sent = signed.and_then(|signed| wallet.send_pczt(&signed));
+    payout_timer.observe_duration();
+
+    let txid = match sent {
+        Ok(txid) => txid,
+        Err(e) => {
+            error!(payout = %id, error = %e, "Failed to broadcast payout");
+            lock(db)?.payouts().finish(id, PayoutStatus::Failed, None, Some(&e.to_string()))?;
+            return Err(e.context("Failed to broadcast payout"));
+        }
+    };
+
+    metrics::WITHDRAWALS_PAID.inc();
+    info!(payout = %id, zcash_txid = %txid, "Broadcast payout");
+
+    lock(db)?.transaction(|db| record_paid(db, &payout, &txid, actor))?;
+
+    // Its notes are spent now, so the next payout's PCZT can't pick them
+    if let Err(e) = build_next(db, wallet) {
+        warn!(error = %e, "Failed to build the next payout PCZT");
+    }
+    Ok(txid)
+}
+
+// Same bookkeeping as an online payout: claim, fee, audit entry and event
+fn record_paid(db: &dyn BridgeStore, payout: &Payout, txid: &str, actor: &str) -> BridgeResult<()> {
+    db.payouts().finish(&payout.id, PayoutStatus::Broadcast, Some(txid), None)?;
+
+    let quote = FeeQuote {
+        direction: FeeDirection::Withdrawal,
+        token: ZCASH_ORIGIN_NETWORK.to_string(),
+        gross_amount: payout.amount,
+        fee: payout.fee,
+        net_amount: payout.net_amount,
+    };
+
+    let inputs = match payout.kind {
+        PayoutKind::Withdrawal => {
+            db.withdrawals().mark_claimed(&payout.id, txid)?;
+            db.fees().record_fee(&quote, &payout.id)?;
+            serde_json::json!({
+                "commitment": payout.id,
+                "zcash_address": payout.zcash_address,
+                "amount": payout.amount,
+                "fee": payout.fee,
+            })
+        }
+        PayoutKind::Exit => {
+            db.fees().record_fee(&quote, &payout.note_id)?;
+            serde_json::json!({
+                "note_id": payout.note_id,
+                "zcash_address": payout.zcash_address,
+                "amount": payout.amount,
+                "fee": payout.fee,
+            })
+        }
+    };
+    db.audit().append(AuditAction::WithdrawalPayout, &inputs_digest(&inputs), Some(txid), actor)?;
+
+    db.events().record_withdrawal(&payout.id, EventState::Paid, Some(&payout.note_id), Some(txid))?;
+    Ok(())
+}
+
+fn lock(db: &Mutex<Box<dyn BridgeStore>>) -> BridgeResult<std::sync::MutexGuard<'_, Box<dyn BridgeStore>>> {
+    db.lock().map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)))
+}
@@ -0,0 +1,613 @@
+use crate::account::signer::BridgeSigner;
+use crate::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use crate::bridge::miden_exit_relayer::ZCASH_TESTNET_CHAIN_ID;
+use crate::db::{self, BridgeStore};
+use crate::db::audit::AuditAction;
+use crate::db::events::{EventState, EventSubject};
+use crate::db::refunds::RefundStatus;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use crate::miden::notes::BRIDGE_USECASE;
+use crate::zcash::bridge_wallet::{BridgeWallet, DepositOutput, TransactionInfo};
+use miden_client::{
+    builder::ClientBuilder,
+    rpc::{Endpoint, GrpcClient},
+    store::NoteFilter,
+};
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use miden_lib::account::faucets::FungibleFaucetExt;
+use miden_objects::{
+    account::AccountId,
+    asset::Asset,
+    note::{NoteAssets, NoteTag},
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::time::{Duration, interval};
+use tracing::{error, info, warn};
+
+/// Audit entries read per query
+const AUDIT_PAGE_SIZE: u32 = 500;
+
+/// Kinds of discrepancy between the Zcash wallet, the bridge database and the Miden store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Deposit the wallet received that was neither minted nor rejected
+    MissingMint,
+    /// Deposit minted more than once, or both minted and refunded
+    DoubleMint,
+    /// Faucet mint with no deposit behind it
+    UnbackedMint,
+    /// Burn (or claimed withdrawal) the wallet never paid out
+    UnpaidBurn,
+    /// Wallet payout with no withdrawal, exit or refund behind it
+    UnbackedPayout,
+    /// Two sides disagree on an amount or a total
+    AmountMismatch,
+}
+
+impl IssueKind {
+    pub const ALL: [IssueKind; 6] = [
+        IssueKind::MissingMint,
+        IssueKind::DoubleMint,
+        IssueKind::UnbackedMint,
+        IssueKind::UnpaidBurn,
+        IssueKind::UnbackedPayout,
+        IssueKind::AmountMismatch,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::MissingMint => "missing_mint",
+            IssueKind::DoubleMint => "double_mint",
+            IssueKind::UnbackedMint => "unbacked_mint",
+            IssueKind::UnpaidBurn => "unpaid_burn",
+            IssueKind::UnbackedPayout => "unbacked_payout",
+            IssueKind::AmountMismatch => "amount_mismatch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// Deposit output, withdrawal commitment, note id or txid the issue is about
+    pub key: String,
+    pub detail: String,
+}
+
+/// Totals seen on each side (zatoshis and wTAZ base units are the same unit)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Totals {
+    /// Deposits the bridge wallet received
+    pub zcash_deposited: u64,
+    /// Withdrawals, exits and refunds the bridge wallet paid out
+    pub zcash_paid_out: u64,
+    /// Tokens the faucet minted into notes
+    pub miden_minted: u64,
+    /// Tokens burned in exit notes
+    pub miden_burned: u64,
+    /// Net deposit amounts in the fee ledger - what should have been minted
+    pub ledger_minted: u64,
+    /// Net payout amounts in the fee ledger - what should have been paid out
+    pub ledger_paid_out: u64,
+    /// Withdrawal commitments not claimed yet
+    pub pending_withdrawals: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileReport {
+    pub generated_at: i64,
+    pub totals: Totals,
+    pub issues: Vec<Issue>,
+}
+
+impl ReconcileReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Number of issues of one kind
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues.iter().filter(|issue| issue.kind == kind).count()
+    }
+}
+
+/// A transaction the bridge wallet sent, with the value that left the wallet
+#[derive(Debug, Clone)]
+pub struct WalletPayout {
+    pub txid: String,
+    pub value: u64,
+}
+
+/// Deposits the bridge wallet received and payouts it sent
+#[derive(Debug, Clone, Default)]
+pub struct WalletLedger {
+    pub deposits: Vec<DepositOutput>,
+    pub payouts: Vec<WalletPayout>,
+}
+
+impl WalletLedger {
+    /// Sync the wallet and read its deposits and payouts
+    ///
+    /// Deposits are the outputs the relayer would process: outputs with a memo or to a deposit address.
+    pub fn load(wallet: &BridgeWallet, deposit_addresses: &HashSet<String>) -> BridgeResult<Self> {
+        let deposits = wallet.extract_all_memos(deposit_addresses)?;
+        let transactions = wallet.parse_transactions(&wallet.list_transactions(None)?)?;
+        let bridge_addresses = wallet.list_addresses(None)?
+            .into_iter()
+            .map(|address| address.address)
+            .collect();
+
+        Ok(Self {
+            deposits,
+            payouts: payouts(&transactions, &bridge_addresses),
+        })
+    }
+}
+
+/// Value each transaction the wallet sent paid to addresses outside the wallet
+pub fn payouts(transactions: &[TransactionInfo], bridge_addresses: &HashSet<String>) -> Vec<WalletPayout> {
+    transactions.iter()
+        .filter(|tx| tx.sent)
+        .filter_map(|tx| {
+            let value: u64 = tx.outputs.iter()
+                .filter(|output| !output.to_address.as_ref().is_some_and(|to| bridge_addresses.contains(to)))
+                .map(|output| output.value)
+                .sum();
+            (value > 0).then(|| WalletPayout { txid: tx.txid.clone(), value })
+        })
+        .collect()
+}
+
+/// A note holding faucet tokens
+#[derive(Debug, Clone)]
+pub struct MidenNote {
+    pub note_id: String,
+    pub amount: u64,
+}
+
+/// What the faucet minted and what was burned back, as recorded in the Miden store
+#[derive(Debug, Clone, Default)]
+pub struct MidenLedger {
+    /// Faucet output notes carrying tokens
+    pub mints: Vec<MidenNote>,
+    /// Withdrawal commitment notes the faucet emitted (they carry no tokens)
+    pub withdrawal_notes: HashSet<String>,
+    /// Exit notes burning tokens for a payout to the Zcash address in their inputs
+    pub exit_burns: Vec<MidenNote>,
+    /// Tokens the faucet has issued so far, from its account storage
+    pub issued_supply: u64,
+}
+
+impl MidenLedger {
+    /// Sync the bridge's Miden client and read the faucet's notes and issuance from its store
+    pub async fn load(project_root: &Path, faucet_id: AccountId) -> BridgeResult<Self> {
+        let rpc_url = std::env::var("RPC_URL")
+            .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
+        let endpoint = Endpoint::try_from(rpc_url.as_str())
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
+        let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
+
+        // Nothing is signed here; with SIGNER_SOCKET set this needs no local keys
+        let signer = BridgeSigner::open(&project_root.join("rust-backend").join("keystore"))?;
+
+        let mut client = ClientBuilder::new()
+            .rpc(rpc_client)
+            .sqlite_store(project_root.join("bridge_store.sqlite3"))
+            .authenticator(signer)
+            .in_debug_mode(true.into())
+            .build()
+            .await
+            .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
+
+        let sync_summary = client.sync_state().await
+            .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+        metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
+
+        let exit_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 0)
+            .map_err(|e| BridgeError::Internal(format!("Failed to create bridge tag: {:?}", e)))?;
+        let withdrawal_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 1)
+            .map_err(|e| BridgeError::Internal(format!("Failed to create withdrawal tag: {:?}", e)))?;
+
+        let mut ledger = Self::default();
+
+        if let Some(faucet) = client.get_account(faucet_id).await
+            .map_err(|e| BridgeError::Internal(format!("Failed to get faucet account: {}", e)))?
+        {
+            ledger.issued_supply = faucet.account().get_token_issuance()
+                .map_err(|e| BridgeError::Internal(format!("Failed to read faucet issuance: {}", e)))?
+                .as_int();
+        }
+
+        let output_notes = client.get_output_notes(NoteFilter::All).await
+            .map_err(|e| BridgeError::Internal(format!("Failed to get output notes: {}", e)))?;
+        for note in output_notes.iter() {
+            let metadata = note.metadata();
+            if metadata.sender() != faucet_id {
+                continue;
+            }
+
+            let note_id = note.id().to_hex();
+            let amount = faucet_amount(note.assets(), faucet_id);
+            if metadata.tag() == withdrawal_tag {
+                ledger.withdrawal_notes.insert(note_id);
+            } else if amount > 0 {
+                ledger.mints.push(MidenNote { note_id, amount });
+            }
+        }
+
+        // Same notes the exit relayer pays out: bridge-tagged, destined for Zcash
+        let input_notes = client.get_input_notes(NoteFilter::Committed).await
+            .map_err(|e| BridgeError::Internal(format!("Failed to get input notes: {}", e)))?;
+        for note in input_notes.iter() {
+            if note.metadata().is_none_or(|metadata| metadata.tag() != exit_tag) {
+                continue;
+            }
+            let inputs = note.details().inputs().values();
+            if inputs.len() < 8 || inputs[4].as_int() != ZCASH_TESTNET_CHAIN_ID {
+                continue;
+            }
+
+            let amount = faucet_amount(note.assets(), faucet_id);
+            if amount > 0 {
+                ledger.exit_burns.push(MidenNote { note_id: note.id().to_hex(), amount });
+            }
+        }
+
+        Ok(ledger)
+    }
+}
+
+/// Tokens of `faucet_id` held by a note
+fn faucet_amount(assets: &NoteAssets, faucet_id: AccountId) -> u64 {
+    assets.iter()
+        .filter_map(|asset| match asset {
+            Asset::Fungible(fungible) if fungible.faucet_id() == faucet_id => Some(fungible.amount()),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Note ids and commitments are compared as lowercase hex with a 0x prefix
+fn normalize_hex(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    match value.strip_prefix("0x") {
+        Some(_) => value,
+        None => format!("0x{}", value),
+    }
+}
+
+/// Zcash txids as the wallet lists them: lowercase hex without a prefix
+fn normalize_txid(txid: &str) -> String {
+    let txid = txid.trim().to_lowercase();
+    txid.strip_prefix("0x").unwrap_or(&txid).to_string()
+}
+
+/// Cross-check the wallet, the bridge database and the Miden store
+///
+/// Every Zcash deposit should have exactly one minted note (or be in the refund queue), every
+/// mint a deposit behind it, every payout a withdrawal, exit or refund behind it, and the
+/// totals should match the fee ledger.
+pub fn reconcile(wallet: &WalletLedger, db: &dyn BridgeStore, miden: &MidenLedger) -> BridgeResult<ReconcileReport> {
+    let mut issues = Vec::new();
+    let mut issue = |kind: IssueKind, key: &str, detail: String| {
+        issues.push(Issue { kind, key: key.to_string(), detail });
+    };
+    let mut totals = Totals::default();
+
+    // Minted deposits and paid exits, from the event log
+    let mut minted: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut paid_exits: HashMap<String, String> = HashMap::new();
+    let mut after_id = 0;
+    loop {
+        let page = db.events().events_after(after_id)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after_id = last.id;
+
+        for event in page {
+            match (event.subject, event.state, event.note_id, event.detail) {
+                (EventSubject::Deposit, EventState::Minted, Some(note_id), _) => {
+                    minted.entry(event.key).or_default().insert(normalize_hex(&note_id));
+                }
+                (EventSubject::Withdrawal, EventState::Paid, _, Some(txid)) => {
+                    paid_exits.insert(normalize_hex(&event.key), txid);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let refunds: HashMap<String, _> = db.refunds().list(None)?
+        .into_iter()
+        .map(|rejected| (format!("{}:{}:{}", rejected.txid, rejected.pool, rejected.output_index), rejected))
+        .collect();
+    let claims: HashMap<String, u64> = db.deposits().list_claims()?
+        .into_iter()
+        .filter_map(|claim| {
+            let (pool, index) = (claim.pool?, claim.output_index?);
+            Some((format!("{}:{}:{}", claim.txid, pool, index), claim.amount))
+        })
+        .collect();
+    let withdrawals = db.withdrawals().list_withdrawals()?;
+
+    // Deposits: Zcash -> bridge database -> Miden
+    let mint_amounts: HashMap<String, u64> = miden.mints.iter()
+        .map(|note| (normalize_hex(&note.note_id), note.amount))
+        .collect();
+    let mut deposit_keys = HashSet::new();
+
+    for output in &wallet.deposits {
+        let key = output.key();
+        totals.zcash_deposited += output.value;
+        deposit_keys.insert(key.clone());
+
+        let notes = minted.get(&key).cloned().unwrap_or_default();
+        let refund = refunds.get(&key);
+
+        match notes.len() {
+            0 if refund.is_some() => {}
+            0 => issue(IssueKind::MissingMint, &key, format!("{} zatoshis received, no note minted", output.value)),
+            1 => {}
+            n => issue(IssueKind::DoubleMint, &key, format!("{} notes minted: {}", n, join(&notes))),
+        }
+
+        if let Some(refund) = refund.filter(|refund| refund.status == RefundStatus::Refunded && !notes.is_empty()) {
+            issue(IssueKind::DoubleMint, &key, format!(
+                "minted as {} and refunded in {}",
+                join(&notes),
+                refund.refund_txid.as_deref().unwrap_or("unknown")
+            ));
+        }
+
+        if let Some(claimed) = claims.get(&key).filter(|claimed| **claimed != output.value) {
+            issue(IssueKind::AmountMismatch, &key, format!(
+                "claim recorded {} zatoshis, wallet received {}",
+                claimed, output.value
+            ));
+        }
+
+        for note_id in &notes {
+            match mint_amounts.get(note_id) {
+                None => issue(IssueKind::MissingMint, &key, format!(
+                    "note {} recorded as minted, but it is not in the Miden store",
+                    note_id
+                )),
+                Some(amount) if *amount > output.value => issue(IssueKind::AmountMismatch, &key, format!(
+                    "note {} holds {} for a deposit of {} zatoshis",
+                    note_id, amount, output.value
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+
+    let mut traced_notes = HashSet::new();
+    for (key, notes) in &minted {
+        if !deposit_keys.contains(key) {
+            issue(IssueKind::UnbackedMint, key, format!(
+                "minted as {}, but the wallet received no such deposit",
+                join(notes)
+            ));
+        }
+        traced_notes.extend(notes.iter().cloned());
+    }
+
+    // Events only keep a deposit's latest state, so a second mint of the same output
+    // shows up as a deposit mint in the audit ledger that no event traces
+    let mut audited_mints = HashMap::new();
+    let mut after_id = 0;
+    loop {
+        let page = db.audit().entries(after_id, AUDIT_PAGE_SIZE)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after_id = last.id;
+
+        for entry in page {
+            if matches!(entry.action, AuditAction::DepositMint | AuditAction::DepositClaim)
+                && let Some(note_id) = &entry.result
+            {
+                audited_mints.insert(normalize_hex(note_id), entry.id);
+            }
+        }
+    }
+
+    let mut traced_minted = 0;
+    for note in &miden.mints {
+        totals.miden_minted += note.amount;
+        let note_id = normalize_hex(&note.note_id);
+        if traced_notes.contains(&note_id) {
+            traced_minted += note.amount;
+        } else if let Some(entry_id) = audited_mints.get(&note_id) {
+            issue(IssueKind::DoubleMint, &note.note_id, format!(
+                "{} minted for a deposit (audit entry #{}) whose mint is recorded as another note",
+                note.amount, entry_id
+            ));
+        } else {
+            issue(IssueKind::UnbackedMint, &note.note_id, format!(
+                "faucet minted {} with no deposit behind it",
+                note.amount
+            ));
+        }
+    }
+
+    // Withdrawals: Miden -> bridge database -> Zcash
+    let payouts: HashMap<String, u64> = wallet.payouts.iter()
+        .map(|payout| (normalize_txid(&payout.txid), payout.value))
+        .collect();
+    let mut backed_txids = HashSet::new();
+    let mut check_payout = |key: &str, txid: &str, amount: u64, issue: &mut dyn FnMut(IssueKind, &str, String)| {
+        backed_txids.insert(normalize_txid(txid));
+        match payouts.get(&normalize_txid(txid)) {
+            None => issue(IssueKind::UnpaidBurn, key, format!(
+                "recorded as paid in {}, which the wallet did not send",
+                txid
+            )),
+            Some(value) if *value > amount => issue(IssueKind::AmountMismatch, key, format!(
+                "{} zatoshis paid in {} for {} burned",
+                value, txid, amount
+            )),
+            Some(_) => {}
+        }
+    };
+
+    for withdrawal in &withdrawals {
+        let Some(txid) = &withdrawal.zcash_txid else {
+            totals.pending_withdrawals += withdrawal.amount;
+            continue;
+        };
+
+        if !miden.withdrawal_notes.contains(&normalize_hex(&withdrawal.note_id)) {
+            issue(IssueKind::UnbackedPayout, &withdrawal.commitment, format!(
+                "paid in {}, but withdrawal note {} is not in the Miden store",
+                txid, withdrawal.note_id
+            ));
+        }
+        check_payout(&withdrawal.commitment, txid, withdrawal.amount, &mut issue);
+    }
+
+    for burn in &miden.exit_burns {
+        totals.miden_burned += burn.amount;
+        match paid_exits.get(&normalize_hex(&burn.note_id)) {
+            None => issue(IssueKind::UnpaidBurn, &burn.note_id, format!("{} burned, no payout recorded", burn.amount)),
+            Some(txid) => check_payout(&burn.note_id, txid, burn.amount, &mut issue),
+        }
+    }
+
+    for refund in refunds.values() {
+        if let Some(txid) = &refund.refund_txid {
+            let key = format!("{}:{}:{}", refund.txid, refund.pool, refund.output_index);
+            check_payout(&key, txid, refund.amount, &mut issue);
+        }
+    }
+
+    // Paid events of withdrawals not in the table above still back their payout
+    backed_txids.extend(paid_exits.values().map(|txid| normalize_txid(txid)));
+
+    let mut backed_paid_out = 0;
+    for payout in &wallet.payouts {
+        totals.zcash_paid_out += payout.value;
+        if backed_txids.contains(&normalize_txid(&payout.txid)) {
+            backed_paid_out += payout.value;
+        } else {
+            issue(IssueKind::UnbackedPayout, &payout.txid, format!(
+                "{} zatoshis paid with no withdrawal, exit or refund behind it",
+                payout.value
+            ));
+        }
+    }
+
+    // Totals: the fee ledger records the net amount of every mint and payout
+    for summary in db.fees().summary()? {
+        match summary.direction.as_str() {
+            "deposit" => totals.ledger_minted += summary.net_amount,
+            "withdrawal" | "refund" => totals.ledger_paid_out += summary.net_amount,
+            _ => {}
+        }
+    }
+    if totals.ledger_minted != traced_minted {
+        issue(IssueKind::AmountMismatch, "totals:minted", format!(
+            "fee ledger has {} minted for deposits, the Miden store {}",
+            totals.ledger_minted, traced_minted
+        ));
+    }
+    if totals.ledger_paid_out != backed_paid_out {
+        issue(IssueKind::AmountMismatch, "totals:paid_out", format!(
+            "fee ledger has {} paid out, the wallet {}",
+            totals.ledger_paid_out, backed_paid_out
+        ));
+    }
+
+    Ok(ReconcileReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        totals,
+        issues,
+    })
+}
+
+fn join(notes: &BTreeSet<String>) -> String {
+    notes.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// Runs the reconciliation on a schedule and exports its findings as metrics
+pub struct Reconciler {
+    bridge_wallet: BridgeWallet,
+    project_root: PathBuf,
+    interval: Duration,
+    db: Mutex<Box<dyn BridgeStore>>,
+}
+
+impl Reconciler {
+    pub fn new(project_root: PathBuf, interval_secs: u64) -> Self {
+        let db = db::open(&project_root)
+            .expect("Failed to open bridge database");
+
+        Self {
+            bridge_wallet: BridgeWallet::new(project_root.clone()),
+            project_root,
+            interval: Duration::from_secs(interval_secs),
+            db: Mutex::new(db),
+        }
+    }
+
+    fn db(&self) -> BridgeResult<std::sync::MutexGuard<'_, Box<dyn BridgeStore>>> {
+        self.db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)))
+    }
+
+    /// Run one reconciliation
+    pub async fn run(&self) -> BridgeResult<ReconcileReport> {
+        let (deposit_addresses, faucet_id) = {
+            let db = self.db()?;
+            (
+                db.deposit_addresses().all_addresses()?,
+                db.faucets().get_faucet_id(ZCASH_ORIGIN_NETWORK)?,
+            )
+        };
+
+        let wallet = WalletLedger::load(&self.bridge_wallet, &deposit_addresses)
+            .map_err(|e| e.context("Failed to read the bridge wallet"))?;
+
+        // Without a faucet nothing was minted yet
+        let miden = match faucet_id {
+            Some(faucet_id) => MidenLedger::load(&self.project_root, faucet_id).await
+                .map_err(|e| e.context("Failed to read the Miden store"))?,
+            None => MidenLedger::default(),
+        };
+
+        let db = self.db()?;
+        reconcile(&wallet, db.as_ref(), &miden)
+    }
+
+    /// Start reconciling on the configured interval
+    pub async fn start(self) {
+        info!(interval_secs = self.interval.as_secs(), "Starting reconciliation");
+        let mut interval = interval(self.interval);
+
+        loop {
+            interval.tick().await;
+
+            match self.run().await {
+                Ok(report) => {
+                    for kind in IssueKind::ALL {
+                        metrics::RECONCILE_ISSUES
+                            .with_label_values(&[kind.as_str()])
+                            .set(report.count(kind) as i64);
+                    }
+                    for issue in &report.issues {
+                        warn!(kind = issue.kind.as_str(), key = %issue.key, detail = %issue.detail, "Reconciliation discrepancy");
+                    }
+                    info!(issues = report.issues.len(), "Reconciliation complete");
+                    metrics::record_relayer_success("reconcile");
+                }
+                Err(e) => error!(error = %e, "Reconciliation failed"),
+            }
+        }
+    }
+}
@@ -1,5 +1,17 @@
-use crate::bridge::deposit::get_or_create_zcash_faucet;
-use crate::zcash::bridge_wallet::BridgeWallet;
+use crate::bridge::deposit::{get_or_create_zcash_faucet, ZCASH_ORIGIN_NETWORK};
+use crate::bridge::fees::{FeeDirection, FeeQuote, FeeSchedule};
+use crate::bridge::memo;
+use crate::bridge::multisig::{DepositView, MintCoordinator, MintProgress, MultisigConfig};
+use crate::bridge::solvency::SolvencyGuard;
+use crate::db::{self, BridgeStore};
+use crate::db::audit::{inputs_digest, AuditAction};
+use crate::db::events::EventState;
+use crate::db::intents::{DepositIntent, IntentStatus, Intents};
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use crate::miden::recipient::build_deposit_recipient;
+use crate::zcash::bridge_wallet::{BridgeWallet, DepositOutput};
+use miden_objects::account::AccountId;
 use miden_objects::Word;
 use std::collections::HashSet;
 use std::fs::{File, OpenOptions};
@@ -7,14 +19,22 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::time::{Duration, interval};
+use tracing::{debug, error, info, info_span, warn};
 
 /// Zcash deposit relayer that periodically scans for deposits and automatically mints notes
 pub struct ZcashRelayer {
     bridge_wallet: Arc<BridgeWallet>,
     memo_file: PathBuf,
     scan_interval: Duration,
-    processed_txids: Arc<Mutex<HashSet<String>>>,
+    processed_outputs: Arc<Mutex<HashSet<String>>>,
     project_root: PathBuf,
+    fee_schedule: FeeSchedule,
+    db: Arc<Mutex<Box<dyn BridgeStore>>>,
+    seen_outputs: Arc<Mutex<HashSet<String>>>,
+    last_balance_refresh: Arc<Mutex<Option<std::time::Instant>>>,
+    solvency: SolvencyGuard,
+    // Set when mints need M-of-N approver signatures (MINT_APPROVERS)
+    multisig: Option<MintCoordinator>,
 }
 
 impl ZcashRelayer {
@@ -25,73 +45,215 @@ impl ZcashRelayer {
     ) -> Self {
         let memo_file = project_root.join("test_memo.txt");
         
-        // Load already processed txids from file
-        let processed_txids = Self::load_processed_txids(&memo_file);
+        // Load already processed outputs from file
+        let processed_outputs = Self::load_processed_outputs(&memo_file);
+        
+        let fee_schedule = FeeSchedule::load(&project_root)
+            .expect("Failed to load fee schedule");
+        let db = db::open(&project_root)
+            .expect("Failed to open bridge database");
+        let solvency = SolvencyGuard::new(bridge_wallet.clone(), project_root.clone());
+        let multisig = MultisigConfig::from_env()
+            .expect("Invalid multisig configuration")
+            .map(|config| {
+                let (keystore_path, store_path, rpc_url) = Self::miden_paths(&project_root);
+                MintCoordinator::new(config, keystore_path, store_path, &rpc_url, "zcash_relayer")
+            });
         
         Self {
             bridge_wallet,
             memo_file,
             scan_interval: Duration::from_secs(scan_interval_secs),
-            processed_txids: Arc::new(Mutex::new(processed_txids)),
+            processed_outputs: Arc::new(Mutex::new(processed_outputs)),
             project_root,
+            fee_schedule,
+            db: Arc::new(Mutex::new(db)),
+            seen_outputs: Arc::new(Mutex::new(HashSet::new())),
+            last_balance_refresh: Arc::new(Mutex::new(None)),
+            solvency,
+            multisig,
         }
     }
 
-    /// Load already processed txids from the memo file
-    fn load_processed_txids(memo_file: &PathBuf) -> HashSet<String> {
-        let mut txids = HashSet::new();
+    /// Load already processed outputs from the memo file
+    ///
+    /// Entries are keyed by output ("txid:pool:index"). Older entries without an
+    /// "Output:" field only carry the txid, and mark the whole transaction as processed.
+    fn load_processed_outputs(memo_file: &PathBuf) -> HashSet<String> {
+        let mut keys = HashSet::new();
         
         if let Ok(file) = File::open(memo_file) {
             let reader = BufReader::new(file);
             for line in reader.lines() {
                 if let Ok(line) = line {
-                    // Extract txid from format: "TXID: <txid> | ..."
-                    if let Some(txid_start) = line.find("TXID: ") {
-                        let txid_part = &line[txid_start + 6..];
-                        if let Some(txid_end) = txid_part.find(" |") {
-                            let txid = txid_part[..txid_end].trim().to_string();
-                            txids.insert(txid);
-                        }
+                    // Format: "TXID: <txid> | Output: <pool>:<index> | ..."
+                    let field = |name: &str| {
+                        line.split(" | ")
+                            .find_map(|part| part.trim().strip_prefix(name))
+                            .map(|value| value.trim().to_string())
+                    };
+                    
+                    if let Some(txid) = field("TXID: ") {
+                        match field("Output: ") {
+                            Some(output) => keys.insert(format!("{}:{}", txid, output)),
+                            None => keys.insert(txid),
+                        };
                     }
                 }
             }
         }
         
-        txids
+        keys
     }
 
-    /// Store memo to file
-    fn store_memo(&self, txid: &str, memo: &str, amount: u64) -> Result<(), String> {
+    /// Check whether an output (or, for legacy entries, its whole transaction) was already minted
+    fn is_processed(processed: &HashSet<String>, output: &DepositOutput) -> bool {
+        processed.contains(&output.key()) || processed.contains(&output.txid)
+    }
+
+    /// Record a minted output in the memo file
+    ///
+    /// Only the output and amount are written, never the memo: it carries the account ID and
+    /// secret the note was minted to.
+    fn record_processed(&self, output: &DepositOutput, amount: u64) -> BridgeResult<()> {
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.memo_file)
-            .map_err(|e| format!("Failed to open memo file: {}", e))?;
+            .map_err(|e| BridgeError::Internal(format!("Failed to open memo file: {}", e)))?;
         
-        let memo_entry = format!("TXID: {} | Amount: {} zatoshis | Memo: {}\n", txid, amount, memo);
-        file.write_all(memo_entry.as_bytes())
-            .map_err(|e| format!("Failed to write memo: {}", e))?;
+        let entry = format!("TXID: {} | Output: {}:{} | Amount: {} zatoshis\n", output.txid, output.pool, output.index, amount);
+        file.write_all(entry.as_bytes())
+            .map_err(|e| BridgeError::Internal(format!("Failed to write memo: {}", e)))?;
         
         Ok(())
     }
 
-    /// Mint note automatically for a deposit
-    async fn mint_note_for_deposit(&self, account_id: miden_objects::account::AccountId, secret: Word, amount: u64) -> Result<(String, String), String> {
+    /// Put an unprocessable deposit in the rejected queue
+    ///
+    /// Only digests of the memo and secret are kept. The user proves ownership
+    /// with either of them when requesting a refund, unless the memo carried a
+    /// refund address.
+    #[tracing::instrument(name = "deposit", skip_all, fields(txid = %output.txid, pool = %output.pool, output_index = output.index))]
+    fn reject_deposit(&self, output: &DepositOutput, memo_content: &str, reason: &str, recipient_hash: Option<&str>) {
+        let memo_digest = memo::knowledge_digest(memo_content);
+        let secret_digest = memo::memo_secret_hex(memo_content)
+            .map(|secret_hex| memo::knowledge_digest(&secret_hex));
+        let (_, memo_refund_address) = memo::split_refund_address(memo_content);
+
+        let recorded = self.db.lock().unwrap().refunds().record_rejection(
+            &output.txid,
+            &output.pool,
+            output.index,
+            output.value,
+            reason,
+            Some(&memo_digest),
+            secret_digest.as_deref(),
+            memo_refund_address.as_deref(),
+        );
+        match recorded {
+            Ok(()) => {
+                metrics::DEPOSITS_FAILED.with_label_values(&["rejected"]).inc();
+                info!(reason = %reason, "Moved deposit to the rejected queue (refundable)");
+                self.publish_event(output, recipient_hash, EventState::Rejected, None, Some(reason));
+            }
+            Err(e) => error!(error = %e, "Failed to record rejected deposit"),
+        }
+    }
+
+    /// Record a deposit state change for /events subscribers
+    ///
+    /// Locks the bridge database, so it can't be called while holding `self.db`.
+    fn publish_event(
+        &self,
+        output: &DepositOutput,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) {
+        let recorded = self.db.lock().unwrap().events().record_deposit(
+            &output.txid,
+            &output.pool,
+            output.index,
+            recipient_hash,
+            state,
+            note_id,
+            detail,
+        );
+        if let Err(e) = recorded {
+            warn!(state = state.as_str(), error = %e, "Failed to record deposit event");
+        }
+    }
+
+    /// Match a deposit output to the intent whose reference code it carried
+    ///
+    /// Fails with `Conflict` when the deposit can't be attributed to the intent (the reason it is
+    /// refunded), or with the store error when the match couldn't be recorded.
+    fn match_intent(intent_store: &dyn Intents, intent: DepositIntent, output: &DepositOutput) -> BridgeResult<DepositIntent> {
+        // Already matched to this output on an earlier scan (waiting for a claim, or a mint to retry)
+        if intent.matches_output(output) {
+            return Ok(intent);
+        }
+        
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        
+        match intent.status {
+            IntentStatus::Pending if intent.expires_at >= now => {}
+            IntentStatus::Pending | IntentStatus::Expired => {
+                return Err(BridgeError::Conflict(format!("deposit intent {} expired", intent.code)));
+            }
+            IntentStatus::Received | IntentStatus::AmountMismatch => {
+                return Err(BridgeError::Conflict(format!("deposit reference code {} was already used by an earlier deposit", intent.code)));
+            }
+        }
+        
+        match intent_store.mark_received(&intent.code, output) {
+            Ok(Some(IntentStatus::AmountMismatch)) => {
+                warn!(
+                    intent = %intent.code,
+                    amount = output.value,
+                    expected_amount = intent.expected_amount.unwrap_or_default(),
+                    "Deposit amount does not match its intent"
+                );
+            }
+            Ok(Some(_)) => {
+                info!(intent = %intent.code, "Matched deposit to intent");
+            }
+            Ok(None) => {
+                return Err(BridgeError::Conflict(format!("deposit reference code {} was already used by an earlier deposit", intent.code)));
+            }
+            Err(e) => {
+                return Err(e.context(&format!("failed to record deposit intent {}", intent.code)));
+            }
+        }
+        
+        Ok(intent)
+    }
+
+    /// Keystore, Miden store and RPC endpoint the relayer mints with
+    fn miden_paths(project_root: &std::path::Path) -> (PathBuf, PathBuf, String) {
         // Use rust-backend/keystore (where faucet was created) instead of project_root/keystore
-        let keystore_path = self.project_root.join("rust-backend").join("keystore");
-        let store_path = self.project_root.join("bridge_store.sqlite3");
-        let faucet_store_path = self.project_root.join("faucets.db");
+        let keystore_path = project_root.join("rust-backend").join("keystore");
+        let store_path = project_root.join("bridge_store.sqlite3");
         let rpc_url = std::env::var("RPC_URL")
             .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
-        
-            // Get or create faucet (will log address on first creation)
-            let faucet_id = get_or_create_zcash_faucet(
-            keystore_path.clone(),
-            store_path.clone(),
-            &rpc_url,
-            faucet_store_path,
-        )
-        .await?;
+        (keystore_path, store_path, rpc_url)
+    }
+
+    /// Get or create faucet (will log address on first creation)
+    async fn faucet_id(&self) -> BridgeResult<AccountId> {
+        let (keystore_path, store_path, rpc_url) = Self::miden_paths(&self.project_root);
+        get_or_create_zcash_faucet(keystore_path, store_path, &rpc_url, &self.db).await
+    }
+
+    /// Mint note automatically for a deposit
+    async fn mint_note_for_deposit(&self, account_id: AccountId, secret: Word, amount: u64) -> BridgeResult<(String, String)> {
+        let (keystore_path, store_path, rpc_url) = Self::miden_paths(&self.project_root);
+        let faucet_id = self.faucet_id().await?;
         
         // Mint note with account_id + secret (builds full recipient)
         crate::bridge::deposit::mint_deposit_note(
@@ -108,191 +270,497 @@ impl ZcashRelayer {
 
     /// Scan for deposits and extract memos
     async fn scan_and_extract_memos(&self) {
-        println!("[Zcash Relayer] Starting Zcash deposit scan...");
+        debug!("Starting Zcash deposit scan");
+        
+        // Per-user deposit addresses - outputs to them are attributed by address, not memo
+        let deposit_addresses = match self.db.lock().unwrap().deposit_addresses().all_addresses() {
+            Ok(addresses) => addresses,
+            Err(e) => {
+                warn!(error = %e, "Failed to load deposit addresses");
+                Default::default()
+            }
+        };
         
-        match self.bridge_wallet.extract_all_memos() {
+        match self.bridge_wallet.extract_all_memos(&deposit_addresses) {
             Ok(memos) => {
+                metrics::record_relayer_success("zcash");
                 let total_count = memos.len();
-                println!("[Zcash Relayer] Found {} transactions with memos", total_count);
+                debug!(outputs = total_count, "Found deposit outputs with memos");
                 
                 if total_count == 0 {
-                    println!("[Zcash Relayer] No transactions with memos found");
                     return;
                 }
                 
-                // Step 1: Identify new transactions while holding the lock (synchronously)
+                // Step 1: Identify new outputs while holding the lock (synchronously)
+                // Each output is its own deposit, keyed by (txid, pool, index)
                 let mut work_items = Vec::new();
+                let mut rejections = Vec::new();
+                let mut waiting = Vec::new();
                 let mut skipped_count = 0;
+                let mut addressed_recipients = HashSet::new();
                 
                 {
-                    let processed = self.processed_txids.lock().unwrap();
+                    let processed = self.processed_outputs.lock().unwrap();
+                    let db = self.db.lock().unwrap();
+                    let refund_queue = db.refunds();
+                    let address_book = db.deposit_addresses();
+                    let deposit_tracker = db.deposits();
+                    let intent_store = db.intents();
                     
-                    for (txid, memo, amount) in memos {
-                        // Skip if already processed
-                        if processed.contains(&txid) {
+                    for output in memos {
+                        // Skip if already processed, claimed or already in the rejected queue
+                        if Self::is_processed(&processed, &output) {
                             skipped_count += 1;
                             continue;
                         }
-                        
-                        // Extract memo content (remove "Memo::Text(" and ")")
-                        let memo_content = memo
-                            .trim()
-                            .strip_prefix("Memo::Text(\"")
-                            .and_then(|s| s.strip_suffix("\")"))
-                            .or_else(|| {
-                                // Also handle plain text without Memo::Text wrapper
-                                Some(memo.trim())
-                            })
-                            .unwrap_or_else(|| memo.trim());
-                        
-                        // Check if memo contains account_id|secret format
-                        if let Some(pipe_pos) = memo_content.find('|') {
-                            // Parse account_id|secret format
-                            let account_id_str = &memo_content[..pipe_pos];
-                            let secret_str = &memo_content[pipe_pos + 1..];
-                            
-                            // Validate account_id (should be 30 hex chars = 15 bytes, with or without 0x)
-                            // AccountId::from_hex expects 0x + 30 hex chars = 32 total chars
-                            let account_id_hex = if account_id_str.starts_with("0x") {
-                                &account_id_str[2..]
-                            } else {
-                                account_id_str
-                            };
-                            
-                            if !account_id_hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                                println!("[Zcash Relayer] Skipping tx {} - account_id contains non-hex characters: {}", txid, account_id_str);
+                        let handled = deposit_tracker.is_output_claimed(&output.txid, &output.pool, output.index)
+                            .and_then(|claimed| Ok(claimed || refund_queue.is_rejected(&output.txid, &output.pool, output.index)?));
+                        match handled {
+                            Ok(false) => {}
+                            Ok(true) => {
+                                skipped_count += 1;
                                 continue;
                             }
-                            
-                            if account_id_hex.len() > 30 {
-                                println!("[Zcash Relayer] Skipping tx {} - account_id too long (max 30 hex chars, got {}): {}", txid, account_id_hex.len(), account_id_str);
-                                continue;
-                            }
-                            
-                            // Pad with leading zeros to 30 chars if needed (AccountId expects 30 hex chars)
-                            let account_id_padded = if account_id_hex.len() < 30 {
-                                format!("{:0>30}", account_id_hex)
-                            } else {
-                                account_id_hex.to_string()
-                            };
-                            
-                            // AccountId::from_hex expects 0x prefix + 30 hex chars
-                            let account_id_for_parse = format!("0x{}", account_id_padded);
-                            
-                            // Validate secret (should be 64 hex chars, with or without 0x)
-                            let secret_hex = if secret_str.starts_with("0x") {
-                                &secret_str[2..]
-                            } else {
-                                secret_str
-                            };
-                            
-                            if secret_hex.len() != 64 || !secret_hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                                println!("[Zcash Relayer] Skipping tx {} - invalid secret format (expected 64 hex chars, got {} chars): {}", txid, secret_hex.len(), secret_str);
+                            // Not knowing is not the same as unseen; try again next cycle
+                            Err(e) => {
+                                error!(txid = %output.txid, pool = %output.pool, output_index = output.index, error = %e, "Failed to look up deposit output, skipping it");
+                                skipped_count += 1;
                                 continue;
                             }
-                            
-                            // Parse account_id and secret
-                            let account_id = match miden_objects::account::AccountId::from_hex(&account_id_for_parse) {
-                                Ok(id) => id,
+                        }
+                        
+                        let _span = info_span!("deposit", txid = %output.txid, pool = %output.pool, output_index = output.index).entered();
+                        
+                        if self.seen_outputs.lock().unwrap().insert(output.key()) {
+                            metrics::DEPOSITS_SEEN.inc();
+                        }
+                        
+                        // Extract memo content (remove "Memo::Text(" and ")")
+                        let memo_content = memo::memo_content(output.memo.as_deref().unwrap_or("")).to_string();
+                        
+                        // Work out who the output is for: a per-user deposit address, or a
+                        // deposit intent reference code in the memo. Both give the recipient
+                        // hash, plus account_id/secret if the user opted into automatic minting.
+                        let deposit_address = output.to_address.as_deref()
+                            .and_then(|address| address_book.get(address).ok().flatten());
+                        
+                        let attributed = if let Some(deposit_address) = deposit_address {
+                            Some((
+                                format!("deposit address {}", deposit_address.address),
+                                deposit_address.recipient_hash,
+                                deposit_address.account_id,
+                                deposit_address.secret,
+                            ))
+                        } else if let Some(code) = memo::parse_reference_code(&memo_content) {
+                            let intent = match intent_store.get(&code) {
+                                Ok(Some(intent)) => intent,
+                                Ok(None) => {
+                                    let reason = format!("unknown deposit reference code {}", code);
+                                    info!(reason = %reason, "Skipping deposit");
+                                    rejections.push((output, memo_content, reason, None));
+                                    continue;
+                                }
                                 Err(e) => {
-                                    eprintln!("[Zcash Relayer] Invalid account_id in tx {}: {} (padded: {}) - {}", txid, account_id_str, account_id_for_parse, e);
+                                    error!(intent = %code, error = %e, "Failed to look up deposit intent");
+                                    skipped_count += 1;
                                     continue;
                                 }
                             };
                             
-                            let secret_with_prefix = if secret_str.starts_with("0x") {
-                                secret_str.to_string()
-                            } else {
-                                format!("0x{}", secret_str)
-                            };
-                            let secret_word = match Word::try_from(secret_with_prefix.as_str()) {
-                                Ok(word) => word,
+                            let intent_recipient = intent.recipient_hash.clone();
+                            match Self::match_intent(intent_store, intent, &output) {
+                                Ok(intent) => Some((
+                                    format!("deposit intent {}", intent.code),
+                                    intent.recipient_hash,
+                                    intent.account_id,
+                                    intent.secret,
+                                )),
+                                // The intent is fine, recording the match failed: retry on the next scan
+                                Err(e @ BridgeError::Database(_)) => {
+                                    error!(intent = %code, error = %e, "Failed to match deposit to intent");
+                                    skipped_count += 1;
+                                    continue;
+                                }
                                 Err(e) => {
-                                    eprintln!("[Zcash Relayer] Invalid secret in tx {}: {} - {}", txid, secret_str, e);
+                                    let reason = e.to_string();
+                                    info!(reason = %reason, "Skipping deposit");
+                                    rejections.push((output, memo_content, reason, Some(intent_recipient)));
                                     continue;
                                 }
-                            };
-                            
-                            // Store work item with account_id and secret
-                            work_items.push((txid, account_id, secret_word, amount));
+                            }
                         } else {
-                            // Fallback: try to parse as old hash format (for backward compatibility)
-                            let recipient_hash = memo_content;
+                            None
+                        };
+                        
+                        if let Some((source, recipient_hash, account_id, secret)) = attributed {
+                            // Digests of this are kept if the deposit is rejected, so the secret proves ownership
+                            let credentials = match (&account_id, &secret) {
+                                (Some(account_id), Some(secret)) => Some(format!("{}|{}", account_id, secret)),
+                                _ => None,
+                            };
                             
-                            // Validate recipient hash format: must be 0x + 64 hex chars = 66 chars total
-                            if recipient_hash.len() != 66 || !recipient_hash.starts_with("0x") {
-                                println!("[Zcash Relayer] Skipping tx {} - invalid memo format (expected account_id|secret or 0x + 64 hex chars, got {} chars): {}", txid, recipient_hash.len(), recipient_hash);
+                            // Minting twice to the same recipient would give the notes the same nullifier,
+                            // and a deposit waiting for a claim could never be claimed: refund either
+                            if deposit_tracker.is_claimed(&recipient_hash).unwrap_or(false)
+                                || !addressed_recipients.insert(recipient_hash.clone()) {
+                                let reason = format!("recipient of {} already received an earlier deposit (use a new address or intent for each deposit)", source);
+                                info!(reason = %reason, "Skipping deposit");
+                                rejections.push((output, credentials.unwrap_or(memo_content), reason, Some(recipient_hash)));
                                 continue;
                             }
                             
-                            // Validate it's all hex after 0x
-                            let hex_part = &recipient_hash[2..];
-                            if !hex_part.chars().all(|c| c.is_ascii_hexdigit()) || hex_part.len() != 64 {
-                                println!("[Zcash Relayer] Skipping tx {} - recipient hash is not valid hex (64 chars): {}", txid, recipient_hash);
+                            let Some(credentials) = credentials else {
+                                // Only the recipient hash is known - the user claims it with /deposit/claim
+                                info!(source = %source, "Deposit is waiting to be claimed");
+                                waiting.push((output, recipient_hash));
+                                skipped_count += 1;
                                 continue;
-                            }
+                            };
                             
-                            // Parse recipient_hash as Word (old format - will fail to mint but won't crash)
-                            let _recipient_word = match Word::try_from(recipient_hash) {
-                                Ok(word) => word,
+                            match memo::parse_deposit_memo(&credentials) {
+                                Ok(deposit_memo) => {
+                                    work_items.push((output, credentials, deposit_memo, Some(recipient_hash)));
+                                }
                                 Err(e) => {
-                                    eprintln!("[Zcash Relayer] Invalid recipient hash in tx {}: {} - {}", txid, recipient_hash, e);
-                                    continue;
+                                    let reason = e.to_string();
+                                    info!(reason = %reason, "Skipping deposit");
+                                    rejections.push((output, credentials, reason, Some(recipient_hash)));
                                 }
-                            };
-                            
-                            // Store work item with hash (old format - will need to be updated)
-                            println!("[Zcash Relayer] Warning: tx {} uses old hash format. Please use account_id|secret format.", txid);
-                            // For now, skip old format transactions
+                            }
                             continue;
                         }
+                        
+                        match memo::parse_deposit_memo(&memo_content) {
+                            Ok(deposit_memo) => {
+                                work_items.push((output, memo_content, deposit_memo, None));
+                            }
+                            Err(e) => {
+                                let reason = e.to_string();
+                                info!(reason = %reason, "Skipping deposit");
+                                rejections.push((output, memo_content, reason, None));
+                            }
+                        }
+                    }
+                    
+                    // Expire stale intents after matching, so deposits seen in this scan still count
+                    match intent_store.expire_stale() {
+                        Ok(0) => {}
+                        Ok(expired) => info!(expired, "Expired stale deposit intents"),
+                        Err(e) => warn!(error = %e, "Failed to expire deposit intents"),
                     }
                 } // Lock is dropped here
                 
+                for (output, recipient_hash) in &waiting {
+                    self.publish_event(output, Some(recipient_hash), EventState::Received, None, None);
+                }
+                
+                // Unprocessable deposits go to the rejected queue so they can be refunded
+                for (output, memo_content, reason, recipient_hash) in rejections {
+                    self.reject_deposit(&output, &memo_content, &reason, recipient_hash.as_deref());
+                }
+                
+                // Minting halts while reserves don't cover the outstanding supply;
+                // the deposits stay unprocessed and are picked up again on the next scan
+                // Deposits waiting for a claim may have a mint proposal from /deposit/claim to sign
+                let approvals = match &self.multisig {
+                    Some(_) => waiting.len(),
+                    None => 0,
+                };
+                if (!work_items.is_empty() || approvals > 0)
+                    && let Err(e) = self.solvency.ensure_solvent().await
+                {
+                    warn!(error = %e, deposits = work_items.len() + approvals, "Not minting deposits");
+                    return;
+                }
+                
                 // Step 2: Process work items asynchronously (without holding the lock)
                 let mut new_count = 0;
-                for (txid, account_id, secret, amount) in work_items {
-                    println!("[Zcash Relayer] Found new deposit in tx {}: account_id={}, amount={} zatoshis", txid, account_id, amount);
-
-                    // Automatically mint note with account_id + secret
-                    println!("[Zcash Relayer] Minting note for deposit tx {}...", txid);
-                    match self.mint_note_for_deposit(account_id, secret, amount).await {
-                        Ok((note_id, tx_id)) => {
-                            // Re-acquire lock to mark as processed
-                            {
-                                let mut processed = self.processed_txids.lock().unwrap();
-                                processed.insert(txid.clone());
-                            }
+                for (output, memo_content, deposit_memo, recipient_hash) in work_items {
+                    if self.mint_deposit(output, memo_content, deposit_memo, recipient_hash).await {
+                        new_count += 1;
+                    }
+                }
+                if let Some(coordinator) = &self.multisig {
+                    for (output, recipient_hash) in &waiting {
+                        if self.approve_claimed_deposit(coordinator, output, recipient_hash).await {
                             new_count += 1;
-                            println!("[Zcash Relayer] ✅ Minted note {} (tx: {}) for deposit tx {}", note_id, tx_id, txid);
-
-                            // Also store in memo file for reference
-                            let _ = self.store_memo(&txid, &format!("{}|{}", account_id, secret), amount);
-                        }
-                        Err(e) => {
-                            eprintln!("[Zcash Relayer] ❌ Failed to mint note for tx {}: {}", txid, e);
                         }
                     }
                 }
                 
                 if new_count == 0 {
-                    println!("[Zcash Relayer] No new memos found ({} total, {} already processed)", total_count, skipped_count);
+                    debug!(outputs = total_count, skipped = skipped_count, "No new deposits");
                 } else {
-                    println!("[Zcash Relayer] Processed {} new memos ({} total, {} skipped)", new_count, total_count, skipped_count);
+                    info!(minted = new_count, outputs = total_count, skipped = skipped_count, "Processed new deposits");
+                }
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to extract memos");
+            }
+        }
+    }
+
+    /// Mint the note for one deposit output, returning whether it was minted
+    #[tracing::instrument(name = "deposit", skip_all, fields(txid = %output.txid, pool = %output.pool, output_index = output.index))]
+    async fn mint_deposit(
+        &self,
+        output: DepositOutput,
+        memo_content: String,
+        deposit_memo: memo::DepositMemo,
+        recipient_hash: Option<String>,
+    ) -> bool {
+        let deposit_key = output.key();
+        let amount = output.value;
+        let account_id = deposit_memo.account_id;
+        let secret = deposit_memo.secret;
+        info!(account_id = %account_id, amount, "Found new deposit");
+
+        // Memo deposits don't come with a recipient hash, but subscribers may know it
+        let event_recipient = recipient_hash.clone().or_else(|| {
+            build_deposit_recipient(account_id, secret).ok().map(|recipient| recipient.digest().to_hex())
+        });
+        self.publish_event(&output, event_recipient.as_deref(), EventState::Received, None, None);
+
+        // Deduct the deposit fee - the note is minted for the net amount
+        let quote = match self.fee_schedule.quote(FeeDirection::Deposit, ZCASH_ORIGIN_NETWORK, amount) {
+            Ok(quote) => quote,
+            Err(e) => {
+                info!(reason = %e, "Skipping deposit");
+                self.reject_deposit(&output, &memo_content, &e.to_string(), event_recipient.as_deref());
+                return false;
+            }
+        };
+        info!(fee = quote.fee, net_amount = quote.net_amount, amount, "Minting note for deposit");
+
+        // Reserve the output first, so a concurrent /deposit/claim can't mint it as well
+        let Some(claim_recipient) = event_recipient.as_deref() else {
+            error!("Failed to build the deposit recipient");
+            return false;
+        };
+        match self.reserve_claim(&output, claim_recipient) {
+            Ok(true) => {}
+            Ok(false) => {
+                let output_claimed = self.db.lock().unwrap()
+                    .deposits()
+                    .is_output_claimed(&output.txid, &output.pool, output.index);
+                match output_claimed {
+                    Ok(true) => {
+                        info!("Deposit was claimed through /deposit/claim");
+                        self.processed_outputs.lock().unwrap().insert(deposit_key);
+                    }
+                    Ok(false) => {
+                        // Minting twice to the same recipient would give the notes the same nullifier
+                        let reason = "recipient already received an earlier deposit (use a new secret for each deposit)";
+                        info!(reason = %reason, "Skipping deposit");
+                        self.reject_deposit(&output, &memo_content, reason, event_recipient.as_deref());
+                    }
+                    Err(e) => error!(error = %e, "Failed to check claim status"),
+                }
+                return false;
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to reserve deposit claim");
+                return false;
+            }
+        }
+
+        // Automatically mint note with account_id + secret, or propose and sign it when
+        // mints need approver signatures
+        let mint_timer = metrics::MINT_LATENCY.start_timer();
+        let mint_result = match &self.multisig {
+            Some(coordinator) => {
+                let view = DepositView {
+                    deposit: deposit_key.clone(),
+                    recipient_hash: claim_recipient.to_string(),
+                    amount,
+                    net_amount: quote.net_amount,
+                };
+                self.contribute_mint(coordinator, &view, true).await
+            }
+            None => self.mint_note_for_deposit(account_id, secret, quote.net_amount).await.map(Some),
+        };
+        mint_timer.observe_duration();
+        match mint_result {
+            Ok(Some((note_id, tx_id))) => {
+                self.record_mint(&output, recipient_hash.as_deref(), event_recipient.as_deref(), &quote, &note_id, &tx_id);
+
+                // Also record it in the memo file, which processed outputs are reloaded from on restart
+                if let Err(e) = self.record_processed(&output, amount) {
+                    warn!(error = %e, "Failed to record processed output in the memo file");
                 }
+                true
+            }
+            // Waiting for approvers: the mint proposal keeps it to one mint from here
+            Ok(None) => {
+                self.release_claim(&output);
+                false
+            }
+            Err(e) => {
+                self.release_claim(&output);
+                metrics::DEPOSITS_FAILED.with_label_values(&["mint_error"]).inc();
+                error!(error = %e, "Failed to mint note for deposit");
+                self.publish_event(&output, event_recipient.as_deref(), EventState::MintFailed, None, Some(e.code()));
+                false
+            }
+        }
+    }
+
+    /// Sign the API's mint proposal for a deposit waiting to be claimed, submitting it once
+    /// enough approvers have signed; returns whether this relayer minted it
+    #[tracing::instrument(name = "deposit", skip_all, fields(txid = %output.txid, pool = %output.pool, output_index = output.index))]
+    async fn approve_claimed_deposit(&self, coordinator: &MintCoordinator, output: &DepositOutput, recipient_hash: &str) -> bool {
+        // Deposits the fee schedule refuses are refused by /deposit/claim as well
+        let Ok(quote) = self.fee_schedule.quote(FeeDirection::Deposit, ZCASH_ORIGIN_NETWORK, output.value) else {
+            return false;
+        };
+        let view = DepositView {
+            deposit: output.key(),
+            recipient_hash: recipient_hash.to_string(),
+            amount: output.value,
+            net_amount: quote.net_amount,
+        };
+
+        // Held by a /deposit/claim in progress until it has proposed the mint; try on the next scan
+        match self.reserve_claim(output, recipient_hash) {
+            Ok(true) => {}
+            Ok(false) => return false,
+            Err(e) => {
+                warn!(error = %e, "Failed to reserve deposit claim");
+                return false;
+            }
+        }
+
+        match self.contribute_mint(coordinator, &view, false).await {
+            Ok(Some((note_id, tx_id))) => {
+                self.record_mint(output, Some(recipient_hash), Some(recipient_hash), &quote, &note_id, &tx_id);
+                true
+            }
+            Ok(None) => {
+                self.release_claim(output);
+                false
             }
             Err(e) => {
-                eprintln!("[Zcash Relayer] Failed to extract memos: {}", e);
+                self.release_claim(output);
+                warn!(error = %e, "Failed to approve mint of claimed deposit");
+                false
+            }
+        }
+    }
+
+    /// Sign the withdrawal commitment notes the API proposed, submitting each once enough
+    /// approvers have
+    async fn approve_withdrawal_notes(&self) {
+        let Some(coordinator) = &self.multisig else {
+            return;
+        };
+        let approved = match self.faucet_id().await {
+            Ok(faucet_id) => coordinator.approve_withdrawal_notes(&self.db, faucet_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = approved {
+            warn!(error = %e, "Failed to approve withdrawal notes");
+        }
+    }
+
+    /// Contribute to a multisig mint, returning the note and transaction if this relayer submitted it
+    async fn contribute_mint(&self, coordinator: &MintCoordinator, view: &DepositView, propose: bool) -> BridgeResult<Option<(String, String)>> {
+        let faucet_id = self.faucet_id().await?;
+        match coordinator.contribute(&self.db, faucet_id, view, propose).await? {
+            MintProgress::Submitted { note_id, tx_id } => Ok(Some((note_id, tx_id))),
+            MintProgress::Pending { signatures, threshold } => {
+                info!(signatures, threshold, "Mint is waiting for approver signatures");
+                Ok(None)
+            }
+            MintProgress::SubmittedElsewhere => {
+                debug!("Mint was submitted by another instance");
+                self.processed_outputs.lock().unwrap().insert(view.deposit.clone());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reserve a deposit output for a mint; false if it's already claimed or reserved
+    fn reserve_claim(&self, output: &DepositOutput, recipient_hash: &str) -> BridgeResult<bool> {
+        self.db.lock().unwrap()
+            .deposits()
+            .reserve_claim(recipient_hash, &output.txid, &output.pool, output.index, output.value)
+    }
+
+    /// Drop the reservation of a mint that didn't happen, so a later scan or claim can retry it
+    fn release_claim(&self, output: &DepositOutput) {
+        let released = self.db.lock().unwrap()
+            .deposits()
+            .release_claim(&output.txid, &output.pool, output.index);
+        if let Err(e) = released {
+            error!(error = %e, "Failed to release deposit claim reservation");
+        }
+    }
+
+    /// Record a minted deposit: processed output, Minted event, claim, fee and audit entry
+    fn record_mint(
+        &self,
+        output: &DepositOutput,
+        recipient_hash: Option<&str>,
+        event_recipient: Option<&str>,
+        quote: &FeeQuote,
+        note_id: &str,
+        tx_id: &str,
+    ) {
+        let deposit_key = output.key();
+        metrics::DEPOSITS_MINTED.inc();
+        // Re-acquire lock to mark as processed
+        {
+            let mut processed = self.processed_outputs.lock().unwrap();
+            processed.insert(deposit_key.clone());
+        }
+        info!(note_id = %note_id, tx_id = %tx_id, "Minted note for deposit");
+        self.publish_event(output, event_recipient, EventState::Minted, Some(note_id), Some(tx_id));
+
+        // Confirm the claim reserved before minting, so it can't be claimed a second time
+        // through /deposit/claim. The fee and the audit entry are written in the same transaction.
+        let recorded = self.db.lock().unwrap().transaction(|db| {
+            let claimed = db.deposits().confirm_claim(&output.txid, &output.pool, output.index)?;
+            db.fees().record_fee(quote, &deposit_key)?;
+            let inputs = serde_json::json!({
+                "recipient_hash": recipient_hash,
+                "deposit": deposit_key,
+                "amount": output.value,
+                "fee": quote.fee,
+            });
+            db.audit().append(AuditAction::DepositMint, &inputs_digest(&inputs), Some(note_id), "zcash_relayer")?;
+            Ok(claimed)
+        });
+        match recorded {
+            Ok(true) => {}
+            Ok(false) => warn!("Deposit claim reservation was gone when its mint finished"),
+            Err(e) => warn!(error = %e, "Failed to record claim, fee and audit entry for deposit"),
+        }
+    }
+
+    /// Refresh bridge wallet balance and height gauges, at most once a minute
+    fn refresh_wallet_metrics(&self) {
+        {
+            let mut last_refresh = self.last_balance_refresh.lock().unwrap();
+            if last_refresh.is_some_and(|at| at.elapsed() < Duration::from_secs(60)) {
+                return;
             }
+            *last_refresh = Some(std::time::Instant::now());
+        }
+        
+        match self.bridge_wallet.get_balance() {
+            Ok(balance) => metrics::record_wallet_balance(&balance),
+            Err(e) => warn!(error = %e, "Failed to read bridge wallet balance"),
         }
     }
 
     /// Start the relayer as a background task
     pub async fn start(self) {
-        println!("[Zcash Relayer] Starting Zcash relayer with scan interval: {:?} seconds", self.scan_interval.as_secs());
+        info!(scan_interval_secs = self.scan_interval.as_secs(), "Starting Zcash relayer");
         
         // Run initial scan
         self.scan_and_extract_memos().await;
+        self.approve_withdrawal_notes().await;
+        self.refresh_wallet_metrics();
         
         // Set up periodic scanning
         let mut interval = interval(self.scan_interval);
@@ -300,6 +768,8 @@ impl ZcashRelayer {
         loop {
             interval.tick().await;
             self.scan_and_extract_memos().await;
+            self.approve_withdrawal_notes().await;
+            self.refresh_wallet_metrics();
         }
     }
 }
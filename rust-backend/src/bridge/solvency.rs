@@ -0,0 +1,266 @@
+use crate::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use crate::bridge::reconcile::MidenLedger;
+use crate::db::{self, BridgeStore};
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use crate::zcash::bridge_wallet::{BridgeWallet, ZcashBalance};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+/// Solvency limits, loaded from the environment
+///
+/// SOLVENCY_MAX_DEFICIT_ZATOSHIS  shortfall of reserves below outstanding supply tolerated before
+///                                minting and payouts halt (default 0)
+/// SOLVENCY_CHECK_INTERVAL_SECS   how long a check is reused before the chain and wallet are read again (default 60)
+#[derive(Debug, Clone)]
+pub struct SolvencyConfig {
+    pub max_deficit: u64,
+    pub check_interval: Duration,
+}
+
+impl SolvencyConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            max_deficit: var("SOLVENCY_MAX_DEFICIT_ZATOSHIS", 0),
+            check_interval: Duration::from_secs(var("SOLVENCY_CHECK_INTERVAL_SECS", 60)),
+        }
+    }
+}
+
+/// Outstanding wTAZ supply compared with the bridge wallet's reserves
+///
+/// wTAZ and TAZ both use 8 decimals, so every amount is in zatoshis.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SolvencyReport {
+    pub checked_at: String,
+    /// Tokens the faucet has issued
+    pub issued: u64,
+    /// Tokens burned back through exit notes
+    pub burned: u64,
+    /// Issued minus burned: what the reserves have to cover
+    pub outstanding: u64,
+    /// Confirmed (spendable) balance per pool: "sapling", "orchard", "transparent"
+    pub reserves_by_pool: BTreeMap<String, u64>,
+    pub reserves: u64,
+    /// How far reserves fall short of the outstanding supply (0 when fully backed)
+    pub deficit: u64,
+    /// Reserves divided by outstanding supply; null while nothing is outstanding
+    pub ratio: Option<f64>,
+    pub max_deficit: u64,
+    /// Minting and payouts are refused while the deficit is above `max_deficit`
+    pub halted: bool,
+}
+
+/// Compare the supply with the reserves
+pub fn assess(issued: u64, burned: u64, reserves_by_pool: BTreeMap<String, u64>, max_deficit: u64) -> SolvencyReport {
+    let outstanding = issued.saturating_sub(burned);
+    let reserves: u64 = reserves_by_pool.values().sum();
+    let deficit = outstanding.saturating_sub(reserves);
+
+    SolvencyReport {
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        issued,
+        burned,
+        outstanding,
+        reserves_by_pool,
+        reserves,
+        deficit,
+        ratio: (outstanding > 0).then(|| reserves as f64 / outstanding as f64),
+        max_deficit,
+        halted: deficit > max_deficit,
+    }
+}
+
+/// Parse a TAZ amount as the wallet prints it ("0.19990000") into zatoshis, without going through floats
+pub fn parse_zatoshis(taz: &str) -> BridgeResult<u64> {
+    let invalid = || BridgeError::Internal(format!("Invalid TAZ amount: {:?}", taz));
+
+    let taz = taz.trim();
+    let (whole, fraction) = taz.split_once('.').unwrap_or((taz, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || fraction.len() > 8
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let whole = if whole.is_empty() { 0 } else { whole.parse::<u64>().map_err(|_| invalid())? };
+    let fraction = format!("{:0<8}", fraction).parse::<u64>().map_err(|_| invalid())?;
+    whole.checked_mul(100_000_000)
+        .and_then(|zatoshis| zatoshis.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Confirmed bridge wallet balance per pool, in zatoshis
+pub fn reserves_by_pool(balance: &ZcashBalance) -> BridgeResult<BTreeMap<String, u64>> {
+    balance.spendable_by_pool.iter()
+        .map(|(pool, spendable)| Ok((pool.clone(), parse_zatoshis(spendable)?)))
+        .collect()
+}
+
+/// Viewing key the operator publishes so anyone can check the reserves themselves
+///
+/// Opt-in, configured from the environment:
+///
+/// RESERVES_DISCLOSURE     "ufvk" publishes the bridge account's unified full viewing key (default off)
+/// BRIDGE_WALLET_BIRTHDAY  height the bridge wallet was created at, where verifiers start scanning
+///
+/// The key reveals every transaction of the bridge wallet, not just its balance.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReservesDisclosure {
+    /// Zcash network the key belongs to ("test")
+    pub network: String,
+    pub ufvk: String,
+    pub birthday_height: u64,
+}
+
+impl ReservesDisclosure {
+    /// The disclosure to publish, or None unless the operator opted in
+    pub fn from_env(bridge_wallet: &BridgeWallet) -> BridgeResult<Option<Self>> {
+        match std::env::var("RESERVES_DISCLOSURE").as_deref() {
+            Ok("ufvk") => {}
+            Ok("off") | Err(_) => return Ok(None),
+            Ok(other) => {
+                return Err(BridgeError::InvalidInput(format!(
+                    "Unknown RESERVES_DISCLOSURE: {} (use ufvk or off)",
+                    other
+                )));
+            }
+        }
+
+        let birthday_height = std::env::var("BRIDGE_WALLET_BIRTHDAY")
+            .ok()
+            .and_then(|height| height.parse::<u64>().ok())
+            .ok_or_else(|| BridgeError::InvalidInput("BRIDGE_WALLET_BIRTHDAY must be set to publish the viewing key".to_string()))?;
+
+        Ok(Some(Self {
+            network: "test".to_string(),
+            ufvk: bridge_wallet.full_viewing_key()?,
+            birthday_height,
+        }))
+    }
+}
+
+/// Checks solvency and refuses minting or payouts while the bridge is short of reserves
+///
+/// A check reads the Miden store and the Zcash wallet, so it is reused for the configured
+/// interval. A check that fails halts as well: solvency can't be shown without it.
+pub struct SolvencyGuard {
+    config: SolvencyConfig,
+    bridge_wallet: Arc<BridgeWallet>,
+    project_root: PathBuf,
+    db: Mutex<Box<dyn BridgeStore>>,
+    last: Mutex<Option<(Instant, SolvencyReport)>>,
+}
+
+impl SolvencyGuard {
+    pub fn new(bridge_wallet: Arc<BridgeWallet>, project_root: PathBuf) -> Self {
+        let db = db::open(&project_root)
+            .expect("Failed to open bridge database");
+
+        Self {
+            config: SolvencyConfig::from_env(),
+            bridge_wallet,
+            project_root,
+            db: Mutex::new(db),
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Read the supply and reserves again
+    pub async fn check(&self) -> BridgeResult<SolvencyReport> {
+        let faucet_id = self.db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)))?
+            .faucets()
+            .get_faucet_id(ZCASH_ORIGIN_NETWORK)?;
+
+        // The Miden client isn't Send, so it runs on a blocking thread like the API's mints
+        let miden = tokio::task::spawn_blocking({
+            let project_root = self.project_root.clone();
+            move || {
+                tokio::runtime::Handle::current().block_on(async {
+                    // Without a faucet nothing was minted yet
+                    match faucet_id {
+                        Some(faucet_id) => MidenLedger::load(&project_root, faucet_id).await,
+                        None => Ok(MidenLedger::default()),
+                    }
+                })
+            }
+        })
+        .await
+        .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+        .map_err(|e| e.context("Failed to read the faucet supply"))?;
+        let burned = miden.exit_burns.iter().map(|note| note.amount).sum();
+
+        let balance = tokio::task::spawn_blocking({
+            let bridge_wallet = self.bridge_wallet.clone();
+            move || bridge_wallet.get_balance()
+        })
+        .await
+        .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+        .map_err(|e| e.context("Failed to read the bridge wallet balance"))?;
+
+        let report = assess(miden.issued_supply, burned, reserves_by_pool(&balance)?, self.config.max_deficit);
+
+        metrics::SOLVENCY_ONCHAIN_SUPPLY.set(report.outstanding as i64);
+        metrics::SOLVENCY_RESERVES.set(report.reserves as i64);
+        metrics::SOLVENCY_RATIO.set(report.ratio.unwrap_or(1.0));
+        metrics::SOLVENCY_HALTED.set(report.halted as i64);
+        if report.halted {
+            error!(
+                outstanding = report.outstanding,
+                reserves = report.reserves,
+                deficit = report.deficit,
+                max_deficit = report.max_deficit,
+                "Bridge reserves below outstanding supply; minting and payouts halted"
+            );
+        } else {
+            info!(outstanding = report.outstanding, reserves = report.reserves, ratio = ?report.ratio, "Solvency checked");
+        }
+
+        *self.last.lock().unwrap() = Some((Instant::now(), report.clone()));
+        Ok(report)
+    }
+
+    /// Latest report, checking again once the last one is older than the check interval
+    pub async fn report(&self) -> BridgeResult<SolvencyReport> {
+        if let Some((at, report)) = self.last.lock().unwrap().as_ref()
+            && at.elapsed() < self.config.check_interval
+        {
+            return Ok(report.clone());
+        }
+        self.check().await
+    }
+
+    /// Err while the bridge is halted, to be called before minting or paying out
+    pub async fn ensure_solvent(&self) -> BridgeResult<()> {
+        let report = match self.report().await {
+            Ok(report) => report,
+            Err(e) => {
+                warn!(error = %e, "Solvency check failed");
+                metrics::SOLVENCY_HALTED.set(1);
+                return Err(BridgeError::InsufficientLiquidity(format!("Bridge halted, solvency check failed: {}", e)));
+            }
+        };
+
+        if report.halted {
+            return Err(BridgeError::InsufficientLiquidity(format!(
+                "Bridge halted: reserves of {} zatoshis are {} short of the {} wTAZ outstanding",
+                report.reserves, report.deficit, report.outstanding
+            )));
+        }
+        Ok(())
+    }
+}
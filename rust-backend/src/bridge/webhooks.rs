@@ -0,0 +1,360 @@
+use crate::db::{self, BridgeStore};
+use crate::db::events::BridgeEvent;
+use crate::db::webhooks::WebhookDelivery;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::time::{Duration, interval};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+/// `sha256=<hex HMAC-SHA256(secret, "<timestamp>.<body>")>`
+pub const SIGNATURE_HEADER: &str = "X-Bridge-Signature";
+/// Unix time the delivery attempt was signed at - receivers should reject old ones
+pub const TIMESTAMP_HEADER: &str = "X-Bridge-Timestamp";
+/// Event type, e.g. "deposit.minted"
+pub const EVENT_HEADER: &str = "X-Bridge-Event";
+/// Delivery id - the same for every retry, so receivers can drop duplicates
+pub const DELIVERY_HEADER: &str = "X-Bridge-Delivery";
+
+/// Most deliveries attempted per dispatcher tick
+const DELIVERY_BATCH: u32 = 50;
+
+/// A receiver of webhook deliveries
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookSubscription {
+    /// Unique name, deliveries are recorded against it
+    pub name: String,
+    pub url: String,
+    /// Shared HMAC key
+    pub secret: String,
+    /// Event types to deliver ("deposit.minted", "withdrawal.paid", ...), all if empty
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|wanted| wanted == event_type)
+    }
+}
+
+/// Webhook subscriptions and retry policy, loaded from webhooks.json
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub subscriptions: Vec<WebhookSubscription>,
+    /// Attempts before a delivery moves to the dead-letter list
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    #[serde(default = "default_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    8
+}
+
+fn default_initial_backoff_secs() -> u64 {
+    30
+}
+
+fn default_max_backoff_secs() -> u64 {
+    6 * 60 * 60
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            subscriptions: Vec::new(),
+            max_attempts: default_max_attempts(),
+            initial_backoff_secs: default_initial_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Load webhook subscriptions, falling back to none if no file exists
+    pub fn load(project_root: &Path) -> BridgeResult<Self> {
+        let path = std::env::var("BRIDGE_WEBHOOKS")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| project_root.join("webhooks.json"));
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| BridgeError::Internal(format!("Failed to read webhook config {:?}: {}", path, e)))?;
+
+        let config: Self = serde_json::from_str(&contents)
+            .map_err(|e| BridgeError::Internal(format!("Failed to parse webhook config {:?}: {}", path, e)))?;
+
+        config.validate()
+            .map_err(|e| BridgeError::Internal(format!("Invalid webhook config {:?}: {}", path, e)))?;
+
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for subscription in &self.subscriptions {
+            if !names.insert(subscription.name.as_str()) {
+                return Err(format!("duplicate subscription name {}", subscription.name));
+            }
+            if !subscription.url.starts_with("https://") && !subscription.url.starts_with("http://") {
+                return Err(format!("subscription {} needs an http(s) url", subscription.name));
+            }
+            if subscription.secret.trim().is_empty() {
+                return Err(format!("subscription {} has no secret", subscription.name));
+            }
+        }
+        if self.max_attempts == 0 {
+            return Err("max_attempts must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn subscription(&self, name: &str) -> Option<&WebhookSubscription> {
+        self.subscriptions.iter().find(|subscription| subscription.name == name)
+    }
+
+    /// Delay before the next attempt, after `attempts` failed ones
+    pub fn backoff_secs(&self, attempts: u32) -> u64 {
+        let doublings = attempts.saturating_sub(1).min(32);
+        self.initial_backoff_secs
+            .saturating_mul(1u64 << doublings)
+            .min(self.max_backoff_secs)
+    }
+}
+
+/// JSON body of a webhook delivery
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    id: i64,
+    #[serde(rename = "type")]
+    event_type: String,
+    created_at: i64,
+    data: &'a BridgeEvent,
+}
+
+/// Signature of a delivery, as sent in the X-Bridge-Signature header
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Check an X-Bridge-Signature header in constant time
+pub fn verify_signature(secret: &str, timestamp: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = signature.trim().strip_prefix("sha256=").and_then(|hex| hex::decode(hex).ok()) else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.trim().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Turns event log entries into signed webhook deliveries
+///
+/// Events are copied into a persistent outbox first, then delivered at least once:
+/// failed attempts are retried with exponential backoff, and deliveries that keep
+/// failing end up in the dead-letter list (see `bridgectl webhooks`).
+pub struct WebhookDispatcher {
+    config: WebhookConfig,
+    db: Arc<Mutex<Box<dyn BridgeStore>>>,
+    client: reqwest::Client,
+    poll_interval: Duration,
+}
+
+impl WebhookDispatcher {
+    pub fn new(project_root: PathBuf, poll_interval_secs: u64) -> Self {
+        let config = WebhookConfig::load(&project_root)
+            .expect("Failed to load webhook config");
+        let db = db::open(&project_root)
+            .expect("Failed to open bridge database");
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            config,
+            db: Arc::new(Mutex::new(db)),
+            client,
+            poll_interval: Duration::from_secs(poll_interval_secs),
+        }
+    }
+
+    /// Queue deliveries for events logged since the last tick
+    ///
+    /// Each batch is queued and the cursor advanced in one transaction.
+    fn enqueue_new_events(&self) -> BridgeResult<usize> {
+        let db = self.db.lock().unwrap();
+        let mut queued = 0;
+
+        loop {
+            let batch_queued = db.transaction(|db| {
+                let outbox = db.webhooks();
+                let batch = db.events().events_after(outbox.last_event_id()?)?;
+                let Some(last) = batch.last().map(|event| event.id) else {
+                    return Ok(None);
+                };
+
+                let mut queued = 0;
+                for event in &batch {
+                    let event_type = event.event_type();
+                    let payload = serde_json::to_string(&WebhookPayload {
+                        id: event.id,
+                        event_type: event_type.clone(),
+                        created_at: event.created_at,
+                        data: event,
+                    })
+                    .map_err(|e| BridgeError::Internal(format!("Failed to serialize webhook payload: {}", e)))?;
+
+                    for subscription in self.config.subscriptions.iter().filter(|s| s.wants(&event_type)) {
+                        if outbox.enqueue(&subscription.name, event.id, &event_type, &payload)? {
+                            queued += 1;
+                        }
+                    }
+                }
+
+                outbox.advance_cursor(last)?;
+                Ok(Some(queued))
+            })?;
+
+            match batch_queued {
+                Some(batch_queued) => queued += batch_queued,
+                None => break,
+            }
+        }
+
+        Ok(queued)
+    }
+
+    /// Attempt all deliveries that are due
+    async fn deliver_due(&self) -> BridgeResult<()> {
+        let due = self.db.lock().unwrap().webhooks().due(DELIVERY_BATCH)?;
+
+        for delivery in due {
+            let span = info_span!("webhook", delivery_id = delivery.id, subscription = %delivery.subscription, event = %delivery.event_type);
+            self.deliver(&delivery).instrument(span).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn deliver(&self, delivery: &WebhookDelivery) -> BridgeResult<()> {
+        let Some(subscription) = self.config.subscription(&delivery.subscription) else {
+            warn!("Subscription is no longer configured, moving delivery to the dead-letter list");
+            metrics::WEBHOOK_DELIVERIES.with_label_values(&["dead"]).inc();
+            return self.db.lock().unwrap().webhooks()
+                .mark_failed(delivery.id, "subscription is no longer configured", None);
+        };
+
+        let timestamp = now_secs();
+        let signature = sign(&subscription.secret, timestamp, delivery.payload.as_bytes());
+
+        let result = self.client
+            .post(&subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+
+        let error = match result {
+            Ok(response) if response.status().is_success() => {
+                metrics::WEBHOOK_DELIVERIES.with_label_values(&["delivered"]).inc();
+                info!(attempt = delivery.attempts + 1, "Delivered webhook");
+                return self.db.lock().unwrap().webhooks().mark_delivered(delivery.id);
+            }
+            Ok(response) => format!("HTTP {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+
+        let attempts = delivery.attempts + 1;
+        if attempts >= self.config.max_attempts {
+            metrics::WEBHOOK_DELIVERIES.with_label_values(&["dead"]).inc();
+            error!(attempts, error = %error, "Webhook delivery failed, moving it to the dead-letter list");
+            self.db.lock().unwrap().webhooks().mark_failed(delivery.id, &error, None)
+        } else {
+            let backoff = self.config.backoff_secs(attempts);
+            metrics::WEBHOOK_DELIVERIES.with_label_values(&["failed"]).inc();
+            warn!(attempts, retry_in_secs = backoff, error = %error, "Webhook delivery failed");
+            self.db.lock().unwrap().webhooks().mark_failed(delivery.id, &error, Some(now_secs() + backoff as i64))
+        }
+    }
+
+    /// Start dispatching webhooks
+    pub async fn start(self) {
+        info!(
+            subscriptions = self.config.subscriptions.len(),
+            poll_interval_secs = self.poll_interval.as_secs(),
+            "Starting webhook dispatcher"
+        );
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            if self.tick().await {
+                metrics::record_relayer_success("webhooks");
+            }
+        }
+    }
+
+    /// Queue new events and attempt the deliveries that are due, once
+    ///
+    /// Returns false if either step failed; the error is logged.
+    pub async fn tick(&self) -> bool {
+        let mut healthy = true;
+
+        match self.enqueue_new_events() {
+            Ok(0) => {}
+            Ok(queued) => debug!(queued, "Queued webhook deliveries"),
+            Err(e) => {
+                error!(error = %e, "Failed to queue webhook deliveries");
+                healthy = false;
+            }
+        }
+
+        if let Err(e) = self.deliver_due().await {
+            error!(error = %e, "Failed to deliver webhooks");
+            healthy = false;
+        }
+
+        healthy
+    }
+}
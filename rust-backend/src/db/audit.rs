@@ -0,0 +1,278 @@
+use super::{BridgeStore, SqliteStore};
+use crate::error::{BridgeError, BridgeResult};
+use rusqlite::{OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Bridge actions that move value or change what the bridge can mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Relayer minted a deposit on its own (memo, address or intent with auto-mint)
+    DepositMint,
+    /// User claimed a deposit through /deposit/claim
+    DepositClaim,
+    /// Withdrawal note with a commitment created through the API
+    WithdrawalCreate,
+    /// TAZ paid out for a withdrawal or legacy exit
+    WithdrawalPayout,
+    RefundApprove,
+    RefundPayout,
+    FaucetCreate,
+    /// Tokens minted by hand with `bridgectl mint`
+    ManualMint,
+    /// Faucet key replaced on-chain with `bridgectl key rotate`
+    KeyRotate,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::DepositMint => "deposit_mint",
+            AuditAction::DepositClaim => "deposit_claim",
+            AuditAction::WithdrawalCreate => "withdrawal_create",
+            AuditAction::WithdrawalPayout => "withdrawal_payout",
+            AuditAction::RefundApprove => "refund_approve",
+            AuditAction::RefundPayout => "refund_payout",
+            AuditAction::FaucetCreate => "faucet_create",
+            AuditAction::ManualMint => "manual_mint",
+            AuditAction::KeyRotate => "key_rotate",
+        }
+    }
+
+    pub fn parse(action: &str) -> Result<Self, String> {
+        match action {
+            "deposit_mint" => Ok(AuditAction::DepositMint),
+            "deposit_claim" => Ok(AuditAction::DepositClaim),
+            "withdrawal_create" => Ok(AuditAction::WithdrawalCreate),
+            "withdrawal_payout" => Ok(AuditAction::WithdrawalPayout),
+            "refund_approve" => Ok(AuditAction::RefundApprove),
+            "refund_payout" => Ok(AuditAction::RefundPayout),
+            "faucet_create" => Ok(AuditAction::FaucetCreate),
+            "manual_mint" => Ok(AuditAction::ManualMint),
+            "key_rotate" => Ok(AuditAction::KeyRotate),
+            other => Err(format!("Unknown audit action: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the chain, starting at 1 with no gaps
+    pub id: i64,
+    pub action: AuditAction,
+    /// inputs_digest() of what the action was called with
+    pub inputs_digest: String,
+    /// Resulting note id, txid or faucet id
+    pub result: Option<String>,
+    /// Process or operator that performed the action
+    pub actor: String,
+    pub created_at: i64,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash of the entry's contents and the previous entry's hash
+    pub fn compute_hash(&self) -> String {
+        // A JSON array keeps field boundaries unambiguous
+        let contents = serde_json::json!([
+            self.id,
+            self.action.as_str(),
+            self.inputs_digest,
+            self.result,
+            self.actor,
+            self.created_at,
+            self.prev_hash,
+        ]);
+        hex::encode(Sha256::digest(contents.to_string().as_bytes()))
+    }
+}
+
+/// prev_hash of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Digest of an action's inputs, so the ledger can prove them without storing them
+pub fn inputs_digest(inputs: &serde_json::Value) -> String {
+    hex::encode(Sha256::digest(inputs.to_string().as_bytes()))
+}
+
+/// Actor name for an admin tool run by hand, e.g. "bridgectl:alice"
+pub fn operator_actor(tool: &str) -> String {
+    let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+    format!("{}:{}", tool, user)
+}
+
+/// Walks the chain from the first entry, checking every link
+pub struct ChainVerifier {
+    next_id: i64,
+    head: String,
+}
+
+impl Default for ChainVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainVerifier {
+    pub fn new() -> Self {
+        Self {
+            next_id: 1,
+            head: GENESIS_HASH.to_string(),
+        }
+    }
+
+    /// Check the next entry, in id order
+    pub fn check(&mut self, entry: &AuditEntry) -> Result<(), String> {
+        if entry.id != self.next_id {
+            return Err(format!(
+                "entry {} found where entry {} was expected (entries removed or reordered)",
+                entry.id, self.next_id
+            ));
+        }
+        if entry.prev_hash != self.head {
+            return Err(format!(
+                "entry {} does not link to entry {} (prev_hash mismatch)",
+                entry.id,
+                entry.id - 1
+            ));
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(format!("entry {} does not match its hash (entry modified)", entry.id));
+        }
+
+        self.next_id += 1;
+        self.head = entry.hash.clone();
+        Ok(())
+    }
+
+    /// Number of entries checked so far
+    pub fn len(&self) -> i64 {
+        self.next_id - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of the last entry checked (GENESIS_HASH if none)
+    pub fn head(&self) -> &str {
+        &self.head
+    }
+}
+
+/// Next entry after `head`, hashed and ready to insert
+pub(super) fn next_entry(
+    head: Option<(i64, String)>,
+    action: AuditAction,
+    inputs_digest: &str,
+    result: Option<&str>,
+    actor: &str,
+) -> AuditEntry {
+    let (prev_id, prev_hash) = head.unwrap_or((0, GENESIS_HASH.to_string()));
+    let mut entry = AuditEntry {
+        id: prev_id + 1,
+        action,
+        inputs_digest: inputs_digest.to_string(),
+        result: result.map(str::to_string),
+        actor: actor.to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        prev_hash,
+        hash: String::new(),
+    };
+    entry.hash = entry.compute_hash();
+    entry
+}
+
+pub(super) const AUDIT_COLUMNS: &str = "id, action, inputs_digest, result, actor, created_at, prev_hash, hash";
+
+fn row_to_entry(row: &rusqlite::Row) -> SqlResult<AuditEntry> {
+    let action: String = row.get(1)?;
+    let action = AuditAction::parse(&action).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            1,
+            rusqlite::types::Type::Text,
+            e.into(),
+        )
+    })?;
+
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        action,
+        inputs_digest: row.get(2)?,
+        result: row.get(3)?,
+        actor: row.get(4)?,
+        created_at: row.get(5)?,
+        prev_hash: row.get(6)?,
+        hash: row.get(7)?,
+    })
+}
+
+/// Append-only ledger of bridge actions, each entry chained to the previous one by hash
+///
+/// The table rejects updates and deletes; edits made around that (e.g. to the file
+/// directly) break the chain, which ChainVerifier detects.
+pub trait Audit {
+    /// Append an action to the ledger and return its entry
+    fn append(
+        &self,
+        action: AuditAction,
+        inputs_digest: &str,
+        result: Option<&str>,
+        actor: &str,
+    ) -> BridgeResult<AuditEntry>;
+
+    /// Entries after `after_id`, oldest first
+    fn entries(&self, after_id: i64, limit: u32) -> BridgeResult<Vec<AuditEntry>>;
+}
+
+impl Audit for SqliteStore {
+    fn append(
+        &self,
+        action: AuditAction,
+        inputs_digest: &str,
+        result: Option<&str>,
+        actor: &str,
+    ) -> BridgeResult<AuditEntry> {
+        // Reading the head and inserting after it must not interleave with another writer
+        (self as &dyn BridgeStore).transaction(|_| {
+            let head = self.conn.query_row(
+                "SELECT id, hash FROM audit_log ORDER BY id DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional()?;
+
+            let entry = next_entry(head, action, inputs_digest, result, actor);
+            self.conn.execute(
+                &format!("INSERT INTO audit_log ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)", AUDIT_COLUMNS),
+                rusqlite::params![
+                    entry.id,
+                    entry.action.as_str(),
+                    entry.inputs_digest,
+                    entry.result,
+                    entry.actor,
+                    entry.created_at,
+                    entry.prev_hash,
+                    entry.hash,
+                ],
+            )?;
+
+            Ok(entry)
+        })
+    }
+
+    fn entries(&self, after_id: i64, limit: u32) -> BridgeResult<Vec<AuditEntry>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM audit_log WHERE id > ?1 ORDER BY id LIMIT ?2",
+            AUDIT_COLUMNS
+        ))?;
+
+        let entries = stmt
+            .query_map(rusqlite::params![after_id, limit], row_to_entry)?
+            .collect::<SqlResult<Vec<_>>>()
+            .map_err(|e| BridgeError::from(e).context("Failed to read audit log"))?;
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,121 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositAddress {
+    pub address: String,
+    pub recipient_hash: String,
+    pub account_id: Option<String>, // Only set when the relayer should mint automatically
+    pub secret: Option<String>,     // Only set when the relayer should mint automatically
+    pub created_at: i64,
+}
+
+impl DepositAddress {
+    /// Whether the relayer has what it needs to mint deposits to this address on its own
+    pub fn auto_mint(&self) -> bool {
+        self.account_id.is_some() && self.secret.is_some()
+    }
+}
+
+/// Diversified Zcash addresses handed out per deposit request
+pub trait DepositAddresses {
+    /// Record a freshly generated address against its intended recipient
+    fn assign(
+        &self,
+        address: &str,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+    ) -> BridgeResult<DepositAddress>;
+
+    /// Get the recipient an address was handed out for
+    fn get(&self, address: &str) -> BridgeResult<Option<DepositAddress>>;
+
+    /// Get all addresses handed out for a recipient hash
+    fn addresses_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>>;
+
+    /// Get every address handed out so far
+    fn all_addresses(&self) -> BridgeResult<HashSet<String>>;
+}
+
+impl DepositAddresses for SqliteStore {
+    fn assign(
+        &self,
+        address: &str,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+    ) -> BridgeResult<DepositAddress> {
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO deposit_addresses (address, recipient_hash, account_id, secret, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![address, recipient_hash, account_id, secret, created_at],
+        )?;
+
+        Ok(DepositAddress {
+            address: address.to_string(),
+            recipient_hash: recipient_hash.to_string(),
+            account_id: account_id.map(str::to_string),
+            secret: secret.map(str::to_string),
+            created_at,
+        })
+    }
+
+    fn get(&self, address: &str) -> BridgeResult<Option<DepositAddress>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT address, recipient_hash, account_id, secret, created_at
+             FROM deposit_addresses WHERE address = ?1"
+        )?;
+
+        let mut rows = stmt.query_map([address], |row| {
+            Ok(DepositAddress {
+                address: row.get(0)?,
+                recipient_hash: row.get(1)?,
+                account_id: row.get(2)?,
+                secret: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+
+        if let Some(row) = rows.next() {
+            Ok(Some(row?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn addresses_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT address FROM deposit_addresses WHERE recipient_hash = ?1"
+        )?;
+
+        let rows = stmt.query_map([recipient_hash], |row| row.get(0))?;
+
+        let mut addresses = HashSet::new();
+        for row in rows {
+            addresses.insert(row?);
+        }
+
+        Ok(addresses)
+    }
+
+    fn all_addresses(&self) -> BridgeResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT address FROM deposit_addresses")?;
+
+        let rows = stmt.query_map([], |row| row.get(0))?;
+
+        let mut addresses = HashSet::new();
+        for row in rows {
+            addresses.insert(row?);
+        }
+
+        Ok(addresses)
+    }
+}
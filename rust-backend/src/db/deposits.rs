@@ -1,94 +1,174 @@
-use rusqlite::{Connection, Result as SqlResult};
-use std::path::PathBuf;
-
-pub struct DepositTracker {
-    conn: Connection,
-}
+use super::SqliteStore;
+use crate::error::BridgeResult;
 
 #[derive(Debug)]
 pub struct DepositRecord {
     pub recipient_hash: String,
     pub txid: String,
+    pub pool: Option<String>, // None for claims recorded before per-output tracking
+    pub output_index: Option<u32>,
     pub amount: u64,
     pub claimed_at: i64,
 }
 
-impl DepositTracker {
-    pub fn new(db_path: PathBuf) -> SqlResult<Self> {
-        let conn = Connection::open(db_path)?;
-        
-        // Create deposits table if it doesn't exist
-        // NOTE: We only store recipient_hash for privacy - we don't store account_id
-        // This prevents double-spending while maintaining privacy
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS deposits (
-                recipient_hash TEXT PRIMARY KEY,
-                txid TEXT NOT NULL,
-                amount INTEGER NOT NULL,
-                claimed_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
-        
-        // Create index for faster lookups
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_deposits_txid ON deposits(txid)",
-            [],
+/// Claimed deposit outputs
+pub trait Deposits {
+    /// Check if a recipient hash has already been claimed (or is reserved by a mint in progress)
+    fn is_claimed(&self, recipient_hash: &str) -> BridgeResult<bool>;
+
+    /// Check if a deposit output has already been claimed (or is reserved by a mint in progress)
+    fn is_output_claimed(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool>;
+
+    /// Record a claimed deposit output
+    /// 
+    /// Returns false if the recipient hash or the output was already claimed.
+    /// NOTE: We only store recipient_hash, NOT account_id, for privacy.
+    /// The bridge doesn't need to know which account claimed the deposit.
+    fn record_claim(
+        &self,
+        recipient_hash: &str,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+    ) -> BridgeResult<bool>;
+
+    /// Reserve a deposit output before minting it
+    ///
+    /// Returns false if the recipient hash or the output is already claimed or reserved, so only
+    /// one of several concurrent claims mints. Follow up with `confirm_claim` once the note is
+    /// minted, or `release_claim` if it wasn't.
+    fn reserve_claim(
+        &self,
+        recipient_hash: &str,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+    ) -> BridgeResult<bool>;
+
+    /// Turn a reservation into a claim, after its note was minted
+    ///
+    /// Returns false if the output had no reservation.
+    fn confirm_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool>;
+
+    /// Drop a reservation whose mint didn't happen, so the deposit can be claimed again
+    fn release_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<()>;
+
+    /// Get a claimed deposit by recipient hash
+    fn get_deposit(&self, recipient_hash: &str) -> BridgeResult<Option<DepositRecord>>;
+
+    /// Get all claimed deposits, oldest first
+    fn list_claims(&self) -> BridgeResult<Vec<DepositRecord>>;
+
+    /// Claimed deposits with `since <= claimed_at < until`, oldest first
+    fn list_claims_between(&self, since: i64, until: i64) -> BridgeResult<Vec<DepositRecord>>;
+
+    /// Reservations whose mint never confirmed or released, oldest first
+    ///
+    /// Left behind when a process stopped mid-mint; an operator checks whether the note exists.
+    fn pending_claims(&self) -> BridgeResult<Vec<DepositRecord>>;
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_record(row: &rusqlite::Row) -> rusqlite::Result<DepositRecord> {
+    Ok(DepositRecord {
+        recipient_hash: row.get(0)?,
+        txid: row.get(1)?,
+        pool: row.get(2)?,
+        output_index: row.get(3)?,
+        amount: row.get(4)?,
+        claimed_at: row.get(5)?,
+    })
+}
+
+impl Deposits for SqliteStore {
+    fn is_claimed(&self, recipient_hash: &str) -> BridgeResult<bool> {
+        let mut stmt = self.conn.prepare(
+            "SELECT 1 FROM deposits WHERE recipient_hash = ?1 LIMIT 1"
         )?;
         
-        Ok(Self { conn })
+        let exists = stmt.exists([recipient_hash])?;
+        Ok(exists)
     }
 
-    /// Check if a recipient hash has already been claimed
-    pub fn is_claimed(&self, recipient_hash: &str) -> SqlResult<bool> {
+    fn is_output_claimed(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
         let mut stmt = self.conn.prepare(
-            "SELECT 1 FROM deposits WHERE recipient_hash = ?1 LIMIT 1"
+            "SELECT 1 FROM deposits WHERE txid = ?1 AND pool = ?2 AND output_index = ?3 LIMIT 1"
         )?;
         
-        let exists = stmt.exists([recipient_hash])?;
+        let exists = stmt.exists(rusqlite::params![txid, pool, output_index])?;
         Ok(exists)
     }
 
-    /// Record a claimed deposit
-    /// 
-    /// NOTE: We only store recipient_hash, NOT account_id, for privacy.
-    /// The bridge doesn't need to know which account claimed the deposit.
-    pub fn record_claim(
+    fn record_claim(
+        &self,
+        recipient_hash: &str,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+    ) -> BridgeResult<bool> {
+        let inserted = self.conn.execute(
+            "INSERT INTO deposits (recipient_hash, txid, pool, output_index, amount, claimed_at, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'claimed')
+             ON CONFLICT DO NOTHING",
+            rusqlite::params![recipient_hash, txid, pool, output_index, amount, now_secs()],
+        )?;
+        
+        Ok(inserted > 0)
+    }
+
+    fn reserve_claim(
         &self,
         recipient_hash: &str,
         txid: &str,
+        pool: &str,
+        output_index: u32,
         amount: u64,
-    ) -> SqlResult<()> {
-        let claimed_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+    ) -> BridgeResult<bool> {
+        let inserted = self.conn.execute(
+            "INSERT INTO deposits (recipient_hash, txid, pool, output_index, amount, claimed_at, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending')
+             ON CONFLICT DO NOTHING",
+            rusqlite::params![recipient_hash, txid, pool, output_index, amount, now_secs()],
+        )?;
         
+        Ok(inserted > 0)
+    }
+
+    fn confirm_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE deposits SET status = 'claimed', claimed_at = ?4
+             WHERE txid = ?1 AND pool = ?2 AND output_index = ?3 AND status = 'pending'",
+            rusqlite::params![txid, pool, output_index, now_secs()],
+        )?;
+        
+        Ok(updated > 0)
+    }
+
+    fn release_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<()> {
         self.conn.execute(
-            "INSERT INTO deposits (recipient_hash, txid, amount, claimed_at)
-             VALUES (?1, ?2, ?3, ?4)
-             ON CONFLICT(recipient_hash) DO NOTHING",
-            rusqlite::params![recipient_hash, txid, amount, claimed_at],
+            "DELETE FROM deposits WHERE txid = ?1 AND pool = ?2 AND output_index = ?3 AND status = 'pending'",
+            rusqlite::params![txid, pool, output_index],
         )?;
         
         Ok(())
     }
 
-    /// Get deposit record by recipient hash
-    pub fn get_deposit(&self, recipient_hash: &str) -> SqlResult<Option<DepositRecord>> {
+    fn get_deposit(&self, recipient_hash: &str) -> BridgeResult<Option<DepositRecord>> {
         let mut stmt = self.conn.prepare(
-            "SELECT recipient_hash, txid, amount, claimed_at
-             FROM deposits WHERE recipient_hash = ?1"
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE recipient_hash = ?1 AND status = 'claimed'"
         )?;
         
-        let mut rows = stmt.query_map([recipient_hash], |row| {
-            Ok(DepositRecord {
-                recipient_hash: row.get(0)?,
-                txid: row.get(1)?,
-                amount: row.get(2)?,
-                claimed_at: row.get(3)?,
-            })
-        })?;
+        let mut rows = stmt.query_map([recipient_hash], row_to_record)?;
         
         if let Some(row) = rows.next() {
             Ok(Some(row?))
@@ -96,5 +176,42 @@ impl DepositTracker {
             Ok(None)
         }
     }
+
+    fn list_claims(&self) -> BridgeResult<Vec<DepositRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'claimed' ORDER BY claimed_at"
+        )?;
+        
+        let rows = stmt.query_map([], row_to_record)?;
+        
+        let mut deposits = Vec::new();
+        for row in rows {
+            deposits.push(row?);
+        }
+        
+        Ok(deposits)
+    }
+
+    fn list_claims_between(&self, since: i64, until: i64) -> BridgeResult<Vec<DepositRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'claimed' AND claimed_at >= ?1 AND claimed_at < ?2
+             ORDER BY claimed_at"
+        )?;
+        
+        let deposits = stmt.query_map([since, until], row_to_record)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(deposits)
+    }
+
+    fn pending_claims(&self) -> BridgeResult<Vec<DepositRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'pending' ORDER BY claimed_at"
+        )?;
+        
+        let deposits = stmt.query_map([], row_to_record)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(deposits)
+    }
 }
 
@@ -0,0 +1,325 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// What an event is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSubject {
+    Deposit,
+    Withdrawal,
+}
+
+impl EventSubject {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSubject::Deposit => "deposit",
+            EventSubject::Withdrawal => "withdrawal",
+        }
+    }
+
+    pub fn parse(subject: &str) -> Result<Self, String> {
+        match subject {
+            "deposit" => Ok(EventSubject::Deposit),
+            "withdrawal" => Ok(EventSubject::Withdrawal),
+            other => Err(format!("Unknown event subject: {}", other)),
+        }
+    }
+}
+
+/// States a deposit or withdrawal moves through
+///
+/// deposit:    received -> minted | mint_failed (retried) | rejected -> refund_requested -> refund_approved -> refunded
+/// withdrawal: created -> committed (note on chain) -> payout_queued (waiting on the offline signer) -> paid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventState {
+    Received,
+    Minted,
+    MintFailed,
+    Rejected,
+    RefundRequested,
+    RefundApproved,
+    Refunded,
+    Created,
+    Committed,
+    PayoutQueued,
+    Paid,
+}
+
+impl EventState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventState::Received => "received",
+            EventState::Minted => "minted",
+            EventState::MintFailed => "mint_failed",
+            EventState::Rejected => "rejected",
+            EventState::RefundRequested => "refund_requested",
+            EventState::RefundApproved => "refund_approved",
+            EventState::Refunded => "refunded",
+            EventState::Created => "created",
+            EventState::Committed => "committed",
+            EventState::PayoutQueued => "payout_queued",
+            EventState::Paid => "paid",
+        }
+    }
+
+    pub fn parse(state: &str) -> Result<Self, String> {
+        match state {
+            "received" => Ok(EventState::Received),
+            "minted" => Ok(EventState::Minted),
+            "mint_failed" => Ok(EventState::MintFailed),
+            "rejected" => Ok(EventState::Rejected),
+            "refund_requested" => Ok(EventState::RefundRequested),
+            "refund_approved" => Ok(EventState::RefundApproved),
+            "refunded" => Ok(EventState::Refunded),
+            "created" => Ok(EventState::Created),
+            "committed" => Ok(EventState::Committed),
+            "payout_queued" => Ok(EventState::PayoutQueued),
+            "paid" => Ok(EventState::Paid),
+            other => Err(format!("Unknown event state: {}", other)),
+        }
+    }
+
+    /// Whether nothing follows this state
+    pub fn is_final(&self) -> bool {
+        matches!(self, EventState::Minted | EventState::Refunded | EventState::Paid)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BridgeEvent {
+    /// Increasing event id, usable as Last-Event-ID
+    pub id: i64,
+    pub subject: EventSubject,
+    pub state: EventState,
+    /// Deposit output ("txid:pool:index") or withdrawal commitment
+    pub key: String,
+    pub txid: Option<String>,
+    /// Not sent to clients - they subscribe with it, so they already know it
+    #[serde(skip)]
+    pub recipient_hash: Option<String>,
+    pub note_id: Option<String>,
+    /// Rejection reason, payout or refund txid, block number, ...
+    pub detail: Option<String>,
+    pub created_at: i64,
+}
+
+impl BridgeEvent {
+    /// Event type as webhook subscribers see it, e.g. "deposit.minted"
+    pub fn event_type(&self) -> String {
+        format!("{}.{}", self.subject.as_str(), self.state.as_str())
+    }
+}
+
+pub(super) const EVENT_COLUMNS: &str = "id, subject, state, event_key, txid, recipient_hash, note_id, detail, created_at";
+
+/// Most events returned by one poll
+pub(super) const EVENT_PAGE_SIZE: i64 = 100;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_event(row: &rusqlite::Row) -> SqlResult<BridgeEvent> {
+    let parse_error = |idx: usize, e: String| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, e.into())
+    };
+    let subject: String = row.get(1)?;
+    let state: String = row.get(2)?;
+
+    Ok(BridgeEvent {
+        id: row.get(0)?,
+        subject: EventSubject::parse(&subject).map_err(|e| parse_error(1, e))?,
+        state: EventState::parse(&state).map_err(|e| parse_error(2, e))?,
+        key: row.get(3)?,
+        txid: row.get(4)?,
+        recipient_hash: row.get(5)?,
+        note_id: row.get(6)?,
+        detail: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+/// Lowercase hex with 0x prefix, the format recipient hashes and commitments are stored in
+pub(super) fn prefixed_hex(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    match value.strip_prefix("0x") {
+        Some(_) => value,
+        None => format!("0x{}", value),
+    }
+}
+
+/// Log of deposit and withdrawal state changes
+///
+/// Written by the API server and the relayers (separate processes sharing the bridge database),
+/// and streamed to clients by the /events endpoints.
+pub trait Events {
+    /// Record a state change of a deposit output
+    ///
+    /// Returns false if the output is already in this state (relayers report some states on every scan).
+    fn record_deposit(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool>;
+
+    /// Record a state change of a withdrawal, keyed by its commitment (or burn note id for legacy exits)
+    ///
+    /// Returns false if the withdrawal is already in this state.
+    fn record_withdrawal(
+        &self,
+        commitment: &str,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool>;
+
+    /// Events after `after_id` for a deposit, identified by recipient hash, txid or output key
+    fn deposit_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>>;
+
+    /// Events after `after_id` for a withdrawal, identified by commitment or note id
+    fn withdrawal_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>>;
+
+    /// All events after `after_id`, oldest first (used by the webhook dispatcher)
+    fn events_after(&self, after_id: i64) -> BridgeResult<Vec<BridgeEvent>>;
+
+    /// Events after `after_id` recorded at or after `since`, oldest first
+    fn events_since(&self, since: i64, after_id: i64) -> BridgeResult<Vec<BridgeEvent>>;
+}
+
+impl Events for SqliteStore {
+    fn record_deposit(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        let key = format!("{}:{}:{}", txid, pool, output_index);
+        self.record_event(EventSubject::Deposit, &key, Some(txid), recipient_hash, state, note_id, detail)
+    }
+
+    fn record_withdrawal(
+        &self,
+        commitment: &str,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        self.record_event(EventSubject::Withdrawal, commitment, None, None, state, note_id, detail)
+    }
+
+    fn deposit_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let id = id.trim().to_lowercase();
+        let txid_or_key = id.strip_prefix("0x").unwrap_or(&id).to_string();
+
+        self.query_events(
+            "event_key = ?2 OR txid = ?2 OR recipient_hash = ?3",
+            EventSubject::Deposit,
+            [&txid_or_key, &prefixed_hex(&id)],
+            after_id,
+        )
+    }
+
+    fn withdrawal_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let id = prefixed_hex(id);
+
+        self.query_events("event_key = ?2 OR note_id = ?3", EventSubject::Withdrawal, [&id, &id], after_id)
+    }
+
+    fn events_after(&self, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM events WHERE id > ?1 ORDER BY id LIMIT ?2",
+            EVENT_COLUMNS
+        ))?;
+
+        let events = stmt
+            .query_map(rusqlite::params![after_id, EVENT_PAGE_SIZE], row_to_event)?
+            .collect::<SqlResult<Vec<_>>>()?;
+
+        Ok(events)
+    }
+
+    fn events_since(&self, since: i64, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM events WHERE id > ?1 AND created_at >= ?2 ORDER BY id LIMIT ?3",
+            EVENT_COLUMNS
+        ))?;
+
+        let events = stmt
+            .query_map(rusqlite::params![after_id, since, EVENT_PAGE_SIZE], row_to_event)?
+            .collect::<SqlResult<Vec<_>>>()?;
+
+        Ok(events)
+    }
+}
+
+impl SqliteStore {
+    fn record_event(
+        &self,
+        subject: EventSubject,
+        key: &str,
+        txid: Option<&str>,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        let current: Option<String> = self.conn.query_row(
+            "SELECT state FROM events WHERE subject = ?1 AND event_key = ?2 ORDER BY id DESC LIMIT 1",
+            rusqlite::params![subject.as_str(), key],
+            |row| row.get(0),
+        ).optional()?;
+
+        if current.as_deref() == Some(state.as_str()) {
+            return Ok(false);
+        }
+
+        self.conn.execute(
+            "INSERT INTO events (subject, state, event_key, txid, recipient_hash, note_id, detail, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                subject.as_str(),
+                state.as_str(),
+                key,
+                txid,
+                recipient_hash,
+                note_id,
+                detail,
+                now_secs(),
+            ],
+        )?;
+
+        Ok(true)
+    }
+
+    fn query_events(&self, matches: &str, subject: EventSubject, ids: [&String; 2], after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM events WHERE subject = ?1 AND ({}) AND id > ?4 ORDER BY id LIMIT ?5",
+            EVENT_COLUMNS, matches
+        ))?;
+
+        let events = stmt
+            .query_map(
+                rusqlite::params![subject.as_str(), ids[0], ids[1], after_id, EVENT_PAGE_SIZE],
+                row_to_event,
+            )?
+            .collect::<SqlResult<Vec<_>>>()?;
+
+        Ok(events)
+    }
+}
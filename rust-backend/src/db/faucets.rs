@@ -1,31 +1,19 @@
-use rusqlite::{Connection, Result as SqlResult};
+use super::SqliteStore;
+use crate::error::BridgeResult;
 use miden_objects::account::AccountId;
 use miden_objects::utils::{Deserializable, Serializable};
-use std::path::PathBuf;
 
-pub struct FaucetStore {
-    conn: Connection,
-}
+/// Faucet account per origin network
+pub trait Faucets {
+    /// Get faucet_id for a given origin network
+    fn get_faucet_id(&self, origin_network: &str) -> BridgeResult<Option<AccountId>>;
 
-impl FaucetStore {
-    pub fn new(db_path: PathBuf) -> SqlResult<Self> {
-        let conn = Connection::open(db_path)?;
-        
-        // Create faucets table if it doesn't exist
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS faucets (
-                origin_network TEXT PRIMARY KEY,
-                faucet_id BLOB NOT NULL,
-                created_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
-        
-        Ok(Self { conn })
-    }
+    /// Store faucet_id for a given origin network
+    fn store_faucet_id(&self, origin_network: &str, faucet_id: &AccountId) -> BridgeResult<()>;
+}
 
-    /// Get faucet_id for a given origin network
-    pub fn get_faucet_id(&self, origin_network: &str) -> SqlResult<Option<AccountId>> {
+impl Faucets for SqliteStore {
+    fn get_faucet_id(&self, origin_network: &str) -> BridgeResult<Option<AccountId>> {
         let mut stmt = self.conn.prepare(
             "SELECT faucet_id FROM faucets WHERE origin_network = ?1"
         )?;
@@ -42,13 +30,12 @@ impl FaucetStore {
         
         match rows.next() {
             Some(Ok(faucet_id)) => Ok(Some(faucet_id)),
-            Some(Err(e)) => Err(e),
+            Some(Err(e)) => Err(e.into()),
             None => Ok(None),
         }
     }
 
-    /// Store faucet_id for a given origin network
-    pub fn store_faucet_id(&self, origin_network: &str, faucet_id: &AccountId) -> SqlResult<()> {
+    fn store_faucet_id(&self, origin_network: &str, faucet_id: &AccountId) -> BridgeResult<()> {
         let faucet_id_bytes = faucet_id.to_bytes();
         let created_at = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
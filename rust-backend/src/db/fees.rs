@@ -0,0 +1,231 @@
+use crate::bridge::fees::FeeQuote;
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeeSummary {
+    pub direction: String,
+    pub token: String,
+    pub transfers: u64,
+    pub gross_amount: u64,
+    pub fees: u64,
+    pub net_amount: u64,
+}
+
+/// Fee charged on one transfer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeAccrual {
+    pub direction: String,
+    pub token: String,
+    /// Deposit output key (txid:pool:index), withdrawal commitment, burn note id, or refund:<output key>
+    pub reference: String,
+    pub gross_amount: u64,
+    pub fee: u64,
+    pub net_amount: u64,
+    pub accrued_at: i64,
+}
+
+/// Deposits, withdrawals and their fees up to some time, counted like the accounting export does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LedgerTotals {
+    /// Zatoshis of claimed deposits
+    pub deposited: u64,
+    /// wTAZ burned for commitment withdrawals and exits
+    pub withdrawn: u64,
+    /// Fees charged on those deposits and withdrawals
+    pub fees: u64,
+}
+
+/// Fee accruals on deposits, withdrawals and refunds
+pub trait Fees {
+    /// Record the fee charged on a transfer
+    ///
+    /// Each (direction, reference) is only accrued once, so retries don't double-count.
+    fn record_fee(&self, quote: &FeeQuote, reference: &str) -> BridgeResult<()>;
+
+    /// Get accrued fee totals grouped by direction and token
+    fn summary(&self) -> BridgeResult<Vec<FeeSummary>>;
+
+    /// Every accrual, oldest first
+    fn accruals(&self) -> BridgeResult<Vec<FeeAccrual>>;
+
+    /// Accruals with `accrued_at >= since`, oldest first
+    fn accruals_since(&self, since: i64) -> BridgeResult<Vec<FeeAccrual>>;
+
+    /// Totals of the deposits claimed and withdrawals burned before `until`, with their fees
+    ///
+    /// Fees count at the time of the deposit or burn they were charged on, even if they
+    /// accrued later; exits, which only the fee ledger knows, count when they accrued.
+    fn totals_before(&self, until: i64) -> BridgeResult<LedgerTotals>;
+
+    /// wTAZ minted for deposits minus wTAZ burned for withdrawals (base units)
+    ///
+    /// Refunds pay back rejected deposits that were never minted, so they don't count.
+    fn outstanding_supply(&self, token: &str) -> BridgeResult<i64>;
+}
+
+impl Fees for SqliteStore {
+    fn record_fee(&self, quote: &FeeQuote, reference: &str) -> BridgeResult<()> {
+        let accrued_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO fee_accruals (direction, token, reference, gross_amount, fee, net_amount, accrued_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(direction, reference) DO NOTHING",
+            rusqlite::params![
+                quote.direction.as_str(),
+                quote.token,
+                reference,
+                quote.gross_amount,
+                quote.fee,
+                quote.net_amount,
+                accrued_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn summary(&self) -> BridgeResult<Vec<FeeSummary>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT direction, token, COUNT(*), SUM(gross_amount), SUM(fee), SUM(net_amount)
+             FROM fee_accruals
+             GROUP BY direction, token
+             ORDER BY token, direction"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(FeeSummary {
+                direction: row.get(0)?,
+                token: row.get(1)?,
+                transfers: row.get(2)?,
+                gross_amount: row.get(3)?,
+                fees: row.get(4)?,
+                net_amount: row.get(5)?,
+            })
+        })?;
+
+        let mut summary = Vec::new();
+        for row in rows {
+            summary.push(row?);
+        }
+
+        Ok(summary)
+    }
+
+    fn accruals(&self) -> BridgeResult<Vec<FeeAccrual>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT direction, token, reference, gross_amount, fee, net_amount, accrued_at
+             FROM fee_accruals
+             ORDER BY id"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(FeeAccrual {
+                direction: row.get(0)?,
+                token: row.get(1)?,
+                reference: row.get(2)?,
+                gross_amount: row.get(3)?,
+                fee: row.get(4)?,
+                net_amount: row.get(5)?,
+                accrued_at: row.get(6)?,
+            })
+        })?;
+
+        let mut accruals = Vec::new();
+        for row in rows {
+            accruals.push(row?);
+        }
+
+        Ok(accruals)
+    }
+
+    fn accruals_since(&self, since: i64) -> BridgeResult<Vec<FeeAccrual>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT direction, token, reference, gross_amount, fee, net_amount, accrued_at
+             FROM fee_accruals
+             WHERE accrued_at >= ?1
+             ORDER BY id"
+        )?;
+
+        let rows = stmt.query_map([since], |row| {
+            Ok(FeeAccrual {
+                direction: row.get(0)?,
+                token: row.get(1)?,
+                reference: row.get(2)?,
+                gross_amount: row.get(3)?,
+                fee: row.get(4)?,
+                net_amount: row.get(5)?,
+                accrued_at: row.get(6)?,
+            })
+        })?;
+
+        let mut accruals = Vec::new();
+        for row in rows {
+            accruals.push(row?);
+        }
+
+        Ok(accruals)
+    }
+
+    fn totals_before(&self, until: i64) -> BridgeResult<LedgerTotals> {
+        let totals = self.conn.query_row(
+            "WITH
+                claims AS (
+                    SELECT amount,
+                        CASE WHEN pool IS NULL OR output_index IS NULL THEN txid
+                             ELSE txid || ':' || pool || ':' || output_index END AS reference
+                    FROM deposits WHERE status = 'claimed' AND claimed_at < ?1
+                ),
+                burns AS (
+                    SELECT LOWER(REPLACE(TRIM(commitment), '0x', '')) AS reference, created_at FROM withdrawals
+                    UNION ALL
+                    SELECT LOWER(REPLACE(TRIM(note_id), '0x', '')), created_at FROM withdrawals
+                ),
+                -- Exits paid by the exit relayer have no withdrawal row, so no burned_at
+                withdrawal_fees AS (
+                    SELECT f.gross_amount, f.fee, f.accrued_at,
+                        (SELECT MIN(b.created_at) FROM burns b
+                         WHERE b.reference = LOWER(REPLACE(TRIM(f.reference), '0x', ''))) AS burned_at
+                    FROM fee_accruals f
+                    WHERE f.direction = 'withdrawal' AND f.reference NOT LIKE 'refund:%'
+                )
+             SELECT
+                (SELECT COALESCE(SUM(amount), 0) FROM claims),
+                ((SELECT COALESCE(SUM(amount), 0) FROM withdrawals WHERE created_at < ?1)
+                  + (SELECT COALESCE(SUM(gross_amount), 0) FROM withdrawal_fees WHERE burned_at IS NULL AND accrued_at < ?1)),
+                ((SELECT COALESCE(SUM(f.fee), 0) FROM claims c
+                  JOIN fee_accruals f ON f.direction = 'deposit' AND f.reference = c.reference)
+                  + (SELECT COALESCE(SUM(fee), 0) FROM withdrawal_fees WHERE COALESCE(burned_at, accrued_at) < ?1))",
+            [until],
+            |row| {
+                Ok(LedgerTotals {
+                    deposited: row.get(0)?,
+                    withdrawn: row.get(1)?,
+                    fees: row.get(2)?,
+                })
+            },
+        )?;
+
+        Ok(totals)
+    }
+
+    fn outstanding_supply(&self, token: &str) -> BridgeResult<i64> {
+        let outstanding = self.conn.query_row(
+            "SELECT
+                COALESCE(SUM(CASE WHEN direction = 'deposit' THEN net_amount ELSE 0 END), 0)
+              - COALESCE(SUM(CASE WHEN direction = 'withdrawal' THEN gross_amount ELSE 0 END), 0)
+             FROM fee_accruals
+             WHERE token = ?1 AND reference NOT LIKE 'refund:%'",
+            [token],
+            |row| row.get(0),
+        )?;
+
+        Ok(outstanding)
+    }
+}
@@ -0,0 +1,264 @@
+use crate::bridge::memo::generate_reference_code;
+use crate::zcash::bridge_wallet::DepositOutput;
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+/// Lifecycle of a deposit intent
+///
+/// pending -> received (deposit matched) | amount_mismatch (matched, but not the expected amount) | expired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IntentStatus {
+    Pending,
+    Received,
+    AmountMismatch,
+    Expired,
+}
+
+impl IntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntentStatus::Pending => "pending",
+            IntentStatus::Received => "received",
+            IntentStatus::AmountMismatch => "amount_mismatch",
+            IntentStatus::Expired => "expired",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, String> {
+        match status {
+            "pending" => Ok(IntentStatus::Pending),
+            "received" => Ok(IntentStatus::Received),
+            "amount_mismatch" => Ok(IntentStatus::AmountMismatch),
+            "expired" => Ok(IntentStatus::Expired),
+            other => Err(format!("Unknown intent status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositIntent {
+    pub code: String,
+    pub recipient_hash: String,
+    pub account_id: Option<String>, // Only set when the relayer should mint automatically
+    pub secret: Option<String>,     // Only set when the relayer should mint automatically
+    pub expected_amount: Option<u64>,
+    pub status: IntentStatus,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub txid: Option<String>,
+    pub pool: Option<String>,
+    pub output_index: Option<u32>,
+    pub received_amount: Option<u64>,
+    pub received_at: Option<i64>,
+}
+
+impl DepositIntent {
+    /// Whether the relayer has what it needs to mint the deposit on its own
+    pub fn auto_mint(&self) -> bool {
+        self.account_id.is_some() && self.secret.is_some()
+    }
+
+    /// Whether this intent was matched to the given output
+    pub fn matches_output(&self, output: &DepositOutput) -> bool {
+        self.txid.as_deref() == Some(output.txid.as_str())
+            && self.pool.as_deref() == Some(output.pool.as_str())
+            && self.output_index == Some(output.index)
+    }
+}
+
+pub(super) const INTENT_COLUMNS: &str = "code, recipient_hash, account_id, secret, expected_amount, status, created_at,
+     expires_at, txid, pool, output_index, received_amount, received_at";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_intent(row: &rusqlite::Row) -> SqlResult<DepositIntent> {
+    let status: String = row.get(5)?;
+    let status = IntentStatus::parse(&status).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            5,
+            rusqlite::types::Type::Text,
+            e.into(),
+        )
+    })?;
+
+    Ok(DepositIntent {
+        code: row.get(0)?,
+        recipient_hash: row.get(1)?,
+        account_id: row.get(2)?,
+        secret: row.get(3)?,
+        expected_amount: row.get(4)?,
+        status,
+        created_at: row.get(6)?,
+        expires_at: row.get(7)?,
+        txid: row.get(8)?,
+        pool: row.get(9)?,
+        output_index: row.get(10)?,
+        received_amount: row.get(11)?,
+        received_at: row.get(12)?,
+    })
+}
+
+/// Deposit intents - short reference codes users put in the memo instead of account_id|secret
+pub trait Intents {
+    /// Register a new intent and return it with its reference code
+    fn create(
+        &self,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+        expected_amount: Option<u64>,
+        ttl_secs: i64,
+    ) -> BridgeResult<DepositIntent>;
+
+    /// Get intent by reference code
+    fn get(&self, code: &str) -> BridgeResult<Option<DepositIntent>>;
+
+    /// Get the reference codes registered for a recipient hash
+    fn codes_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>>;
+
+    /// Match a pending intent to the deposit output that carried its code
+    ///
+    /// Returns the new status (received or amount_mismatch), or None if the intent
+    /// was no longer pending.
+    fn mark_received(&self, code: &str, output: &DepositOutput) -> BridgeResult<Option<IntentStatus>>;
+
+    /// Expire pending intents past their expiry time
+    ///
+    /// Returns the number of intents expired.
+    fn expire_stale(&self) -> BridgeResult<usize>;
+}
+
+impl Intents for SqliteStore {
+    fn create(
+        &self,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+        expected_amount: Option<u64>,
+        ttl_secs: i64,
+    ) -> BridgeResult<DepositIntent> {
+        let created_at = now_secs();
+        let expires_at = created_at + ttl_secs;
+
+        // Codes are short, so retry on the (unlikely) collision with an existing one
+        loop {
+            let code = generate_reference_code();
+            let inserted = self.conn.execute(
+                "INSERT INTO deposit_intents
+                    (code, recipient_hash, account_id, secret, expected_amount, status, created_at, expires_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(code) DO NOTHING",
+                rusqlite::params![
+                    code,
+                    recipient_hash,
+                    account_id,
+                    secret,
+                    expected_amount,
+                    IntentStatus::Pending.as_str(),
+                    created_at,
+                    expires_at,
+                ],
+            )?;
+
+            if inserted > 0 {
+                return Ok(DepositIntent {
+                    code,
+                    recipient_hash: recipient_hash.to_string(),
+                    account_id: account_id.map(str::to_string),
+                    secret: secret.map(str::to_string),
+                    expected_amount,
+                    status: IntentStatus::Pending,
+                    created_at,
+                    expires_at,
+                    txid: None,
+                    pool: None,
+                    output_index: None,
+                    received_amount: None,
+                    received_at: None,
+                });
+            }
+        }
+    }
+
+    fn get(&self, code: &str) -> BridgeResult<Option<DepositIntent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM deposit_intents WHERE code = ?1",
+            INTENT_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query_map([code.trim().to_uppercase()], row_to_intent)?;
+
+        if let Some(row) = rows.next() {
+            Ok(Some(row?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn codes_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT code FROM deposit_intents WHERE recipient_hash = ?1"
+        )?;
+
+        let rows = stmt.query_map([recipient_hash], |row| row.get(0))?;
+
+        let mut codes = HashSet::new();
+        for row in rows {
+            codes.insert(row?);
+        }
+
+        Ok(codes)
+    }
+
+    fn mark_received(&self, code: &str, output: &DepositOutput) -> BridgeResult<Option<IntentStatus>> {
+        let Some(intent) = self.get(code)? else {
+            return Ok(None);
+        };
+
+        let status = match intent.expected_amount {
+            Some(expected) if expected != output.value => IntentStatus::AmountMismatch,
+            _ => IntentStatus::Received,
+        };
+
+        let updated = self.conn.execute(
+            "UPDATE deposit_intents
+             SET status = ?1, txid = ?2, pool = ?3, output_index = ?4, received_amount = ?5, received_at = ?6
+             WHERE code = ?7 AND status = ?8",
+            rusqlite::params![
+                status.as_str(),
+                output.txid,
+                output.pool,
+                output.index,
+                output.value,
+                now_secs(),
+                intent.code,
+                IntentStatus::Pending.as_str(),
+            ],
+        )?;
+
+        Ok((updated > 0).then_some(status))
+    }
+
+    fn expire_stale(&self) -> BridgeResult<usize> {
+        let expired = self.conn.execute(
+            "UPDATE deposit_intents SET status = ?1 WHERE status = ?2 AND expires_at < ?3",
+            rusqlite::params![
+                IntentStatus::Expired.as_str(),
+                IntentStatus::Pending.as_str(),
+                now_secs(),
+            ],
+        )?;
+
+        Ok(expired)
+    }
+}
@@ -0,0 +1,420 @@
+use crate::error::{BridgeError, BridgeResult};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags, Transaction, TransactionBehavior};
+use std::path::Path;
+use tracing::info;
+
+/// One forward step of the bridge database schema
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    apply: fn(&Connection, &Path) -> BridgeResult<()>,
+}
+
+/// All migrations, in order. Append new ones, never edit or reorder applied ones.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        apply: |conn, _| Ok(conn.execute_batch(INITIAL_SCHEMA)?),
+    },
+    Migration {
+        version: 2,
+        name: "import_legacy_databases",
+        apply: import_legacy_databases,
+    },
+    Migration {
+        version: 3,
+        name: "audit_log",
+        apply: |conn, _| Ok(conn.execute_batch(AUDIT_LOG)?),
+    },
+    Migration {
+        version: 4,
+        name: "mint_approvals",
+        apply: |conn, _| Ok(conn.execute_batch(MINT_APPROVALS)?),
+    },
+    Migration {
+        version: 5,
+        name: "payouts",
+        apply: |conn, _| Ok(conn.execute_batch(PAYOUTS)?),
+    },
+    Migration {
+        version: 6,
+        name: "deposit_claim_reservations",
+        apply: |conn, _| Ok(conn.execute_batch(DEPOSIT_CLAIM_RESERVATIONS)?),
+    },
+    Migration {
+        version: 7,
+        name: "accounting_indexes",
+        apply: |conn, _| Ok(conn.execute_batch(ACCOUNTING_INDEXES)?),
+    },
+];
+
+/// Schema version this build expects
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+const INITIAL_SCHEMA: &str = "
+    -- NOTE: We only store recipient_hash for privacy - we don't store account_id
+    CREATE TABLE deposits (
+        recipient_hash TEXT PRIMARY KEY,
+        txid TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        claimed_at INTEGER NOT NULL,
+        pool TEXT,
+        output_index INTEGER
+    );
+    CREATE INDEX idx_deposits_txid ON deposits(txid);
+    -- Each output of a transaction can only be claimed once
+    CREATE UNIQUE INDEX idx_deposits_output ON deposits(txid, pool, output_index);
+
+    -- commitment = hash(secret); the secret is never stored
+    CREATE TABLE withdrawals (
+        commitment TEXT PRIMARY KEY,
+        note_id TEXT UNIQUE NOT NULL,
+        amount INTEGER NOT NULL,
+        block_number INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        claimed_at INTEGER,
+        zcash_txid TEXT
+    );
+    CREATE INDEX idx_withdrawals_note_id ON withdrawals(note_id);
+    CREATE INDEX idx_withdrawals_claimed ON withdrawals(claimed_at);
+
+    CREATE TABLE faucets (
+        origin_network TEXT PRIMARY KEY,
+        faucet_id BLOB NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+
+    -- reference = Zcash output key (txid:pool:index) for deposits, commitment or note_id for withdrawals
+    CREATE TABLE fee_accruals (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        direction TEXT NOT NULL,
+        token TEXT NOT NULL,
+        reference TEXT NOT NULL,
+        gross_amount INTEGER NOT NULL,
+        fee INTEGER NOT NULL,
+        net_amount INTEGER NOT NULL,
+        accrued_at INTEGER NOT NULL,
+        UNIQUE(direction, reference)
+    );
+
+    -- One rejected output per row; memos and secrets are only kept as digests
+    CREATE TABLE rejected_deposits (
+        txid TEXT NOT NULL,
+        pool TEXT NOT NULL,
+        output_index INTEGER NOT NULL,
+        amount INTEGER NOT NULL,
+        reason TEXT NOT NULL,
+        memo_digest TEXT,
+        secret_digest TEXT,
+        memo_refund_address TEXT,
+        refund_address TEXT,
+        status TEXT NOT NULL,
+        rejected_at INTEGER NOT NULL,
+        requested_at INTEGER,
+        approved_at INTEGER,
+        refund_txid TEXT,
+        refunded_at INTEGER,
+        PRIMARY KEY (txid, pool, output_index)
+    );
+    CREATE INDEX idx_rejected_deposits_status ON rejected_deposits(status);
+
+    -- account_id and secret are only stored if the user asked for automatic minting
+    CREATE TABLE deposit_addresses (
+        address TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        account_id TEXT,
+        secret TEXT,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_deposit_addresses_recipient ON deposit_addresses(recipient_hash);
+
+    CREATE TABLE deposit_intents (
+        code TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        account_id TEXT,
+        secret TEXT,
+        expected_amount INTEGER,
+        status TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        expires_at INTEGER NOT NULL,
+        txid TEXT,
+        pool TEXT,
+        output_index INTEGER,
+        received_amount INTEGER,
+        received_at INTEGER
+    );
+    CREATE INDEX idx_deposit_intents_recipient ON deposit_intents(recipient_hash);
+
+    -- Append-only: one row per deposit or withdrawal state change
+    CREATE TABLE events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        subject TEXT NOT NULL,
+        state TEXT NOT NULL,
+        event_key TEXT NOT NULL,
+        txid TEXT,
+        recipient_hash TEXT,
+        note_id TEXT,
+        detail TEXT,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_events_key ON events(subject, event_key);
+    CREATE INDEX idx_events_txid ON events(txid);
+    CREATE INDEX idx_events_recipient_hash ON events(recipient_hash);
+    CREATE INDEX idx_events_note_id ON events(note_id);
+
+    -- Each event is delivered at most once per subscription
+    CREATE TABLE webhook_deliveries (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        subscription TEXT NOT NULL,
+        event_id INTEGER NOT NULL,
+        event_type TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        status TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at INTEGER NOT NULL,
+        last_error TEXT,
+        created_at INTEGER NOT NULL,
+        delivered_at INTEGER,
+        UNIQUE (subscription, event_id)
+    );
+    CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+
+    -- Last event log id that was turned into webhook deliveries
+    CREATE TABLE webhook_cursor (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        last_event_id INTEGER NOT NULL
+    );
+";
+
+const AUDIT_LOG: &str = "
+    -- Hash-chained ledger of bridge actions; see db::audit
+    CREATE TABLE audit_log (
+        id INTEGER PRIMARY KEY,
+        action TEXT NOT NULL,
+        inputs_digest TEXT NOT NULL,
+        result TEXT,
+        actor TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        prev_hash TEXT NOT NULL,
+        hash TEXT NOT NULL UNIQUE
+    );
+    CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+    BEGIN
+        SELECT RAISE(ABORT, 'audit_log is append-only');
+    END;
+    CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+    BEGIN
+        SELECT RAISE(ABORT, 'audit_log is append-only');
+    END;
+";
+
+const MINT_APPROVALS: &str = "
+    -- Multisig mints: one proposal per deposit output, signed by the approvers; see db::mint_approvals
+    CREATE TABLE mint_proposals (
+        deposit TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        net_amount INTEGER NOT NULL,
+        message TEXT NOT NULL,
+        proposer TEXT NOT NULL,
+        status TEXT NOT NULL,
+        tx_id TEXT,
+        detail TEXT,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_mint_proposals_status ON mint_proposals(status, created_at);
+
+    CREATE TABLE mint_signatures (
+        deposit TEXT NOT NULL REFERENCES mint_proposals(deposit),
+        signer TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        signed_at INTEGER NOT NULL,
+        PRIMARY KEY (deposit, signer)
+    );
+";
+
+const PAYOUTS: &str = "
+    -- Zcash payouts signed offline: one row per withdrawal or exit, with its PCZT; see db::payouts
+    CREATE TABLE payouts (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        note_id TEXT NOT NULL,
+        zcash_address TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        fee INTEGER NOT NULL,
+        net_amount INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        pczt TEXT NOT NULL,
+        signed_pczt TEXT,
+        txid TEXT,
+        detail TEXT,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_payouts_status ON payouts(status, created_at);
+";
+
+const DEPOSIT_CLAIM_RESERVATIONS: &str = "
+    -- pending: reserved by a mint in progress; claimed: note minted. See Deposits::reserve_claim
+    ALTER TABLE deposits ADD COLUMN status TEXT NOT NULL DEFAULT 'claimed';
+";
+
+const ACCOUNTING_INDEXES: &str = "
+    -- Date ranges of the accounting export. See bridge::accounting::export
+    CREATE INDEX idx_deposits_claimed_at ON deposits(claimed_at);
+    CREATE INDEX idx_withdrawals_created_at ON withdrawals(created_at);
+    CREATE INDEX idx_fee_accruals_accrued_at ON fee_accruals(accrued_at);
+    CREATE INDEX idx_events_created_at ON events(created_at);
+";
+
+/// Per-store database files used before bridge.db, and the tables each one held
+const LEGACY_DATABASES: &[(&str, &[&str])] = &[
+    ("deposits.db", &["deposits"]),
+    ("withdrawals.db", &["withdrawals"]),
+    ("faucets.db", &["faucets"]),
+    ("fees.db", &["fee_accruals"]),
+    ("refunds.db", &["rejected_deposits"]),
+    ("deposit_addresses.db", &["deposit_addresses"]),
+    ("intents.db", &["deposit_intents"]),
+    ("events.db", &["events"]),
+    ("webhooks.db", &["webhook_deliveries", "webhook_cursor"]),
+];
+
+/// Bring the schema up to date, one transaction per migration
+///
+/// Fails if the database was written by a newer build or its history doesn't match ours.
+pub fn run(conn: &Connection, project_root: &Path) -> BridgeResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    for migration in MIGRATIONS {
+        // IMMEDIATE takes the write lock up front, so two processes starting together
+        // can't both apply the same migration
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        check_history(&tx)?;
+
+        let applied: Option<String> = tx
+            .query_row(
+                "SELECT name FROM schema_version WHERE version = ?1",
+                [migration.version],
+                |row| row.get(0),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e),
+            })?;
+
+        if applied.is_some() {
+            continue;
+        }
+
+        (migration.apply)(&tx, project_root)
+            .map_err(|e| e.context(&format!("Migration {} ({}) failed", migration.version, migration.name)))?;
+
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![migration.version, migration.name, chrono::Utc::now().timestamp()],
+        )?;
+        tx.commit()?;
+
+        info!(version = migration.version, name = migration.name, "Applied bridge database migration");
+    }
+
+    Ok(())
+}
+
+// Every applied version must be one of ours, under the same name
+fn check_history(conn: &Connection) -> BridgeResult<()> {
+    let mut stmt = conn.prepare("SELECT version, name FROM schema_version ORDER BY version")?;
+    let applied = stmt
+        .query_map([], |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for (version, name) in applied {
+        match MIGRATIONS.iter().find(|m| m.version == version) {
+            Some(migration) if migration.name == name => {}
+            Some(migration) => {
+                return Err(BridgeError::Database(format!(
+                    "bridge.db migration {} is '{}' but this build expects '{}'",
+                    version, name, migration.name
+                )));
+            }
+            None => {
+                return Err(BridgeError::Database(format!(
+                    "bridge.db is at schema version {} but this build only knows up to {}; upgrade the bridge",
+                    version,
+                    latest_version()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Copy rows from the old per-store files, keeping ids so event cursors stay valid.
+// Columns are matched by name, since older files may predate some of them.
+fn import_legacy_databases(conn: &Connection, project_root: &Path) -> BridgeResult<()> {
+    for (file, tables) in LEGACY_DATABASES {
+        let path = project_root.join(file);
+        if !path.exists() {
+            continue;
+        }
+
+        let legacy = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| BridgeError::from(e).context(&format!("Failed to open {}", file)))?;
+
+        for table in *tables {
+            let legacy_columns = table_columns(&legacy, table)?;
+            if legacy_columns.is_empty() {
+                continue;
+            }
+
+            let current_columns = table_columns(conn, table)?;
+            let columns: Vec<String> = legacy_columns
+                .into_iter()
+                .filter(|column| current_columns.contains(column))
+                .collect();
+
+            let column_list = columns.join(", ");
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let mut insert = conn.prepare(&format!(
+                "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+                table, column_list, placeholders
+            ))?;
+
+            let mut select = legacy.prepare(&format!("SELECT {} FROM {}", column_list, table))?;
+            let mut rows = select.query([])?;
+            let mut imported = 0;
+            while let Some(row) = rows.next()? {
+                let values = (0..columns.len())
+                    .map(|i| row.get::<_, Value>(i))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                imported += insert.execute(rusqlite::params_from_iter(values))?;
+            }
+
+            info!(file = %file, table = %table, rows = imported, "Imported legacy table into bridge.db");
+        }
+    }
+
+    Ok(())
+}
+
+fn table_columns(conn: &Connection, table: &str) -> BridgeResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(columns)
+}
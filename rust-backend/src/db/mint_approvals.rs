@@ -0,0 +1,220 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of a multisig mint
+///
+/// pending (collecting signatures) -> submitting (one relayer proving it) -> submitted,
+/// or failed, after which the deposit can be proposed again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Submitting,
+    Submitted,
+    Failed,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Pending => "pending",
+            ProposalStatus::Submitting => "submitting",
+            ProposalStatus::Submitted => "submitted",
+            ProposalStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, String> {
+        match status {
+            "pending" => Ok(ProposalStatus::Pending),
+            "submitting" => Ok(ProposalStatus::Submitting),
+            "submitted" => Ok(ProposalStatus::Submitted),
+            "failed" => Ok(ProposalStatus::Failed),
+            other => Err(format!("Unknown mint proposal status: {}", other)),
+        }
+    }
+}
+
+/// A deposit mint waiting for approver signatures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintProposal {
+    pub deposit: String, // Zcash output key (txid:pool:index)
+    pub recipient_hash: String,
+    pub amount: u64,     // deposited
+    pub net_amount: u64, // minted, after the deposit fee
+    pub message: String, // transaction summary commitment the approvers sign
+    pub proposer: String,
+    pub status: ProposalStatus,
+    pub tx_id: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintSignature {
+    pub signer: String,    // approver public key commitment
+    pub signature: String, // serialized Falcon signature, hex
+    pub signed_at: i64,
+}
+
+pub(super) const PROPOSAL_COLUMNS: &str =
+    "deposit, recipient_hash, amount, net_amount, message, proposer, status, tx_id, detail, created_at";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_proposal(row: &rusqlite::Row) -> SqlResult<MintProposal> {
+    let status: String = row.get(6)?;
+    let status = ProposalStatus::parse(&status).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            6,
+            rusqlite::types::Type::Text,
+            e.into(),
+        )
+    })?;
+
+    Ok(MintProposal {
+        deposit: row.get(0)?,
+        recipient_hash: row.get(1)?,
+        amount: row.get(2)?,
+        net_amount: row.get(3)?,
+        message: row.get(4)?,
+        proposer: row.get(5)?,
+        status,
+        tx_id: row.get(7)?,
+        detail: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+/// Multisig mint proposals and the approver signatures collected for them
+pub trait MintApprovals {
+    /// Open a proposal for a deposit
+    ///
+    /// Returns false if the deposit already has a proposal that hasn't failed. A failed one is
+    /// replaced, and its signatures dropped.
+    fn propose(&self, proposal: &MintProposal) -> BridgeResult<bool>;
+
+    /// Get the proposal for a deposit
+    fn get(&self, deposit: &str) -> BridgeResult<Option<MintProposal>>;
+
+    /// Proposals still collecting signatures, oldest first
+    fn pending(&self) -> BridgeResult<Vec<MintProposal>>;
+
+    /// Add an approver's signature to a pending proposal
+    ///
+    /// Returns false if the approver already signed, or the proposal isn't pending.
+    fn add_signature(&self, deposit: &str, signer: &str, signature: &str) -> BridgeResult<bool>;
+
+    /// Signatures collected for a deposit's proposal
+    fn signatures(&self, deposit: &str) -> BridgeResult<Vec<MintSignature>>;
+
+    /// Move a pending proposal to submitting
+    ///
+    /// Returns false if another relayer already took it, so only one of them submits.
+    fn start_submission(&self, deposit: &str) -> BridgeResult<bool>;
+
+    /// Record how a submission ended: submitted with its transaction, or failed
+    fn finish(&self, deposit: &str, status: ProposalStatus, tx_id: Option<&str>, detail: Option<&str>) -> BridgeResult<()>;
+}
+
+impl MintApprovals for SqliteStore {
+    fn propose(&self, proposal: &MintProposal) -> BridgeResult<bool> {
+        self.conn.execute(
+            "DELETE FROM mint_signatures WHERE deposit = ?1
+             AND EXISTS (SELECT 1 FROM mint_proposals WHERE deposit = ?1 AND status = 'failed')",
+            [&proposal.deposit],
+        )?;
+
+        let inserted = self.conn.execute(
+            "INSERT INTO mint_proposals (deposit, recipient_hash, amount, net_amount, message, proposer, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending', ?7)
+             ON CONFLICT (deposit) DO UPDATE SET
+                 recipient_hash = excluded.recipient_hash, amount = excluded.amount,
+                 net_amount = excluded.net_amount, message = excluded.message,
+                 proposer = excluded.proposer, status = 'pending', tx_id = NULL, detail = NULL,
+                 created_at = excluded.created_at
+             WHERE mint_proposals.status = 'failed'",
+            rusqlite::params![
+                proposal.deposit,
+                proposal.recipient_hash,
+                proposal.amount,
+                proposal.net_amount,
+                proposal.message,
+                proposal.proposer,
+                now_secs(),
+            ],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    fn get(&self, deposit: &str) -> BridgeResult<Option<MintProposal>> {
+        let proposal = self.conn
+            .query_row(
+                &format!("SELECT {} FROM mint_proposals WHERE deposit = ?1", PROPOSAL_COLUMNS),
+                [deposit],
+                row_to_proposal,
+            )
+            .optional()?;
+        Ok(proposal)
+    }
+
+    fn pending(&self) -> BridgeResult<Vec<MintProposal>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM mint_proposals WHERE status = 'pending' ORDER BY created_at, deposit",
+            PROPOSAL_COLUMNS
+        ))?;
+        let proposals = stmt.query_map([], row_to_proposal)?.collect::<SqlResult<Vec<_>>>()?;
+        Ok(proposals)
+    }
+
+    fn add_signature(&self, deposit: &str, signer: &str, signature: &str) -> BridgeResult<bool> {
+        let inserted = self.conn.execute(
+            "INSERT INTO mint_signatures (deposit, signer, signature, signed_at)
+             SELECT ?1, ?2, ?3, ?4 WHERE EXISTS
+                 (SELECT 1 FROM mint_proposals WHERE deposit = ?1 AND status = 'pending')
+             ON CONFLICT DO NOTHING",
+            rusqlite::params![deposit, signer, signature, now_secs()],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn signatures(&self, deposit: &str) -> BridgeResult<Vec<MintSignature>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signer, signature, signed_at FROM mint_signatures WHERE deposit = ?1 ORDER BY signed_at, signer"
+        )?;
+        let signatures = stmt
+            .query_map([deposit], |row| {
+                Ok(MintSignature {
+                    signer: row.get(0)?,
+                    signature: row.get(1)?,
+                    signed_at: row.get(2)?,
+                })
+            })?
+            .collect::<SqlResult<Vec<_>>>()?;
+        Ok(signatures)
+    }
+
+    fn start_submission(&self, deposit: &str) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE mint_proposals SET status = 'submitting' WHERE deposit = ?1 AND status = 'pending'",
+            [deposit],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn finish(&self, deposit: &str, status: ProposalStatus, tx_id: Option<&str>, detail: Option<&str>) -> BridgeResult<()> {
+        self.conn.execute(
+            "UPDATE mint_proposals SET status = ?2, tx_id = ?3, detail = ?4 WHERE deposit = ?1",
+            rusqlite::params![deposit, status.as_str(), tx_id, detail],
+        )?;
+        Ok(())
+    }
+}
@@ -1,4 +1,198 @@
+pub mod audit;
+pub mod deposit_addresses;
 pub mod deposits;
+pub mod events;
 pub mod faucets;
+pub mod fees;
+pub mod intents;
+pub mod migrations;
+pub mod mint_approvals;
+pub mod payouts;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod refunds;
+pub mod webhooks;
 pub mod withdrawals;
 
+use crate::error::BridgeResult;
+use audit::Audit;
+use deposit_addresses::DepositAddresses;
+use deposits::Deposits;
+use events::Events;
+use faucets::Faucets;
+use fees::Fees;
+use intents::Intents;
+use mint_approvals::MintApprovals;
+use payouts::Payouts;
+use refunds::Refunds;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+use std::path::{Path, PathBuf};
+use webhooks::Webhooks;
+use withdrawals::Withdrawals;
+
+/// All bridge persistence, shared by the API server, the relayers and the admin bins
+///
+/// Each area of bridge state is its own trait, reached through the accessors, so that
+/// call sites read `db.deposits().record_claim(..)` whatever the backend.
+pub trait BridgeStore: Send {
+    /// Backend name, for logs
+    fn backend(&self) -> &'static str;
+
+    /// Highest migration applied to this database
+    fn schema_version(&self) -> BridgeResult<u32>;
+
+    /// Run `f` in a transaction, committing if it returns Ok
+    ///
+    /// Calls nested inside another transaction just join it. Use `transaction` instead,
+    /// this form only exists to keep the trait object-safe.
+    fn in_transaction(&self, f: &mut dyn FnMut(&dyn BridgeStore) -> BridgeResult<()>) -> BridgeResult<()>;
+
+    fn deposits(&self) -> &dyn Deposits;
+    fn withdrawals(&self) -> &dyn Withdrawals;
+    fn faucets(&self) -> &dyn Faucets;
+    fn fees(&self) -> &dyn Fees;
+    fn refunds(&self) -> &dyn Refunds;
+    fn deposit_addresses(&self) -> &dyn DepositAddresses;
+    fn intents(&self) -> &dyn Intents;
+    fn events(&self) -> &dyn Events;
+    fn webhooks(&self) -> &dyn Webhooks;
+    fn audit(&self) -> &dyn Audit;
+    fn mint_approvals(&self) -> &dyn MintApprovals;
+    fn payouts(&self) -> &dyn Payouts;
+}
+
+impl<'s> dyn BridgeStore + 's {
+    /// Run `f` in a transaction, committing if it returns Ok
+    ///
+    /// Calls nested inside another transaction just join it.
+    pub fn transaction<T>(&self, f: impl FnOnce(&dyn BridgeStore) -> BridgeResult<T>) -> BridgeResult<T> {
+        let mut f = Some(f);
+        let mut result = None;
+        self.in_transaction(&mut |store| {
+            let f = f.take().expect("transaction body runs once");
+            result = Some(f(store)?);
+            Ok(())
+        })?;
+        Ok(result.expect("transaction body ran"))
+    }
+}
+
+/// Open the bridge database: Postgres if BRIDGE_DATABASE_URL is set, bridge.db otherwise
+pub fn open(project_root: &Path) -> BridgeResult<Box<dyn BridgeStore>> {
+    match std::env::var("BRIDGE_DATABASE_URL") {
+        Ok(url) => open_url(&url),
+        Err(_) => Ok(Box::new(SqliteStore::open(project_root)?)),
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn open_url(url: &str) -> BridgeResult<Box<dyn BridgeStore>> {
+    Ok(Box::new(postgres::PostgresStore::connect(url)?))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open_url(_url: &str) -> BridgeResult<Box<dyn BridgeStore>> {
+    Err(crate::error::BridgeError::Database(
+        "BRIDGE_DATABASE_URL is set but this build has no Postgres support; rebuild with --features postgres".to_string(),
+    ))
+}
+
+/// The default backend: one SQLite file (bridge.db in the project root)
+///
+/// Fine for a single API server next to the relayers; several API replicas need Postgres.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open bridge.db in the project root (or BRIDGE_DB), applying pending migrations
+    pub fn open(project_root: &Path) -> BridgeResult<Self> {
+        let db_path = std::env::var("BRIDGE_DB")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| project_root.join("bridge.db"));
+
+        let conn = Connection::open(db_path)?;
+
+        // Several processes write to the same file, so wait for their writes instead of failing
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+
+        migrations::run(&conn, project_root)?;
+
+        Ok(Self { conn })
+    }
+}
+
+impl BridgeStore for SqliteStore {
+    fn backend(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn schema_version(&self) -> BridgeResult<u32> {
+        let version = self.conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(version)
+    }
+
+    fn in_transaction(&self, f: &mut dyn FnMut(&dyn BridgeStore) -> BridgeResult<()>) -> BridgeResult<()> {
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        f(self)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn deposits(&self) -> &dyn Deposits {
+        self
+    }
+
+    fn withdrawals(&self) -> &dyn Withdrawals {
+        self
+    }
+
+    fn faucets(&self) -> &dyn Faucets {
+        self
+    }
+
+    fn fees(&self) -> &dyn Fees {
+        self
+    }
+
+    fn refunds(&self) -> &dyn Refunds {
+        self
+    }
+
+    fn deposit_addresses(&self) -> &dyn DepositAddresses {
+        self
+    }
+
+    fn intents(&self) -> &dyn Intents {
+        self
+    }
+
+    fn events(&self) -> &dyn Events {
+        self
+    }
+
+    fn webhooks(&self) -> &dyn Webhooks {
+        self
+    }
+
+    fn audit(&self) -> &dyn Audit {
+        self
+    }
+
+    fn mint_approvals(&self) -> &dyn MintApprovals {
+        self
+    }
+
+    fn payouts(&self) -> &dyn Payouts {
+        self
+    }
+}
@@ -0,0 +1,276 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of a Zcash payout signed off the API host
+///
+/// queued (waiting for the payouts ahead of it) -> building (one process building its PCZT)
+/// -> unsigned (PCZT built and proven) -> signed (signature imported from the offline signer)
+/// -> broadcasting (one process sending it) -> broadcast, or failed, after which an operator
+/// checks the transaction didn't land and rebuilds it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutStatus {
+    Queued,
+    Building,
+    Unsigned,
+    Signed,
+    Broadcasting,
+    Broadcast,
+    Failed,
+}
+
+impl PayoutStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutStatus::Queued => "queued",
+            PayoutStatus::Building => "building",
+            PayoutStatus::Unsigned => "unsigned",
+            PayoutStatus::Signed => "signed",
+            PayoutStatus::Broadcasting => "broadcasting",
+            PayoutStatus::Broadcast => "broadcast",
+            PayoutStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, String> {
+        match status {
+            "queued" => Ok(PayoutStatus::Queued),
+            "building" => Ok(PayoutStatus::Building),
+            "unsigned" => Ok(PayoutStatus::Unsigned),
+            "signed" => Ok(PayoutStatus::Signed),
+            "broadcasting" => Ok(PayoutStatus::Broadcasting),
+            "broadcast" => Ok(PayoutStatus::Broadcast),
+            "failed" => Ok(PayoutStatus::Failed),
+            other => Err(format!("Unknown payout status: {}", other)),
+        }
+    }
+}
+
+/// What a payout pays out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutKind {
+    /// A claimed withdrawal commitment
+    Withdrawal,
+    /// A legacy exit note, found by the exit relayer
+    Exit,
+}
+
+impl PayoutKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutKind::Withdrawal => "withdrawal",
+            PayoutKind::Exit => "exit",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Result<Self, String> {
+        match kind {
+            "withdrawal" => Ok(PayoutKind::Withdrawal),
+            "exit" => Ok(PayoutKind::Exit),
+            other => Err(format!("Unknown payout kind: {}", other)),
+        }
+    }
+}
+
+/// A queued Zcash payout and its PCZT at each stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payout {
+    pub id: String, // withdrawal commitment, or burn note id for exits
+    pub kind: PayoutKind,
+    pub note_id: String,
+    pub zcash_address: String,
+    pub amount: u64,     // burned
+    pub fee: u64,
+    pub net_amount: u64, // paid out
+    pub status: PayoutStatus,
+    pub pczt: String,                // unsigned, proven PCZT, hex; empty until built
+    pub signed_pczt: Option<String>, // hex
+    pub txid: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+pub(super) const PAYOUT_COLUMNS: &str =
+    "id, kind, note_id, zcash_address, amount, fee, net_amount, status, pczt, signed_pczt, txid, detail, created_at, updated_at";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn parse_column<T>(index: usize, value: Result<T, String>) -> SqlResult<T> {
+    value.map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, e.into())
+    })
+}
+
+fn row_to_payout(row: &rusqlite::Row) -> SqlResult<Payout> {
+    let kind: String = row.get(1)?;
+    let status: String = row.get(7)?;
+
+    Ok(Payout {
+        id: row.get(0)?,
+        kind: parse_column(1, PayoutKind::parse(&kind))?,
+        note_id: row.get(2)?,
+        zcash_address: row.get(3)?,
+        amount: row.get(4)?,
+        fee: row.get(5)?,
+        net_amount: row.get(6)?,
+        status: parse_column(7, PayoutStatus::parse(&status))?,
+        pczt: row.get(8)?,
+        signed_pczt: row.get(9)?,
+        txid: row.get(10)?,
+        detail: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+/// Payouts waiting on the offline signer, and how each one ended
+pub trait Payouts {
+    /// Queue a payout in its status, with its PCZT if it already has one
+    ///
+    /// Returns false if a payout with the same id is already queued, whatever its status.
+    fn queue(&self, payout: &Payout) -> BridgeResult<bool>;
+
+    /// Get a payout by id
+    fn get(&self, id: &str) -> BridgeResult<Option<Payout>>;
+
+    /// Payouts in a status, oldest first
+    fn with_status(&self, status: PayoutStatus) -> BridgeResult<Vec<Payout>>;
+
+    /// Every payout, oldest first
+    fn list(&self) -> BridgeResult<Vec<Payout>>;
+
+    /// Attach the signed PCZT to an unsigned payout
+    ///
+    /// Returns false if the payout isn't waiting for a signature.
+    fn attach_signed(&self, id: &str, signed_pczt: &str) -> BridgeResult<bool>;
+
+    /// Move a signed payout to broadcasting
+    ///
+    /// Returns false if another process already took it, so only one of them sends it.
+    fn start_broadcast(&self, id: &str) -> BridgeResult<bool>;
+
+    /// Record how a broadcast ended: broadcast with its txid, or failed
+    fn finish(&self, id: &str, status: PayoutStatus, txid: Option<&str>, detail: Option<&str>) -> BridgeResult<()>;
+
+    /// Move a queued or failed payout to building, unless another payout's PCZT is out
+    ///
+    /// zcash-devtool picks the notes a PCZT spends, and the wallet only sees them spent once the
+    /// transaction is broadcast, so a PCZT is only built while no other payout is building,
+    /// unsigned, signed or broadcasting. Returns false if the payout can't be built now.
+    fn start_build(&self, id: &str) -> BridgeResult<bool>;
+
+    /// Attach the PCZT of a payout being built, queueing it for signing
+    ///
+    /// Returns false if the payout isn't being built.
+    fn attach_pczt(&self, id: &str, pczt: &str) -> BridgeResult<bool>;
+}
+
+impl Payouts for SqliteStore {
+    fn queue(&self, payout: &Payout) -> BridgeResult<bool> {
+        let now = now_secs();
+        let inserted = self.conn.execute(
+            "INSERT INTO payouts (id, kind, note_id, zcash_address, amount, fee, net_amount, status, pczt, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)
+             ON CONFLICT DO NOTHING",
+            rusqlite::params![
+                payout.id,
+                payout.kind.as_str(),
+                payout.note_id,
+                payout.zcash_address,
+                payout.amount,
+                payout.fee,
+                payout.net_amount,
+                payout.status.as_str(),
+                payout.pczt,
+                now,
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn get(&self, id: &str) -> BridgeResult<Option<Payout>> {
+        let payout = self.conn
+            .query_row(
+                &format!("SELECT {} FROM payouts WHERE id = ?1", PAYOUT_COLUMNS),
+                [id],
+                row_to_payout,
+            )
+            .optional()?;
+        Ok(payout)
+    }
+
+    fn with_status(&self, status: PayoutStatus) -> BridgeResult<Vec<Payout>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM payouts WHERE status = ?1 ORDER BY created_at, id",
+            PAYOUT_COLUMNS
+        ))?;
+        let payouts = stmt.query_map([status.as_str()], row_to_payout)?.collect::<SqlResult<Vec<_>>>()?;
+        Ok(payouts)
+    }
+
+    fn list(&self) -> BridgeResult<Vec<Payout>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM payouts ORDER BY created_at, id",
+            PAYOUT_COLUMNS
+        ))?;
+        let payouts = stmt.query_map([], row_to_payout)?.collect::<SqlResult<Vec<_>>>()?;
+        Ok(payouts)
+    }
+
+    fn attach_signed(&self, id: &str, signed_pczt: &str) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE payouts SET status = 'signed', signed_pczt = ?2, updated_at = ?3
+             WHERE id = ?1 AND status = 'unsigned'",
+            rusqlite::params![id, signed_pczt, now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn start_broadcast(&self, id: &str) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE payouts SET status = 'broadcasting', updated_at = ?2 WHERE id = ?1 AND status = 'signed'",
+            rusqlite::params![id, now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn finish(&self, id: &str, status: PayoutStatus, txid: Option<&str>, detail: Option<&str>) -> BridgeResult<()> {
+        self.conn.execute(
+            "UPDATE payouts SET status = ?2, txid = ?3, detail = ?4, updated_at = ?5 WHERE id = ?1",
+            rusqlite::params![id, status.as_str(), txid, detail, now_secs()],
+        )?;
+        Ok(())
+    }
+
+    fn start_build(&self, id: &str) -> BridgeResult<bool> {
+        // One statement, so SQLite's write lock keeps two builds from both passing the check
+        let updated = self.conn.execute(
+            "UPDATE payouts SET status = 'building', updated_at = ?2
+             WHERE id = ?1 AND status IN ('queued', 'failed')
+               AND NOT EXISTS (
+                   SELECT 1 FROM payouts WHERE status IN ('building', 'unsigned', 'signed', 'broadcasting')
+               )",
+            rusqlite::params![id, now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn attach_pczt(&self, id: &str, pczt: &str) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE payouts SET status = 'unsigned', pczt = ?2, signed_pczt = NULL, txid = NULL, detail = NULL,
+                 updated_at = ?3
+             WHERE id = ?1 AND status = 'building'",
+            rusqlite::params![id, pczt, now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+}
@@ -0,0 +1,68 @@
+use super::PostgresStore;
+use crate::db::audit::{AUDIT_COLUMNS, Audit, AuditAction, AuditEntry, next_entry};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+/// Key of the advisory lock held while appending, so replicas can't fork the chain
+const AUDIT_LOCK: i64 = 0x0061_7564_6974; // "audit"
+
+fn row_to_entry(row: &Row) -> BridgeResult<AuditEntry> {
+    let action: String = row.try_get(1)?;
+
+    Ok(AuditEntry {
+        id: row.try_get(0)?,
+        action: AuditAction::parse(&action).map_err(BridgeError::Database)?,
+        inputs_digest: row.try_get(2)?,
+        result: row.try_get(3)?,
+        actor: row.try_get(4)?,
+        created_at: row.try_get(5)?,
+        prev_hash: row.try_get(6)?,
+        hash: row.try_get(7)?,
+    })
+}
+
+impl Audit for PostgresStore {
+    fn append(
+        &self,
+        action: AuditAction,
+        inputs_digest: &str,
+        result: Option<&str>,
+        actor: &str,
+    ) -> BridgeResult<AuditEntry> {
+        self.transaction(|| {
+            self.query("SELECT pg_advisory_xact_lock($1)", &[&AUDIT_LOCK])?;
+
+            let head = self
+                .query_opt("SELECT id, hash FROM audit_log ORDER BY id DESC LIMIT 1", &[])?
+                .map(|row| Ok::<_, BridgeError>((row.try_get(0)?, row.try_get(1)?)))
+                .transpose()?;
+
+            let entry = next_entry(head, action, inputs_digest, result, actor);
+            self.execute(
+                &format!("INSERT INTO audit_log ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)", AUDIT_COLUMNS),
+                &[
+                    &entry.id,
+                    &entry.action.as_str(),
+                    &entry.inputs_digest,
+                    &entry.result,
+                    &entry.actor,
+                    &entry.created_at,
+                    &entry.prev_hash,
+                    &entry.hash,
+                ],
+            )?;
+
+            Ok(entry)
+        })
+    }
+
+    fn entries(&self, after_id: i64, limit: u32) -> BridgeResult<Vec<AuditEntry>> {
+        self.query(
+            &format!("SELECT {} FROM audit_log WHERE id > $1 ORDER BY id LIMIT $2", AUDIT_COLUMNS),
+            &[&after_id, &(limit as i64)],
+        )?
+        .iter()
+        .map(row_to_entry)
+        .collect()
+    }
+}
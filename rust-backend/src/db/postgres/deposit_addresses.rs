@@ -0,0 +1,70 @@
+use super::PostgresStore;
+use crate::db::deposit_addresses::{DepositAddress, DepositAddresses};
+use crate::error::BridgeResult;
+use std::collections::HashSet;
+
+impl DepositAddresses for PostgresStore {
+    fn assign(
+        &self,
+        address: &str,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+    ) -> BridgeResult<DepositAddress> {
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.execute(
+            "INSERT INTO deposit_addresses (address, recipient_hash, account_id, secret, created_at)
+             VALUES ($1, $2, $3, $4, $5)",
+            &[&address, &recipient_hash, &account_id, &secret, &created_at],
+        )?;
+
+        Ok(DepositAddress {
+            address: address.to_string(),
+            recipient_hash: recipient_hash.to_string(),
+            account_id: account_id.map(str::to_string),
+            secret: secret.map(str::to_string),
+            created_at,
+        })
+    }
+
+    fn get(&self, address: &str) -> BridgeResult<Option<DepositAddress>> {
+        let row = self.query_opt(
+            "SELECT address, recipient_hash, account_id, secret, created_at
+             FROM deposit_addresses WHERE address = $1",
+            &[&address],
+        )?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(DepositAddress {
+            address: row.try_get(0)?,
+            recipient_hash: row.try_get(1)?,
+            account_id: row.try_get(2)?,
+            secret: row.try_get(3)?,
+            created_at: row.try_get(4)?,
+        }))
+    }
+
+    fn addresses_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>> {
+        self.query(
+            "SELECT address FROM deposit_addresses WHERE recipient_hash = $1",
+            &[&recipient_hash],
+        )?
+        .iter()
+        .map(|row| Ok(row.try_get(0)?))
+        .collect()
+    }
+
+    fn all_addresses(&self) -> BridgeResult<HashSet<String>> {
+        self.query("SELECT address FROM deposit_addresses", &[])?
+            .iter()
+            .map(|row| Ok(row.try_get(0)?))
+            .collect()
+    }
+}
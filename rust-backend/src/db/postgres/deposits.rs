@@ -0,0 +1,139 @@
+use super::PostgresStore;
+use crate::db::deposits::{DepositRecord, Deposits};
+use crate::error::BridgeResult;
+use postgres::Row;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+impl Deposits for PostgresStore {
+    fn is_claimed(&self, recipient_hash: &str) -> BridgeResult<bool> {
+        let row = self.query_opt(
+            "SELECT 1 FROM deposits WHERE recipient_hash = $1 LIMIT 1",
+            &[&recipient_hash],
+        )?;
+        Ok(row.is_some())
+    }
+
+    fn is_output_claimed(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let row = self.query_opt(
+            "SELECT 1 FROM deposits WHERE txid = $1 AND pool = $2 AND output_index = $3 LIMIT 1",
+            &[&txid, &pool, &(output_index as i64)],
+        )?;
+        Ok(row.is_some())
+    }
+
+    fn record_claim(
+        &self,
+        recipient_hash: &str,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+    ) -> BridgeResult<bool> {
+        let inserted = self.execute(
+            "INSERT INTO deposits (recipient_hash, txid, pool, output_index, amount, claimed_at, status)
+             VALUES ($1, $2, $3, $4, $5, $6, 'claimed')
+             ON CONFLICT DO NOTHING",
+            &[&recipient_hash, &txid, &pool, &(output_index as i64), &(amount as i64), &now_secs()],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    fn reserve_claim(
+        &self,
+        recipient_hash: &str,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+    ) -> BridgeResult<bool> {
+        let inserted = self.execute(
+            "INSERT INTO deposits (recipient_hash, txid, pool, output_index, amount, claimed_at, status)
+             VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+             ON CONFLICT DO NOTHING",
+            &[&recipient_hash, &txid, &pool, &(output_index as i64), &(amount as i64), &now_secs()],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    fn confirm_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE deposits SET status = 'claimed', claimed_at = $4
+             WHERE txid = $1 AND pool = $2 AND output_index = $3 AND status = 'pending'",
+            &[&txid, &pool, &(output_index as i64), &now_secs()],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn release_claim(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<()> {
+        self.execute(
+            "DELETE FROM deposits WHERE txid = $1 AND pool = $2 AND output_index = $3 AND status = 'pending'",
+            &[&txid, &pool, &(output_index as i64)],
+        )?;
+
+        Ok(())
+    }
+
+    fn get_deposit(&self, recipient_hash: &str) -> BridgeResult<Option<DepositRecord>> {
+        let row = self.query_opt(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE recipient_hash = $1 AND status = 'claimed'",
+            &[&recipient_hash],
+        )?;
+
+        row.map(|row| row_to_record(&row)).transpose()
+    }
+
+    fn list_claims(&self) -> BridgeResult<Vec<DepositRecord>> {
+        self.query(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'claimed' ORDER BY claimed_at",
+            &[],
+        )?
+        .iter()
+        .map(row_to_record)
+        .collect()
+    }
+
+    fn list_claims_between(&self, since: i64, until: i64) -> BridgeResult<Vec<DepositRecord>> {
+        self.query(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'claimed' AND claimed_at >= $1 AND claimed_at < $2
+             ORDER BY claimed_at",
+            &[&since, &until],
+        )?
+        .iter()
+        .map(row_to_record)
+        .collect()
+    }
+
+    fn pending_claims(&self) -> BridgeResult<Vec<DepositRecord>> {
+        self.query(
+            "SELECT recipient_hash, txid, pool, output_index, amount, claimed_at
+             FROM deposits WHERE status = 'pending' ORDER BY claimed_at",
+            &[],
+        )?
+        .iter()
+        .map(row_to_record)
+        .collect()
+    }
+}
+
+fn row_to_record(row: &Row) -> BridgeResult<DepositRecord> {
+    Ok(DepositRecord {
+        recipient_hash: row.try_get(0)?,
+        txid: row.try_get(1)?,
+        pool: row.try_get(2)?,
+        output_index: row.try_get::<_, Option<i64>>(3)?.map(|index| index as u32),
+        amount: row.try_get::<_, i64>(4)? as u64,
+        claimed_at: row.try_get(5)?,
+    })
+}
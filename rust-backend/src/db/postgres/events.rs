@@ -0,0 +1,148 @@
+use super::PostgresStore;
+use crate::db::events::{
+    BridgeEvent, EVENT_COLUMNS, EVENT_PAGE_SIZE, EventState, EventSubject, Events, prefixed_hex,
+};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_event(row: &Row) -> BridgeResult<BridgeEvent> {
+    let subject: String = row.try_get(1)?;
+    let state: String = row.try_get(2)?;
+
+    Ok(BridgeEvent {
+        id: row.try_get(0)?,
+        subject: EventSubject::parse(&subject).map_err(BridgeError::Database)?,
+        state: EventState::parse(&state).map_err(BridgeError::Database)?,
+        key: row.try_get(3)?,
+        txid: row.try_get(4)?,
+        recipient_hash: row.try_get(5)?,
+        note_id: row.try_get(6)?,
+        detail: row.try_get(7)?,
+        created_at: row.try_get(8)?,
+    })
+}
+
+impl Events for PostgresStore {
+    fn record_deposit(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        let key = format!("{}:{}:{}", txid, pool, output_index);
+        self.record_event(EventSubject::Deposit, &key, Some(txid), recipient_hash, state, note_id, detail)
+    }
+
+    fn record_withdrawal(
+        &self,
+        commitment: &str,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        self.record_event(EventSubject::Withdrawal, commitment, None, None, state, note_id, detail)
+    }
+
+    fn deposit_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let id = id.trim().to_lowercase();
+        let txid_or_key = id.strip_prefix("0x").unwrap_or(&id).to_string();
+
+        self.query_events(
+            "event_key = $2 OR txid = $2 OR recipient_hash = $3",
+            EventSubject::Deposit,
+            [&txid_or_key, &prefixed_hex(&id)],
+            after_id,
+        )
+    }
+
+    fn withdrawal_events(&self, id: &str, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        let id = prefixed_hex(id);
+
+        self.query_events("event_key = $2 OR note_id = $3", EventSubject::Withdrawal, [&id, &id], after_id)
+    }
+
+    fn events_after(&self, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        self.query(
+            &format!("SELECT {} FROM events WHERE id > $1 ORDER BY id LIMIT $2", EVENT_COLUMNS),
+            &[&after_id, &EVENT_PAGE_SIZE],
+        )?
+        .iter()
+        .map(row_to_event)
+        .collect()
+    }
+
+    fn events_since(&self, since: i64, after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        self.query(
+            &format!("SELECT {} FROM events WHERE id > $1 AND created_at >= $2 ORDER BY id LIMIT $3", EVENT_COLUMNS),
+            &[&after_id, &since, &EVENT_PAGE_SIZE],
+        )?
+        .iter()
+        .map(row_to_event)
+        .collect()
+    }
+}
+
+impl PostgresStore {
+    fn record_event(
+        &self,
+        subject: EventSubject,
+        key: &str,
+        txid: Option<&str>,
+        recipient_hash: Option<&str>,
+        state: EventState,
+        note_id: Option<&str>,
+        detail: Option<&str>,
+    ) -> BridgeResult<bool> {
+        let current = self.query_opt(
+            "SELECT state FROM events WHERE subject = $1 AND event_key = $2 ORDER BY id DESC LIMIT 1",
+            &[&subject.as_str(), &key],
+        )?;
+
+        if let Some(current) = current
+            && current.try_get::<_, &str>(0)? == state.as_str()
+        {
+            return Ok(false);
+        }
+
+        self.execute(
+            "INSERT INTO events (subject, state, event_key, txid, recipient_hash, note_id, detail, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &subject.as_str(),
+                &state.as_str(),
+                &key,
+                &txid,
+                &recipient_hash,
+                &note_id,
+                &detail,
+                &now_secs(),
+            ],
+        )?;
+
+        Ok(true)
+    }
+
+    fn query_events(&self, matches: &str, subject: EventSubject, ids: [&String; 2], after_id: i64) -> BridgeResult<Vec<BridgeEvent>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM events WHERE subject = $1 AND ({}) AND id > $4 ORDER BY id LIMIT $5",
+                EVENT_COLUMNS, matches
+            ),
+            &[&subject.as_str(), ids[0], ids[1], &after_id, &EVENT_PAGE_SIZE],
+        )?
+        .iter()
+        .map(row_to_event)
+        .collect()
+    }
+}
@@ -0,0 +1,39 @@
+use super::PostgresStore;
+use crate::db::faucets::Faucets;
+use crate::error::{BridgeError, BridgeResult};
+use miden_objects::account::AccountId;
+use miden_objects::utils::{Deserializable, Serializable};
+
+impl Faucets for PostgresStore {
+    fn get_faucet_id(&self, origin_network: &str) -> BridgeResult<Option<AccountId>> {
+        let row = self.query_opt(
+            "SELECT faucet_id FROM faucets WHERE origin_network = $1",
+            &[&origin_network],
+        )?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let faucet_id_bytes: Vec<u8> = row.try_get(0)?;
+        let faucet_id = AccountId::read_from_bytes(&faucet_id_bytes)
+            .map_err(|e| BridgeError::Database(format!("Invalid faucet id for {}: {}", origin_network, e)))?;
+
+        Ok(Some(faucet_id))
+    }
+
+    fn store_faucet_id(&self, origin_network: &str, faucet_id: &AccountId) -> BridgeResult<()> {
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.execute(
+            "INSERT INTO faucets (origin_network, faucet_id, created_at) VALUES ($1, $2, $3)
+             ON CONFLICT (origin_network) DO UPDATE SET faucet_id = excluded.faucet_id, created_at = excluded.created_at",
+            &[&origin_network, &faucet_id.to_bytes(), &created_at],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use super::PostgresStore;
+use crate::bridge::fees::FeeQuote;
+use crate::db::fees::{FeeAccrual, FeeSummary, Fees, LedgerTotals};
+use crate::error::BridgeResult;
+
+impl Fees for PostgresStore {
+    fn record_fee(&self, quote: &FeeQuote, reference: &str) -> BridgeResult<()> {
+        let accrued_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.execute(
+            "INSERT INTO fee_accruals (direction, token, reference, gross_amount, fee, net_amount, accrued_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (direction, reference) DO NOTHING",
+            &[
+                &quote.direction.as_str(),
+                &quote.token,
+                &reference,
+                &(quote.gross_amount as i64),
+                &(quote.fee as i64),
+                &(quote.net_amount as i64),
+                &accrued_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn summary(&self) -> BridgeResult<Vec<FeeSummary>> {
+        // SUM over BIGINT is NUMERIC in Postgres
+        self.query(
+            "SELECT direction, token, COUNT(*), SUM(gross_amount)::BIGINT, SUM(fee)::BIGINT, SUM(net_amount)::BIGINT
+             FROM fee_accruals
+             GROUP BY direction, token
+             ORDER BY token, direction",
+            &[],
+        )?
+        .iter()
+        .map(|row| {
+            Ok(FeeSummary {
+                direction: row.try_get(0)?,
+                token: row.try_get(1)?,
+                transfers: row.try_get::<_, i64>(2)? as u64,
+                gross_amount: row.try_get::<_, i64>(3)? as u64,
+                fees: row.try_get::<_, i64>(4)? as u64,
+                net_amount: row.try_get::<_, i64>(5)? as u64,
+            })
+        })
+        .collect()
+    }
+
+    fn accruals(&self) -> BridgeResult<Vec<FeeAccrual>> {
+        self.query(
+            "SELECT direction, token, reference, gross_amount, fee, net_amount, accrued_at
+             FROM fee_accruals
+             ORDER BY id",
+            &[],
+        )?
+        .iter()
+        .map(|row| {
+            Ok(FeeAccrual {
+                direction: row.try_get(0)?,
+                token: row.try_get(1)?,
+                reference: row.try_get(2)?,
+                gross_amount: row.try_get::<_, i64>(3)? as u64,
+                fee: row.try_get::<_, i64>(4)? as u64,
+                net_amount: row.try_get::<_, i64>(5)? as u64,
+                accrued_at: row.try_get(6)?,
+            })
+        })
+        .collect()
+    }
+
+    fn accruals_since(&self, since: i64) -> BridgeResult<Vec<FeeAccrual>> {
+        self.query(
+            "SELECT direction, token, reference, gross_amount, fee, net_amount, accrued_at
+             FROM fee_accruals
+             WHERE accrued_at >= $1
+             ORDER BY id",
+            &[&since],
+        )?
+        .iter()
+        .map(|row| {
+            Ok(FeeAccrual {
+                direction: row.try_get(0)?,
+                token: row.try_get(1)?,
+                reference: row.try_get(2)?,
+                gross_amount: row.try_get::<_, i64>(3)? as u64,
+                fee: row.try_get::<_, i64>(4)? as u64,
+                net_amount: row.try_get::<_, i64>(5)? as u64,
+                accrued_at: row.try_get(6)?,
+            })
+        })
+        .collect()
+    }
+
+    fn totals_before(&self, until: i64) -> BridgeResult<LedgerTotals> {
+        // SUM over BIGINT is NUMERIC in Postgres
+        let rows = self.query(
+            "WITH
+                claims AS (
+                    SELECT amount,
+                        CASE WHEN pool IS NULL OR output_index IS NULL THEN txid
+                             ELSE txid || ':' || pool || ':' || output_index END AS reference
+                    FROM deposits WHERE status = 'claimed' AND claimed_at < $1
+                ),
+                burns AS (
+                    SELECT LOWER(REPLACE(TRIM(commitment), '0x', '')) AS reference, created_at FROM withdrawals
+                    UNION ALL
+                    SELECT LOWER(REPLACE(TRIM(note_id), '0x', '')), created_at FROM withdrawals
+                ),
+                -- Exits paid by the exit relayer have no withdrawal row, so no burned_at
+                withdrawal_fees AS (
+                    SELECT f.gross_amount, f.fee, f.accrued_at,
+                        (SELECT MIN(b.created_at) FROM burns b
+                         WHERE b.reference = LOWER(REPLACE(TRIM(f.reference), '0x', ''))) AS burned_at
+                    FROM fee_accruals f
+                    WHERE f.direction = 'withdrawal' AND f.reference NOT LIKE 'refund:%'
+                )
+             SELECT
+                (SELECT COALESCE(SUM(amount), 0) FROM claims)::BIGINT,
+                ((SELECT COALESCE(SUM(amount), 0) FROM withdrawals WHERE created_at < $1)
+                  + (SELECT COALESCE(SUM(gross_amount), 0) FROM withdrawal_fees WHERE burned_at IS NULL AND accrued_at < $1))::BIGINT,
+                ((SELECT COALESCE(SUM(f.fee), 0) FROM claims c
+                  JOIN fee_accruals f ON f.direction = 'deposit' AND f.reference = c.reference)
+                  + (SELECT COALESCE(SUM(fee), 0) FROM withdrawal_fees WHERE COALESCE(burned_at, accrued_at) < $1))::BIGINT",
+            &[&until],
+        )?;
+
+        Ok(LedgerTotals {
+            deposited: rows[0].try_get::<_, i64>(0)? as u64,
+            withdrawn: rows[0].try_get::<_, i64>(1)? as u64,
+            fees: rows[0].try_get::<_, i64>(2)? as u64,
+        })
+    }
+
+    fn outstanding_supply(&self, token: &str) -> BridgeResult<i64> {
+        let rows = self.query(
+            "SELECT (
+                COALESCE(SUM(CASE WHEN direction = 'deposit' THEN net_amount ELSE 0 END), 0)
+              - COALESCE(SUM(CASE WHEN direction = 'withdrawal' THEN gross_amount ELSE 0 END), 0)
+             )::BIGINT
+             FROM fee_accruals
+             WHERE token = $1 AND reference NOT LIKE 'refund:%'",
+            &[&token],
+        )?;
+
+        Ok(rows[0].try_get(0)?)
+    }
+}
@@ -0,0 +1,144 @@
+use super::PostgresStore;
+use crate::bridge::memo::generate_reference_code;
+use crate::db::intents::{DepositIntent, INTENT_COLUMNS, IntentStatus, Intents};
+use crate::error::{BridgeError, BridgeResult};
+use crate::zcash::bridge_wallet::DepositOutput;
+use postgres::Row;
+use std::collections::HashSet;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_intent(row: &Row) -> BridgeResult<DepositIntent> {
+    let status: String = row.try_get(5)?;
+
+    Ok(DepositIntent {
+        code: row.try_get(0)?,
+        recipient_hash: row.try_get(1)?,
+        account_id: row.try_get(2)?,
+        secret: row.try_get(3)?,
+        expected_amount: row.try_get::<_, Option<i64>>(4)?.map(|amount| amount as u64),
+        status: IntentStatus::parse(&status).map_err(BridgeError::Database)?,
+        created_at: row.try_get(6)?,
+        expires_at: row.try_get(7)?,
+        txid: row.try_get(8)?,
+        pool: row.try_get(9)?,
+        output_index: row.try_get::<_, Option<i64>>(10)?.map(|index| index as u32),
+        received_amount: row.try_get::<_, Option<i64>>(11)?.map(|amount| amount as u64),
+        received_at: row.try_get(12)?,
+    })
+}
+
+impl Intents for PostgresStore {
+    fn create(
+        &self,
+        recipient_hash: &str,
+        account_id: Option<&str>,
+        secret: Option<&str>,
+        expected_amount: Option<u64>,
+        ttl_secs: i64,
+    ) -> BridgeResult<DepositIntent> {
+        let created_at = now_secs();
+        let expires_at = created_at + ttl_secs;
+
+        // Codes are short, so retry on the (unlikely) collision with an existing one
+        loop {
+            let code = generate_reference_code();
+            let inserted = self.execute(
+                "INSERT INTO deposit_intents
+                    (code, recipient_hash, account_id, secret, expected_amount, status, created_at, expires_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 ON CONFLICT (code) DO NOTHING",
+                &[
+                    &code,
+                    &recipient_hash,
+                    &account_id,
+                    &secret,
+                    &expected_amount.map(|amount| amount as i64),
+                    &IntentStatus::Pending.as_str(),
+                    &created_at,
+                    &expires_at,
+                ],
+            )?;
+
+            if inserted > 0 {
+                return Ok(DepositIntent {
+                    code,
+                    recipient_hash: recipient_hash.to_string(),
+                    account_id: account_id.map(str::to_string),
+                    secret: secret.map(str::to_string),
+                    expected_amount,
+                    status: IntentStatus::Pending,
+                    created_at,
+                    expires_at,
+                    txid: None,
+                    pool: None,
+                    output_index: None,
+                    received_amount: None,
+                    received_at: None,
+                });
+            }
+        }
+    }
+
+    fn get(&self, code: &str) -> BridgeResult<Option<DepositIntent>> {
+        self.query_opt(
+            &format!("SELECT {} FROM deposit_intents WHERE code = $1", INTENT_COLUMNS),
+            &[&code.trim().to_uppercase()],
+        )?
+        .map(|row| row_to_intent(&row))
+        .transpose()
+    }
+
+    fn codes_for_recipient(&self, recipient_hash: &str) -> BridgeResult<HashSet<String>> {
+        self.query(
+            "SELECT code FROM deposit_intents WHERE recipient_hash = $1",
+            &[&recipient_hash],
+        )?
+        .iter()
+        .map(|row| Ok(row.try_get(0)?))
+        .collect()
+    }
+
+    fn mark_received(&self, code: &str, output: &DepositOutput) -> BridgeResult<Option<IntentStatus>> {
+        let Some(intent) = self.get(code)? else {
+            return Ok(None);
+        };
+
+        let status = match intent.expected_amount {
+            Some(expected) if expected != output.value => IntentStatus::AmountMismatch,
+            _ => IntentStatus::Received,
+        };
+
+        let updated = self.execute(
+            "UPDATE deposit_intents
+             SET status = $1, txid = $2, pool = $3, output_index = $4, received_amount = $5, received_at = $6
+             WHERE code = $7 AND status = $8",
+            &[
+                &status.as_str(),
+                &output.txid,
+                &output.pool,
+                &(output.index as i64),
+                &(output.value as i64),
+                &now_secs(),
+                &intent.code,
+                &IntentStatus::Pending.as_str(),
+            ],
+        )?;
+
+        Ok((updated > 0).then_some(status))
+    }
+
+    fn expire_stale(&self) -> BridgeResult<usize> {
+        let expired = self.execute(
+            "UPDATE deposit_intents SET status = $1 WHERE status = $2 AND expires_at < $3",
+            &[&IntentStatus::Expired.as_str(), &IntentStatus::Pending.as_str(), &now_secs()],
+        )?;
+
+        Ok(expired as usize)
+    }
+}
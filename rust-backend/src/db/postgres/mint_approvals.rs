@@ -0,0 +1,125 @@
+use super::PostgresStore;
+use crate::db::mint_approvals::{MintApprovals, MintProposal, MintSignature, ProposalStatus, PROPOSAL_COLUMNS};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_proposal(row: &Row) -> BridgeResult<MintProposal> {
+    let status: String = row.try_get(6)?;
+
+    Ok(MintProposal {
+        deposit: row.try_get(0)?,
+        recipient_hash: row.try_get(1)?,
+        amount: row.try_get::<_, i64>(2)? as u64,
+        net_amount: row.try_get::<_, i64>(3)? as u64,
+        message: row.try_get(4)?,
+        proposer: row.try_get(5)?,
+        status: ProposalStatus::parse(&status).map_err(BridgeError::Database)?,
+        tx_id: row.try_get(7)?,
+        detail: row.try_get(8)?,
+        created_at: row.try_get(9)?,
+    })
+}
+
+impl MintApprovals for PostgresStore {
+    fn propose(&self, proposal: &MintProposal) -> BridgeResult<bool> {
+        self.execute(
+            "DELETE FROM mint_signatures WHERE deposit = $1
+             AND EXISTS (SELECT 1 FROM mint_proposals WHERE deposit = $1 AND status = 'failed')",
+            &[&proposal.deposit],
+        )?;
+
+        let inserted = self.execute(
+            "INSERT INTO mint_proposals (deposit, recipient_hash, amount, net_amount, message, proposer, status, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7)
+             ON CONFLICT (deposit) DO UPDATE SET
+                 recipient_hash = excluded.recipient_hash, amount = excluded.amount,
+                 net_amount = excluded.net_amount, message = excluded.message,
+                 proposer = excluded.proposer, status = 'pending', tx_id = NULL, detail = NULL,
+                 created_at = excluded.created_at
+             WHERE mint_proposals.status = 'failed'",
+            &[
+                &proposal.deposit,
+                &proposal.recipient_hash,
+                &(proposal.amount as i64),
+                &(proposal.net_amount as i64),
+                &proposal.message,
+                &proposal.proposer,
+                &now_secs(),
+            ],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    fn get(&self, deposit: &str) -> BridgeResult<Option<MintProposal>> {
+        self.query_opt(
+            &format!("SELECT {} FROM mint_proposals WHERE deposit = $1", PROPOSAL_COLUMNS),
+            &[&deposit],
+        )?
+        .map(|row| row_to_proposal(&row))
+        .transpose()
+    }
+
+    fn pending(&self) -> BridgeResult<Vec<MintProposal>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM mint_proposals WHERE status = 'pending' ORDER BY created_at, deposit",
+                PROPOSAL_COLUMNS
+            ),
+            &[],
+        )?
+        .iter()
+        .map(row_to_proposal)
+        .collect()
+    }
+
+    fn add_signature(&self, deposit: &str, signer: &str, signature: &str) -> BridgeResult<bool> {
+        let inserted = self.execute(
+            "INSERT INTO mint_signatures (deposit, signer, signature, signed_at)
+             SELECT $1, $2, $3, $4 WHERE EXISTS
+                 (SELECT 1 FROM mint_proposals WHERE deposit = $1 AND status = 'pending')
+             ON CONFLICT DO NOTHING",
+            &[&deposit, &signer, &signature, &now_secs()],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn signatures(&self, deposit: &str) -> BridgeResult<Vec<MintSignature>> {
+        self.query(
+            "SELECT signer, signature, signed_at FROM mint_signatures WHERE deposit = $1 ORDER BY signed_at, signer",
+            &[&deposit],
+        )?
+        .iter()
+        .map(|row| {
+            Ok(MintSignature {
+                signer: row.try_get(0)?,
+                signature: row.try_get(1)?,
+                signed_at: row.try_get(2)?,
+            })
+        })
+        .collect()
+    }
+
+    fn start_submission(&self, deposit: &str) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE mint_proposals SET status = 'submitting' WHERE deposit = $1 AND status = 'pending'",
+            &[&deposit],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn finish(&self, deposit: &str, status: ProposalStatus, tx_id: Option<&str>, detail: Option<&str>) -> BridgeResult<()> {
+        self.execute(
+            "UPDATE mint_proposals SET status = $2, tx_id = $3, detail = $4 WHERE deposit = $1",
+            &[&deposit, &status.as_str(), &tx_id, &detail],
+        )?;
+        Ok(())
+    }
+}
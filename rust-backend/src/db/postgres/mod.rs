@@ -0,0 +1,476 @@
+//! Postgres backend, for running several API replicas against one database
+//!
+//! Built with `--features postgres` and selected by setting BRIDGE_DATABASE_URL.
+//! The schema mirrors bridge.db, with its own migration history.
+
+mod audit;
+mod deposit_addresses;
+mod deposits;
+mod events;
+mod faucets;
+mod fees;
+mod intents;
+mod mint_approvals;
+mod payouts;
+mod refunds;
+mod webhooks;
+mod withdrawals;
+
+use super::audit::Audit;
+use super::deposit_addresses::DepositAddresses;
+use super::deposits::Deposits;
+use super::events::Events;
+use super::faucets::Faucets;
+use super::fees::Fees;
+use super::intents::Intents;
+use super::mint_approvals::MintApprovals;
+use super::payouts::Payouts;
+use super::refunds::Refunds;
+use super::webhooks::Webhooks;
+use super::withdrawals::Withdrawals;
+use super::BridgeStore;
+use crate::error::{BridgeError, BridgeResult};
+use postgres::types::ToSql;
+use postgres::{Client, NoTls, Row};
+use std::cell::{Cell, RefCell, RefMut};
+use tracing::info;
+
+type Params<'p> = &'p [&'p (dyn ToSql + Sync)];
+
+/// All migrations, in order. Append new ones, never edit or reorder applied ones.
+const MIGRATIONS: &[(u32, &str, &str)] = &[
+    (1, "initial_schema", INITIAL_SCHEMA),
+    (2, "audit_log", AUDIT_LOG),
+    (3, "mint_approvals", MINT_APPROVALS),
+    (4, "payouts", PAYOUTS),
+    (5, "deposit_claim_reservations", DEPOSIT_CLAIM_RESERVATIONS),
+    (6, "accounting_indexes", ACCOUNTING_INDEXES),
+];
+
+const INITIAL_SCHEMA: &str = "
+    -- NOTE: We only store recipient_hash for privacy - we don't store account_id
+    CREATE TABLE deposits (
+        recipient_hash TEXT PRIMARY KEY,
+        txid TEXT NOT NULL,
+        amount BIGINT NOT NULL,
+        claimed_at BIGINT NOT NULL,
+        pool TEXT,
+        output_index BIGINT
+    );
+    CREATE INDEX idx_deposits_txid ON deposits(txid);
+    -- Each output of a transaction can only be claimed once
+    CREATE UNIQUE INDEX idx_deposits_output ON deposits(txid, pool, output_index);
+
+    -- commitment = hash(secret); the secret is never stored
+    CREATE TABLE withdrawals (
+        commitment TEXT PRIMARY KEY,
+        note_id TEXT UNIQUE NOT NULL,
+        amount BIGINT NOT NULL,
+        block_number BIGINT NOT NULL,
+        created_at BIGINT NOT NULL,
+        claimed_at BIGINT,
+        zcash_txid TEXT
+    );
+    CREATE INDEX idx_withdrawals_claimed ON withdrawals(claimed_at);
+
+    CREATE TABLE faucets (
+        origin_network TEXT PRIMARY KEY,
+        faucet_id BYTEA NOT NULL,
+        created_at BIGINT NOT NULL
+    );
+
+    -- reference = Zcash output key (txid:pool:index) for deposits, commitment or note_id for withdrawals
+    CREATE TABLE fee_accruals (
+        id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+        direction TEXT NOT NULL,
+        token TEXT NOT NULL,
+        reference TEXT NOT NULL,
+        gross_amount BIGINT NOT NULL,
+        fee BIGINT NOT NULL,
+        net_amount BIGINT NOT NULL,
+        accrued_at BIGINT NOT NULL,
+        UNIQUE (direction, reference)
+    );
+
+    -- One rejected output per row; memos and secrets are only kept as digests
+    CREATE TABLE rejected_deposits (
+        txid TEXT NOT NULL,
+        pool TEXT NOT NULL,
+        output_index BIGINT NOT NULL,
+        amount BIGINT NOT NULL,
+        reason TEXT NOT NULL,
+        memo_digest TEXT,
+        secret_digest TEXT,
+        memo_refund_address TEXT,
+        refund_address TEXT,
+        status TEXT NOT NULL,
+        rejected_at BIGINT NOT NULL,
+        requested_at BIGINT,
+        approved_at BIGINT,
+        refund_txid TEXT,
+        refunded_at BIGINT,
+        PRIMARY KEY (txid, pool, output_index)
+    );
+    CREATE INDEX idx_rejected_deposits_status ON rejected_deposits(status);
+
+    -- account_id and secret are only stored if the user asked for automatic minting
+    CREATE TABLE deposit_addresses (
+        address TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        account_id TEXT,
+        secret TEXT,
+        created_at BIGINT NOT NULL
+    );
+    CREATE INDEX idx_deposit_addresses_recipient ON deposit_addresses(recipient_hash);
+
+    CREATE TABLE deposit_intents (
+        code TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        account_id TEXT,
+        secret TEXT,
+        expected_amount BIGINT,
+        status TEXT NOT NULL,
+        created_at BIGINT NOT NULL,
+        expires_at BIGINT NOT NULL,
+        txid TEXT,
+        pool TEXT,
+        output_index BIGINT,
+        received_amount BIGINT,
+        received_at BIGINT
+    );
+    CREATE INDEX idx_deposit_intents_recipient ON deposit_intents(recipient_hash);
+
+    -- Append-only: one row per deposit or withdrawal state change
+    CREATE TABLE events (
+        id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+        subject TEXT NOT NULL,
+        state TEXT NOT NULL,
+        event_key TEXT NOT NULL,
+        txid TEXT,
+        recipient_hash TEXT,
+        note_id TEXT,
+        detail TEXT,
+        created_at BIGINT NOT NULL
+    );
+    CREATE INDEX idx_events_key ON events(subject, event_key);
+    CREATE INDEX idx_events_txid ON events(txid);
+    CREATE INDEX idx_events_recipient_hash ON events(recipient_hash);
+    CREATE INDEX idx_events_note_id ON events(note_id);
+
+    -- Each event is delivered at most once per subscription
+    CREATE TABLE webhook_deliveries (
+        id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+        subscription TEXT NOT NULL,
+        event_id BIGINT NOT NULL,
+        event_type TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        status TEXT NOT NULL,
+        attempts BIGINT NOT NULL DEFAULT 0,
+        next_attempt_at BIGINT NOT NULL,
+        last_error TEXT,
+        created_at BIGINT NOT NULL,
+        delivered_at BIGINT,
+        UNIQUE (subscription, event_id)
+    );
+    CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+
+    -- Last event log id that was turned into webhook deliveries
+    CREATE TABLE webhook_cursor (
+        id BIGINT PRIMARY KEY CHECK (id = 1),
+        last_event_id BIGINT NOT NULL
+    );
+";
+
+const AUDIT_LOG: &str = "
+    -- Hash-chained ledger of bridge actions; see db::audit
+    CREATE TABLE audit_log (
+        id BIGINT PRIMARY KEY,
+        action TEXT NOT NULL,
+        inputs_digest TEXT NOT NULL,
+        result TEXT,
+        actor TEXT NOT NULL,
+        created_at BIGINT NOT NULL,
+        prev_hash TEXT NOT NULL,
+        hash TEXT NOT NULL UNIQUE
+    );
+    CREATE FUNCTION audit_log_append_only() RETURNS trigger LANGUAGE plpgsql AS $$
+    BEGIN
+        RAISE EXCEPTION 'audit_log is append-only';
+    END
+    $$;
+    CREATE TRIGGER audit_log_no_change BEFORE UPDATE OR DELETE ON audit_log
+        FOR EACH ROW EXECUTE FUNCTION audit_log_append_only();
+    CREATE TRIGGER audit_log_no_truncate BEFORE TRUNCATE ON audit_log
+        FOR EACH STATEMENT EXECUTE FUNCTION audit_log_append_only();
+";
+
+const MINT_APPROVALS: &str = "
+    -- Multisig mints: one proposal per deposit output, signed by the approvers; see db::mint_approvals
+    CREATE TABLE mint_proposals (
+        deposit TEXT PRIMARY KEY,
+        recipient_hash TEXT NOT NULL,
+        amount BIGINT NOT NULL,
+        net_amount BIGINT NOT NULL,
+        message TEXT NOT NULL,
+        proposer TEXT NOT NULL,
+        status TEXT NOT NULL,
+        tx_id TEXT,
+        detail TEXT,
+        created_at BIGINT NOT NULL
+    );
+    CREATE INDEX idx_mint_proposals_status ON mint_proposals(status, created_at);
+
+    CREATE TABLE mint_signatures (
+        deposit TEXT NOT NULL REFERENCES mint_proposals(deposit),
+        signer TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        signed_at BIGINT NOT NULL,
+        PRIMARY KEY (deposit, signer)
+    );
+";
+
+const PAYOUTS: &str = "
+    -- Zcash payouts signed offline: one row per withdrawal or exit, with its PCZT; see db::payouts
+    CREATE TABLE payouts (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        note_id TEXT NOT NULL,
+        zcash_address TEXT NOT NULL,
+        amount BIGINT NOT NULL,
+        fee BIGINT NOT NULL,
+        net_amount BIGINT NOT NULL,
+        status TEXT NOT NULL,
+        pczt TEXT NOT NULL,
+        signed_pczt TEXT,
+        txid TEXT,
+        detail TEXT,
+        created_at BIGINT NOT NULL,
+        updated_at BIGINT NOT NULL
+    );
+    CREATE INDEX idx_payouts_status ON payouts(status, created_at);
+";
+
+const DEPOSIT_CLAIM_RESERVATIONS: &str = "
+    -- pending: reserved by a mint in progress; claimed: note minted. See Deposits::reserve_claim
+    ALTER TABLE deposits ADD COLUMN status TEXT NOT NULL DEFAULT 'claimed';
+";
+
+const ACCOUNTING_INDEXES: &str = "
+    -- Date ranges of the accounting export. See bridge::accounting::export
+    CREATE INDEX idx_deposits_claimed_at ON deposits(claimed_at);
+    CREATE INDEX idx_withdrawals_created_at ON withdrawals(created_at);
+    CREATE INDEX idx_fee_accruals_accrued_at ON fee_accruals(accrued_at);
+    CREATE INDEX idx_events_created_at ON events(created_at);
+";
+
+/// Key of the advisory lock replicas take while migrating, so only one of them applies each step
+const MIGRATION_LOCK: i64 = 0x6272_6964_6765; // "bridge"
+
+impl From<postgres::Error> for BridgeError {
+    fn from(e: postgres::Error) -> Self {
+        BridgeError::Database(e.to_string())
+    }
+}
+
+// The postgres client drives a runtime of its own, which tokio refuses to start from an
+// async task, so step out of the task while a query runs
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::task::block_in_place(f)
+    } else {
+        f()
+    }
+}
+
+pub struct PostgresStore {
+    // Only None while dropping
+    client: RefCell<Option<Client>>,
+    in_transaction: Cell<bool>,
+}
+
+impl PostgresStore {
+    /// Connect to BRIDGE_DATABASE_URL, applying pending migrations
+    pub fn connect(url: &str) -> BridgeResult<Self> {
+        let client = blocking(|| Client::connect(url, NoTls))
+            .map_err(|e| BridgeError::from(e).context("Failed to connect to Postgres"))?;
+
+        let store = Self {
+            client: RefCell::new(Some(client)),
+            in_transaction: Cell::new(false),
+        };
+        store.migrate()?;
+
+        Ok(store)
+    }
+
+    // All pending migrations go in one transaction; Postgres DDL is transactional
+    fn migrate(&self) -> BridgeResult<()> {
+        self.transaction(|| {
+            self.query("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK])?;
+            self.batch_execute(
+                "CREATE TABLE IF NOT EXISTS schema_version (
+                    version BIGINT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    applied_at BIGINT NOT NULL
+                )",
+            )?;
+
+            let applied = self
+                .query("SELECT version, name FROM schema_version ORDER BY version", &[])?
+                .iter()
+                .map(|row| Ok((row.try_get::<_, i64>(0)? as u32, row.try_get::<_, String>(1)?)))
+                .collect::<BridgeResult<Vec<_>>>()?;
+
+            let latest = MIGRATIONS.last().map(|(version, _, _)| *version).unwrap_or(0);
+            for (version, name) in &applied {
+                match MIGRATIONS.iter().find(|(v, _, _)| v == version) {
+                    Some((_, expected, _)) if expected == name => {}
+                    Some((_, expected, _)) => {
+                        return Err(BridgeError::Database(format!(
+                            "Postgres migration {} is '{}' but this build expects '{}'",
+                            version, name, expected
+                        )));
+                    }
+                    None => {
+                        return Err(BridgeError::Database(format!(
+                            "Postgres database is at schema version {} but this build only knows up to {}; upgrade the bridge",
+                            version, latest
+                        )));
+                    }
+                }
+            }
+
+            for (version, name, sql) in MIGRATIONS {
+                if applied.iter().any(|(v, _)| v == version) {
+                    continue;
+                }
+
+                self.batch_execute(sql)
+                    .map_err(|e| e.context(&format!("Migration {} ({}) failed", version, name)))?;
+                self.execute(
+                    "INSERT INTO schema_version (version, name, applied_at) VALUES ($1, $2, $3)",
+                    &[&(*version as i64), name, &chrono::Utc::now().timestamp()],
+                )?;
+
+                info!(version, name, "Applied Postgres bridge database migration");
+            }
+
+            Ok(())
+        })
+    }
+
+    fn transaction<T>(&self, f: impl FnOnce() -> BridgeResult<T>) -> BridgeResult<T> {
+        if self.in_transaction.get() {
+            return f();
+        }
+
+        self.batch_execute("BEGIN")?;
+        self.in_transaction.set(true);
+        let result = f();
+        self.in_transaction.set(false);
+
+        match result {
+            Ok(value) => {
+                self.batch_execute("COMMIT")?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.batch_execute("ROLLBACK")?;
+                Err(e)
+            }
+        }
+    }
+
+    fn client(&self) -> RefMut<'_, Client> {
+        RefMut::map(self.client.borrow_mut(), |client| client.as_mut().expect("Postgres client is open"))
+    }
+
+    fn batch_execute(&self, sql: &str) -> BridgeResult<()> {
+        Ok(blocking(|| self.client().batch_execute(sql))?)
+    }
+
+    fn execute(&self, sql: &str, params: Params) -> BridgeResult<u64> {
+        Ok(blocking(|| self.client().execute(sql, params))?)
+    }
+
+    fn query(&self, sql: &str, params: Params) -> BridgeResult<Vec<Row>> {
+        Ok(blocking(|| self.client().query(sql, params))?)
+    }
+
+    fn query_opt(&self, sql: &str, params: Params) -> BridgeResult<Option<Row>> {
+        Ok(blocking(|| self.client().query_opt(sql, params))?)
+    }
+}
+
+// Closing the connection blocks on the client's runtime too
+impl Drop for PostgresStore {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.get_mut().take() {
+            blocking(|| drop(client));
+        }
+    }
+}
+
+impl BridgeStore for PostgresStore {
+    fn backend(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn schema_version(&self) -> BridgeResult<u32> {
+        let row = self.query_opt("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])?;
+        let version: i64 = row.map(|row| row.try_get(0)).transpose()?.unwrap_or(0);
+        Ok(version as u32)
+    }
+
+    fn in_transaction(&self, f: &mut dyn FnMut(&dyn BridgeStore) -> BridgeResult<()>) -> BridgeResult<()> {
+        self.transaction(|| f(self))
+    }
+
+    fn deposits(&self) -> &dyn Deposits {
+        self
+    }
+
+    fn withdrawals(&self) -> &dyn Withdrawals {
+        self
+    }
+
+    fn faucets(&self) -> &dyn Faucets {
+        self
+    }
+
+    fn fees(&self) -> &dyn Fees {
+        self
+    }
+
+    fn refunds(&self) -> &dyn Refunds {
+        self
+    }
+
+    fn deposit_addresses(&self) -> &dyn DepositAddresses {
+        self
+    }
+
+    fn intents(&self) -> &dyn Intents {
+        self
+    }
+
+    fn events(&self) -> &dyn Events {
+        self
+    }
+
+    fn webhooks(&self) -> &dyn Webhooks {
+        self
+    }
+
+    fn audit(&self) -> &dyn Audit {
+        self
+    }
+
+    fn mint_approvals(&self) -> &dyn MintApprovals {
+        self
+    }
+
+    fn payouts(&self) -> &dyn Payouts {
+        self
+    }
+}
@@ -0,0 +1,136 @@
+use super::PostgresStore;
+use crate::db::payouts::{Payout, PayoutKind, PayoutStatus, Payouts, PAYOUT_COLUMNS};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+/// Key of the advisory lock held while checking that no other payout's PCZT is out
+const PAYOUT_BUILD_LOCK: i64 = 0x7063_7a74; // "pczt"
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_payout(row: &Row) -> BridgeResult<Payout> {
+    let kind: String = row.try_get(1)?;
+    let status: String = row.try_get(7)?;
+
+    Ok(Payout {
+        id: row.try_get(0)?,
+        kind: PayoutKind::parse(&kind).map_err(BridgeError::Database)?,
+        note_id: row.try_get(2)?,
+        zcash_address: row.try_get(3)?,
+        amount: row.try_get::<_, i64>(4)? as u64,
+        fee: row.try_get::<_, i64>(5)? as u64,
+        net_amount: row.try_get::<_, i64>(6)? as u64,
+        status: PayoutStatus::parse(&status).map_err(BridgeError::Database)?,
+        pczt: row.try_get(8)?,
+        signed_pczt: row.try_get(9)?,
+        txid: row.try_get(10)?,
+        detail: row.try_get(11)?,
+        created_at: row.try_get(12)?,
+        updated_at: row.try_get(13)?,
+    })
+}
+
+impl Payouts for PostgresStore {
+    fn queue(&self, payout: &Payout) -> BridgeResult<bool> {
+        let inserted = self.execute(
+            "INSERT INTO payouts (id, kind, note_id, zcash_address, amount, fee, net_amount, status, pczt, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $10)
+             ON CONFLICT DO NOTHING",
+            &[
+                &payout.id,
+                &payout.kind.as_str(),
+                &payout.note_id,
+                &payout.zcash_address,
+                &(payout.amount as i64),
+                &(payout.fee as i64),
+                &(payout.net_amount as i64),
+                &payout.status.as_str(),
+                &payout.pczt,
+                &now_secs(),
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn get(&self, id: &str) -> BridgeResult<Option<Payout>> {
+        self.query_opt(
+            &format!("SELECT {} FROM payouts WHERE id = $1", PAYOUT_COLUMNS),
+            &[&id],
+        )?
+        .map(|row| row_to_payout(&row))
+        .transpose()
+    }
+
+    fn with_status(&self, status: PayoutStatus) -> BridgeResult<Vec<Payout>> {
+        self.query(
+            &format!("SELECT {} FROM payouts WHERE status = $1 ORDER BY created_at, id", PAYOUT_COLUMNS),
+            &[&status.as_str()],
+        )?
+        .iter()
+        .map(row_to_payout)
+        .collect()
+    }
+
+    fn list(&self) -> BridgeResult<Vec<Payout>> {
+        self.query(&format!("SELECT {} FROM payouts ORDER BY created_at, id", PAYOUT_COLUMNS), &[])?
+            .iter()
+            .map(row_to_payout)
+            .collect()
+    }
+
+    fn attach_signed(&self, id: &str, signed_pczt: &str) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE payouts SET status = 'signed', signed_pczt = $2, updated_at = $3
+             WHERE id = $1 AND status = 'unsigned'",
+            &[&id, &signed_pczt, &now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn start_broadcast(&self, id: &str) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE payouts SET status = 'broadcasting', updated_at = $2 WHERE id = $1 AND status = 'signed'",
+            &[&id, &now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn finish(&self, id: &str, status: PayoutStatus, txid: Option<&str>, detail: Option<&str>) -> BridgeResult<()> {
+        self.execute(
+            "UPDATE payouts SET status = $2, txid = $3, detail = $4, updated_at = $5 WHERE id = $1",
+            &[&id, &status.as_str(), &txid, &detail, &now_secs()],
+        )?;
+        Ok(())
+    }
+
+    fn start_build(&self, id: &str) -> BridgeResult<bool> {
+        // Under read committed two builds could both pass the check, so they take turns
+        self.transaction(|| {
+            self.query("SELECT pg_advisory_xact_lock($1)", &[&PAYOUT_BUILD_LOCK])?;
+            let updated = self.execute(
+                "UPDATE payouts SET status = 'building', updated_at = $2
+                 WHERE id = $1 AND status IN ('queued', 'failed')
+                   AND NOT EXISTS (
+                       SELECT 1 FROM payouts WHERE status IN ('building', 'unsigned', 'signed', 'broadcasting')
+                   )",
+                &[&id, &now_secs()],
+            )?;
+            Ok(updated > 0)
+        })
+    }
+
+    fn attach_pczt(&self, id: &str, pczt: &str) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE payouts SET status = 'unsigned', pczt = $2, signed_pczt = NULL, txid = NULL, detail = NULL,
+                 updated_at = $3
+             WHERE id = $1 AND status = 'building'",
+            &[&id, &pczt, &now_secs()],
+        )?;
+        Ok(updated > 0)
+    }
+}
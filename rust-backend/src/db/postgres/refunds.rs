@@ -0,0 +1,189 @@
+use super::PostgresStore;
+use crate::db::refunds::{REJECTED_COLUMNS, RefundStatus, Refunds, RejectedDeposit};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_rejected(row: &Row) -> BridgeResult<RejectedDeposit> {
+    let status: String = row.try_get(9)?;
+
+    Ok(RejectedDeposit {
+        txid: row.try_get(0)?,
+        pool: row.try_get(1)?,
+        output_index: row.try_get::<_, i64>(2)? as u32,
+        amount: row.try_get::<_, i64>(3)? as u64,
+        reason: row.try_get(4)?,
+        memo_digest: row.try_get(5)?,
+        secret_digest: row.try_get(6)?,
+        memo_refund_address: row.try_get(7)?,
+        refund_address: row.try_get(8)?,
+        status: RefundStatus::parse(&status).map_err(BridgeError::Database)?,
+        rejected_at: row.try_get(10)?,
+        requested_at: row.try_get(11)?,
+        approved_at: row.try_get(12)?,
+        refund_txid: row.try_get(13)?,
+        refunded_at: row.try_get(14)?,
+    })
+}
+
+impl Refunds for PostgresStore {
+    fn record_rejection(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+        reason: &str,
+        memo_digest: Option<&str>,
+        secret_digest: Option<&str>,
+        memo_refund_address: Option<&str>,
+    ) -> BridgeResult<()> {
+        self.execute(
+            "INSERT INTO rejected_deposits
+                (txid, pool, output_index, amount, reason, memo_digest, secret_digest, memo_refund_address, status, rejected_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (txid, pool, output_index) DO NOTHING",
+            &[
+                &txid,
+                &pool,
+                &(output_index as i64),
+                &(amount as i64),
+                &reason,
+                &memo_digest,
+                &secret_digest,
+                &memo_refund_address,
+                &RefundStatus::Rejected.as_str(),
+                &now_secs(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn is_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let row = self.query_opt(
+            "SELECT 1 FROM rejected_deposits WHERE txid = $1 AND pool = $2 AND output_index = $3 LIMIT 1",
+            &[&txid, &pool, &(output_index as i64)],
+        )?;
+        Ok(row.is_some())
+    }
+
+    fn get_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<Option<RejectedDeposit>> {
+        self.query_opt(
+            &format!(
+                "SELECT {} FROM rejected_deposits WHERE txid = $1 AND pool = $2 AND output_index = $3",
+                REJECTED_COLUMNS
+            ),
+            &[&txid, &pool, &(output_index as i64)],
+        )?
+        .map(|row| row_to_rejected(&row))
+        .transpose()
+    }
+
+    fn get_rejected_for_tx(&self, txid: &str) -> BridgeResult<Vec<RejectedDeposit>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM rejected_deposits WHERE txid = $1 ORDER BY pool, output_index",
+                REJECTED_COLUMNS
+            ),
+            &[&txid],
+        )?
+        .iter()
+        .map(row_to_rejected)
+        .collect()
+    }
+
+    fn list(&self, status: Option<RefundStatus>) -> BridgeResult<Vec<RejectedDeposit>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM rejected_deposits
+                 WHERE $1::TEXT IS NULL OR status = $1
+                 ORDER BY rejected_at",
+                REJECTED_COLUMNS
+            ),
+            &[&status.map(|s| s.as_str())],
+        )?
+        .iter()
+        .map(row_to_rejected)
+        .collect()
+    }
+
+    fn request_refund(&self, txid: &str, pool: &str, output_index: u32, refund_address: &str) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE rejected_deposits
+             SET refund_address = $1, status = $2, requested_at = $3
+             WHERE txid = $4 AND pool = $5 AND output_index = $6 AND status IN ($7, $2)",
+            &[
+                &refund_address,
+                &RefundStatus::Requested.as_str(),
+                &now_secs(),
+                &txid,
+                &pool,
+                &(output_index as i64),
+                &RefundStatus::Rejected.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn approve(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE rejected_deposits
+             SET status = $1, approved_at = $2
+             WHERE txid = $3 AND pool = $4 AND output_index = $5 AND status = $6",
+            &[
+                &RefundStatus::Approved.as_str(),
+                &now_secs(),
+                &txid,
+                &pool,
+                &(output_index as i64),
+                &RefundStatus::Requested.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn mark_refunded(&self, txid: &str, pool: &str, output_index: u32, refund_txid: &str) -> BridgeResult<()> {
+        self.execute(
+            "UPDATE rejected_deposits
+             SET status = $1, refund_txid = $2, refunded_at = $3
+             WHERE txid = $4 AND pool = $5 AND output_index = $6 AND status = $7",
+            &[
+                &RefundStatus::Refunded.as_str(),
+                &refund_txid,
+                &now_secs(),
+                &txid,
+                &pool,
+                &(output_index as i64),
+                &RefundStatus::Approved.as_str(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn mark_unrefundable(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE rejected_deposits
+             SET status = $1
+             WHERE txid = $2 AND pool = $3 AND output_index = $4 AND status = $5",
+            &[
+                &RefundStatus::Unrefundable.as_str(),
+                &txid,
+                &pool,
+                &(output_index as i64),
+                &RefundStatus::Approved.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+}
@@ -0,0 +1,122 @@
+use super::PostgresStore;
+use crate::db::webhooks::{DELIVERY_COLUMNS, DeliveryStatus, WebhookDelivery, Webhooks};
+use crate::error::{BridgeError, BridgeResult};
+use postgres::Row;
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_delivery(row: &Row) -> BridgeResult<WebhookDelivery> {
+    let status: String = row.try_get(5)?;
+
+    Ok(WebhookDelivery {
+        id: row.try_get(0)?,
+        subscription: row.try_get(1)?,
+        event_id: row.try_get(2)?,
+        event_type: row.try_get(3)?,
+        payload: row.try_get(4)?,
+        status: DeliveryStatus::parse(&status).map_err(BridgeError::Database)?,
+        attempts: row.try_get::<_, i64>(6)? as u32,
+        next_attempt_at: row.try_get(7)?,
+        last_error: row.try_get(8)?,
+        created_at: row.try_get(9)?,
+        delivered_at: row.try_get(10)?,
+    })
+}
+
+impl Webhooks for PostgresStore {
+    fn last_event_id(&self) -> BridgeResult<i64> {
+        let rows = self.query("SELECT COALESCE(MAX(last_event_id), 0) FROM webhook_cursor", &[])?;
+        Ok(rows[0].try_get(0)?)
+    }
+
+    fn advance_cursor(&self, event_id: i64) -> BridgeResult<()> {
+        self.execute(
+            "INSERT INTO webhook_cursor (id, last_event_id) VALUES (1, $1)
+             ON CONFLICT (id) DO UPDATE
+             SET last_event_id = GREATEST(webhook_cursor.last_event_id, excluded.last_event_id)",
+            &[&event_id],
+        )?;
+        Ok(())
+    }
+
+    fn enqueue(&self, subscription: &str, event_id: i64, event_type: &str, payload: &str) -> BridgeResult<bool> {
+        let now = now_secs();
+        let inserted = self.execute(
+            "INSERT INTO webhook_deliveries
+                (subscription, event_id, event_type, payload, status, attempts, next_attempt_at, created_at)
+             VALUES ($1, $2, $3, $4, $5, 0, $6, $6)
+             ON CONFLICT (subscription, event_id) DO NOTHING",
+            &[&subscription, &event_id, &event_type, &payload, &DeliveryStatus::Pending.as_str(), &now],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn due(&self, limit: u32) -> BridgeResult<Vec<WebhookDelivery>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM webhook_deliveries
+                 WHERE status = $1 AND next_attempt_at <= $2
+                 ORDER BY next_attempt_at, id LIMIT $3",
+                DELIVERY_COLUMNS
+            ),
+            &[&DeliveryStatus::Pending.as_str(), &now_secs(), &(limit as i64)],
+        )?
+        .iter()
+        .map(row_to_delivery)
+        .collect()
+    }
+
+    fn mark_delivered(&self, id: i64) -> BridgeResult<()> {
+        self.execute(
+            "UPDATE webhook_deliveries
+             SET status = $1, attempts = attempts + 1, last_error = NULL, delivered_at = $2
+             WHERE id = $3",
+            &[&DeliveryStatus::Delivered.as_str(), &now_secs(), &id],
+        )?;
+        Ok(())
+    }
+
+    fn mark_failed(&self, id: i64, error: &str, retry_at: Option<i64>) -> BridgeResult<()> {
+        let status = match retry_at {
+            Some(_) => DeliveryStatus::Pending,
+            None => DeliveryStatus::Dead,
+        };
+
+        self.execute(
+            "UPDATE webhook_deliveries
+             SET status = $1, attempts = attempts + 1, last_error = $2, next_attempt_at = COALESCE($3, next_attempt_at)
+             WHERE id = $4",
+            &[&status.as_str(), &error, &retry_at, &id],
+        )?;
+        Ok(())
+    }
+
+    fn retry(&self, id: i64) -> BridgeResult<bool> {
+        let updated = self.execute(
+            "UPDATE webhook_deliveries SET status = $1, attempts = 0, next_attempt_at = $2
+             WHERE id = $3 AND status = $4",
+            &[&DeliveryStatus::Pending.as_str(), &now_secs(), &id, &DeliveryStatus::Dead.as_str()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn list(&self, status: Option<DeliveryStatus>, limit: u32) -> BridgeResult<Vec<WebhookDelivery>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM webhook_deliveries
+                 WHERE $1::TEXT IS NULL OR status = $1
+                 ORDER BY id DESC LIMIT $2",
+                DELIVERY_COLUMNS
+            ),
+            &[&status.map(|s| s.as_str()), &(limit as i64)],
+        )?
+        .iter()
+        .map(row_to_delivery)
+        .collect()
+    }
+}
@@ -0,0 +1,118 @@
+use super::PostgresStore;
+use crate::db::withdrawals::{WithdrawalRecord, Withdrawals};
+use crate::error::BridgeResult;
+use postgres::Row;
+
+const WITHDRAWAL_COLUMNS: &str = "commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_withdrawal(row: &Row) -> BridgeResult<WithdrawalRecord> {
+    Ok(WithdrawalRecord {
+        commitment: row.try_get(0)?,
+        note_id: row.try_get(1)?,
+        amount: row.try_get::<_, i64>(2)? as u64,
+        block_number: row.try_get::<_, i64>(3)? as u32,
+        created_at: row.try_get(4)?,
+        claimed_at: row.try_get(5)?,
+        zcash_txid: row.try_get(6)?,
+    })
+}
+
+impl Withdrawals for PostgresStore {
+    fn record_withdrawal(
+        &self,
+        commitment: &str,
+        note_id: &str,
+        amount: u64,
+        block_number: u32,
+    ) -> BridgeResult<bool> {
+        let inserted = self.execute(
+            "INSERT INTO withdrawals (commitment, note_id, amount, block_number, created_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT DO NOTHING",
+            &[&commitment, &note_id, &(amount as i64), &(block_number as i64), &now_secs()],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    fn get_withdrawal(&self, commitment: &str) -> BridgeResult<Option<WithdrawalRecord>> {
+        self.query_opt(
+            &format!("SELECT {} FROM withdrawals WHERE commitment = $1", WITHDRAWAL_COLUMNS),
+            &[&commitment],
+        )?
+        .map(|row| row_to_withdrawal(&row))
+        .transpose()
+    }
+
+    fn get_withdrawal_by_note_id(&self, note_id: &str) -> BridgeResult<Option<WithdrawalRecord>> {
+        self.query_opt(
+            &format!("SELECT {} FROM withdrawals WHERE note_id = $1", WITHDRAWAL_COLUMNS),
+            &[&note_id],
+        )?
+        .map(|row| row_to_withdrawal(&row))
+        .transpose()
+    }
+
+    fn mark_claimed(
+        &self,
+        commitment: &str,
+        zcash_txid: &str,
+    ) -> BridgeResult<()> {
+        self.execute(
+            "UPDATE withdrawals
+             SET claimed_at = $1, zcash_txid = $2
+             WHERE commitment = $3",
+            &[&now_secs(), &zcash_txid, &commitment],
+        )?;
+
+        Ok(())
+    }
+
+    fn is_claimed(&self, commitment: &str) -> BridgeResult<bool> {
+        let row = self.query_opt(
+            "SELECT 1 FROM withdrawals WHERE commitment = $1 AND claimed_at IS NOT NULL LIMIT 1",
+            &[&commitment],
+        )?;
+        Ok(row.is_some())
+    }
+
+    fn get_unclaimed_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>> {
+        self.query(
+            &format!("SELECT {} FROM withdrawals WHERE claimed_at IS NULL", WITHDRAWAL_COLUMNS),
+            &[],
+        )?
+        .iter()
+        .map(row_to_withdrawal)
+        .collect()
+    }
+
+    fn list_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>> {
+        self.query(
+            &format!("SELECT {} FROM withdrawals ORDER BY created_at", WITHDRAWAL_COLUMNS),
+            &[],
+        )?
+        .iter()
+        .map(row_to_withdrawal)
+        .collect()
+    }
+
+    fn list_withdrawals_between(&self, since: i64, until: i64) -> BridgeResult<Vec<WithdrawalRecord>> {
+        self.query(
+            &format!(
+                "SELECT {} FROM withdrawals WHERE created_at >= $1 AND created_at < $2 ORDER BY created_at",
+                WITHDRAWAL_COLUMNS
+            ),
+            &[&since, &until],
+        )?
+        .iter()
+        .map(row_to_withdrawal)
+        .collect()
+    }
+}
@@ -0,0 +1,312 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Lifecycle of a rejected deposit
+///
+/// rejected -> requested (user asked for a refund) -> approved (operator) -> refunded (TAZ sent),
+/// or unrefundable if the refund fee would take the whole deposit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundStatus {
+    Rejected,
+    Requested,
+    Approved,
+    Refunded,
+    Unrefundable,
+}
+
+impl RefundStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RefundStatus::Rejected => "rejected",
+            RefundStatus::Requested => "requested",
+            RefundStatus::Approved => "approved",
+            RefundStatus::Refunded => "refunded",
+            RefundStatus::Unrefundable => "unrefundable",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, String> {
+        match status {
+            "rejected" => Ok(RefundStatus::Rejected),
+            "requested" => Ok(RefundStatus::Requested),
+            "approved" => Ok(RefundStatus::Approved),
+            "refunded" => Ok(RefundStatus::Refunded),
+            "unrefundable" => Ok(RefundStatus::Unrefundable),
+            other => Err(format!("Unknown refund status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedDeposit {
+    pub txid: String,
+    pub pool: String,
+    pub output_index: u32,
+    pub amount: u64,
+    pub reason: String,
+    pub memo_digest: Option<String>, // knowledge_digest(memo) - proves the user sent the memo
+    pub secret_digest: Option<String>, // knowledge_digest(secret) - proves the user knows the secret
+    pub memo_refund_address: Option<String>, // refund address embedded in the memo
+    pub refund_address: Option<String>,
+    pub status: RefundStatus,
+    pub rejected_at: i64,
+    pub requested_at: Option<i64>,
+    pub approved_at: Option<i64>,
+    pub refund_txid: Option<String>,
+    pub refunded_at: Option<i64>,
+}
+
+pub(super) const REJECTED_COLUMNS: &str = "txid, pool, output_index, amount, reason, memo_digest, secret_digest,
+     memo_refund_address, refund_address, status, rejected_at, requested_at, approved_at, refund_txid, refunded_at";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_rejected(row: &rusqlite::Row) -> SqlResult<RejectedDeposit> {
+    let status: String = row.get(9)?;
+    let status = RefundStatus::parse(&status).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            9,
+            rusqlite::types::Type::Text,
+            e.into(),
+        )
+    })?;
+
+    Ok(RejectedDeposit {
+        txid: row.get(0)?,
+        pool: row.get(1)?,
+        output_index: row.get(2)?,
+        amount: row.get(3)?,
+        reason: row.get(4)?,
+        memo_digest: row.get(5)?,
+        secret_digest: row.get(6)?,
+        memo_refund_address: row.get(7)?,
+        refund_address: row.get(8)?,
+        status,
+        rejected_at: row.get(10)?,
+        requested_at: row.get(11)?,
+        approved_at: row.get(12)?,
+        refund_txid: row.get(13)?,
+        refunded_at: row.get(14)?,
+    })
+}
+
+/// Queue of deposits the relayer could not mint, and their refund state
+pub trait Refunds {
+    /// Put a deposit output in the rejected queue
+    fn record_rejection(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+        reason: &str,
+        memo_digest: Option<&str>,
+        secret_digest: Option<&str>,
+        memo_refund_address: Option<&str>,
+    ) -> BridgeResult<()>;
+
+    /// Check if a deposit output is already in the rejected queue
+    fn is_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool>;
+
+    /// Get rejected deposit output by (txid, pool, output_index)
+    fn get_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<Option<RejectedDeposit>>;
+
+    /// Get all rejected outputs of a transaction
+    fn get_rejected_for_tx(&self, txid: &str) -> BridgeResult<Vec<RejectedDeposit>>;
+
+    /// List rejected deposits, optionally filtered by status
+    fn list(&self, status: Option<RefundStatus>) -> BridgeResult<Vec<RejectedDeposit>>;
+
+    /// Record the user's refund request
+    ///
+    /// Returns false if the deposit is unknown or already approved/refunded.
+    fn request_refund(&self, txid: &str, pool: &str, output_index: u32, refund_address: &str) -> BridgeResult<bool>;
+
+    /// Operator approval of a requested refund
+    ///
+    /// Returns false if the refund was not in the requested state.
+    fn approve(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool>;
+
+    /// Mark an approved refund as sent
+    fn mark_refunded(&self, txid: &str, pool: &str, output_index: u32, refund_txid: &str) -> BridgeResult<()>;
+
+    /// Give up on an approved refund the refund fee would swallow
+    ///
+    /// Returns false if the refund was not in the approved state.
+    fn mark_unrefundable(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool>;
+}
+
+impl Refunds for SqliteStore {
+    fn record_rejection(
+        &self,
+        txid: &str,
+        pool: &str,
+        output_index: u32,
+        amount: u64,
+        reason: &str,
+        memo_digest: Option<&str>,
+        secret_digest: Option<&str>,
+        memo_refund_address: Option<&str>,
+    ) -> BridgeResult<()> {
+        self.conn.execute(
+            "INSERT INTO rejected_deposits
+                (txid, pool, output_index, amount, reason, memo_digest, secret_digest, memo_refund_address, status, rejected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(txid, pool, output_index) DO NOTHING",
+            rusqlite::params![
+                txid,
+                pool,
+                output_index,
+                amount,
+                reason,
+                memo_digest,
+                secret_digest,
+                memo_refund_address,
+                RefundStatus::Rejected.as_str(),
+                now_secs(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn is_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let mut stmt = self.conn.prepare(
+            "SELECT 1 FROM rejected_deposits WHERE txid = ?1 AND pool = ?2 AND output_index = ?3 LIMIT 1"
+        )?;
+
+        let exists = stmt.exists(rusqlite::params![txid, pool, output_index])?;
+        Ok(exists)
+    }
+
+    fn get_rejected(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<Option<RejectedDeposit>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM rejected_deposits WHERE txid = ?1 AND pool = ?2 AND output_index = ?3",
+            REJECTED_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query_map(rusqlite::params![txid, pool, output_index], row_to_rejected)?;
+
+        if let Some(row) = rows.next() {
+            Ok(Some(row?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_rejected_for_tx(&self, txid: &str) -> BridgeResult<Vec<RejectedDeposit>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM rejected_deposits WHERE txid = ?1 ORDER BY pool, output_index",
+            REJECTED_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([txid], row_to_rejected)?;
+
+        let mut deposits = Vec::new();
+        for row in rows {
+            deposits.push(row?);
+        }
+
+        Ok(deposits)
+    }
+
+    fn list(&self, status: Option<RefundStatus>) -> BridgeResult<Vec<RejectedDeposit>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM rejected_deposits
+             WHERE ?1 IS NULL OR status = ?1
+             ORDER BY rejected_at",
+            REJECTED_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([status.map(|s| s.as_str())], row_to_rejected)?;
+
+        let mut deposits = Vec::new();
+        for row in rows {
+            deposits.push(row?);
+        }
+
+        Ok(deposits)
+    }
+
+    fn request_refund(&self, txid: &str, pool: &str, output_index: u32, refund_address: &str) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE rejected_deposits
+             SET refund_address = ?1, status = ?2, requested_at = ?3
+             WHERE txid = ?4 AND pool = ?5 AND output_index = ?6 AND status IN (?7, ?2)",
+            rusqlite::params![
+                refund_address,
+                RefundStatus::Requested.as_str(),
+                now_secs(),
+                txid,
+                pool,
+                output_index,
+                RefundStatus::Rejected.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn approve(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE rejected_deposits
+             SET status = ?1, approved_at = ?2
+             WHERE txid = ?3 AND pool = ?4 AND output_index = ?5 AND status = ?6",
+            rusqlite::params![
+                RefundStatus::Approved.as_str(),
+                now_secs(),
+                txid,
+                pool,
+                output_index,
+                RefundStatus::Requested.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn mark_refunded(&self, txid: &str, pool: &str, output_index: u32, refund_txid: &str) -> BridgeResult<()> {
+        self.conn.execute(
+            "UPDATE rejected_deposits
+             SET status = ?1, refund_txid = ?2, refunded_at = ?3
+             WHERE txid = ?4 AND pool = ?5 AND output_index = ?6 AND status = ?7",
+            rusqlite::params![
+                RefundStatus::Refunded.as_str(),
+                refund_txid,
+                now_secs(),
+                txid,
+                pool,
+                output_index,
+                RefundStatus::Approved.as_str(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn mark_unrefundable(&self, txid: &str, pool: &str, output_index: u32) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE rejected_deposits
+             SET status = ?1
+             WHERE txid = ?2 AND pool = ?3 AND output_index = ?4 AND status = ?5",
+            rusqlite::params![
+                RefundStatus::Unrefundable.as_str(),
+                txid,
+                pool,
+                output_index,
+                RefundStatus::Approved.as_str(),
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+}
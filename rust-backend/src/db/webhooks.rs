@@ -0,0 +1,221 @@
+use super::SqliteStore;
+use crate::error::BridgeResult;
+use rusqlite::{Result as SqlResult};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of a webhook delivery
+///
+/// pending -> delivered | pending (retried with backoff) -> dead (gave up, can be retried by hand)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Dead,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Dead => "dead",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self, String> {
+        match status {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "delivered" => Ok(DeliveryStatus::Delivered),
+            "dead" => Ok(DeliveryStatus::Dead),
+            other => Err(format!("Unknown delivery status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub subscription: String,
+    pub event_id: i64,
+    pub event_type: String,
+    pub payload: String, // exact JSON body that gets signed and sent
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub delivered_at: Option<i64>,
+}
+
+pub(super) const DELIVERY_COLUMNS: &str = "id, subscription, event_id, event_type, payload, status, attempts,
+     next_attempt_at, last_error, created_at, delivered_at";
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn row_to_delivery(row: &rusqlite::Row) -> SqlResult<WebhookDelivery> {
+    let status: String = row.get(5)?;
+    let status = DeliveryStatus::parse(&status).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(
+            5,
+            rusqlite::types::Type::Text,
+            e.into(),
+        )
+    })?;
+
+    Ok(WebhookDelivery {
+        id: row.get(0)?,
+        subscription: row.get(1)?,
+        event_id: row.get(2)?,
+        event_type: row.get(3)?,
+        payload: row.get(4)?,
+        status,
+        attempts: row.get(6)?,
+        next_attempt_at: row.get(7)?,
+        last_error: row.get(8)?,
+        created_at: row.get(9)?,
+        delivered_at: row.get(10)?,
+    })
+}
+
+/// Outbox of webhook deliveries, one row per (subscription, event)
+pub trait Webhooks {
+    /// Last event id that was enqueued (0 if none)
+    fn last_event_id(&self) -> BridgeResult<i64>;
+
+    /// Remember that all events up to `event_id` were enqueued
+    fn advance_cursor(&self, event_id: i64) -> BridgeResult<()>;
+
+    /// Queue an event for a subscription, due immediately
+    ///
+    /// Returns false if the event was already queued for it.
+    fn enqueue(&self, subscription: &str, event_id: i64, event_type: &str, payload: &str) -> BridgeResult<bool>;
+
+    /// Pending deliveries whose next attempt is due, oldest first
+    fn due(&self, limit: u32) -> BridgeResult<Vec<WebhookDelivery>>;
+
+    fn mark_delivered(&self, id: i64) -> BridgeResult<()>;
+
+    /// Record a failed attempt, retrying at `retry_at` or moving the delivery to the dead-letter list if None
+    fn mark_failed(&self, id: i64, error: &str, retry_at: Option<i64>) -> BridgeResult<()>;
+
+    /// Put a dead delivery back in the queue, with a fresh set of attempts
+    ///
+    /// Returns false if the delivery is not in the dead-letter list.
+    fn retry(&self, id: i64) -> BridgeResult<bool>;
+
+    /// List deliveries, optionally filtered by status, newest first
+    fn list(&self, status: Option<DeliveryStatus>, limit: u32) -> BridgeResult<Vec<WebhookDelivery>>;
+}
+
+impl Webhooks for SqliteStore {
+    fn last_event_id(&self) -> BridgeResult<i64> {
+        let last = self.conn.query_row(
+            "SELECT COALESCE(MAX(last_event_id), 0) FROM webhook_cursor",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(last)
+    }
+
+    fn advance_cursor(&self, event_id: i64) -> BridgeResult<()> {
+        self.conn.execute(
+            "INSERT INTO webhook_cursor (id, last_event_id) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET last_event_id = MAX(last_event_id, excluded.last_event_id)",
+            rusqlite::params![event_id],
+        )?;
+        Ok(())
+    }
+
+    fn enqueue(&self, subscription: &str, event_id: i64, event_type: &str, payload: &str) -> BridgeResult<bool> {
+        let now = now_secs();
+        let inserted = self.conn.execute(
+            "INSERT INTO webhook_deliveries
+                (subscription, event_id, event_type, payload, status, attempts, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)
+             ON CONFLICT(subscription, event_id) DO NOTHING",
+            rusqlite::params![subscription, event_id, event_type, payload, DeliveryStatus::Pending.as_str(), now],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn due(&self, limit: u32) -> BridgeResult<Vec<WebhookDelivery>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM webhook_deliveries
+             WHERE status = ?1 AND next_attempt_at <= ?2
+             ORDER BY next_attempt_at, id LIMIT ?3",
+            DELIVERY_COLUMNS
+        ))?;
+
+        let deliveries = stmt
+            .query_map(
+                rusqlite::params![DeliveryStatus::Pending.as_str(), now_secs(), limit],
+                row_to_delivery,
+            )?
+            .collect::<SqlResult<Vec<_>>>()?;
+
+        Ok(deliveries)
+    }
+
+    fn mark_delivered(&self, id: i64) -> BridgeResult<()> {
+        self.conn.execute(
+            "UPDATE webhook_deliveries
+             SET status = ?1, attempts = attempts + 1, last_error = NULL, delivered_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![DeliveryStatus::Delivered.as_str(), now_secs(), id],
+        )?;
+        Ok(())
+    }
+
+    fn mark_failed(&self, id: i64, error: &str, retry_at: Option<i64>) -> BridgeResult<()> {
+        let status = match retry_at {
+            Some(_) => DeliveryStatus::Pending,
+            None => DeliveryStatus::Dead,
+        };
+
+        self.conn.execute(
+            "UPDATE webhook_deliveries
+             SET status = ?1, attempts = attempts + 1, last_error = ?2, next_attempt_at = COALESCE(?3, next_attempt_at)
+             WHERE id = ?4",
+            rusqlite::params![status.as_str(), error, retry_at, id],
+        )?;
+        Ok(())
+    }
+
+    fn retry(&self, id: i64) -> BridgeResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE webhook_deliveries SET status = ?1, attempts = 0, next_attempt_at = ?2
+             WHERE id = ?3 AND status = ?4",
+            rusqlite::params![DeliveryStatus::Pending.as_str(), now_secs(), id, DeliveryStatus::Dead.as_str()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn list(&self, status: Option<DeliveryStatus>, limit: u32) -> BridgeResult<Vec<WebhookDelivery>> {
+        let deliveries = match status {
+            Some(status) => {
+                let mut stmt = self.conn.prepare(&format!(
+                    "SELECT {} FROM webhook_deliveries WHERE status = ?1 ORDER BY id DESC LIMIT ?2",
+                    DELIVERY_COLUMNS
+                ))?;
+                stmt.query_map(rusqlite::params![status.as_str(), limit], row_to_delivery)?
+                    .collect::<SqlResult<Vec<_>>>()?
+            }
+            None => {
+                let mut stmt = self.conn.prepare(&format!(
+                    "SELECT {} FROM webhook_deliveries ORDER BY id DESC LIMIT ?1",
+                    DELIVERY_COLUMNS
+                ))?;
+                stmt.query_map(rusqlite::params![limit], row_to_delivery)?
+                    .collect::<SqlResult<Vec<_>>>()?
+            }
+        };
+
+        Ok(deliveries)
+    }
+}
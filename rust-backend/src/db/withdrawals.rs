@@ -1,9 +1,5 @@
-use rusqlite::{Connection, Result as SqlResult};
-use std::path::PathBuf;
-
-pub struct WithdrawalTracker {
-    conn: Connection,
-}
+use super::SqliteStore;
+use crate::error::BridgeResult;
 
 #[derive(Debug)]
 pub struct WithdrawalRecord {
@@ -16,65 +12,69 @@ pub struct WithdrawalRecord {
     pub zcash_txid: Option<String>,
 }
 
-impl WithdrawalTracker {
-    pub fn new(db_path: PathBuf) -> SqlResult<Self> {
-        let conn = Connection::open(db_path)?;
-        
-        // Create withdrawals table if it doesn't exist
-        // commitment = hash(secret) - this is what's stored on-chain
-        // secret is never stored - user provides it when claiming
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS withdrawals (
-                commitment TEXT PRIMARY KEY,
-                note_id TEXT UNIQUE NOT NULL,
-                amount INTEGER NOT NULL,
-                block_number INTEGER NOT NULL,
-                created_at INTEGER NOT NULL,
-                claimed_at INTEGER,
-                zcash_txid TEXT
-            )",
-            [],
-        )?;
-        
-        // Create index for faster lookups
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_withdrawals_note_id ON withdrawals(note_id)",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_withdrawals_claimed ON withdrawals(claimed_at)",
-            [],
-        )?;
-        
-        Ok(Self { conn })
-    }
-
+/// Withdrawal commitments and their payouts
+pub trait Withdrawals {
     /// Record a new withdrawal commitment
-    pub fn record_withdrawal(
+    ///
+    /// Returns false if the commitment or the note was already recorded.
+    fn record_withdrawal(
         &self,
         commitment: &str,
         note_id: &str,
         amount: u64,
         block_number: u32,
-    ) -> SqlResult<()> {
+    ) -> BridgeResult<bool>;
+
+    /// Get withdrawal by commitment
+    fn get_withdrawal(&self, commitment: &str) -> BridgeResult<Option<WithdrawalRecord>>;
+
+    /// Get withdrawal by note_id
+    fn get_withdrawal_by_note_id(&self, note_id: &str) -> BridgeResult<Option<WithdrawalRecord>>;
+
+    /// Mark withdrawal as claimed
+    fn mark_claimed(
+        &self,
+        commitment: &str,
+        zcash_txid: &str,
+    ) -> BridgeResult<()>;
+
+    /// Check if withdrawal is already claimed
+    fn is_claimed(&self, commitment: &str) -> BridgeResult<bool>;
+
+    /// Get all unclaimed withdrawals
+    fn get_unclaimed_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>>;
+
+    /// Get all withdrawals, claimed or not, oldest first
+    fn list_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>>;
+
+    /// Withdrawals created with `since <= created_at < until`, claimed or not, oldest first
+    fn list_withdrawals_between(&self, since: i64, until: i64) -> BridgeResult<Vec<WithdrawalRecord>>;
+}
+
+impl Withdrawals for SqliteStore {
+    fn record_withdrawal(
+        &self,
+        commitment: &str,
+        note_id: &str,
+        amount: u64,
+        block_number: u32,
+    ) -> BridgeResult<bool> {
         let created_at = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
         
-        self.conn.execute(
+        let inserted = self.conn.execute(
             "INSERT INTO withdrawals (commitment, note_id, amount, block_number, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5)
-             ON CONFLICT(commitment) DO NOTHING",
+             ON CONFLICT DO NOTHING",
             rusqlite::params![commitment, note_id, amount, block_number, created_at],
         )?;
         
-        Ok(())
+        Ok(inserted > 0)
     }
 
-    /// Get withdrawal by commitment
-    pub fn get_withdrawal(&self, commitment: &str) -> SqlResult<Option<WithdrawalRecord>> {
+    fn get_withdrawal(&self, commitment: &str) -> BridgeResult<Option<WithdrawalRecord>> {
         let mut stmt = self.conn.prepare(
             "SELECT commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid
              FROM withdrawals WHERE commitment = ?1"
@@ -99,8 +99,7 @@ impl WithdrawalTracker {
         }
     }
 
-    /// Get withdrawal by note_id
-    pub fn get_withdrawal_by_note_id(&self, note_id: &str) -> SqlResult<Option<WithdrawalRecord>> {
+    fn get_withdrawal_by_note_id(&self, note_id: &str) -> BridgeResult<Option<WithdrawalRecord>> {
         let mut stmt = self.conn.prepare(
             "SELECT commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid
              FROM withdrawals WHERE note_id = ?1"
@@ -125,12 +124,11 @@ impl WithdrawalTracker {
         }
     }
 
-    /// Mark withdrawal as claimed
-    pub fn mark_claimed(
+    fn mark_claimed(
         &self,
         commitment: &str,
         zcash_txid: &str,
-    ) -> SqlResult<()> {
+    ) -> BridgeResult<()> {
         let claimed_at = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -146,8 +144,7 @@ impl WithdrawalTracker {
         Ok(())
     }
 
-    /// Check if withdrawal is already claimed
-    pub fn is_claimed(&self, commitment: &str) -> SqlResult<bool> {
+    fn is_claimed(&self, commitment: &str) -> BridgeResult<bool> {
         let mut stmt = self.conn.prepare(
             "SELECT 1 FROM withdrawals WHERE commitment = ?1 AND claimed_at IS NOT NULL LIMIT 1"
         )?;
@@ -156,8 +153,7 @@ impl WithdrawalTracker {
         Ok(exists)
     }
 
-    /// Get all unclaimed withdrawals
-    pub fn get_unclaimed_withdrawals(&self) -> SqlResult<Vec<WithdrawalRecord>> {
+    fn get_unclaimed_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>> {
         let mut stmt = self.conn.prepare(
             "SELECT commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid
              FROM withdrawals WHERE claimed_at IS NULL"
@@ -182,5 +178,57 @@ impl WithdrawalTracker {
         
         Ok(withdrawals)
     }
+
+    fn list_withdrawals(&self) -> BridgeResult<Vec<WithdrawalRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid
+             FROM withdrawals ORDER BY created_at"
+        )?;
+        
+        let rows = stmt.query_map([], |row| {
+            Ok(WithdrawalRecord {
+                commitment: row.get(0)?,
+                note_id: row.get(1)?,
+                amount: row.get(2)?,
+                block_number: row.get(3)?,
+                created_at: row.get(4)?,
+                claimed_at: row.get(5)?,
+                zcash_txid: row.get(6)?,
+            })
+        })?;
+        
+        let mut withdrawals = Vec::new();
+        for row in rows {
+            withdrawals.push(row?);
+        }
+        
+        Ok(withdrawals)
+    }
+
+    fn list_withdrawals_between(&self, since: i64, until: i64) -> BridgeResult<Vec<WithdrawalRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT commitment, note_id, amount, block_number, created_at, claimed_at, zcash_txid
+             FROM withdrawals WHERE created_at >= ?1 AND created_at < ?2 ORDER BY created_at"
+        )?;
+        
+        let rows = stmt.query_map([since, until], |row| {
+            Ok(WithdrawalRecord {
+                commitment: row.get(0)?,
+                note_id: row.get(1)?,
+                amount: row.get(2)?,
+                block_number: row.get(3)?,
+                created_at: row.get(4)?,
+                claimed_at: row.get(5)?,
+                zcash_txid: row.get(6)?,
+            })
+        })?;
+        
+        let mut withdrawals = Vec::new();
+        for row in rows {
+            withdrawals.push(row?);
+        }
+        
+        Ok(withdrawals)
+    }
 }
 
@@ -0,0 +1,248 @@
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use utoipa::openapi::{ContentBuilder, Ref, RefOr, Response, ResponseBuilder};
+use utoipa::{IntoResponses, ToSchema};
+
+/// Errors returned by the bridge library and the API
+///
+/// Every variant has a stable machine-readable code and an HTTP status. Clients
+/// should branch on `code`; the message is for humans and may change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeError {
+    /// Malformed or unacceptable request data (bad account ID, memo, amount, address)
+    InvalidInput(String),
+    /// Missing or unknown API key
+    Unauthorized(String),
+    /// API key lacks the role the endpoint requires
+    Forbidden(String),
+    /// Deposit, withdrawal, intent or refund does not exist
+    NotFound(String),
+    /// Deposit or withdrawal has already been claimed or paid out
+    AlreadyClaimed(String),
+    /// Request conflicts with the current state (rejected deposit, refund already approved, ...)
+    Conflict(String),
+    /// Bridge wallet can't cover the payout right now
+    InsufficientLiquidity(String),
+    /// Rate limit or hourly quota exhausted
+    RateLimited(String),
+    /// Proof-of-work missing, expired, already used or below the difficulty
+    InvalidProofOfWork(String),
+    /// Miden node unreachable or failing
+    ChainUnavailable(String),
+    /// Zcash wallet (zcash-devtool) unreachable or failing
+    WalletUnavailable(String),
+    /// Building, executing or proving a Miden transaction failed
+    ProvingFailed(String),
+    /// The network rejected a transaction
+    TransactionFailed(String),
+    /// Local database error
+    Database(String),
+    /// Anything else
+    Internal(String),
+}
+
+pub type BridgeResult<T> = Result<T, BridgeError>;
+
+impl BridgeError {
+    /// Stable error code sent to clients
+    pub fn code(&self) -> &'static str {
+        match self {
+            BridgeError::InvalidInput(_) => "invalid_input",
+            BridgeError::Unauthorized(_) => "unauthorized",
+            BridgeError::Forbidden(_) => "forbidden",
+            BridgeError::NotFound(_) => "not_found",
+            BridgeError::AlreadyClaimed(_) => "already_claimed",
+            BridgeError::Conflict(_) => "conflict",
+            BridgeError::InsufficientLiquidity(_) => "insufficient_liquidity",
+            BridgeError::RateLimited(_) => "rate_limited",
+            BridgeError::InvalidProofOfWork(_) => "invalid_proof_of_work",
+            BridgeError::ChainUnavailable(_) => "chain_unavailable",
+            BridgeError::WalletUnavailable(_) => "wallet_unavailable",
+            BridgeError::ProvingFailed(_) => "proving_failed",
+            BridgeError::TransactionFailed(_) => "transaction_failed",
+            BridgeError::Database(_) => "database_error",
+            BridgeError::Internal(_) => "internal_error",
+        }
+    }
+
+    /// HTTP status the API responds with
+    pub fn status(&self) -> Status {
+        match self {
+            BridgeError::InvalidInput(_) => Status::BadRequest,
+            BridgeError::Unauthorized(_) => Status::Unauthorized,
+            BridgeError::Forbidden(_) | BridgeError::InvalidProofOfWork(_) => Status::Forbidden,
+            BridgeError::NotFound(_) => Status::NotFound,
+            BridgeError::AlreadyClaimed(_) | BridgeError::Conflict(_) => Status::Conflict,
+            BridgeError::InsufficientLiquidity(_) => Status::ServiceUnavailable,
+            BridgeError::RateLimited(_) => Status::TooManyRequests,
+            BridgeError::ChainUnavailable(_)
+            | BridgeError::WalletUnavailable(_)
+            | BridgeError::TransactionFailed(_) => Status::BadGateway,
+            BridgeError::ProvingFailed(_) | BridgeError::Database(_) | BridgeError::Internal(_) => {
+                Status::InternalServerError
+            }
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            BridgeError::InvalidInput(message)
+            | BridgeError::Unauthorized(message)
+            | BridgeError::Forbidden(message)
+            | BridgeError::NotFound(message)
+            | BridgeError::AlreadyClaimed(message)
+            | BridgeError::Conflict(message)
+            | BridgeError::InsufficientLiquidity(message)
+            | BridgeError::RateLimited(message)
+            | BridgeError::InvalidProofOfWork(message)
+            | BridgeError::ChainUnavailable(message)
+            | BridgeError::WalletUnavailable(message)
+            | BridgeError::ProvingFailed(message)
+            | BridgeError::TransactionFailed(message)
+            | BridgeError::Database(message)
+            | BridgeError::Internal(message) => message,
+        }
+    }
+
+    /// Same kind of error with context prepended to the message
+    pub fn context(self, context: &str) -> Self {
+        let wrap = |message: String| format!("{}: {}", context, message);
+        match self {
+            BridgeError::InvalidInput(message) => BridgeError::InvalidInput(wrap(message)),
+            BridgeError::Unauthorized(message) => BridgeError::Unauthorized(wrap(message)),
+            BridgeError::Forbidden(message) => BridgeError::Forbidden(wrap(message)),
+            BridgeError::NotFound(message) => BridgeError::NotFound(wrap(message)),
+            BridgeError::AlreadyClaimed(message) => BridgeError::AlreadyClaimed(wrap(message)),
+            BridgeError::Conflict(message) => BridgeError::Conflict(wrap(message)),
+            BridgeError::InsufficientLiquidity(message) => BridgeError::InsufficientLiquidity(wrap(message)),
+            BridgeError::RateLimited(message) => BridgeError::RateLimited(wrap(message)),
+            BridgeError::InvalidProofOfWork(message) => BridgeError::InvalidProofOfWork(wrap(message)),
+            BridgeError::ChainUnavailable(message) => BridgeError::ChainUnavailable(wrap(message)),
+            BridgeError::WalletUnavailable(message) => BridgeError::WalletUnavailable(wrap(message)),
+            BridgeError::ProvingFailed(message) => BridgeError::ProvingFailed(wrap(message)),
+            BridgeError::TransactionFailed(message) => BridgeError::TransactionFailed(wrap(message)),
+            BridgeError::Database(message) => BridgeError::Database(wrap(message)),
+            BridgeError::Internal(message) => BridgeError::Internal(wrap(message)),
+        }
+    }
+
+    /// One error of each kind any endpoint can return, used to document the possible responses
+    ///
+    /// `InvalidProofOfWork` only comes from the `StateCreationLimit` guard, see `StateCreationErrors`.
+    fn kinds() -> [BridgeError; 14] {
+        let none = String::new;
+        [
+            BridgeError::InvalidInput(none()),
+            BridgeError::Unauthorized(none()),
+            BridgeError::Forbidden(none()),
+            BridgeError::NotFound(none()),
+            BridgeError::AlreadyClaimed(none()),
+            BridgeError::Conflict(none()),
+            BridgeError::InsufficientLiquidity(none()),
+            BridgeError::RateLimited(none()),
+            BridgeError::ChainUnavailable(none()),
+            BridgeError::WalletUnavailable(none()),
+            BridgeError::ProvingFailed(none()),
+            BridgeError::TransactionFailed(none()),
+            BridgeError::Database(none()),
+            BridgeError::Internal(none()),
+        ]
+    }
+
+    /// Response body for this error
+    pub fn envelope(&self) -> ErrorResponse {
+        ErrorResponse {
+            success: false,
+            code: self.code().to_string(),
+            error: self.message().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl From<rusqlite::Error> for BridgeError {
+    fn from(e: rusqlite::Error) -> Self {
+        BridgeError::Database(e.to_string())
+    }
+}
+
+/// Lets code that still returns `Result<_, String>` (bins, scripts) use `?` on bridge calls
+impl From<BridgeError> for String {
+    fn from(e: BridgeError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Error envelope returned by every failing API endpoint
+///
+/// `{ "success": false, "code": "already_claimed", "error": "Deposit ... has already been claimed." }`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub success: bool,
+    /// Stable error code, e.g. `invalid_input`, `not_found`, `already_claimed`, `insufficient_liquidity`
+    pub code: String,
+    /// Human-readable message
+    pub error: String,
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for BridgeError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        let status = self.status();
+        if status.code >= 500 {
+            tracing::error!(code = self.code(), error = %self, "Request failed");
+        }
+        response::status::Custom(status, Json(self.envelope())).respond_to(request)
+    }
+}
+
+/// Documents the error responses in the OpenAPI spec, one per HTTP status with the codes it can carry
+impl IntoResponses for BridgeError {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        error_responses(BridgeError::kinds())
+    }
+}
+
+/// Error responses of endpoints behind the `StateCreationLimit` guard, which adds
+/// `invalid_proof_of_work` to the 403
+pub struct StateCreationErrors;
+
+impl IntoResponses for StateCreationErrors {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        error_responses(BridgeError::kinds().into_iter().chain([BridgeError::InvalidProofOfWork(String::new())]))
+    }
+}
+
+fn error_responses(kinds: impl IntoIterator<Item = BridgeError>) -> BTreeMap<String, RefOr<Response>> {
+    let mut codes_by_status: BTreeMap<u16, Vec<&'static str>> = BTreeMap::new();
+    for kind in kinds {
+        codes_by_status.entry(kind.status().code).or_default().push(kind.code());
+    }
+
+    codes_by_status
+        .into_iter()
+        .map(|(status, codes)| {
+            let response = ResponseBuilder::new()
+                .description(format!("Error envelope with code {}", codes.join(", ")))
+                .content(
+                    "application/json",
+                    ContentBuilder::new()
+                        .schema(Some(Ref::from_schema_name("ErrorResponse")))
+                        .build(),
+                )
+                .build();
+            (status.to_string(), RefOr::T(response))
+        })
+        .collect()
+}
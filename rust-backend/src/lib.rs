@@ -3,6 +3,11 @@ pub mod zcash;
 pub mod miden;
 pub mod bridge;
 pub mod db;
+pub mod auth;
+pub mod rate_limit;
+pub mod metrics;
+pub mod logging;
+pub mod error;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -0,0 +1,234 @@
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Field names whose values are never written to the logs
+const SECRET_FIELDS: &[&str] = &["secret", "secret_key", "private_key", "seed", "mnemonic", "password", "api_key"];
+
+/// Field names holding Miden account IDs, which are shortened in the logs
+const ACCOUNT_FIELDS: &[&str] = &["account", "account_id"];
+
+const REDACTED: &str = "[redacted]";
+
+/// Install the global tracing subscriber
+///
+/// BRIDGE_LOG         level filter, e.g. "info" or "info,rust_backend::bridge=debug" (falls back to RUST_LOG, then "info")
+/// BRIDGE_LOG_FORMAT  "json" or "text" (default json in production mode, text otherwise)
+///
+/// Fields named like secrets are replaced with "[redacted]" and account IDs are shortened,
+/// in both formats, so call sites don't have to remember to do it.
+pub fn init() {
+    let filter = EnvFilter::try_from_env("BRIDGE_LOG")
+        .or_else(|_| EnvFilter::try_from_default_env())
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let json = match std::env::var("BRIDGE_LOG_FORMAT") {
+        Ok(format) => format.eq_ignore_ascii_case("json"),
+        Err(_) => crate::auth::production_mode(),
+    };
+
+    let result = if json {
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .fmt_fields(RedactingFields { json: true })
+            .event_format(JsonFormat)
+            .try_init()
+    } else {
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .fmt_fields(RedactingFields { json: false })
+            .try_init()
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Logging] Failed to install tracing subscriber: {}", e);
+    }
+}
+
+/// Shorten an account ID to its first 8 and last 4 characters
+pub fn mask_account_id(account_id: &str) -> String {
+    let chars: Vec<char> = account_id.chars().collect();
+    if chars.len() <= 16 {
+        return REDACTED.to_string();
+    }
+    let head: String = chars[..8].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+fn matches_field(name: &str, names: &[&str]) -> bool {
+    names
+        .iter()
+        .any(|candidate| name == *candidate || name.ends_with(&format!("_{}", candidate)))
+}
+
+/// Apply the redaction rules to a field value
+fn redact(name: &str, value: Value) -> Value {
+    if matches_field(name, SECRET_FIELDS) {
+        return Value::String(REDACTED.to_string());
+    }
+    if matches_field(name, ACCOUNT_FIELDS) {
+        if let Value::String(account_id) = &value {
+            return Value::String(mask_account_id(account_id));
+        }
+    }
+    value
+}
+
+/// Collects an event's or span's fields, redacting sensitive ones by name
+#[derive(Default)]
+struct RedactingVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl RedactingVisitor {
+    fn record(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = Some(match value {
+                Value::String(message) => message,
+                other => other.to_string(),
+            });
+        } else {
+            self.fields.insert(field.name().to_string(), redact(field.name(), value));
+        }
+    }
+
+    /// `message key=value ...` for the text format
+    fn to_text(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(message) = &self.message {
+            parts.push(message.clone());
+        }
+        for (name, value) in &self.fields {
+            match value {
+                Value::String(value) => parts.push(format!("{}={}", name, value)),
+                other => parts.push(format!("{}={}", name, other)),
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+impl Visit for RedactingVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, Value::String(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+}
+
+/// Formats span and event fields with redaction applied
+///
+/// In JSON mode span fields are stored as a JSON object so `JsonFormat` can embed them.
+struct RedactingFields {
+    json: bool,
+}
+
+impl<'writer> FormatFields<'writer> for RedactingFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = RedactingVisitor::default();
+        fields.record(&mut visitor);
+
+        if self.json {
+            let mut object = visitor.fields;
+            if let Some(message) = visitor.message {
+                object.insert("message".to_string(), Value::String(message));
+            }
+            write!(writer, "{}", Value::Object(object))
+        } else {
+            write!(writer, "{}", visitor.to_text())
+        }
+    }
+
+    fn add_fields(&self, current: &'writer mut FormattedFields<Self>, fields: &tracing::span::Record<'_>) -> fmt::Result {
+        if !self.json {
+            if !current.fields.is_empty() {
+                current.fields.push(' ');
+            }
+            return self.format_fields(current.as_writer(), fields);
+        }
+
+        // Merge newly recorded span fields into the stored JSON object
+        let mut visitor = RedactingVisitor::default();
+        fields.record(&mut visitor);
+
+        let mut object = match serde_json::from_str::<Value>(&current.fields) {
+            Ok(Value::Object(object)) => object,
+            _ => Map::new(),
+        };
+        object.extend(visitor.fields);
+        current.fields = Value::Object(object).to_string();
+        Ok(())
+    }
+}
+
+/// One JSON object per line: timestamp, level, target, message, fields and the enclosing spans
+///
+/// Spans carry the correlation IDs (Zcash txid, note ID, withdrawal commitment).
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut visitor = RedactingVisitor::default();
+        event.record(&mut visitor);
+
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let mut object = Map::new();
+                object.insert("name".to_string(), Value::String(span.name().to_string()));
+                if let Some(fields) = span.extensions().get::<FormattedFields<N>>() {
+                    if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(&fields.fields) {
+                        object.extend(fields);
+                    }
+                }
+                spans.push(Value::Object(object));
+            }
+        }
+
+        let metadata = event.metadata();
+        let mut line = Map::new();
+        line.insert(
+            "timestamp".to_string(),
+            Value::String(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+        );
+        line.insert("level".to_string(), Value::String(metadata.level().to_string()));
+        line.insert("target".to_string(), Value::String(metadata.target().to_string()));
+        if let Some(message) = visitor.message {
+            line.insert("message".to_string(), Value::String(message));
+        }
+        if !visitor.fields.is_empty() {
+            line.insert("fields".to_string(), Value::Object(visitor.fields));
+        }
+        if !spans.is_empty() {
+            line.insert("spans".to_string(), Value::Array(spans));
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
@@ -6,7 +6,6 @@ use miden_client::{
     address::NetworkId,
     auth::AuthSecretKey,
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
     rpc::{Endpoint, GrpcClient, NodeRpcClient},
 };
 use miden_client_sqlite_store::ClientBuilderSqliteExt;
@@ -16,32 +15,53 @@ use miden_objects::{
     asset::TokenSymbol,
     Felt,
 };
-use rand::{rngs::StdRng, RngCore, rng};
+use rand::{RngCore, rng};
 use rocket::serde::json::Json;
 use rocket::serde::{Deserialize, Serialize};
 use rocket::http::Status;
 use rocket::response::status;
+use rocket::response::stream::{Event, EventStream};
+use rocket::request::{FromRequest, Outcome};
 use rocket_cors::{AllowedOrigins, CorsOptions};
-use rust_backend::bridge::deposit::{ClaimDepositRequest, ClaimDepositResponse};
-use rust_backend::db::deposits::DepositTracker;
-use rust_backend::db::withdrawals::WithdrawalTracker;
+use rust_backend::account::keystore::{self, EncryptedKeyStore};
+use rust_backend::account::signer::BridgeSigner;
+use rust_backend::auth::{production_mode, AdminAuth, ApiKeys, AuthFailure, OperatorAuth, Role};
+use rust_backend::metrics;
+use rust_backend::rate_limit::{PowChallenge, PowFailure, RateLimit, RateLimitConfig, RateLimitFailure, RateLimiter, StateCreationLimit, TooManyRequests};
+use rust_backend::bridge::accounting::{export, AccountingExport, ExportRange, ExportTable};
+use rust_backend::bridge::deposit::{ClaimDepositRequest, ClaimDepositResponse, ZCASH_ORIGIN_NETWORK};
+use rust_backend::bridge::fees::{FeeDirection, FeeQuote, FeeSchedule};
+use rust_backend::bridge::multisig::{DepositView, MintCoordinator, MintProgress, MultisigConfig};
+use rust_backend::bridge::payouts::{self, PayoutSigning};
+use rust_backend::bridge::solvency::{parse_zatoshis, ReservesDisclosure, SolvencyGuard, SolvencyReport};
+use rust_backend::db::BridgeStore;
+use rust_backend::db::audit::{inputs_digest, AuditAction};
+use rust_backend::db::events::{BridgeEvent, EventState, EventSubject, Events};
+use rust_backend::db::fees::FeeSummary;
+use rust_backend::db::payouts::PayoutKind;
+use rust_backend::db::intents::{DepositIntent, IntentStatus};
+use rust_backend::db::refunds::{RefundStatus, Refunds, RejectedDeposit};
+use rust_backend::error::{BridgeError, BridgeResult, ErrorResponse, StateCreationErrors};
 use rust_backend::miden::recipient::build_deposit_recipient;
-use rust_backend::miden::notes::reconstruct_deposit_note;
-use rust_backend::zcash::bridge_wallet::BridgeWallet;
+use rust_backend::miden::notes::{reconstruct_deposit_note, withdrawal_commitment_note};
+use rust_backend::zcash::bridge_wallet::{is_testnet_address, BridgeWallet};
 use std::collections::BTreeSet;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::time::Duration;
+use tracing::{debug, error, info, warn, Instrument};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
 use miden_objects::{account::AccountId, Word};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct BlockInfo {
     block_num: u32,
     chain_tip: u32,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct AccountResponse {
     account_id: String, // bech32
@@ -49,7 +69,7 @@ struct AccountResponse {
     success: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ReconstructNoteRequest {
     account_id: String,
@@ -58,7 +78,7 @@ struct ReconstructNoteRequest {
     amount: u64,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ReconstructNoteResponse {
     note_id: String,
@@ -89,7 +109,7 @@ struct PrepareConsumeResponse {
     message: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ConsumeNoteRequest {
     account_id: String, // Can be bech32 or hex
@@ -99,7 +119,7 @@ struct ConsumeNoteRequest {
     created_at: Option<String>, // ISO 8601 timestamp (e.g., "2025-11-30T22:44:41.890Z")
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ConsumeNoteResponse {
     transaction_id: String,
@@ -108,13 +128,13 @@ struct ConsumeNoteResponse {
     message: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct BalanceRequest {
     account_id: String, // Can be bech32 or hex
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct BalanceResponse {
     balance: String, // Balance in tokens (e.g., "0.3")
@@ -123,13 +143,13 @@ struct BalanceResponse {
     success: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct PoolBalanceRequest {
     faucet_id: Option<String>, // Optional, if not provided uses default
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct PoolBalanceResponse {
     balance: String,
@@ -138,6 +158,21 @@ struct PoolBalanceResponse {
     success: bool,
 }
 
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct ReservesResponse {
+    reserves: SolvencyReport,
+    success: bool,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct ReservesDisclosureResponse {
+    disclosure: ReservesDisclosure,
+    faucet_id: Option<String>, // Public wTAZ faucet whose supply the reserves back
+    success: bool,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "rocket::serde")]
 struct WithdrawalRequest {
@@ -157,7 +192,7 @@ struct WithdrawalResponse {
 }
 
 // New commitment-based withdrawal structs
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct CommitmentWithdrawalRequest {
     account_id: String, // User's Miden account (bech32 or hex)
@@ -166,7 +201,7 @@ struct CommitmentWithdrawalRequest {
     faucet_id: Option<String>, // Optional, defaults to wTAZ faucet
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct CommitmentWithdrawalResponse {
     commitment: String, // hash(secret) - user must save this
@@ -175,14 +210,16 @@ struct CommitmentWithdrawalResponse {
     amount: u64, // Amount in base units
     amount_taz: String, // Amount in TAZ (formatted)
     note_id: String,
-    transaction_id: String,
+    transaction_id: Option<String>, // None while the note waits for bridge approvers
     network: String, // "testnet"
     created_at: String, // ISO 8601 timestamp
+    fee: u64, // Withdrawal fee in base units (deducted from the payout)
+    net_amount: u64, // Amount paid out on Zcash after the fee
     success: bool,
     message: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ClaimWithdrawalRequest {
     commitment: Option<String>, // hash(secret) from withdrawal (optional if providing .mno)
@@ -197,15 +234,63 @@ struct ClaimWithdrawalRequest {
     created_at: Option<String>, // Not used for claim, but part of .mno format
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct ClaimWithdrawalResponse {
     success: bool,
+    /// Not set while the payout waits for the offline signer; follow /events/withdrawal/<commitment>
     zcash_txid: Option<String>,
     message: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct FeeQuoteResponse {
+    quote: FeeQuote,
+    success: bool,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct FeeSummaryResponse {
+    fees: Vec<FeeSummary>,
+    success: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct AccountingExportResponse {
+    export: AccountingExport,
+    success: bool,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct RefundRequest {
+    txid: String, // Zcash txid of the rejected deposit
+    pool: Option<String>, // Output pool, only needed if several outputs of the tx were rejected
+    output_index: Option<u32>, // Output index within the pool
+    refund_address: Option<String>, // Required unless the memo carried refund=<address>
+    memo: Option<String>, // Proof of ownership: the memo sent with the deposit
+    secret: Option<String>, // Proof of ownership: the secret from the deposit memo
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct RefundResponse {
+    txid: String,
+    pool: String,
+    output_index: u32,
+    amount: u64,
+    reason: String,
+    status: RefundStatus,
+    refund_address: Option<String>,
+    refund_txid: Option<String>,
+    success: bool,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct FaucetResponse {
     faucet_account_id: String,
@@ -217,13 +302,47 @@ struct FaucetResponse {
 
 struct State {
     rpc: Arc<dyn NodeRpcClient + Send + Sync + 'static>,
-    keystore: Arc<FilesystemKeyStore<StdRng>>,
+    // None when SIGNER_SOCKET is set: the signer process holds every key, this one none
+    keystore: Option<Arc<EncryptedKeyStore>>,
     bridge_wallet: Arc<BridgeWallet>,
-    deposit_tracker: Arc<Mutex<DepositTracker>>,
-    withdrawal_tracker: Arc<Mutex<WithdrawalTracker>>,
+    fee_schedule: FeeSchedule,
+    db: Arc<Mutex<Box<dyn BridgeStore>>>,
+    // Held while a withdrawal is paid out, so two claims of one commitment can't both pay
+    withdrawal_claims: Mutex<()>,
+    solvency: SolvencyGuard,
+    reserves_disclosure: Option<ReservesDisclosure>,
+    // Set when mints need M-of-N approver signatures (MINT_APPROVERS)
+    multisig: Option<Arc<MintCoordinator>>,
+    // Offline: payouts are queued as PCZTs for the signer host instead of sent (ZCASH_PAYOUT_SIGNING)
+    payout_signing: PayoutSigning,
 }
 
-async fn init_client(keystore: Arc<FilesystemKeyStore<StdRng>>) -> Result<miden_client::Client<FilesystemKeyStore<StdRng>>, String> {
+impl State {
+    fn db(&self) -> BridgeResult<MutexGuard<'_, Box<dyn BridgeStore>>> {
+        self.db.lock()
+            .map_err(|e| BridgeError::Internal(format!("Failed to lock bridge database: {}", e)))
+    }
+
+    // New accounts get their keys here, so they can't be created on a host without a keystore
+    fn keystore(&self) -> BridgeResult<Arc<EncryptedKeyStore>> {
+        self.keystore.clone().ok_or_else(|| {
+            BridgeError::Conflict("SIGNER_SOCKET is set, so this API holds no keystore to create accounts in".to_string())
+        })
+    }
+}
+
+// Record a state change for /events subscribers; failing to record it doesn't fail the request
+// NOTE: locks the bridge database, so don't call it while holding state.db()
+fn publish_event(state: &State, record: impl FnOnce(&dyn Events) -> BridgeResult<bool>) {
+    let recorded = state.db()
+        .and_then(|db| record(db.events()));
+    
+    if let Err(e) = recorded {
+        warn!(error = %e, "Failed to record bridge event");
+    }
+}
+
+async fn init_client(keystore: Arc<EncryptedKeyStore>) -> Result<miden_client::Client<EncryptedKeyStore>, String> {
     // Initialize client
     let rpc_url = std::env::var("RPC_URL")
         .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
@@ -260,21 +379,28 @@ async fn init_client(keystore: Arc<FilesystemKeyStore<StdRng>>) -> Result<miden_
         .map_err(|e| format!("Failed to build client: {} (store path: {:?})", e, store_path_display))
 }
 
+#[utoipa::path(
+    tag = "chain",
+    summary = "Latest Miden block and chain tip",
+    responses((status = 200, body = BlockInfo), BridgeError),
+)]
 #[get("/block")]
-async fn get_block(state: &rocket::State<State>) -> Result<Json<BlockInfo>, String> {
+async fn get_block(_rate_limit: RateLimit, state: &rocket::State<State>) -> Result<Json<BlockInfo>, BridgeError> {
     // Get latest block header
     let (block_header, _) = state
         .rpc
         .get_block_header_by_number(None, false)
         .await
-        .map_err(|e| format!("RPC error: {}", e))?;
+        .map_err(|e| BridgeError::ChainUnavailable(format!("RPC error: {}", e)))?;
 
     // Get chain tip by syncing notes
     let sync_response = state
         .rpc
         .sync_notes(0u32.into(), None, &BTreeSet::new())
         .await
-        .map_err(|e| format!("RPC error: {}", e))?;
+        .map_err(|e| BridgeError::ChainUnavailable(format!("RPC error: {}", e)))?;
+
+    metrics::MIDEN_SYNC_HEIGHT.set(block_header.block_num().as_u32() as i64);
 
     Ok(Json(BlockInfo {
         block_num: block_header.block_num().as_u32(),
@@ -282,20 +408,56 @@ async fn get_block(state: &rocket::State<State>) -> Result<Json<BlockInfo>, Stri
     }))
 }
 
+#[utoipa::path(
+    tag = "chain",
+    summary = "Liveness check",
+    responses((status = 200, description = "Liveness check", content_type = "text/plain", body = String)),
+)]
 #[get("/health")]
 fn health() -> &'static str {
     "OK"
 }
 
+// Prometheus metrics
+// Ledger-derived gauges are refreshed on each scrape; relayer metrics are served by the relayer processes
+#[utoipa::path(
+    tag = "chain",
+    summary = "Prometheus metrics",
+    responses((status = 200, description = "Prometheus metrics", content_type = "text/plain", body = String)),
+)]
+#[get("/metrics")]
+fn get_metrics(state: &rocket::State<State>) -> (rocket::http::ContentType, String) {
+    match state.db() {
+        Ok(db) => {
+            match db.withdrawals().get_unclaimed_withdrawals() {
+                Ok(pending) => metrics::WITHDRAWALS_PENDING.set(pending.len() as i64),
+                Err(e) => warn!(error = %e, "Failed to count pending withdrawals"),
+            }
+            match db.fees().outstanding_supply(ZCASH_ORIGIN_NETWORK) {
+                Ok(outstanding) => metrics::WTAZ_OUTSTANDING.set(outstanding),
+                Err(e) => warn!(error = %e, "Failed to compute outstanding supply"),
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to refresh ledger metrics"),
+    }
+
+    (rocket::http::ContentType::Plain, metrics::gather())
+}
+
 #[options("/account/create")]
 fn options_create_account() -> status::Custom<&'static str> {
     status::Custom(rocket::http::Status::Ok, "")
 }
 
+#[utoipa::path(
+    tag = "account",
+    summary = "Create a private Miden wallet account",
+    responses((status = 200, body = AccountResponse), StateCreationErrors),
+)]
 #[post("/account/create")]
-async fn create_account(state: &rocket::State<State>) -> Result<Json<AccountResponse>, status::Custom<Json<serde_json::Value>>> {
-    let keystore_clone = state.keystore.clone();
-    let keystore_for_key = state.keystore.clone();
+async fn create_account(_state_limit: StateCreationLimit, state: &rocket::State<State>) -> Result<Json<AccountResponse>, BridgeError> {
+    let keystore_clone = state.keystore()?;
+    let keystore_for_key = keystore_clone.clone();
     let result = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async {
@@ -340,35 +502,24 @@ async fn create_account(state: &rocket::State<State>) -> Result<Json<AccountResp
             })
         })
     })
-    .await;
-    
-    let inner_result: Result<AccountResponse, String> = match result {
-        Ok(inner) => inner,
-        Err(e) => {
-            let error_json = serde_json::json!({
-                "success": false,
-                "error": format!("Spawn blocking error: {}", e)
-            });
-            return Err(status::Custom(rocket::http::Status::InternalServerError, Json(error_json)));
-        }
-    };
+    .await
+    .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+    .map_err(|e: String| BridgeError::Internal(format!("Failed to create account: {}", e)))?;
     
-    match inner_result {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => {
-            let error_json = serde_json::json!({
-                "success": false,
-                "error": format!("Failed to create account: {}", e)
-            });
-            Err(status::Custom(rocket::http::Status::InternalServerError, Json(error_json)))
-        }
-    }
+    Ok(Json(result))
 }
 
+// Admin only - not mounted in production mode
+#[utoipa::path(
+    tag = "admin",
+    summary = "Create a test faucet",
+    responses((status = 200, body = FaucetResponse), BridgeError),
+    security(("api_key" = [])),
+)]
 #[post("/faucet/create")]
-async fn create_faucet(state: &rocket::State<State>) -> Result<Json<FaucetResponse>, String> {
-    let keystore_clone = state.keystore.clone();
-    let keystore_for_key = state.keystore.clone();
+async fn create_faucet(_auth: AdminAuth, state: &rocket::State<State>) -> Result<Json<FaucetResponse>, BridgeError> {
+    let keystore_clone = state.keystore()?;
+    let keystore_for_key = keystore_clone.clone();
     let result = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async {
@@ -409,10 +560,11 @@ async fn create_faucet(state: &rocket::State<State>) -> Result<Json<FaucetRespon
             let faucet_account_id_bech32 = faucet_account.id().to_bech32(NetworkId::Testnet);
             
             // Resync to show newly deployed faucet
-            client
+            let sync_summary = client
                 .sync_state()
                 .await
                 .map_err(|e| format!("Failed to sync state: {}", e))?;
+            metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
             
             tokio::time::sleep(Duration::from_secs(2)).await;
             
@@ -426,13 +578,13 @@ async fn create_faucet(state: &rocket::State<State>) -> Result<Json<FaucetRespon
         })
     })
     .await
-    .map_err(|e| format!("Spawn blocking error: {}", e))?
-    .map_err(|e: String| format!("Client operation error: {}", e))?;
+    .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+    .map_err(|e: String| BridgeError::Internal(format!("Client operation error: {}", e)))?;
 
     Ok(Json(result))
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct MintRequest {
     faucet_id: String,
@@ -440,7 +592,7 @@ struct MintRequest {
     amount: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct MintResponse {
     success: bool,
@@ -449,15 +601,24 @@ struct MintResponse {
     message: String,
 }
 
+// Admin only - not mounted in production mode
+#[utoipa::path(
+    tag = "admin",
+    summary = "Mint test tokens from a faucet",
+    request_body = MintRequest,
+    responses((status = 200, body = MintResponse), BridgeError),
+    security(("api_key" = [])),
+)]
 #[post("/faucet/mint", format = "json", data = "<request>")]
 async fn mint_from_faucet(
+    _auth: AdminAuth,
     _state: &rocket::State<State>,
     request: Json<MintRequest>,
-) -> Result<Json<MintResponse>, String> {
+) -> Result<Json<MintResponse>, BridgeError> {
     // Parse faucet ID
     let faucet_id = if request.faucet_id.starts_with("mtst") || request.faucet_id.starts_with("mm") {
         AccountId::from_bech32(&request.faucet_id)
-            .map_err(|e| format!("Invalid faucet_id bech32: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid faucet_id bech32: {}", e)))?
             .1
     } else {
         let hex_str = if request.faucet_id.starts_with("0x") {
@@ -467,13 +628,13 @@ async fn mint_from_faucet(
         };
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Invalid faucet_id hex: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid faucet_id hex: {}", e)))?
     };
 
     // Parse recipient ID
     let recipient_id = if request.recipient_id.starts_with("mtst") || request.recipient_id.starts_with("mm") {
         AccountId::from_bech32(&request.recipient_id)
-            .map_err(|e| format!("Invalid recipient_id bech32: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid recipient_id bech32: {}", e)))?
             .1
     } else {
         let hex_str = if request.recipient_id.starts_with("0x") {
@@ -483,16 +644,16 @@ async fn mint_from_faucet(
         };
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Invalid recipient_id hex: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid recipient_id hex: {}", e)))?
     };
 
     // Parse amount
     let amount = request.amount.parse::<u64>()
-        .map_err(|e| format!("Invalid amount: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid amount: {}", e)))?;
 
     // Mint note using the bridge deposit mint function
     let project_root = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get current directory: {}", e)))?;
     let keystore_path = project_root.join("keystore");
     let store_path = project_root.join("bridge_store.sqlite3");
     let rpc_url = std::env::var("RPC_URL")
@@ -532,8 +693,8 @@ async fn mint_from_faucet(
         })
     })
     .await
-    .map_err(|e| format!("Spawn blocking error: {}", e))?
-    .map_err(|e: String| format!("Mint note error: {}", e))?;
+    .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+    .map_err(|e| e.context("Mint note error"))?;
 
     Ok(Json(MintResponse {
         success: true,
@@ -543,25 +704,153 @@ async fn mint_from_faucet(
     }))
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct HashRequest {
     account_id: String,
     secret: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 struct HashResponse {
     recipient_hash: String,
     success: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct DepositAddressRequest {
+    account_id: Option<String>, // With secret: the relayer mints automatically when the deposit arrives
+    secret: Option<String>,
+    recipient_hash: Option<String>, // Alone: deposit is claimed later with /deposit/claim
+    auto_mint: Option<bool>, // Set to false to keep account_id/secret off the bridge (defaults to true)
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
-struct ErrorResponse {
+struct DepositAddressResponse {
+    address: String,
+    recipient_hash: String,
+    auto_mint: bool,
     success: bool,
-    error: String,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct DepositIntentRequest {
+    account_id: Option<String>, // With secret: the relayer mints automatically when the deposit arrives
+    secret: Option<String>,
+    recipient_hash: Option<String>, // Alone: deposit is claimed later with /deposit/claim
+    auto_mint: Option<bool>, // Set to false to keep account_id/secret off the bridge (defaults to true)
+    expected_amount: Option<u64>, // In zatoshis - deposits of another amount are flagged
+    expires_in_secs: Option<i64>, // Defaults to DEPOSIT_INTENT_TTL_SECS (24h)
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+struct DepositIntentResponse {
+    code: String, // Put this in the memo
+    recipient_hash: String,
+    expected_amount: Option<u64>,
+    status: IntentStatus,
+    expires_at: i64,
+    auto_mint: bool,
+    txid: Option<String>,
+    received_amount: Option<u64>,
+    success: bool,
+    message: String,
+}
+
+impl DepositIntentResponse {
+    fn from_intent(intent: DepositIntent) -> Self {
+        let auto_mint = intent.auto_mint();
+        let message = match intent.status {
+            IntentStatus::Pending => format!("Waiting for your deposit. Send TAZ to the bridge address with memo {}.", intent.code),
+            IntentStatus::Received if auto_mint => "Deposit received. The note is minted automatically.".to_string(),
+            IntentStatus::Received => "Deposit received. Claim it with POST /deposit/claim.".to_string(),
+            IntentStatus::AmountMismatch => format!(
+                "Deposit received, but {} zatoshis were sent instead of the expected {}.",
+                intent.received_amount.unwrap_or_default(),
+                intent.expected_amount.unwrap_or_default()
+            ),
+            IntentStatus::Expired => "Intent expired before a deposit arrived. Deposits with this code are refundable.".to_string(),
+        };
+        
+        Self {
+            code: intent.code,
+            recipient_hash: intent.recipient_hash,
+            expected_amount: intent.expected_amount,
+            status: intent.status,
+            expires_at: intent.expires_at,
+            auto_mint,
+            txid: intent.txid,
+            received_amount: intent.received_amount,
+            success: true,
+            message,
+        }
+    }
+}
+
+#[catch(401)]
+fn unauthorized(request: &rocket::Request) -> Json<ErrorResponse> {
+    Json(BridgeError::Unauthorized(auth_failure(request, "Missing or invalid API key")).envelope())
+}
+
+#[catch(403)]
+fn forbidden(request: &rocket::Request) -> Json<ErrorResponse> {
+    if let Some(error) = &request.local_cache(|| PowFailure(None)).0 {
+        return Json(error.envelope());
+    }
+    Json(BridgeError::Forbidden(auth_failure(request, "API key does not grant access to this endpoint")).envelope())
+}
+
+#[catch(429)]
+fn too_many_requests(request: &rocket::Request) -> TooManyRequests<Json<ErrorResponse>> {
+    let limited = request.local_cache(|| RateLimitFailure(None)).0.clone();
+    
+    TooManyRequests {
+        retry_after_secs: limited.as_ref().map(|l| l.retry_after_secs).unwrap_or(60),
+        body: Json(BridgeError::RateLimited(
+            limited
+                .map(|l| l.message)
+                .unwrap_or_else(|| "Too many requests".to_string()),
+        ).envelope()),
+    }
+}
+
+// Unknown routes, malformed JSON bodies and anything else Rocket rejects before a handler runs
+#[catch(default)]
+fn default_catcher(status: Status, _request: &rocket::Request) -> status::Custom<Json<ErrorResponse>> {
+    let error = match status.code {
+        404 => BridgeError::NotFound("No such endpoint".to_string()),
+        400..=499 => BridgeError::InvalidInput(format!("Invalid request: {}", status.reason_lossy())),
+        _ => BridgeError::Internal(status.reason_lossy().to_string()),
+    };
+    status::Custom(status, Json(error.envelope()))
+}
+
+// Message left by the auth request guards, if they rejected the request
+fn auth_failure(request: &rocket::Request, fallback: &str) -> String {
+    request.local_cache(|| AuthFailure(None))
+        .0
+        .clone()
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+// One-time proof-of-work challenge for state-creating endpoints (only enforced when POW_DIFFICULTY > 0)
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Issue a proof-of-work challenge for state-creating endpoints",
+    responses((status = 200, body = PowChallenge), BridgeError),
+)]
+#[get("/pow/challenge")]
+fn get_pow_challenge(
+    _rate_limit: RateLimit,
+    rate_limiter: &rocket::State<RateLimiter>,
+) -> Json<PowChallenge> {
+    Json(rate_limiter.issue_challenge())
 }
 
 #[options("/deposit/hash")]
@@ -571,38 +860,39 @@ fn options_hash() -> rocket::http::Status {
 
 // Simple GET endpoint for fast hash generation (query params instead of JSON body)
 // Rocket requires query params to be optional, so we check them manually
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Compute the recipient hash for an account and secret",
+    responses((status = 200, body = HashResponse), BridgeError),
+)]
 #[get("/deposit/hash?<account_id>&<secret>")]
 fn get_hash_endpoint(
+    _rate_limit: RateLimit,
     account_id: Option<String>,
     secret: Option<String>,
-) -> Result<Json<HashResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<HashResponse>, BridgeError> {
     let account_id = account_id.ok_or_else(|| {
-        status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Missing account_id parameter".to_string(),
-            }),
-        )
+        BridgeError::InvalidInput("Missing account_id parameter".to_string())
     })?;
     
     let secret = secret.ok_or_else(|| {
-        status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Missing secret parameter".to_string(),
-            }),
-        )
+        BridgeError::InvalidInput("Missing secret parameter".to_string())
     })?;
     
     generate_hash_internal(&account_id, &secret)
 }
 
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Compute the recipient hash for an account and secret",
+    request_body = HashRequest,
+    responses((status = 200, body = HashResponse), BridgeError),
+)]
 #[post("/deposit/hash", format = "json", data = "<request>")]
 async fn generate_hash_endpoint(
+    _rate_limit: RateLimit,
     request: Json<HashRequest>,
-) -> Result<Json<HashResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<HashResponse>, BridgeError> {
     generate_hash_internal(&request.account_id, &request.secret)
 }
 
@@ -610,19 +900,13 @@ async fn generate_hash_endpoint(
 fn generate_hash_internal(
     account_id_str: &str,
     secret_str: &str,
-) -> Result<Json<HashResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<HashResponse>, BridgeError> {
     // Trim whitespace from account_id and secret
     let account_id_str = account_id_str.trim();
     let secret_str = secret_str.trim();
     
     if account_id_str.is_empty() {
-        return Err(status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "account_id cannot be empty. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format.".to_string(),
-            }),
-        ));
+        return Err(BridgeError::InvalidInput("account_id cannot be empty. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format.".to_string()));
     }
     
     // Parse account_id and secret - handle both hex and bech32 formats
@@ -639,16 +923,10 @@ fn generate_hash_internal(
                     account_id_str
                 };
                 AccountId::from_hex(hex_str).map_err(|hex_err| {
-                    status::Custom(
-                        Status::BadRequest,
-                        Json(ErrorResponse {
-                            success: false,
-                            error: format!(
-                                "Invalid account_id format. Tried bech32 (mtst1...): {}. Tried hex: {}. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format.",
-                                bech32_err, hex_err
-                            ),
-                        }),
-                    )
+                    BridgeError::InvalidInput(format!(
+                        "Invalid account_id format. Tried bech32 (mtst1...): {}. Tried hex: {}. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format.",
+                        bech32_err, hex_err
+                    ))
                 })?
             }
         }
@@ -660,8 +938,6 @@ fn generate_hash_internal(
             account_id_str
         };
         
-        eprintln!("DEBUG /deposit/hash: Received hex_str: '{}', length: {}", hex_str, hex_str.len());
-        
         // AccountId::from_hex expects 32 characters total (including 0x prefix)
         // So if hex is 30 chars, add 0x to make 32 total. If 32 chars, add 0x to make 34 (but that's wrong)
         // Actually, let's just add 0x prefix - it should handle the length
@@ -671,20 +947,13 @@ fn generate_hash_internal(
             hex_str.to_string()
         };
         
-        eprintln!("DEBUG /deposit/hash: Final hex with prefix: '{}', length: {}", hex_with_prefix, hex_with_prefix.len());
         AccountId::from_hex(&hex_with_prefix)
             .map_err(|e| {
-                eprintln!("DEBUG /deposit/hash: Failed to parse hex '{}': {:?}", hex_with_prefix, e);
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!(
-                            "Invalid hex account_id: {}. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format (with or without 0x prefix).",
-                            e
-                        ),
-                    }),
-                )
+                debug!(account_id = %hex_with_prefix, error = ?e, "Failed to parse hex account_id");
+                BridgeError::InvalidInput(format!(
+                    "Invalid hex account_id: {}. Please provide a valid Miden account ID in bech32 (mtst1...) or hex format (with or without 0x prefix).",
+                    e
+                ))
             })?
     };
     
@@ -697,26 +966,11 @@ fn generate_hash_internal(
     
     let secret = Word::try_from(secret_hex.as_str())
         .map_err(|e| {
-            status::Custom(
-                Status::BadRequest,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Invalid secret: {}", e),
-                }),
-            )
+            BridgeError::InvalidInput(format!("Invalid secret: {}", e))
         })?;
     
     // Build recipient and get hash
-    let recipient = build_deposit_recipient(account_id, secret)
-        .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to build recipient: {}", e),
-                }),
-            )
-        })?;
+    let recipient = build_deposit_recipient(account_id, secret)?;
     let recipient_hash = recipient.digest().to_hex();
     
     Ok(Json(HashResponse {
@@ -725,21 +979,207 @@ fn generate_hash_internal(
     }))
 }
 
+// Work out the recipient hash of a deposit address/intent request, and the credentials
+// (account_id, secret) the relayer needs to mint on its own if the user opted in
+fn resolve_deposit_recipient(
+    account_id: Option<&str>,
+    secret: Option<&str>,
+    recipient_hash: Option<&str>,
+    auto_mint: Option<bool>,
+) -> Result<(String, Option<(String, String)>), BridgeError> {
+    use rust_backend::bridge::memo::normalize_hex;
+    
+    let bad_request = BridgeError::InvalidInput;
+    
+    match (account_id, secret, recipient_hash) {
+        (Some(account_id), Some(secret), _) => {
+            let recipient_hash = generate_hash_internal(account_id, secret)?.into_inner().recipient_hash;
+            
+            let credentials = if auto_mint.unwrap_or(true) {
+                let account_id = account_id.trim();
+                let account_id_hex = match AccountId::from_bech32(account_id) {
+                    Ok((_, acc_id)) => acc_id.to_hex(),
+                    Err(_) => format!("0x{}", normalize_hex(account_id)),
+                };
+                Some((account_id_hex, format!("0x{}", normalize_hex(secret))))
+            } else {
+                None
+            };
+            
+            Ok((recipient_hash, credentials))
+        }
+        (None, None, Some(recipient_hash)) => {
+            let hex = normalize_hex(recipient_hash);
+            if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(bad_request("Invalid recipient_hash (expected 0x + 64 hex chars).".to_string()));
+            }
+            Ok((format!("0x{}", hex), None))
+        }
+        _ => Err(bad_request("Provide either account_id and secret, or recipient_hash.".to_string())),
+    }
+}
+
+#[options("/deposit/address")]
+fn options_deposit_address() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Hand out a fresh diversified unified address for one deposit
+// Deposits to it are attributed by address, so no memo is needed
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Get a fresh deposit address for one deposit",
+    request_body = DepositAddressRequest,
+    responses((status = 200, body = DepositAddressResponse), StateCreationErrors),
+)]
+#[post("/deposit/address", format = "json", data = "<request>")]
+async fn create_deposit_address(
+    _state_limit: StateCreationLimit,
+    state: &rocket::State<State>,
+    request: Json<DepositAddressRequest>,
+) -> Result<Json<DepositAddressResponse>, BridgeError> {
+    let (recipient_hash, credentials) = resolve_deposit_recipient(
+        request.account_id.as_deref(),
+        request.secret.as_deref(),
+        request.recipient_hash.as_deref(),
+        request.auto_mint,
+    )?;
+    
+    let address = state.bridge_wallet.generate_address()
+        .map_err(|e| e.context("Failed to generate deposit address"))?;
+    
+    let deposit_address = state.db()
+        .map_err(|e| e.to_string())
+        .and_then(|db| {
+            db.deposit_addresses().assign(
+                &address,
+                &recipient_hash,
+                credentials.as_ref().map(|(account_id, _)| account_id.as_str()),
+                credentials.as_ref().map(|(_, secret)| secret.as_str()),
+            )
+            .map_err(|e| format!("Failed to record deposit address: {}", e))
+        })
+        .map_err(BridgeError::Internal)?;
+    
+    let auto_mint = deposit_address.auto_mint();
+    let message = if auto_mint {
+        "Send TAZ to this address (no memo needed). The note is minted automatically once the deposit is seen."
+    } else {
+        "Send TAZ to this address (no memo needed), then claim it with POST /deposit/claim."
+    };
+    
+    Ok(Json(DepositAddressResponse {
+        address: deposit_address.address,
+        recipient_hash: deposit_address.recipient_hash,
+        auto_mint,
+        success: true,
+        message: format!("{} Use a new address for every deposit.", message),
+    }))
+}
+
+#[options("/deposit/intent")]
+fn options_deposit_intent() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Register a deposit intent - the returned short reference code replaces account_id|secret in the memo
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Create a deposit intent with a short memo reference code",
+    request_body = DepositIntentRequest,
+    responses((status = 200, body = DepositIntentResponse), StateCreationErrors),
+)]
+#[post("/deposit/intent", format = "json", data = "<request>")]
+fn create_deposit_intent(
+    _state_limit: StateCreationLimit,
+    state: &rocket::State<State>,
+    request: Json<DepositIntentRequest>,
+) -> Result<Json<DepositIntentResponse>, BridgeError> {
+    // Default 24h, capped at 7 days
+    const MAX_INTENT_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+    let default_ttl = std::env::var("DEPOSIT_INTENT_TTL_SECS")
+        .ok()
+        .and_then(|ttl| ttl.parse::<i64>().ok())
+        .unwrap_or(24 * 60 * 60);
+    let ttl_secs = request.expires_in_secs.unwrap_or(default_ttl);
+    
+    if ttl_secs <= 0 || ttl_secs > MAX_INTENT_TTL_SECS {
+        return Err(BridgeError::InvalidInput(format!("expires_in_secs must be between 1 and {}.", MAX_INTENT_TTL_SECS)));
+    }
+    
+    if request.expected_amount == Some(0) {
+        return Err(BridgeError::InvalidInput("expected_amount must be positive.".to_string()));
+    }
+    
+    let (recipient_hash, credentials) = resolve_deposit_recipient(
+        request.account_id.as_deref(),
+        request.secret.as_deref(),
+        request.recipient_hash.as_deref(),
+        request.auto_mint,
+    )?;
+    
+    let intent = state.db()
+        .map_err(|e| e.to_string())
+        .and_then(|db| {
+            db.intents().create(
+                &recipient_hash,
+                credentials.as_ref().map(|(account_id, _)| account_id.as_str()),
+                credentials.as_ref().map(|(_, secret)| secret.as_str()),
+                request.expected_amount,
+                ttl_secs,
+            )
+            .map_err(|e| format!("Failed to record deposit intent: {}", e))
+        })
+        .map_err(BridgeError::Internal)?;
+    
+    Ok(Json(DepositIntentResponse::from_intent(intent)))
+}
+
+// Check a deposit intent - lets the UI show "waiting for your deposit"
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Look up a deposit intent",
+    responses((status = 200, body = DepositIntentResponse), BridgeError),
+)]
+#[get("/deposit/intent/<code>")]
+fn get_deposit_intent(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    code: &str,
+) -> Result<Json<DepositIntentResponse>, BridgeError> {
+    let intent = state.db()
+        .map_err(|e| e.to_string())
+        .and_then(|db| db.intents().get(code)
+            .map_err(|e| format!("Failed to lookup deposit intent: {}", e)))
+        .map_err(BridgeError::Internal)?
+        .ok_or_else(|| BridgeError::NotFound("No deposit intent found for this code.".to_string()))?;
+    
+    Ok(Json(DepositIntentResponse::from_intent(intent)))
+}
+
 #[options("/deposit/claim")]
 fn options_claim() -> rocket::http::Status {
     rocket::http::Status::Ok
 }
 
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Claim a deposit by minting its note",
+    request_body = ClaimDepositRequest,
+    responses((status = 200, body = ClaimDepositResponse), BridgeError),
+)]
 #[post("/deposit/claim", format = "json", data = "<request>")]
+#[tracing::instrument(name = "deposit", skip_all, fields(txid = tracing::field::Empty, pool = tracing::field::Empty, output_index = tracing::field::Empty))]
 async fn claim_deposit_endpoint(
+    _rate_limit: RateLimit,
     state: &rocket::State<State>,
     request: Json<ClaimDepositRequest>,
-) -> Result<Json<ClaimDepositResponse>, String> {
+) -> Result<Json<ClaimDepositResponse>, BridgeError> {
     // Parse account_id and secret - handle both hex and bech32 formats
     let account_id = if request.account_id.starts_with("mtst") || request.account_id.starts_with("mm") {
         // Parse bech32 format (e.g., mtst1...) - returns (NetworkId, AccountId)
         let (_, acc_id) = AccountId::from_bech32(&request.account_id)
-            .map_err(|e| format!("Invalid bech32 account_id: {}", e))?;
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid bech32 account_id: {}", e)))?;
         acc_id
     } else {
         // Parse hex format - check if it starts with 0x
@@ -757,7 +1197,7 @@ async fn claim_deposit_endpoint(
         };
         
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Invalid hex account_id: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid hex account_id: {}", e)))?
     };
     
     // Parse secret - Word::try_from expects hex with 0x prefix
@@ -768,26 +1208,21 @@ async fn claim_deposit_endpoint(
     };
     
     let secret = Word::try_from(secret_hex.as_str())
-        .map_err(|e| format!("Invalid secret: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid secret: {}", e)))?;
     
     // Rebuild recipient hash to scan for deposits
-    let recipient = build_deposit_recipient(account_id, secret)
-        .map_err(|e| format!("Failed to build recipient: {}", e))?;
+    let recipient = build_deposit_recipient(account_id, secret)?;
     let recipient_hash = recipient.digest().to_hex();
     
     // Check if this recipient hash has already been claimed (double-spend protection)
     {
-        let tracker = state.deposit_tracker.lock()
-            .map_err(|e| format!("Failed to lock deposit tracker: {}", e))?;
+        let db = state.db()?;
         
-        if tracker.is_claimed(&recipient_hash)
-            .map_err(|e| format!("Failed to check claim status: {}", e))? {
-            return Ok(Json(ClaimDepositResponse {
-                success: false,
-                note_id: None,
-                transaction_id: None,
-                message: "This deposit has already been claimed. Each recipient hash can only be used once.".to_string(),
-            }));
+        if db.deposits().is_claimed(&recipient_hash)
+            .map_err(|e| e.context("Failed to check claim status"))? {
+            return Err(BridgeError::AlreadyClaimed(
+                "This deposit has already been claimed. Each recipient hash can only be used once.".to_string(),
+            ));
         }
     } // Lock released here
     
@@ -795,21 +1230,71 @@ async fn claim_deposit_endpoint(
     let bridge_address = std::env::var("BRIDGE_ZCASH_ADDRESS")
         .unwrap_or_else(|_| "utest1s7vrs7ycxvpu379zvtxt0fnc0efseur2f8g2s8puqls7nk45l6p7wvglu3rph9us9qzsjww44ly3wxlsul0jcpqx8qwvwqz4sq48rjj0cn59956sjsrz5ufuswd5ujy89n3vh264wx3843pxscnrf0ulku4990h65h5ll9r0j3q82mjgm2sx7lfnrkfkuqw9l2m7yfmgc4jvzq6n8j2".to_string());
     
+    // Diversified addresses handed out for this recipient also identify its deposits
+    let deposit_addresses = state.db()?
+        .deposit_addresses()
+        .addresses_for_recipient(&recipient_hash)
+        .map_err(|e| e.context("Failed to lookup deposit addresses"))?;
+    
+    // So do the reference codes of its deposit intents
+    let reference_codes = state.db()?
+        .intents()
+        .codes_for_recipient(&recipient_hash)
+        .map_err(|e| e.context("Failed to lookup deposit intents"))?;
+    
     let deposit_info = rust_backend::bridge::deposit::scan_zcash_deposits(
         &state.bridge_wallet,
         &recipient_hash,
         &bridge_address,
+        &deposit_addresses,
+        &reference_codes,
     )
     .await
-    .map_err(|e| format!("Failed to scan deposits: {}", e))?;
+    .map_err(|e| e.context("Failed to scan deposits"))?;
     
-    let (txid, amount) = deposit_info.ok_or_else(|| {
-        "No deposit found with matching recipient hash. Make sure you've sent TAZ to the bridge address with the correct memo.".to_string()
+    let deposit_output = deposit_info.ok_or_else(|| {
+        BridgeError::NotFound("No deposit found with matching recipient hash. Make sure you've sent TAZ to the bridge address with the correct memo.".to_string())
     })?;
+    let amount = deposit_output.value;
+    tracing::Span::current()
+        .record("txid", deposit_output.txid.as_str())
+        .record("pool", deposit_output.pool.as_str())
+        .record("output_index", deposit_output.index);
+    
+    // Rejected deposits (e.g. sent after their intent expired) can only be refunded
+    if state.db()?
+        .refunds()
+        .is_rejected(&deposit_output.txid, &deposit_output.pool, deposit_output.index)
+        .map_err(|e| e.context("Failed to check rejected deposits"))? {
+        return Err(BridgeError::Conflict(format!(
+            "Deposit output {} was rejected. Request a refund with POST /deposit/refund.",
+            deposit_output.key()
+        )));
+    }
+    
+    // Each output can only be claimed once, even if several outputs share a transaction
+    {
+        let db = state.db()?;
+        
+        if db.deposits().is_output_claimed(&deposit_output.txid, &deposit_output.pool, deposit_output.index)
+            .map_err(|e| e.context("Failed to check claim status"))? {
+            return Err(BridgeError::AlreadyClaimed(format!(
+                "Deposit output {} has already been claimed.",
+                deposit_output.key()
+            )));
+        }
+    } // Lock released here
+    
+    // Minting halts while reserves don't cover the outstanding wTAZ supply
+    state.solvency.ensure_solvent().await?;
+    
+    // Deduct the deposit fee - the note is minted for the net amount
+    let quote = state.fee_schedule.quote(FeeDirection::Deposit, ZCASH_ORIGIN_NETWORK, amount)?;
+    let net_amount = quote.net_amount;
     
     // Get or create faucet automatically (auto-deploy on first deposit)
     let current_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get current directory: {}", e)))?;
     
     // If we're in rust-backend, go up one level to project root
     let project_root = if current_dir.file_name()
@@ -823,7 +1308,6 @@ async fn claim_deposit_endpoint(
     
     let keystore_path = project_root.join("rust-backend").join("keystore");
     let store_path = project_root.join("bridge_store.sqlite3");
-    let faucet_store_path = project_root.join("faucets.db");
     let rpc_url = std::env::var("RPC_URL")
         .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
     
@@ -831,7 +1315,7 @@ async fn claim_deposit_endpoint(
     let faucet_id = tokio::task::spawn_blocking({
         let keystore_path = keystore_path.clone();
         let store_path = store_path.clone();
-        let faucet_store_path = faucet_store_path.clone();
+        let db = state.db.clone();
         let rpc_url = rpc_url.clone();
         move || {
             let rt = tokio::runtime::Handle::current();
@@ -840,48 +1324,149 @@ async fn claim_deposit_endpoint(
                     keystore_path,
                     store_path,
                     &rpc_url,
-                    faucet_store_path,
+                    &db,
                 )
                 .await
             })
         }
     })
     .await
-    .map_err(|e| format!("Spawn blocking error: {}", e))?
-    .map_err(|e: String| format!("Get or create faucet error: {}", e))?;
+    .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+    .map_err(|e| e.context("Get or create faucet error"))?;
+    
+    // Reserve the output before minting, so of two concurrent claims (or a claim and the
+    // relayer) only one mints; the reservation is confirmed or released below
+    let reserved = state.db()?
+        .deposits()
+        .reserve_claim(&recipient_hash, &deposit_output.txid, &deposit_output.pool, deposit_output.index, amount)
+        .map_err(|e| e.context("Failed to reserve claim"))?;
+    if !reserved {
+        return Err(BridgeError::AlreadyClaimed(format!(
+            "Deposit output {} has already been claimed.",
+            deposit_output.key()
+        )));
+    }
+    let release_claim = || {
+        let released = state.db()
+            .and_then(|db| db.deposits().release_claim(&deposit_output.txid, &deposit_output.pool, deposit_output.index));
+        if let Err(e) = released {
+            error!(error = %e, "Failed to release deposit claim reservation");
+        }
+    };
     
-    // Claim the deposit by minting note to user's account
+    // Claim the deposit by minting note to user's account; with approvers configured the
+    // claim opens a mint proposal that relayers sign after checking the deposit themselves
     // Wrap in spawn_blocking to handle Send/Sync issues with Miden client
-    let (note_id, tx_id) = tokio::task::spawn_blocking(move || {
+    let mint_timer = metrics::MINT_LATENCY.start_timer();
+    let span = tracing::Span::current();
+    let multisig = state.multisig.clone();
+    let view = DepositView {
+        deposit: deposit_output.key(),
+        recipient_hash: recipient_hash.clone(),
+        amount,
+        net_amount,
+    };
+    let db = state.db.clone();
+    let mint_result = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async {
-            rust_backend::bridge::deposit::mint_deposit_note(
-                account_id,
-                secret,
-                faucet_id,
-                amount,
-                keystore_path,
-                store_path,
-                &rpc_url,
-            )
-            .await
-        })
+            match multisig {
+                Some(coordinator) => coordinator.contribute(&db, faucet_id, &view, true).await,
+                None => rust_backend::bridge::deposit::mint_deposit_note(
+                    account_id,
+                    secret,
+                    faucet_id,
+                    net_amount,
+                    keystore_path,
+                    store_path,
+                    &rpc_url,
+                )
+                .await
+                .map(|(note_id, tx_id)| MintProgress::Submitted { note_id, tx_id }),
+            }
+        }.instrument(span))
     })
     .await
-    .map_err(|e| format!("Spawn blocking error: {}", e))?
-    .map_err(|e: String| format!("Mint deposit note error: {}", e))?;
+    .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))
+    .and_then(|result| result.map_err(|e| e.context("Mint deposit note error")));
+    mint_timer.observe_duration();
+    
+    let (note_id, tx_id) = match mint_result {
+        Ok(MintProgress::Submitted { note_id, tx_id }) => {
+            metrics::DEPOSITS_MINTED.inc();
+            (note_id, tx_id)
+        }
+        // The mint proposal keeps it to one mint from here; whoever submits it records the claim
+        Ok(MintProgress::Pending { signatures, threshold }) => {
+            release_claim();
+            return Ok(Json(ClaimDepositResponse {
+                success: true,
+                note_id: None,
+                transaction_id: None,
+                message: format!(
+                    "Deposit verified. Its mint is waiting for bridge approvers ({} of {} signatures); the note is minted once enough of them sign.",
+                    signatures, threshold
+                ),
+            }));
+        }
+        Ok(MintProgress::SubmittedElsewhere) => {
+            release_claim();
+            return Ok(Json(ClaimDepositResponse {
+                success: true,
+                note_id: None,
+                transaction_id: None,
+                message: "Deposit verified. Its mint was submitted by a bridge relayer; sync your wallet to receive the note.".to_string(),
+            }));
+        }
+        Err(e) => {
+            release_claim();
+            metrics::DEPOSITS_FAILED.with_label_values(&["mint_error"]).inc();
+            publish_event(state, |events| events.record_deposit(
+                &deposit_output.txid,
+                &deposit_output.pool,
+                deposit_output.index,
+                Some(&recipient_hash),
+                EventState::MintFailed,
+                None,
+                Some(e.code()),
+            ));
+            return Err(e);
+        }
+    };
     
-    // Record the claim to prevent double-spending
+    // Confirm the reserved claim, together with its fee
     // NOTE: We only store recipient_hash, NOT account_id, for privacy
-    let tracker = state.deposit_tracker.lock()
-        .map_err(|e| format!("Failed to lock deposit tracker: {}", e))?;
+    state.db()?.transaction(|db| {
+        let confirmed = db.deposits()
+            .confirm_claim(&deposit_output.txid, &deposit_output.pool, deposit_output.index)
+            .map_err(|e| e.context("Failed to record claim"))?;
+        if !confirmed {
+            warn!(note_id = %note_id, "Deposit claim reservation was gone when its mint finished");
+        }
+        
+        db.fees().record_fee(&quote, &deposit_output.key())
+            .map_err(|e| e.context("Failed to record fee"))?;
+        
+        let inputs = serde_json::json!({
+            "recipient_hash": recipient_hash,
+            "deposit": deposit_output.key(),
+            "amount": amount,
+            "fee": quote.fee,
+        });
+        db.audit().append(AuditAction::DepositClaim, &inputs_digest(&inputs), Some(&note_id), "api")
+            .map_err(|e| e.context("Failed to append audit entry"))?;
+        Ok(())
+    })?;
     
-    tracker.record_claim(
-        &recipient_hash,
-        &txid.clone(),
-        amount,
-    )
-    .map_err(|e| format!("Failed to record claim: {}", e))?;
+    publish_event(state, |events| events.record_deposit(
+        &deposit_output.txid,
+        &deposit_output.pool,
+        deposit_output.index,
+        Some(&recipient_hash),
+        EventState::Minted,
+        Some(&note_id),
+        Some(&tx_id),
+    ));
     
     Ok(Json(ClaimDepositResponse {
         success: true,
@@ -891,15 +1476,195 @@ async fn claim_deposit_endpoint(
     }))
 }
 
+// Find the rejected output a refund call refers to
+// pool/output_index may be omitted when only one output of the transaction was rejected
+fn find_rejected_output(
+    refund_queue: &dyn Refunds,
+    txid: &str,
+    pool: Option<&str>,
+    output_index: Option<u32>,
+) -> Result<RejectedDeposit, BridgeError> {
+    let lookup_error = |e: BridgeError| e.context("Failed to lookup rejected deposit");
+    
+    let mut candidates = match (pool, output_index) {
+        (Some(pool), Some(output_index)) => refund_queue
+            .get_rejected(txid, &pool.to_lowercase(), output_index)
+            .map_err(lookup_error)?
+            .into_iter()
+            .collect(),
+        _ => refund_queue.get_rejected_for_tx(txid)
+            .map_err(lookup_error)?
+            .into_iter()
+            .filter(|d| pool.map_or(true, |p| d.pool.eq_ignore_ascii_case(p)))
+            .filter(|d| output_index.map_or(true, |i| d.output_index == i))
+            .collect::<Vec<_>>(),
+    };
+    
+    match candidates.len() {
+        0 => Err(BridgeError::NotFound("No rejected deposit found for this txid.".to_string())),
+        1 => Ok(candidates.remove(0)),
+        _ => Err(BridgeError::InvalidInput(format!(
+            "Several outputs of this transaction were rejected ({}). Specify pool and output_index.",
+            candidates.iter()
+                .map(|d| format!("{}:{}", d.pool, d.output_index))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+#[options("/deposit/refund")]
+fn options_deposit_refund() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Request a refund for a deposit the relayer could not mint
+// Ownership is proven by the memo or secret, unless the memo already named a refund address
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Request a refund for a rejected deposit",
+    request_body = RefundRequest,
+    responses((status = 200, body = RefundResponse), BridgeError),
+)]
+#[post("/deposit/refund", format = "json", data = "<request>")]
+async fn request_deposit_refund(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    request: Json<RefundRequest>,
+) -> Result<Json<RefundResponse>, BridgeError> {
+    use rust_backend::bridge::memo::{knowledge_digest, memo_content, normalize_hex};
+    
+    let db = state.db()?;
+    let refund_queue = db.refunds();
+    
+    let rejected = find_rejected_output(
+        &refund_queue,
+        request.txid.trim(),
+        request.pool.as_deref(),
+        request.output_index,
+    )?;
+    
+    if matches!(rejected.status, RefundStatus::Approved | RefundStatus::Refunded | RefundStatus::Unrefundable) {
+        return Err(BridgeError::Conflict(format!("Refund is already {}.", rejected.status.as_str())));
+    }
+    
+    let refund_address = if let Some(memo_address) = &rejected.memo_refund_address {
+        // The memo fixed the refund address, so no further proof is needed
+        if let Some(requested_address) = &request.refund_address {
+            if requested_address.trim() != memo_address {
+                return Err(BridgeError::InvalidInput("This deposit's memo names a refund address; refunds can only go there.".to_string()));
+            }
+        }
+        memo_address.clone()
+    } else {
+        let memo_proof = request.memo.as_deref()
+            .map(|memo| knowledge_digest(memo_content(memo)));
+        let secret_proof = request.secret.as_deref()
+            .map(|secret| knowledge_digest(&normalize_hex(secret)));
+        
+        let proven = (memo_proof.is_some() && memo_proof == rejected.memo_digest)
+            || (secret_proof.is_some() && secret_proof == rejected.secret_digest);
+        
+        if !proven {
+            return Err(BridgeError::Forbidden("Could not verify ownership. Provide the exact memo or the secret used for this deposit.".to_string()));
+        }
+        
+        request.refund_address.as_deref()
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .ok_or_else(|| BridgeError::InvalidInput("Missing refund_address.".to_string()))?
+    };
+    
+    if !is_testnet_address(&refund_address) {
+        return Err(BridgeError::InvalidInput(format!("Invalid Zcash testnet refund address: {}", refund_address)));
+    }
+    
+    let requested = refund_queue.request_refund(&rejected.txid, &rejected.pool, rejected.output_index, &refund_address)
+        .map_err(|e| e.context("Failed to record refund request"))?;
+    drop(db);
+    
+    if !requested {
+        return Err(BridgeError::Conflict("Refund could not be requested; it may have been approved in the meantime.".to_string()));
+    }
+    
+    publish_event(state, |events| events.record_deposit(
+        &rejected.txid,
+        &rejected.pool,
+        rejected.output_index,
+        None,
+        EventState::RefundRequested,
+        None,
+        None,
+    ));
+    
+    Ok(Json(RefundResponse {
+        txid: rejected.txid,
+        pool: rejected.pool,
+        output_index: rejected.output_index,
+        amount: rejected.amount,
+        reason: rejected.reason,
+        status: RefundStatus::Requested,
+        refund_address: Some(refund_address),
+        refund_txid: None,
+        success: true,
+        message: "Refund requested. It will be sent once an operator approves it.".to_string(),
+    }))
+}
+
+// Check the state of a rejected deposit and its refund
+#[utoipa::path(
+    tag = "deposit",
+    summary = "Status of a rejected deposit and its refund",
+    responses((status = 200, body = RefundResponse), BridgeError),
+)]
+#[get("/deposit/refund/<txid>?<pool>&<output_index>")]
+fn get_deposit_refund(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    txid: &str,
+    pool: Option<&str>,
+    output_index: Option<u32>,
+) -> Result<Json<RefundResponse>, BridgeError> {
+    let rejected = find_rejected_output(state.db()?.refunds(), txid.trim(), pool, output_index)?;
+    
+    let message = match rejected.status {
+        RefundStatus::Rejected => "Deposit was rejected. Request a refund with POST /deposit/refund.",
+        RefundStatus::Requested => "Refund requested, waiting for operator approval.",
+        RefundStatus::Approved => "Refund approved, waiting to be sent.",
+        RefundStatus::Refunded => "Refund sent.",
+        RefundStatus::Unrefundable => "Deposit is too small to cover the refund fee and cannot be refunded.",
+    };
+    
+    Ok(Json(RefundResponse {
+        txid: rejected.txid,
+        pool: rejected.pool,
+        output_index: rejected.output_index,
+        amount: rejected.amount,
+        reason: rejected.reason,
+        status: rejected.status,
+        refund_address: rejected.refund_address,
+        refund_txid: rejected.refund_txid,
+        success: true,
+        message: message.to_string(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "note",
+    summary = "Rebuild a deposit note and its ID",
+    request_body = ReconstructNoteRequest,
+    responses((status = 200, body = ReconstructNoteResponse), BridgeError),
+)]
 #[post("/note/reconstruct", format = "json", data = "<request>")]
 async fn reconstruct_note_endpoint(
+    _rate_limit: RateLimit,
     _state: &rocket::State<State>,
     request: Json<ReconstructNoteRequest>,
-) -> Result<Json<ReconstructNoteResponse>, String> {
+) -> Result<Json<ReconstructNoteResponse>, BridgeError> {
     // Parse account_id
     let account_id = if request.account_id.starts_with("mtst") || request.account_id.starts_with("mm") {
         let (_, acc_id) = AccountId::from_bech32(&request.account_id)
-            .map_err(|e| format!("Invalid bech32 account_id: {}", e))?;
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid bech32 account_id: {}", e)))?;
         acc_id
     } else {
         let hex_str = if request.account_id.starts_with("0x") {
@@ -909,7 +1674,7 @@ async fn reconstruct_note_endpoint(
         };
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Failed to parse account_id: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Failed to parse account_id: {}", e)))?
     };
     
     // Parse secret
@@ -919,12 +1684,12 @@ async fn reconstruct_note_endpoint(
         format!("0x{}", request.secret)
     };
     let secret = Word::try_from(secret_hex.as_str())
-        .map_err(|e| format!("Failed to parse secret: {}", e))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to parse secret: {}", e)))?;
     
     // Parse faucet_id
     let faucet_id = if request.faucet_id.starts_with("mtst") || request.faucet_id.starts_with("mm") {
         let (_, fid) = AccountId::from_bech32(&request.faucet_id)
-            .map_err(|e| format!("Invalid bech32 faucet_id: {}", e))?;
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid bech32 faucet_id: {}", e)))?;
         fid
     } else {
         let hex_str = if request.faucet_id.starts_with("0x") {
@@ -934,17 +1699,16 @@ async fn reconstruct_note_endpoint(
         };
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Failed to parse faucet_id: {}", e))?
+            .map_err(|e| BridgeError::InvalidInput(format!("Failed to parse faucet_id: {}", e)))?
     };
     
     // Reconstruct the note
     let note = reconstruct_deposit_note(account_id, secret, faucet_id, request.amount)
-        .map_err(|e| format!("Failed to reconstruct note: {:?}", e))?;
+        .map_err(|e| e.context("Failed to reconstruct note"))?;
     
     // Get note ID and recipient hash
     let note_id = note.id().to_hex();
-    let recipient = build_deposit_recipient(account_id, secret)
-        .map_err(|e| format!("Failed to build recipient: {:?}", e))?;
+    let recipient = build_deposit_recipient(account_id, secret)?;
     let recipient_hash = recipient.digest().to_hex();
     
     Ok(Json(ReconstructNoteResponse {
@@ -956,22 +1720,23 @@ async fn reconstruct_note_endpoint(
     }))
 }
 
+#[utoipa::path(
+    tag = "note",
+    summary = "Consume a deposit note into an account",
+    request_body = ConsumeNoteRequest,
+    responses((status = 200, body = ConsumeNoteResponse), BridgeError),
+)]
 #[post("/note/consume", format = "json", data = "<request>")]
 async fn consume_note_endpoint(
-    _state: &rocket::State<State>,
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
     request: Json<ConsumeNoteRequest>,
-) -> Result<Json<ConsumeNoteResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<ConsumeNoteResponse>, BridgeError> {
     // Parse account_id (accepts both bech32 and hex)
     let account_id = if request.account_id.starts_with("mtst") || request.account_id.starts_with("mm") {
         let (_, acc_id) = AccountId::from_bech32(&request.account_id)
             .map_err(|e| {
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!("Invalid bech32 account_id: {}", e),
-                    }),
-                )
+                BridgeError::InvalidInput(format!("Invalid bech32 account_id: {}", e))
             })?;
         acc_id
     } else {
@@ -983,13 +1748,7 @@ async fn consume_note_endpoint(
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
             .map_err(|e| {
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!("Failed to parse account_id: {}", e),
-                    }),
-                )
+                BridgeError::InvalidInput(format!("Failed to parse account_id: {}", e))
             })?
     };
     
@@ -1001,13 +1760,7 @@ async fn consume_note_endpoint(
     };
     let secret = Word::try_from(secret_hex.as_str())
         .map_err(|e| {
-            status::Custom(
-                Status::BadRequest,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to parse secret: {}", e),
-                }),
-            )
+            BridgeError::InvalidInput(format!("Failed to parse secret: {}", e))
         })?;
     
     // Check timestamp if provided (gives relayer time to process)
@@ -1020,13 +1773,7 @@ async fn consume_note_endpoint(
         // Parse ISO 8601 timestamp and convert to UTC
         let created_at = chrono::DateTime::parse_from_rfc3339(created_at_str)
             .map_err(|e| {
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!("Invalid created_at timestamp format: {}. Expected ISO 8601 (e.g., 2025-11-30T22:44:41.890Z)", e),
-                    }),
-                )
+                BridgeError::InvalidInput(format!("Invalid created_at timestamp format: {}. Expected ISO 8601 (e.g., 2025-11-30T22:44:41.890Z)", e))
             })?
             .with_timezone(&chrono::Utc);
         
@@ -1036,86 +1783,33 @@ async fn consume_note_endpoint(
         
         if elapsed_minutes < min_wait_minutes as i64 {
             let remaining = min_wait_minutes as i64 - elapsed_minutes;
-            return Err(status::Custom(
-                Status::BadRequest,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!(
-                        "Note is too new. Please wait {} more minute(s) before consuming. (Note created {} minute(s) ago, minimum wait: {} minutes)",
-                        remaining,
-                        elapsed_minutes,
-                        min_wait_minutes
-                    ),
-                }),
-            ));
+            return Err(BridgeError::InvalidInput(format!(
+                "Note is too new. Please wait {} more minute(s) before consuming. (Note created {} minute(s) ago, minimum wait: {} minutes)",
+                remaining,
+                elapsed_minutes,
+                min_wait_minutes
+            )));
         }
         
-        println!("[Consume Note] Timestamp check passed: note is {} minute(s) old (minimum: {} minutes)", elapsed_minutes, min_wait_minutes);
+        debug!(elapsed_minutes, min_wait_minutes, "Note age check passed");
     }
     
-    // Always get faucet_id from faucets.db (ignore faucet_id in .mno file)
-    println!("[Consume Note] Getting faucet_id from faucets.db...");
-    let current_dir = std::env::current_dir()
+    // Always get faucet_id from the bridge database (ignore faucet_id in .mno file)
+    let faucet_id = state.db()?
+        .faucets()
+        .get_faucet_id("zcash_testnet")
         .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to get current directory: {}", e),
-                }),
-            )
-        })?;
-    let project_root = if current_dir.file_name()
-        .and_then(|n| n.to_str())
-        .map(|n| n == "rust-backend")
-        .unwrap_or(false) {
-        current_dir.parent().unwrap().to_path_buf()
-    } else {
-        current_dir
-    };
-    let faucet_store_path = project_root.join("faucets.db");
-    use rust_backend::db::faucets::FaucetStore;
-    let faucet_store = FaucetStore::new(faucet_store_path)
-        .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to open faucet store: {}", e),
-                }),
-            )
-        })?;
-    let faucet_id = faucet_store.get_faucet_id("zcash_testnet")
-        .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to get faucet from store: {}", e),
-                }),
-            )
+            e.context("Failed to get faucet from store")
         })?
         .ok_or_else(|| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: "Faucet not found in store. Please create a faucet first.".to_string(),
-                }),
-            )
+            BridgeError::Internal("Faucet not found in store. Please create a faucet first.".to_string())
         })?;
-    println!("[Consume Note] Using faucet_id from faucets.db: {}", faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet));
+    debug!(faucet_id = %faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet), "Using faucet_id from bridge database");
     
     // Setup paths (same logic as init_client)
     let current_dir = std::env::current_dir()
         .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to get current directory: {}", e),
-                }),
-            )
+            BridgeError::Internal(format!("Failed to get current directory: {}", e))
         })?;
     
     // If we're in rust-backend, go up one level to project root
@@ -1138,6 +1832,7 @@ async fn consume_note_endpoint(
         let keystore_path = keystore_path.clone();
         let store_path = store_path.clone();
         let rpc_url = rpc_url.clone();
+        let span = tracing::Span::current();
         move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
             rt.block_on(async {
@@ -1151,28 +1846,14 @@ async fn consume_note_endpoint(
                     &rpc_url,
                 )
                 .await
-            })
+            }.instrument(span))
         }
     })
     .await
     .map_err(|e| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Spawn blocking error: {}", e),
-            }),
-        )
+        BridgeError::Internal(format!("Spawn blocking error: {}", e))
     })?
-    .map_err(|e: String| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Consume note error: {}", e),
-            }),
-        )
-    })?;
+    .map_err(|e| e.context("Consume note error"))?;
     
     Ok(Json(ConsumeNoteResponse {
         transaction_id: tx_id,
@@ -1183,6 +1864,7 @@ async fn consume_note_endpoint(
 }
 
 // Helper function to consume a deposit note (extracted from consume_note.rs pattern)
+#[tracing::instrument(name = "note", skip_all, fields(note_id = tracing::field::Empty))]
 async fn consume_deposit_note(
     account_id: AccountId,
     secret: Word,
@@ -1191,110 +1873,102 @@ async fn consume_deposit_note(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-) -> Result<(String, String), String> {
+) -> BridgeResult<(String, String)> {
     use miden_client::transaction::TransactionRequestBuilder;
     use miden_objects::note::NoteTag;
     
     // Initialize Miden client
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
     
-    if !keystore_path.exists() {
-        return Err(format!("Keystore directory does not exist: {:?}", keystore_path));
-    }
-    
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore at {:?}: {}", keystore_path, e))?,
-    );
+    // The keystore, or the signer process if SIGNER_SOCKET is set
+    let signer = BridgeSigner::open(&keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path)
-        .authenticator(keystore)
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Add bridge note tag
     use rust_backend::miden::notes::BRIDGE_USECASE;
     client.add_note_tag(NoteTag::for_local_use_case(BRIDGE_USECASE, 0).expect("Bridge use case tag should be valid"))
         .await
-        .map_err(|e| format!("Failed to add note tag: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to add note tag: {}", e)))?;
     
     // Sync state
-    client.sync_state().await
-        .map_err(|e| format!("Failed to sync client state: {}", e))?;
+    let sync_summary = client.sync_state().await
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+    metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
     
     // Check if account exists
     let wallet_account = client.get_account(account_id).await
-        .map_err(|e| format!("Failed to get account: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get account: {}", e)))?;
     
     if wallet_account.is_none() {
-        return Err(format!(
+        return Err(BridgeError::NotFound(format!(
             "Account {} not found in client store. The account must be created and added to the client first.",
             account_id.to_bech32(miden_objects::address::NetworkId::Testnet)
-        ));
+        )));
     }
     
     // Reconstruct the note
-    println!("[Consume Note] Reconstructing note...");
     let note = reconstruct_deposit_note(account_id, secret, faucet_id, amount)
-        .map_err(|e| format!("Failed to reconstruct note: {:?}", e))?;
+        .map_err(|e| e.context("Failed to reconstruct note"))?;
     
     // Get note ID and commitment before moving the note
     let note_id = note.id();
     let note_id_hex = note_id.to_hex();
     let note_commitment = note.commitment();
-    println!("[Consume Note] Note reconstructed:");
-    println!("  Note ID: {}", note_id_hex);
-    println!("  Note Commitment: 0x{}", note_commitment.to_hex());
+    tracing::Span::current().record("note_id", note_id_hex.as_str());
+    debug!(note_commitment = %format!("0x{}", note_commitment.to_hex()), "Note reconstructed");
     
     // Build consume transaction using unauthenticated_input_notes
-    println!("[Consume Note] Building transaction...");
     let secret_word: miden_objects::Word = secret;
     let tx_request = TransactionRequestBuilder::new()
         .unauthenticated_input_notes([(note, Some(secret_word.into()))])
         .build()
         .map_err(|e| {
             let error_msg = format!("{:?}", e);
-            eprintln!("[Consume Note] Transaction build error: {}", error_msg);
-            format!("Failed to build transaction: {}", error_msg)
+            error!(error = %error_msg, "Consume transaction build failed");
+            BridgeError::Internal(format!("Failed to build transaction: {}", error_msg))
         })?;
-    println!("[Consume Note] Transaction built successfully");
     
     // Execute transaction (same pattern as mint_deposit_note)
-    println!("[Consume Note] Executing transaction...");
-    println!("  Account: {}", account_id.to_bech32(miden_objects::address::NetworkId::Testnet));
-    println!("  Note ID: {}", note_id_hex);
-    println!("  Faucet ID: {}", faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet));
-    println!("  Amount: {}", amount);
+    info!(
+        account_id = %account_id.to_bech32(miden_objects::address::NetworkId::Testnet),
+        faucet_id = %faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet),
+        amount,
+        "Executing consume transaction"
+    );
     
     let tx_result = client
         .execute_transaction(account_id, tx_request)
         .await
         .map_err(|e| {
             let error_msg = format!("{:?}", e);
-            eprintln!("[Consume Note] Transaction execution failed: {}", error_msg);
-            format!("Failed to execute transaction: {}", error_msg)
+            error!(error = %error_msg, "Consume transaction execution failed");
+            BridgeError::ProvingFailed(format!("Failed to execute transaction: {}", error_msg))
         })?;
     
     // Prove transaction
-    println!("[Consume Note] Proving transaction...");
+    debug!("Proving consume transaction");
     let proven_tx = client
         .prove_transaction(&tx_result)
         .await
         .map_err(|e| {
             let error_msg = format!("{:?}", e);
-            eprintln!("[Consume Note] Transaction proof failed: {}", error_msg);
-            format!("Failed to prove transaction: {}", error_msg)
+            error!(error = %error_msg, "Consume transaction proof failed");
+            BridgeError::ProvingFailed(format!("Failed to prove transaction: {}", error_msg))
         })?;
     
     // Submit proven transaction
-    println!("[Consume Note] Submitting proven transaction...");
+    debug!("Submitting proven consume transaction");
     let submission_height = client
         .submit_proven_transaction(proven_tx, &tx_result)
         .await
@@ -1302,10 +1976,8 @@ async fn consume_deposit_note(
             // Format the error with full details
             let error_debug = format!("{:?}", e);
             let error_display = format!("{}", e);
-            eprintln!("[Consume Note] Transaction submission failed!");
-            eprintln!("  Error (Display): {}", error_display);
-            eprintln!("  Error (Debug): {}", error_debug);
-            format!("Failed to submit transaction: {}", error_debug)
+            error!(error = %error_display, details = %error_debug, "Consume transaction submission failed");
+            BridgeError::TransactionFailed(format!("Failed to submit transaction: {}", error_debug))
         })?;
     
     // Apply transaction (updates account state in SQLite store)
@@ -1314,20 +1986,18 @@ async fn consume_deposit_note(
         .await
         .map_err(|e| {
             let error_msg = format!("{:?}", e);
-            eprintln!("[Consume Note] Transaction apply failed: {}", error_msg);
-            format!("Failed to apply transaction: {}", error_msg)
+            error!(error = %error_msg, "Consume transaction apply failed");
+            BridgeError::Internal(format!("Failed to apply transaction: {}", error_msg))
         })?;
     
     // Sync state to ensure balance is updated in SQLite store
-    println!("[Consume Note] Syncing state to update balance...");
-    client.sync_state().await
-        .map_err(|e| format!("Failed to sync state after consumption: {}", e))?;
+    let sync_summary = client.sync_state().await
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync state after consumption: {}", e)))?;
+    metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
     
     let tx_id = tx_result.executed_transaction().id().to_hex();
     
-    println!("[Consume Note] Transaction submitted and state synced successfully!");
-    println!("  TX ID: 0x{}", tx_id);
-    println!("  Balance should now be updated in SQLite store");
+    info!(tx_id = %format!("0x{}", tx_id), "Consume transaction submitted and state synced");
     
     Ok((tx_id, note_id_hex))
 }
@@ -1337,22 +2007,23 @@ fn options_account_balance() -> rocket::http::Status {
     rocket::http::Status::Ok
 }
 
+#[utoipa::path(
+    tag = "account",
+    summary = "wTAZ balance of an account",
+    request_body = BalanceRequest,
+    responses((status = 200, body = BalanceResponse), BridgeError),
+)]
 #[post("/account/balance", format = "json", data = "<request>")]
 async fn get_account_balance(
-    _state: &rocket::State<State>,
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
     request: Json<BalanceRequest>,
-) -> Result<Json<BalanceResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<BalanceResponse>, BridgeError> {
     // Parse account_id (accepts both bech32 and hex)
     let account_id = if request.account_id.starts_with("mtst") || request.account_id.starts_with("mm") {
         let (_, acc_id) = AccountId::from_bech32(&request.account_id)
             .map_err(|e| {
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!("Invalid bech32 account_id: {}", e),
-                    }),
-                )
+                BridgeError::InvalidInput(format!("Invalid bech32 account_id: {}", e))
             })?;
         acc_id
     } else {
@@ -1364,26 +2035,14 @@ async fn get_account_balance(
         let hex_with_prefix = format!("0x{}", hex_str);
         AccountId::from_hex(&hex_with_prefix)
             .map_err(|e| {
-                status::Custom(
-                    Status::BadRequest,
-                    Json(ErrorResponse {
-                        success: false,
-                        error: format!("Failed to parse account_id: {}", e),
-                    }),
-                )
+                BridgeError::InvalidInput(format!("Failed to parse account_id: {}", e))
             })?
     };
     
     // Setup paths
     let current_dir = std::env::current_dir()
         .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to get current directory: {}", e),
-                }),
-            )
+            BridgeError::Internal(format!("Failed to get current directory: {}", e))
         })?;
     
     let project_root = if current_dir.file_name()
@@ -1400,37 +2059,15 @@ async fn get_account_balance(
     let rpc_url = std::env::var("RPC_URL")
         .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
     
-    // Always get faucet ID from faucets.db (same as consume endpoint)
-    use rust_backend::db::faucets::FaucetStore;
-    let faucet_store = FaucetStore::new(project_root.join("faucets.db"))
+    // Always get faucet ID from the bridge database (same as consume endpoint)
+    let faucet_id = state.db()?
+        .faucets()
+        .get_faucet_id("zcash_testnet")
         .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to open faucets.db: {}", e),
-                }),
-            )
-        })?;
-    
-    let faucet_id = faucet_store.get_faucet_id("zcash_testnet")
-        .map_err(|e| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Failed to get faucet from faucets.db: {}", e),
-                }),
-            )
+            e.context("Failed to get faucet from bridge database")
         })?
         .ok_or_else(|| {
-            status::Custom(
-                Status::InternalServerError,
-                Json(ErrorResponse {
-                    success: false,
-                    error: "Faucet ID not found in faucets.db. Please create a faucet first.".to_string(),
-                }),
-            )
+            BridgeError::Internal("Faucet ID not found in bridge database. Please create a faucet first.".to_string())
         })?;
     
     // Convert to hex for logging
@@ -1438,7 +2075,7 @@ async fn get_account_balance(
     let faucet_bytes = faucet_id.to_bytes();
     let faucet_id_hex = format!("0x{}", faucet_bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>());
     
-    println!("[Balance Endpoint] Using faucet ID from faucets.db: {}", faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet));
+    debug!(faucet_id = %faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet), "Using faucet ID from bridge database");
     
     // Get balance
     let balance_result = tokio::task::spawn_blocking({
@@ -1461,23 +2098,9 @@ async fn get_account_balance(
     })
     .await
     .map_err(|e| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Spawn blocking error: {}", e),
-            }),
-        )
+        BridgeError::Internal(format!("Spawn blocking error: {}", e))
     })?
-    .map_err(|e: String| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to get balance: {}", e),
-            }),
-        )
-    })?;
+    .map_err(|e| e.context("Failed to get balance"))?;
     
     Ok(Json(BalanceResponse {
         balance: balance_result.0,
@@ -1494,46 +2117,41 @@ async fn get_account_balance_helper(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-) -> Result<(String, u64), String> {
+) -> BridgeResult<(String, u64)> {
     // Initialize full client (needed for private accounts - they're stored locally, not queryable via RPC)
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
     
-    if !keystore_path.exists() {
-        return Err(format!("Keystore directory does not exist: {:?}", keystore_path));
-    }
-    
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore at {:?}: {}", keystore_path, e))?,
-    );
+    // The keystore, or the signer process if SIGNER_SOCKET is set
+    let signer = BridgeSigner::open(&keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path)
-        .authenticator(keystore)
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
     // Sync state to get latest account data
-    client.sync_state().await
-        .map_err(|e| format!("Failed to sync client state: {}", e))?;
+    let sync_summary = client.sync_state().await
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync client state: {}", e)))?;
+    metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
     
     // Get account from client store (works for both public and private accounts)
     // Private accounts are stored locally, not queryable via RPC
     let account_record = client.get_account(account_id).await
-        .map_err(|e| format!("Failed to get account from client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get account from client: {}", e)))?;
     
     let account_record = account_record
         .ok_or_else(|| {
-            format!(
+            BridgeError::NotFound(format!(
                 "Account {} not found in client store. The account must be created and added to the client first.",
                 account_id.to_bech32(miden_objects::address::NetworkId::Testnet)
-            )
+            ))
         })?;
     
     // Get the account object from AccountRecord
@@ -1542,11 +2160,11 @@ async fn get_account_balance_helper(
     let vault = account.vault();
     
     // Get balance for the faucet
-    println!("[Balance Helper] Getting balance for faucet: {}", faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet));
+    debug!(faucet_id = %faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet), "Getting balance");
     let balance = vault.get_balance(faucet_id)
-        .map_err(|e| format!("Failed to get balance from vault: {:?}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get balance from vault: {:?}", e)))?;
     
-    println!("[Balance Helper] Raw balance: {}", balance);
+    debug!(balance, "Raw balance");
     
     // Convert to tokens (8 decimals for wTAZ)
     // get_balance returns u64 directly
@@ -1566,11 +2184,18 @@ fn options_pool_balance() -> rocket::http::Status {
     rocket::http::Status::Ok
 }
 
+#[utoipa::path(
+    tag = "account",
+    summary = "Spendable TAZ in the bridge wallet",
+    request_body = PoolBalanceRequest,
+    responses((status = 200, body = PoolBalanceResponse), BridgeError),
+)]
 #[post("/pool/balance", format = "json", data = "<request>")]
 async fn get_pool_balance(
+    _rate_limit: RateLimit,
     state: &rocket::State<State>,
     request: Json<PoolBalanceRequest>,
-) -> Result<Json<PoolBalanceResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<PoolBalanceResponse>, BridgeError> {
     // Request is optional (can be empty JSON), we ignore it and use default faucet
     let _ = request;
     // Get Zcash bridge wallet balance (pool balance in TAZ)
@@ -1582,33 +2207,16 @@ async fn get_pool_balance(
     })
     .await
     .map_err(|e| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Spawn blocking error: {}", e),
-            }),
-        )
+        BridgeError::Internal(format!("Spawn blocking error: {}", e))
     })?
-    .map_err(|e: String| {
-        status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to get pool balance: {}", e),
-            }),
-        )
-    })?;
+    .map_err(|e| e.context("Failed to get pool balance"))?;
     
-    // Parse TAZ balance string to number
-    let balance_str = balance_result.spendable.trim();
-    let balance_num: f64 = balance_str.parse().unwrap_or(0.0);
+    // Parse TAZ balance string to base units (8 decimals for wTAZ, but TAZ uses 8 decimals too)
+    let balance_raw = parse_zatoshis(&balance_result.spendable)
+        .map_err(|e| e.context("Failed to parse pool balance"))?;
     
     // Format to 8 decimal places (TAZ uses 8 decimals)
-    let balance_formatted = format!("{:.8}", balance_num);
-    
-    // Convert to base units (8 decimals for wTAZ, but TAZ uses 8 decimals too)
-    let balance_raw = (balance_num * 1e8) as u64;
+    let balance_formatted = format!("{}.{:08}", balance_raw / 100_000_000, balance_raw % 100_000_000);
     
     Ok(Json(PoolBalanceResponse {
         balance: balance_formatted,
@@ -1618,8 +2226,186 @@ async fn get_pool_balance(
     }))
 }
 
+#[options("/reserves")]
+fn options_reserves() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Outstanding wTAZ against the bridge wallet's confirmed balance, checked at most every SOLVENCY_CHECK_INTERVAL_SECS
+#[utoipa::path(
+    tag = "account",
+    summary = "Outstanding wTAZ supply against the bridge wallet's reserves",
+    responses((status = 200, body = ReservesResponse), BridgeError),
+)]
+#[get("/reserves")]
+async fn get_reserves(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+) -> Result<Json<ReservesResponse>, BridgeError> {
+    let reserves = state.solvency.report().await
+        .map_err(|e| e.context("Failed to check reserves"))?;
+    
+    Ok(Json(ReservesResponse {
+        reserves,
+        success: true,
+    }))
+}
+
+#[options("/reserves/disclosure")]
+fn options_reserves_disclosure() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Only served when the operator opts in with RESERVES_DISCLOSURE=ufvk; `verify_reserves` scans with it
+#[utoipa::path(
+    tag = "account",
+    summary = "Viewing key for verifying the bridge's reserves independently",
+    responses((status = 200, body = ReservesDisclosureResponse), BridgeError),
+)]
+#[get("/reserves/disclosure")]
+fn get_reserves_disclosure(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+) -> Result<Json<ReservesDisclosureResponse>, BridgeError> {
+    let disclosure = state.reserves_disclosure.clone()
+        .ok_or_else(|| BridgeError::NotFound("This bridge does not publish its viewing key".to_string()))?;
+    
+    let faucet_id = state.db()?
+        .faucets()
+        .get_faucet_id(ZCASH_ORIGIN_NETWORK)
+        .map_err(|e| e.context("Failed to lookup faucet"))?
+        .map(|faucet_id| faucet_id.to_bech32(NetworkId::Testnet));
+    
+    Ok(Json(ReservesDisclosureResponse {
+        disclosure,
+        faucet_id,
+        success: true,
+    }))
+}
+
+#[options("/fees/quote")]
+fn options_fee_quote() -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+// Quote the bridge fee for a transfer before the user commits to it
+#[utoipa::path(
+    tag = "fees",
+    summary = "Quote the fee for a deposit or withdrawal",
+    responses((status = 200, body = FeeQuoteResponse), BridgeError),
+)]
+#[get("/fees/quote?<direction>&<amount>&<token>")]
+fn get_fee_quote(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    direction: Option<String>,
+    amount: Option<u64>,
+    token: Option<String>,
+) -> Result<Json<FeeQuoteResponse>, BridgeError> {
+    let direction = direction.ok_or_else(|| {
+        BridgeError::InvalidInput("Missing direction parameter (deposit or withdrawal)".to_string())
+    })?;
+    
+    let amount = amount.ok_or_else(|| {
+        BridgeError::InvalidInput("Missing amount parameter (base units)".to_string())
+    })?;
+    
+    let direction = FeeDirection::parse(&direction)?;
+    
+    let token = token.unwrap_or_else(|| ZCASH_ORIGIN_NETWORK.to_string());
+    
+    let quote = state.fee_schedule.quote(direction, &token, amount)?;
+    
+    Ok(Json(FeeQuoteResponse {
+        quote,
+        success: true,
+    }))
+}
+
+// Accrued fee totals by direction and token
+// Operator only - not mounted in production mode
+#[utoipa::path(
+    tag = "fees",
+    summary = "Fees collected per token and direction",
+    responses((status = 200, body = FeeSummaryResponse), BridgeError),
+    security(("api_key" = [])),
+)]
+#[get("/fees/summary")]
+fn get_fee_summary(
+    _auth: OperatorAuth,
+    state: &rocket::State<State>,
+) -> Result<Json<FeeSummaryResponse>, BridgeError> {
+    let fees = state.db()?
+        .fees()
+        .summary()
+        .map_err(|e| e.context("Failed to load fee summary"))?;
+    
+    Ok(Json(FeeSummaryResponse {
+        fees,
+        success: true,
+    }))
+}
+
+// Ledger of bridge flows for finance: everything as JSON, or one table as CSV
+// Operator only - not mounted in production mode
+#[utoipa::path(
+    tag = "fees",
+    summary = "Export deposits, withdrawals and daily totals for a date range",
+    params(
+        ("from" = String, Query, description = "First day, YYYY-MM-DD (UTC)"),
+        ("to" = String, Query, description = "Last day, YYYY-MM-DD (UTC); at most 366 days in all"),
+        ("format" = Option<String>, Query, description = "json (default) or csv"),
+        ("table" = Option<String>, Query, description = "Table to return as CSV: deposits, withdrawals or daily"),
+        ("include_private" = Option<bool>, Query, description = "Add recipient hashes and account ids of deposits"),
+    ),
+    responses(
+        (status = 200, description = "The export, or one of its tables as CSV", content(
+            (AccountingExportResponse = "application/json"),
+            (String = "text/csv"),
+        )),
+        BridgeError,
+    ),
+    security(("api_key" = [])),
+)]
+#[get("/accounting/export?<from>&<to>&<format>&<table>&<include_private>")]
+fn export_accounting(
+    _auth: OperatorAuth,
+    state: &rocket::State<State>,
+    from: Option<String>,
+    to: Option<String>,
+    format: Option<String>,
+    table: Option<String>,
+    include_private: Option<bool>,
+) -> Result<(rocket::http::ContentType, String), BridgeError> {
+    let (Some(from), Some(to)) = (from, to) else {
+        return Err(BridgeError::InvalidInput("Missing from or to parameter (YYYY-MM-DD)".to_string()));
+    };
+    let range = ExportRange::parse(&from, &to)?;
+    
+    let ledger = export(&**state.db()?, range, include_private.unwrap_or(false))
+        .map_err(|e| e.context("Failed to build accounting export"))?;
+    
+    match format.as_deref().unwrap_or("json") {
+        "json" => {
+            let body = rocket::serde::json::to_string(&AccountingExportResponse {
+                export: ledger,
+                success: true,
+            })
+            .map_err(|e| BridgeError::Internal(format!("Failed to serialize export: {}", e)))?;
+            Ok((rocket::http::ContentType::JSON, body))
+        }
+        "csv" => {
+            let table = table.ok_or_else(|| {
+                BridgeError::InvalidInput("Missing table parameter (deposits, withdrawals or daily)".to_string())
+            })?;
+            Ok((rocket::http::ContentType::CSV, ledger.to_csv(ExportTable::parse(&table)?)))
+        }
+        other => Err(BridgeError::InvalidInput(format!("Unknown format: {} (use json or csv)", other))),
+    }
+}
+
 // Helper function to parse account ID from string (bech32 or hex)
-fn parse_account_id(account_id_str: &str) -> Result<AccountId, String> {
+fn parse_account_id(account_id_str: &str) -> BridgeResult<AccountId> {
     if account_id_str.starts_with("mtst") || account_id_str.starts_with("mm") {
         // Try bech32 format first
         match AccountId::from_bech32(account_id_str) {
@@ -1632,7 +2418,7 @@ fn parse_account_id(account_id_str: &str) -> Result<AccountId, String> {
                     account_id_str
                 };
                 AccountId::from_hex(hex_str).map_err(|hex_err| {
-                    format!("Tried bech32: {}. Tried hex: {}", bech32_err, hex_err)
+                    BridgeError::InvalidInput(format!("Tried bech32: {}. Tried hex: {}", bech32_err, hex_err))
                 })
             }
         }
@@ -1649,7 +2435,7 @@ fn parse_account_id(account_id_str: &str) -> Result<AccountId, String> {
             hex_str.to_string()
         };
         AccountId::from_hex(&hex_with_prefix)
-            .map_err(|e| format!("Invalid hex format: {}", e))
+            .map_err(|e| BridgeError::InvalidInput(format!("Invalid hex format: {}", e)))
     }
 }
 
@@ -1659,11 +2445,19 @@ fn options_withdrawal_create_commitment() -> rocket::http::Status {
     rocket::http::Status::Ok
 }
 
+#[utoipa::path(
+    tag = "withdrawal",
+    summary = "Burn wTAZ into a commitment withdrawal note",
+    request_body = CommitmentWithdrawalRequest,
+    responses((status = 200, body = CommitmentWithdrawalResponse), StateCreationErrors),
+)]
 #[post("/withdrawal/create-commitment", format = "json", data = "<request>")]
 async fn create_commitment_withdrawal(
+    _state_limit: StateCreationLimit,
     state: &rocket::State<State>,
+    rate_limiter: &rocket::State<RateLimiter>,
     request: Json<CommitmentWithdrawalRequest>,
-) -> Result<Json<CommitmentWithdrawalResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<CommitmentWithdrawalResponse>, BridgeError> {
     use miden_crypto::hash::rpo::Rpo256;
     use miden_objects::{Felt, FieldElement};
     use miden_client::transaction::{TransactionRequestBuilder, OutputNote};
@@ -1672,23 +2466,18 @@ async fn create_commitment_withdrawal(
     
     // Parse account ID
     let account_id = parse_account_id(&request.account_id)
-        .map_err(|e| status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Invalid account_id: {}", e),
-            }),
-        ))?;
+        .map_err(|e| e.context("Invalid account_id"))?;
+    
+    // Each withdrawal makes the faucet submit a proven transaction, so limit them per account
+    rate_limiter.check_account(&account_id.to_hex())
+        .map_err(|limited| BridgeError::RateLimited(limited.message))?;
+    
+    // Quote the withdrawal fee up front so amounts that can't cover it are rejected early
+    let quote = state.fee_schedule.quote(FeeDirection::Withdrawal, ZCASH_ORIGIN_NETWORK, request.amount)?;
     
     // Get project root
     let current_dir = std::env::current_dir()
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to get current directory: {}", e),
-            }),
-        ))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get current directory: {}", e)))?;
     
     let project_root = if current_dir.file_name()
         .and_then(|n| n.to_str())
@@ -1701,24 +2490,17 @@ async fn create_commitment_withdrawal(
     
     let keystore_path = project_root.join("rust-backend").join("keystore");
     let store_path = project_root.join("bridge_store.sqlite3");
-    let faucet_store_path = project_root.join("faucets.db");
     let rpc_url = std::env::var("RPC_URL")
         .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
     
     // Get faucet ID
     let faucet_id = if let Some(faucet_id_str) = &request.faucet_id {
         parse_account_id(faucet_id_str)
-            .map_err(|e| status::Custom(
-                Status::BadRequest,
-                Json(ErrorResponse {
-                    success: false,
-                    error: format!("Invalid faucet_id: {}", e),
-                }),
-            ))?
+            .map_err(|e| e.context("Invalid faucet_id"))?
     } else {
         let keystore_path_clone = keystore_path.clone();
         let store_path_clone = store_path.clone();
-        let faucet_store_path_clone = faucet_store_path.clone();
+        let db = state.db.clone();
         let rpc_url_clone = rpc_url.clone();
         tokio::task::spawn_blocking(move || {
             let rt = tokio::runtime::Runtime::new()
@@ -1728,26 +2510,14 @@ async fn create_commitment_withdrawal(
                     keystore_path_clone,
                     store_path_clone,
                     &rpc_url_clone,
-                    faucet_store_path_clone,
+                    &db,
                 )
                 .await
             })
         })
         .await
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Spawn blocking error: {}", e),
-            }),
-        ))?
-        .map_err(|e: String| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Get or create faucet error: {}", e),
-            }),
-        ))?
+        .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+        .map_err(|e| e.context("Get or create faucet error"))?
     };
     
     // Generate random secret
@@ -1769,13 +2539,7 @@ async fn create_commitment_withdrawal(
         (secret, commitment_word)
     })
     .await
-    .map_err(|e| status::Custom(
-        Status::InternalServerError,
-        Json(ErrorResponse {
-            success: false,
-            error: format!("Failed to generate secret: {}", e),
-        }),
-    ))?;
+    .map_err(|e| BridgeError::Internal(format!("Failed to generate secret: {}", e)))?;
     
     let commitment_hex = commitment_word.iter()
         .map(|f| format!("{:016x}", f.as_int()))
@@ -1787,70 +2551,99 @@ async fn create_commitment_withdrawal(
     let amount = request.amount;
     let zcash_address = request.zcash_address.clone();
     
-    // Create private note with wTAZ addressed to faucet
-    let (note_id, tx_id) = tokio::task::spawn_blocking({
-        let keystore_path = keystore_path.clone();
-        let store_path = store_path.clone();
-        let rpc_url = rpc_url.clone();
-        let commitment_word = commitment_word;
-        let amount = amount;
-        move || {
-            let rt = tokio::runtime::Runtime::new()
-                .expect("Failed to create runtime");
-            rt.block_on(async {
-                create_commitment_withdrawal_internal(
-                    account_id,
-                    faucet_id,
+    // Record withdrawal in database, together with its audit entry
+    // Get block number (we'll use 0 for now, relayer will update it)
+    let record_withdrawal = |note_id: &str| -> Result<(), BridgeError> {
+        state.db()?.transaction(|db| {
+            let recorded = db.withdrawals()
+                .record_withdrawal(
+                    &commitment_hex,
+                    note_id,
                     amount,
-                    commitment_word,
-                    keystore_path,
-                    store_path,
-                    &rpc_url,
+                    0, // block_number - will be updated by relayer
                 )
-                .await
+                .map_err(|e| e.context("Failed to record withdrawal"))?;
+            
+            if !recorded {
+                return Err(BridgeError::Conflict(format!("Withdrawal commitment {} is already recorded.", commitment_hex)));
+            }
+            
+            let inputs = serde_json::json!({
+                "commitment": commitment_hex,
+                "zcash_address": zcash_address,
+                "amount": amount,
+                "fee": quote.fee,
+            });
+            db.audit().append(AuditAction::WithdrawalCreate, &inputs_digest(&inputs), Some(note_id), "api")
+                .map_err(|e| e.context("Failed to append audit entry"))?;
+            Ok(())
+        })
+    };
+    
+    // Emit the commitment note from the faucet. With approvers configured it goes through a
+    // proposal that relayers sign once they find the withdrawal in the database, so it is
+    // recorded first; the note ID doesn't depend on the transaction, so it is known up front.
+    let (note_id, tx_id, pending) = match state.multisig.clone() {
+        Some(coordinator) => {
+            let note_id = withdrawal_commitment_note(faucet_id, commitment_word)?.id().to_hex();
+            record_withdrawal(&note_id)?;
+            
+            let view = DepositView::withdrawal(&commitment_hex, amount);
+            let db = state.db.clone();
+            let span = tracing::Span::current();
+            let progress = tokio::task::spawn_blocking(move || {
+                tokio::runtime::Handle::current()
+                    .block_on(coordinator.contribute_withdrawal_note(&db, faucet_id, &view, true).instrument(span))
+            })
+            .await
+            .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+            .map_err(|e| e.context("Failed to propose withdrawal note"))?;
+            
+            match progress {
+                MintProgress::Submitted { tx_id, .. } => (note_id, Some(tx_id), None),
+                MintProgress::Pending { signatures, threshold } => (note_id, None, Some(format!(
+                    "Its commitment note is waiting for bridge approvers ({} of {} signatures).",
+                    signatures, threshold
+                ))),
+                MintProgress::SubmittedElsewhere => (note_id, None, Some(
+                    "Its commitment note was submitted by a bridge relayer.".to_string(),
+                )),
+            }
+        }
+        None => {
+            let (note_id, tx_id) = tokio::task::spawn_blocking({
+                let keystore_path = keystore_path.clone();
+                let store_path = store_path.clone();
+                let rpc_url = rpc_url.clone();
+                let commitment_word = commitment_word;
+                let amount = amount;
+                move || {
+                    let rt = tokio::runtime::Runtime::new()
+                        .expect("Failed to create runtime");
+                    rt.block_on(async {
+                        create_commitment_withdrawal_internal(
+                            account_id,
+                            faucet_id,
+                            amount,
+                            commitment_word,
+                            keystore_path,
+                            store_path,
+                            &rpc_url,
+                        )
+                        .await
+                    })
+                }
             })
+            .await
+            .map_err(|e| BridgeError::Internal(format!("Spawn blocking error: {}", e)))?
+            .map_err(|e| e.context("Failed to create withdrawal"))?;
+            
+            record_withdrawal(&note_id)?;
+            (note_id, Some(tx_id), None)
         }
-    })
-    .await
-    .map_err(|e| status::Custom(
-        Status::InternalServerError,
-        Json(ErrorResponse {
-            success: false,
-            error: format!("Spawn blocking error: {}", e),
-        }),
-    ))?
-    .map_err(|e: String| status::Custom(
-        Status::InternalServerError,
-        Json(ErrorResponse {
-            success: false,
-            error: format!("Failed to create withdrawal: {}", e),
-        }),
-    ))?;
-    
-    // Record withdrawal in database
-    let withdrawal_tracker = state.withdrawal_tracker.lock()
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to lock withdrawal tracker: {}", e),
-            }),
-        ))?;
+    };
     
-    // Get block number (we'll use 0 for now, relayer will update it)
-    withdrawal_tracker.record_withdrawal(
-        &commitment_hex,
-        &note_id,
-        amount,
-        0, // block_number - will be updated by relayer
-    )
-    .map_err(|e| status::Custom(
-        Status::InternalServerError,
-        Json(ErrorResponse {
-            success: false,
-            error: format!("Failed to record withdrawal: {}", e),
-        }),
-    ))?;
+    publish_event(state, |events| events.record_withdrawal(&commitment_hex, EventState::Created, Some(&note_id), tx_id.as_deref()));
     
     let secret_hex = secret.iter()
         .map(|f| format!("{:016x}", f.as_int()))
@@ -1875,12 +2668,18 @@ async fn create_commitment_withdrawal(
         transaction_id: tx_id,
         network: "testnet".to_string(),
         created_at,
+        fee: quote.fee,
+        net_amount: quote.net_amount,
         success: true,
-        message: "Withdrawal created successfully. Save this .mno file - you'll need it to claim.".to_string(),
+        message: match pending {
+            Some(pending) => format!("Withdrawal created. {} Save this .mno file - you'll need it to claim.", pending),
+            None => "Withdrawal created successfully. Save this .mno file - you'll need it to claim.".to_string(),
+        },
     }))
 }
 
 // Helper function to create commitment withdrawal
+#[tracing::instrument(name = "withdrawal", skip_all, fields(commitment = %commitment.to_hex(), note_id = tracing::field::Empty))]
 async fn create_commitment_withdrawal_internal(
     account_id: AccountId,
     faucet_id: AccountId,
@@ -1889,47 +2688,40 @@ async fn create_commitment_withdrawal_internal(
     keystore_path: PathBuf,
     store_path: PathBuf,
     rpc_url: &str,
-) -> Result<(String, String), String> {
+) -> BridgeResult<(String, String)> {
     use miden_client::transaction::{TransactionRequestBuilder, OutputNote};
-    use miden_objects::{
-        asset::FungibleAsset,
-        note::{Note, NoteAssets, NoteExecutionHint, NoteMetadata, NoteRecipient, NoteTag, NoteType},
-        Felt,
-    };
-    use rust_backend::miden::notes::BRIDGE_USECASE;
     
     // Initialize client
     let endpoint = Endpoint::try_from(rpc_url)
-        .map_err(|e| format!("Failed to parse RPC endpoint: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to parse RPC endpoint: {}", e)))?;
     
     let rpc_client = Arc::new(GrpcClient::new(&endpoint, 10_000));
     
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path.clone())
-            .map_err(|e| format!("Failed to create keystore: {}", e))?,
-    );
+    // The faucet key is in the keystore, or with the signer process if SIGNER_SOCKET is set
+    let signer = BridgeSigner::open(&keystore_path)?;
     
     let mut client = ClientBuilder::new()
         .rpc(rpc_client)
         .sqlite_store(store_path)
-        .authenticator(keystore)
+        .authenticator(signer)
         .in_debug_mode(true.into())
         .build()
         .await
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build client: {}", e)))?;
     
-    client.sync_state().await
-        .map_err(|e| format!("Failed to sync state: {}", e))?;
+    let sync_summary = client.sync_state().await
+        .map_err(|e| BridgeError::ChainUnavailable(format!("Failed to sync state: {}", e)))?;
+    metrics::MIDEN_SYNC_HEIGHT.set(sync_summary.block_num.as_u32() as i64);
     
     // Check if faucet account exists in client store
     let faucet_account = client.get_account(faucet_id).await
-        .map_err(|e| format!("Failed to get faucet account: {}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get faucet account: {}", e)))?;
     
     if faucet_account.is_none() {
-        return Err(format!(
+        return Err(BridgeError::NotFound(format!(
             "Faucet account {} not found in client store. The faucet must be created and added to the client first (via /faucet/create or get_or_create_zcash_faucet).",
             faucet_id.to_bech32(miden_objects::address::NetworkId::Testnet)
-        ));
+        )));
     }
     
     // Skip the user-to-faucet transfer for now (causes NoMastForestWithProcedure error)
@@ -1937,65 +2729,17 @@ async fn create_commitment_withdrawal_internal(
     // The commitment note serves as a withdrawal request
     // When claiming, we'll verify the user has wTAZ balance before sending Zcash
     
-    // Create note tag for withdrawal
-    let note_tag = NoteTag::for_local_use_case(BRIDGE_USECASE, 1) // Use tag 1 for withdrawals
-        .map_err(|e| format!("Failed to create tag: {:?}", e))?;
-    
-    // Create public note with commitment
-    // Commitment is stored in note inputs
-    let commitment_felts: [Felt; 4] = [
-        commitment[0],
-        commitment[1],
-        commitment[2],
-        commitment[3],
-    ];
-    
-    // Create public note with commitment in inputs
-    // For public notes, we need a script. Use the CROSSCHAIN script (even though we're not using it, it's required)
-    use miden_objects::note::NoteInputs;
-    use rust_backend::miden::bridge_scripts::crosschain;
-    
-    let commitment_inputs = NoteInputs::new(vec![
-        commitment_felts[0],
-        commitment_felts[1],
-        commitment_felts[2],
-        commitment_felts[3],
-    ])
-    .map_err(|e| format!("Failed to create inputs: {:?}", e))?;
-    
-    // Use a zero secret and the CROSSCHAIN script (required for NoteRecipient)
-    let zero_secret = Word::new([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(0)]);
-    let script = crosschain();
-    
-    let public_recipient = NoteRecipient::new(
-        zero_secret,
-        script,
-        commitment_inputs,
-    );
-    
-    let public_metadata = NoteMetadata::new(
-        faucet_id,
-        NoteType::Public, // PUBLIC so relayer can scan
-        note_tag,
-        NoteExecutionHint::always(),
-        Felt::new(0),
-    )
-    .map_err(|e| format!("Failed to create metadata: {:?}", e))?;
-    
-    // Create full note with commitment (no assets - just a commitment marker)
-    let empty_assets = NoteAssets::new(vec![]).map_err(|e| format!("Failed to create assets: {:?}", e))?;
-    let full_note = Note::new(
-        empty_assets.clone(),
-        public_metadata.clone(),
-        public_recipient.clone(),
-    );
+    // Public note with the commitment in its inputs, the same note multisig approvers sign
+    let full_note = withdrawal_commitment_note(faucet_id, commitment)
+        .map_err(|e| e.context("Failed to create note"))?;
     let public_note_id = full_note.id().to_hex();
+    tracing::Span::current().record("note_id", public_note_id.as_str());
     
     // Create transaction to emit public note using Full note (like deposit code)
     let tx_request = TransactionRequestBuilder::new()
         .own_output_notes(vec![OutputNote::Full(full_note)])
         .build()
-        .map_err(|e| format!("Failed to build transaction: {:?}", e))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to build transaction: {:?}", e)))?;
     
     let tx_id_2 = client
         .submit_new_transaction(faucet_id, tx_request)
@@ -2003,15 +2747,14 @@ async fn create_commitment_withdrawal_internal(
         .map_err(|e| {
             let error_str = format!("{:?}", e);
             let error_display = format!("{}", e);
-            println!("[Withdrawal] ❌ Transaction submission error: {}", error_display);
-            println!("[Withdrawal] ❌ Error details: {}", error_str);
-            if error_str.contains("NoMastForestWithProcedure") {
+            error!(error = %error_display, details = %error_str, "Withdrawal transaction submission failed");
+            BridgeError::TransactionFailed(if error_str.contains("NoMastForestWithProcedure") {
                 format!("Faucet account may not be deployed on-chain. Error: {}", error_str)
             } else if error_str.contains("transaction request") {
                 format!("Transaction request error: {}. This may indicate the faucet account is not properly initialized or the note format is invalid.", error_str)
             } else {
                 format!("Failed to submit transaction: {} (details: {})", error_display, error_str)
-            }
+            })
         })?;
     
     let tx_id_2_str = format!("{:?}", tx_id_2);
@@ -2024,41 +2767,31 @@ fn options_withdrawal_claim() -> rocket::http::Status {
     rocket::http::Status::Ok
 }
 
+#[utoipa::path(
+    tag = "withdrawal",
+    summary = "Claim a withdrawal and receive TAZ on Zcash",
+    request_body = ClaimWithdrawalRequest,
+    responses((status = 200, body = ClaimWithdrawalResponse), BridgeError),
+)]
 #[post("/withdrawal/claim", format = "json", data = "<request>")]
+#[tracing::instrument(name = "withdrawal", skip_all, fields(commitment = tracing::field::Empty))]
 async fn claim_withdrawal(
+    _rate_limit: RateLimit,
     state: &rocket::State<State>,
     request: Json<ClaimWithdrawalRequest>,
-) -> Result<Json<ClaimWithdrawalResponse>, status::Custom<Json<ErrorResponse>>> {
+) -> Result<Json<ClaimWithdrawalResponse>, BridgeError> {
     use miden_crypto::hash::rpo::Rpo256;
     use miden_objects::{Felt, Word};
     
     // Extract fields from .mno format or direct fields
     let commitment = request.commitment.as_ref()
-        .ok_or_else(|| status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Missing commitment. Provide either commitment field or .mno file format.".to_string(),
-            }),
-        ))?;
+        .ok_or_else(|| BridgeError::InvalidInput("Missing commitment. Provide either commitment field or .mno file format.".to_string()))?;
     
     let secret_str = request.secret.as_ref()
-        .ok_or_else(|| status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Missing secret. Provide either secret field or .mno file format.".to_string(),
-            }),
-        ))?;
+        .ok_or_else(|| BridgeError::InvalidInput("Missing secret. Provide either secret field or .mno file format.".to_string()))?;
     
     let zcash_address = request.zcash_address.as_ref()
-        .ok_or_else(|| status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Missing zcash_address. Provide either zcash_address field or .mno file format.".to_string(),
-            }),
-        ))?;
+        .ok_or_else(|| BridgeError::InvalidInput("Missing zcash_address. Provide either zcash_address field or .mno file format.".to_string()))?;
     
     // Parse secret
     let secret_hex = if secret_str.starts_with("0x") {
@@ -2068,13 +2801,7 @@ async fn claim_withdrawal(
     };
     
     let secret = Word::try_from(format!("0x{}", secret_hex))
-        .map_err(|e| status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Invalid secret: {}", e),
-            }),
-        ))?;
+        .map_err(|e| BridgeError::InvalidInput(format!("Invalid secret: {}", e)))?;
     
     // Verify commitment = hash(secret)
     use miden_objects::utils::Serializable;
@@ -2102,13 +2829,7 @@ async fn claim_withdrawal(
     };
     
     if computed_commitment_no_prefix != commitment_no_prefix {
-        return Err(status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: "Commitment does not match secret. Invalid secret provided.".to_string(),
-            }),
-        ));
+        return Err(BridgeError::InvalidInput("Commitment does not match secret. Invalid secret provided.".to_string()));
     }
     
     // Look up withdrawal (use commitment with 0x prefix for database lookup)
@@ -2117,53 +2838,31 @@ async fn claim_withdrawal(
     } else {
         format!("0x{}", commitment)
     };
+    tracing::Span::current().record("commitment", commitment_for_db.as_str());
+    
+    // Payouts halt while reserves don't cover the outstanding wTAZ supply
+    state.solvency.ensure_solvent().await?;
     
-    let withdrawal_tracker = state.withdrawal_tracker.lock()
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to lock withdrawal tracker: {}", e),
-            }),
-        ))?;
-    
-    let withdrawal = withdrawal_tracker.get_withdrawal(&commitment_for_db)
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to lookup withdrawal: {}", e),
-            }),
-        ))?
-        .ok_or_else(|| status::Custom(
-            Status::NotFound,
-            Json(ErrorResponse {
-                success: false,
-                error: "Withdrawal not found. Make sure the commitment is correct.".to_string(),
-            }),
-        ))?;
+    let _claim = state.withdrawal_claims.lock()
+        .map_err(|e| BridgeError::Internal(format!("Failed to lock withdrawal claims: {}", e)))?;
+    
+    let withdrawal = state.db()?
+        .withdrawals()
+        .get_withdrawal(&commitment_for_db)
+        .map_err(|e| e.context("Failed to lookup withdrawal"))?
+        .ok_or_else(|| BridgeError::NotFound("Withdrawal not found. Make sure the commitment is correct.".to_string()))?;
     
     // Check if already claimed
     if withdrawal.claimed_at.is_some() {
-        return Err(status::Custom(
-            Status::BadRequest,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Withdrawal already claimed. Zcash TX: {}", 
-                    withdrawal.zcash_txid.as_ref().unwrap_or(&"unknown".to_string())),
-            }),
-        ));
+        return Err(BridgeError::AlreadyClaimed(format!(
+            "Withdrawal already claimed. Zcash TX: {}",
+            withdrawal.zcash_txid.as_deref().unwrap_or("unknown")
+        )));
     }
     
     // Send Zcash transaction
     let current_dir = std::env::current_dir()
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to get current directory: {}", e),
-            }),
-        ))?;
+        .map_err(|e| BridgeError::Internal(format!("Failed to get current directory: {}", e)))?;
     
     let project_root = if current_dir.file_name()
         .and_then(|n| n.to_str())
@@ -2176,32 +2875,85 @@ async fn claim_withdrawal(
     
     let bridge_wallet = Arc::new(BridgeWallet::new(project_root));
     
-    let amount_taz = withdrawal.amount as f64 / 1e8;
+    // Deduct the withdrawal fee - the payout is the net amount
+    let quote = state.fee_schedule.quote(FeeDirection::Withdrawal, ZCASH_ORIGIN_NETWORK, withdrawal.amount)?;
+    
+    let amount_taz = quote.net_amount as f64 / 1e8;
     let amount_str = format!("{:.8}", amount_taz);
     
-    let zcash_txid = bridge_wallet.send(
+    // Without the spending key here, queue the payout for the offline signer; it's marked
+    // claimed once bridgectl broadcasts the signed transaction
+    if state.payout_signing == PayoutSigning::Offline {
+        let queued = payouts::queue(
+            &state.db,
+            &bridge_wallet,
+            PayoutKind::Withdrawal,
+            &commitment_for_db,
+            &withdrawal.note_id,
+            zcash_address,
+            &quote,
+        )?;
+        
+        if queued.is_none() {
+            let status = state.db()?
+                .payouts()
+                .get(&commitment_for_db)?
+                .map(|payout| payout.status.as_str())
+                .unwrap_or("unknown");
+            return Err(BridgeError::AlreadyClaimed(format!(
+                "Withdrawal already claimed. Payout is {}",
+                status
+            )));
+        }
+        
+        return Ok(Json(ClaimWithdrawalResponse {
+            success: true,
+            zcash_txid: None,
+            message: format!(
+                "Withdrawal claimed. {} TAZ to {} is queued for signing and will be sent once signed",
+                amount_str, zcash_address
+            ),
+        }));
+    }
+    
+    let payout_timer = metrics::PAYOUT_LATENCY.start_timer();
+    let send_result = bridge_wallet.send(
         zcash_address,
         &amount_str,
         None, // No memo needed
         None, // No fee rate override
-    )
-    .map_err(|e| status::Custom(
-        Status::InternalServerError,
-        Json(ErrorResponse {
-            success: false,
-            error: format!("Failed to send Zcash transaction: {}", e),
-        }),
-    ))?;
-    
-    // Mark as claimed
-    withdrawal_tracker.mark_claimed(&commitment_for_db, &zcash_txid)
-        .map_err(|e| status::Custom(
-            Status::InternalServerError,
-            Json(ErrorResponse {
-                success: false,
-                error: format!("Failed to mark withdrawal as claimed: {}", e),
-            }),
-        ))?;
+    );
+    payout_timer.observe_duration();
+    
+    let zcash_txid = send_result
+    .map_err(|e| e.context("Failed to send Zcash transaction"))?;
+    
+    metrics::WITHDRAWALS_PAID.inc();
+    
+    // Mark as claimed, together with the fee
+    state.db()?.transaction(|db| {
+        db.withdrawals().mark_claimed(&commitment_for_db, &zcash_txid)
+            .map_err(|e| e.context("Failed to mark withdrawal as claimed"))?;
+        db.fees().record_fee(&quote, &commitment_for_db)
+            .map_err(|e| e.context("Failed to record fee"))?;
+        
+        let inputs = serde_json::json!({
+            "commitment": commitment_for_db,
+            "zcash_address": zcash_address,
+            "amount": withdrawal.amount,
+            "fee": quote.fee,
+        });
+        db.audit().append(AuditAction::WithdrawalPayout, &inputs_digest(&inputs), Some(&zcash_txid), "api")
+            .map_err(|e| e.context("Failed to append audit entry"))?;
+        Ok(())
+    })?;
+    
+    publish_event(state, |events| events.record_withdrawal(
+        &commitment_for_db,
+        EventState::Paid,
+        Some(&withdrawal.note_id),
+        Some(&zcash_txid),
+    ));
     
     Ok(Json(ClaimWithdrawalResponse {
         success: true,
@@ -2210,8 +2962,170 @@ async fn claim_withdrawal(
     }))
 }
 
+// Event streams - clients subscribe to a deposit or withdrawal instead of polling
+// How often a stream polls the event log (the relayers write it from their own processes)
+const EVENT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Last-Event-ID header an EventSource sends when it reconnects
+struct LastEventId(Option<i64>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        let id = request.headers()
+            .get_one("Last-Event-ID")
+            .and_then(|id| id.trim().parse().ok());
+        Outcome::Success(LastEventId(id))
+    }
+}
+
+// Push events for one deposit or withdrawal as they are logged, ending after a final state
+fn bridge_event_stream(
+    db: Arc<Mutex<Box<dyn BridgeStore>>>,
+    subject: EventSubject,
+    id: String,
+    mut after: i64,
+    mut shutdown: rocket::Shutdown,
+) -> EventStream![] {
+    EventStream! {
+        let mut poll = tokio::time::interval(EVENT_POLL_INTERVAL);
+        loop {
+            rocket::tokio::select! {
+                _ = poll.tick() => {}
+                _ = &mut shutdown => break,
+            }
+            
+            let batch = match db.lock() {
+                Ok(db) => match subject {
+                    EventSubject::Deposit => db.events().deposit_events(&id, after),
+                    EventSubject::Withdrawal => db.events().withdrawal_events(&id, after),
+                },
+                Err(e) => Err(BridgeError::Internal(format!("Failed to lock bridge database: {}", e))),
+            };
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    warn!(error = %e, "Failed to poll event log");
+                    continue;
+                }
+            };
+            
+            let finished = batch.iter().any(|event| event.state.is_final());
+            for event in batch {
+                after = event.id;
+                yield Event::json(&event).id(event.id.to_string());
+            }
+            if finished {
+                break;
+            }
+        }
+    }
+}
+
+#[options("/events/deposit/<_id>")]
+fn options_deposit_events(_id: &str) -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+#[utoipa::path(
+    tag = "events",
+    summary = "Stream state changes of a deposit, by recipient hash, txid or txid:pool:index",
+    params(("Last-Event-ID" = Option<i64>, Header, description = "Resume after this event id")),
+    responses(
+        (status = 200, description = "Server-sent events, one `BridgeEvent` per state change. The stream ends after a final state (minted, refunded).", content_type = "text/event-stream", body = BridgeEvent),
+        BridgeError,
+    ),
+)]
+#[get("/events/deposit/<id>?<after>")]
+fn deposit_events(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    id: &str,
+    after: Option<i64>,
+    last_event_id: LastEventId,
+    shutdown: rocket::Shutdown,
+) -> EventStream![] {
+    let after = last_event_id.0.or(after).unwrap_or(0);
+    bridge_event_stream(state.db.clone(), EventSubject::Deposit, id.to_string(), after, shutdown)
+}
+
+#[options("/events/withdrawal/<_id>")]
+fn options_withdrawal_events(_id: &str) -> rocket::http::Status {
+    rocket::http::Status::Ok
+}
+
+#[utoipa::path(
+    tag = "events",
+    summary = "Stream state changes of a withdrawal, by commitment (or burn note id for legacy exits)",
+    params(("Last-Event-ID" = Option<i64>, Header, description = "Resume after this event id")),
+    responses(
+        (status = 200, description = "Server-sent events, one `BridgeEvent` per state change. The stream ends once the withdrawal is paid.", content_type = "text/event-stream", body = BridgeEvent),
+        BridgeError,
+    ),
+)]
+#[get("/events/withdrawal/<id>?<after>")]
+fn withdrawal_events(
+    _rate_limit: RateLimit,
+    state: &rocket::State<State>,
+    id: &str,
+    after: Option<i64>,
+    last_event_id: LastEventId,
+    shutdown: rocket::Shutdown,
+) -> EventStream![] {
+    let after = last_event_id.0.or(after).unwrap_or(0);
+    bridge_event_stream(state.db.clone(), EventSubject::Withdrawal, id.to_string(), after, shutdown)
+}
+
+// OpenAPI 3 spec of the HTTP API, generated from the handler annotations and request/response types
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Miden-Zcash Bridge API"),
+    paths(
+        get_block, health, get_metrics, create_account, create_faucet, mint_from_faucet, get_pow_challenge,
+        get_hash_endpoint, generate_hash_endpoint, create_deposit_address, create_deposit_intent, get_deposit_intent,
+        claim_deposit_endpoint, request_deposit_refund, get_deposit_refund, reconstruct_note_endpoint,
+        consume_note_endpoint, get_account_balance, get_pool_balance, get_reserves, get_reserves_disclosure, get_fee_quote, get_fee_summary, export_accounting,
+        create_commitment_withdrawal, claim_withdrawal, deposit_events, withdrawal_events,
+    ),
+    components(schemas(ErrorResponse)),
+    modifiers(&ApiKeySecurity),
+)]
+struct ApiDoc;
+
+// Operator and admin endpoints take an API key (see auth.rs)
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme("api_key", SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))));
+    }
+}
+
+fn openapi_json() -> String {
+    ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("Failed to serialize OpenAPI spec")
+}
+
+// Served so clients can generate bindings; `rust-backend --print-openapi` prints the same document for CI diffs
+#[get("/openapi.json")]
+fn get_openapi() -> (rocket::http::ContentType, String) {
+    (rocket::http::ContentType::JSON, openapi_json())
+}
+
 #[launch]
 fn rocket() -> _ {
+    // Print the API spec and exit, without touching the wallet, database or network
+    if std::env::args().any(|arg| arg == "--print-openapi") {
+        println!("{}", openapi_json());
+        std::process::exit(0);
+    }
+    
     // Load .env file from project root (works whether running from root or rust-backend)
     let current_dir = std::env::current_dir()
         .expect("Failed to get current directory");
@@ -2228,12 +3142,17 @@ fn rocket() -> _ {
     
     if env_path.exists() {
         dotenv::from_path(&env_path).ok();
-        println!("Loaded .env from: {:?}", env_path);
     } else {
         // Also try .env in current directory
         dotenv::dotenv().ok();
     }
     
+    // Logging is configured from the environment, so set it up once .env is loaded
+    rust_backend::logging::init();
+    if env_path.exists() {
+        info!(path = ?env_path, "Loaded .env");
+    }
+    
     // Set project_root - if we're in rust-backend, go up one level, otherwise use current dir
     let project_root = if current_dir.file_name()
         .and_then(|n| n.to_str())
@@ -2248,38 +3167,84 @@ fn rocket() -> _ {
     let rpc_url = std::env::var("RPC_URL")
         .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
     
-    println!("Connecting to RPC endpoint: {}", rpc_url);
+    info!(rpc_url = %rpc_url, "Connecting to RPC endpoint");
     
     let endpoint = Endpoint::try_from(rpc_url.as_str())
         .expect("Failed to parse RPC endpoint");
     
     let rpc = Arc::new(GrpcClient::new(&endpoint, 10_000));
     
-    // Initialize keystore
-    let keystore_path = PathBuf::from("./keystore");
-    let keystore = Arc::new(
-        FilesystemKeyStore::<StdRng>::new(keystore_path)
-            .expect("Failed to create keystore"),
-    );
+    // Unlock the encrypted keystore with KEYSTORE_PASSPHRASE or KEYSTORE_KEY, unless the signer process holds the keys
+    let keystore = match BridgeSigner::remote_socket() {
+        Some(socket) => {
+            info!(socket = %socket, "Faucet transactions are signed by the signer process; no local keystore");
+            None
+        }
+        None => {
+            let keystore_path = PathBuf::from("./keystore");
+            Some(keystore::unlock(&keystore_path).expect("Failed to unlock keystore"))
+        }
+    };
     
     // Initialize bridge wallet (project_root already set above)
     let bridge_wallet = Arc::new(BridgeWallet::new(project_root.clone()));
     
-    // Initialize deposit tracker database
-    let db_path = project_root.join("deposits.db");
-    let deposit_tracker = DepositTracker::new(db_path)
-        .expect("Failed to initialize deposit tracker database");
+    // Open the bridge database shared with the relayers, migrating it if needed
+    let db = rust_backend::db::open(&project_root)
+        .expect("Failed to open bridge database");
+    info!(backend = db.backend(), schema_version = db.schema_version().unwrap_or(0), "Bridge database ready");
+    let db = Arc::new(Mutex::new(db));
+    
+    // Load fee schedule
+    let fee_schedule = FeeSchedule::load(&project_root)
+        .expect("Failed to load fee schedule");
     
-    // Initialize withdrawal tracker database
-    let withdrawal_db_path = project_root.join("withdrawals.db");
-    let withdrawal_tracker = WithdrawalTracker::new(withdrawal_db_path)
-        .expect("Failed to initialize withdrawal tracker database");
+    // Minting and payouts halt while reserves fall short of the outstanding wTAZ supply
+    let solvency = SolvencyGuard::new(bridge_wallet.clone(), project_root.clone());
+    
+    // Publish the bridge wallet's viewing key for proof of reserves, if the operator opted in
+    let reserves_disclosure = match ReservesDisclosure::from_env(&bridge_wallet) {
+        Ok(Some(disclosure)) => {
+            info!(birthday_height = disclosure.birthday_height, "Publishing bridge wallet viewing key at /reserves/disclosure");
+            Some(disclosure)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!(error = %e, "Not publishing bridge wallet viewing key");
+            None
+        }
+    };
+    
+    let payout_signing = PayoutSigning::from_env()
+        .expect("Invalid payout signing configuration");
+    if payout_signing == PayoutSigning::Offline {
+        info!("Withdrawal payouts are queued for the offline signer");
+    }
+    
+    // Mints need M-of-N approver signatures when MINT_APPROVERS is set
+    let multisig = MultisigConfig::from_env()
+        .expect("Invalid multisig configuration")
+        .map(|config| {
+            info!(
+                approvers = config.approvers.len(),
+                threshold = config.threshold,
+                signer = config.signer.is_some(),
+                "Deposit mints need approver signatures"
+            );
+            Arc::new(MintCoordinator::new(
+                config,
+                project_root.join("rust-backend").join("keystore"),
+                project_root.join("bridge_store.sqlite3"),
+                &rpc_url,
+                "api",
+            ))
+        });
     
     // Deploy wTAZ faucet on startup if it doesn't exist
-    println!("[Server] Checking for wTAZ faucet...");
+    info!("Checking for wTAZ faucet");
     let keystore_path = PathBuf::from("./keystore");
     let store_path = project_root.join("bridge_store.sqlite3");
-    let faucet_store_path = project_root.join("faucets.db");
+    let faucet_db = db.clone();
     let rpc_url_clone = rpc_url.clone();
     
     // Deploy faucet synchronously using a new runtime
@@ -2290,7 +3255,7 @@ fn rocket() -> _ {
                 keystore_path,
                 store_path,
                 &rpc_url_clone,
-                faucet_store_path,
+                &faucet_db,
             )
             .await
         })
@@ -2303,17 +3268,17 @@ fn rocket() -> _ {
             use miden_objects::utils::Serializable;
             let faucet_bytes = faucet_id.to_bytes();
             let faucet_hex: String = faucet_bytes.iter().map(|b| format!("{:02x}", b)).collect();
-            println!("[Server] ✅ wTAZ Faucet ready:");
-            println!("[Server]    Bech32: {}", faucet_bech32);
-            println!("[Server]    Hex:    0x{}", faucet_hex);
-            println!("[Server]    Use this faucet ID for .mno files and UI balance display");
+            info!(
+                faucet_id = %faucet_bech32,
+                faucet_hex = %format!("0x{}", faucet_hex),
+                "wTAZ faucet ready - use this faucet ID for .mno files and UI balance display"
+            );
         }
         Ok(Err(e)) => {
-            eprintln!("[Server] ⚠️  Failed to deploy faucet: {}", e);
-            eprintln!("[Server]    Faucet will be created on first deposit");
+            warn!(error = %e, "Failed to deploy faucet; it will be created on first deposit");
         }
         Err(e) => {
-            eprintln!("[Server] ⚠️  Failed to spawn faucet deployment task: {:?}", e);
+            warn!(error = ?e, "Failed to spawn faucet deployment task");
         }
     }
     
@@ -2323,17 +3288,62 @@ fn rocket() -> _ {
         .parse::<u16>()
         .unwrap_or(8001);
     
-    println!("[Server] Rocket server starting on http://127.0.0.1:{}", port);
-    rocket::build()
-        .configure(rocket::Config::figment().merge(("port", port)))
+    // API keys for operator/admin endpoints
+    let api_keys = ApiKeys::from_env();
+    let production = production_mode();
+    if production {
+        info!("Production mode: operator and admin endpoints are not mounted");
+    } else {
+        info!(
+            operator_keys = api_keys.count(Role::Operator),
+            admin_keys = api_keys.count(Role::Admin),
+            "Operator/admin endpoints require an API key"
+        );
+    }
+    
+    // Rate limits / proof-of-work for public endpoints, and a cap on JSON request bodies
+    let rate_limit_config = RateLimitConfig::from_env();
+    info!(config = ?rate_limit_config, "Rate limits");
+    let max_body_bytes = std::env::var("MAX_BODY_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse::<u64>().ok())
+        .unwrap_or(16 * 1024);
+    let limits = rocket::data::Limits::default()
+        .limit("json", max_body_bytes.into())
+        .limit("string", max_body_bytes.into())
+        .limit("bytes", max_body_bytes.into());
+    
+    let figment = rate_limit_config
+        .configure_ip_header(rocket::Config::figment().merge(("port", port)).merge(("limits", limits)));
+    
+    info!(port, "Rocket server starting on http://127.0.0.1:{}", port);
+    let server = rocket::build()
+        .configure(figment)
         .manage(State {
             rpc,
             keystore,
             bridge_wallet,
-            deposit_tracker: Arc::new(Mutex::new(deposit_tracker)),
-            withdrawal_tracker: Arc::new(Mutex::new(withdrawal_tracker)),
+            fee_schedule,
+            db,
+            withdrawal_claims: Mutex::new(()),
+            solvency,
+            reserves_disclosure,
+            multisig,
+            payout_signing,
         })
-        .mount("/", routes![get_block, health, options_create_account, create_account, create_faucet, mint_from_faucet, options_hash, get_hash_endpoint, generate_hash_endpoint, options_claim, claim_deposit_endpoint, reconstruct_note_endpoint, consume_note_endpoint, options_account_balance, get_account_balance, options_pool_balance, get_pool_balance, options_withdrawal_create_commitment, create_commitment_withdrawal, options_withdrawal_claim, claim_withdrawal])
+        .manage(api_keys)
+        .manage(RateLimiter::new(rate_limit_config))
+        .register("/", catchers![unauthorized, forbidden, too_many_requests, default_catcher])
+        .mount("/", routes![get_block, health, get_metrics, get_openapi, get_pow_challenge, options_create_account, create_account, options_hash, get_hash_endpoint, generate_hash_endpoint, options_claim, claim_deposit_endpoint, reconstruct_note_endpoint, consume_note_endpoint, options_account_balance, get_account_balance, options_pool_balance, get_pool_balance, options_reserves, get_reserves, options_reserves_disclosure, get_reserves_disclosure, options_withdrawal_create_commitment, create_commitment_withdrawal, options_withdrawal_claim, claim_withdrawal, options_fee_quote, get_fee_quote, options_deposit_refund, request_deposit_refund, get_deposit_refund, options_deposit_address, create_deposit_address, options_deposit_intent, create_deposit_intent, get_deposit_intent, options_deposit_events, deposit_events, options_withdrawal_events, withdrawal_events]);
+    
+    // Operator and admin endpoints are left out entirely in production
+    let server = if production {
+        server
+    } else {
+        server.mount("/", routes![create_faucet, mint_from_faucet, get_fee_summary, export_accounting])
+    };
+    
+    server
         .attach(
             CorsOptions::default()
                 .allowed_origins(AllowedOrigins::all())
@@ -2345,8 +3355,12 @@ fn rocket() -> _ {
                 )
                 .allowed_headers(rocket_cors::AllowedHeaders::some(&[
                     "Authorization",
+                    "X-API-Key",
+                    "X-PoW-Challenge",
+                    "X-PoW-Nonce",
                     "Accept",
                     "Content-Type",
+                    "Last-Event-ID",
                 ]))
                 .allow_credentials(true)
                 .to_cors()
@@ -0,0 +1,197 @@
+use prometheus::{
+    register_gauge, register_gauge_vec, register_histogram, register_int_counter,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Encoder, Gauge, GaugeVec,
+    Histogram, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+use std::sync::LazyLock;
+
+/// Latency buckets (seconds) for mints and payouts - both wait on proving or the Zcash CLI
+const LATENCY_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0];
+
+pub static DEPOSITS_SEEN: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!("bridge_deposits_seen_total", "Zcash deposit outputs seen for the first time").unwrap()
+});
+
+pub static DEPOSITS_MINTED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!("bridge_deposits_minted_total", "Deposits minted as wTAZ notes").unwrap()
+});
+
+pub static DEPOSITS_FAILED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "bridge_deposits_failed_total",
+        "Deposits that could not be minted, by reason (rejected, mint_error)",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+pub static MINT_LATENCY: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "bridge_mint_latency_seconds",
+        "Time to mint a deposit note",
+        LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+pub static PAYOUT_LATENCY: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "bridge_payout_latency_seconds",
+        "Time to send a Zcash payout",
+        LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+pub static WITHDRAWALS_PENDING: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("bridge_withdrawals_pending", "Withdrawal commitments not yet claimed").unwrap()
+});
+
+pub static WITHDRAWALS_PAID: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!("bridge_withdrawals_paid_total", "Withdrawals paid out on Zcash").unwrap()
+});
+
+pub static WALLET_SPENDABLE: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!(
+        "bridge_wallet_spendable_zatoshis",
+        "Spendable bridge wallet balance by pool",
+        &["pool"]
+    )
+    .unwrap()
+});
+
+pub static WTAZ_OUTSTANDING: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!(
+        "bridge_wtaz_outstanding_supply",
+        "wTAZ minted minus wTAZ burned for withdrawals, from the bridge database ledger (base units)"
+    )
+    .unwrap()
+});
+
+pub static MIDEN_SYNC_HEIGHT: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("bridge_miden_sync_height", "Last Miden block the client synced to").unwrap()
+});
+
+pub static ZCASH_SCAN_HEIGHT: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("bridge_zcash_scan_height", "Zcash chain height seen by the bridge wallet").unwrap()
+});
+
+pub static WEBHOOK_DELIVERIES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "bridge_webhook_deliveries_total",
+        "Webhook delivery attempts, by outcome (delivered, failed, dead)",
+        &["outcome"]
+    )
+    .unwrap()
+});
+
+pub static RECONCILE_ISSUES: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!(
+        "bridge_reconcile_issues",
+        "Discrepancies found by the last reconciliation run, by kind",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+/// The on-chain counterpart of `WTAZ_OUTSTANDING`; the two drifting apart is worth an alert
+pub static SOLVENCY_ONCHAIN_SUPPLY: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!(
+        "bridge_solvency_onchain_supply",
+        "wTAZ in circulation on Miden, issued by the faucet minus exit burns, as checked against reserves (base units)"
+    )
+    .unwrap()
+});
+
+pub static SOLVENCY_RESERVES: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("bridge_solvency_reserves_zatoshis", "Confirmed bridge wallet balance backing the wTAZ supply").unwrap()
+});
+
+pub static SOLVENCY_RATIO: LazyLock<Gauge> = LazyLock::new(|| {
+    register_gauge!("bridge_solvency_ratio", "Reserves divided by outstanding supply").unwrap()
+});
+
+pub static SOLVENCY_HALTED: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("bridge_solvency_halted", "1 while minting and payouts are halted for a reserve deficit").unwrap()
+});
+
+pub static RELAYER_LAST_SUCCESS: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!(
+        "bridge_relayer_last_success_timestamp_seconds",
+        "Unix time of the last relayer tick that completed without errors",
+        &["relayer"]
+    )
+    .unwrap()
+});
+
+/// Record a relayer tick that completed without errors
+pub fn record_relayer_success(relayer: &str) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64();
+    RELAYER_LAST_SUCCESS.with_label_values(&[relayer]).set(now);
+}
+
+/// Update wallet gauges from a bridge wallet balance
+pub fn record_wallet_balance(balance: &crate::zcash::bridge_wallet::ZcashBalance) {
+    for (pool, spendable) in &balance.spendable_by_pool {
+        let zatoshis = spendable.parse::<f64>().unwrap_or(0.0) * 1e8;
+        WALLET_SPENDABLE.with_label_values(&[pool.as_str()]).set(zatoshis.round());
+    }
+    if let Some(height) = balance.height {
+        ZCASH_SCAN_HEIGHT.set(height as i64);
+    }
+}
+
+/// Render all registered metrics in the Prometheus text format
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        tracing::warn!(error = %e, "Failed to encode metrics");
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[rocket::get("/metrics")]
+fn metrics_endpoint() -> (rocket::http::ContentType, String) {
+    (rocket::http::ContentType::Plain, gather())
+}
+
+/// Serve `/metrics` in the background on the port in the `var` environment variable, if it is set
+///
+/// A value that isn't a port number is reported and ignored, rather than silently leaving the
+/// process without metrics. Must be called from within a Tokio runtime.
+pub fn serve_from_env(var: &str) {
+    let Ok(value) = std::env::var(var) else {
+        return;
+    };
+
+    match value.trim().parse::<u16>() {
+        Ok(port) => {
+            println!("Metrics: http://127.0.0.1:{}/metrics", port);
+            tokio::spawn(serve(port));
+        }
+        Err(e) => {
+            tracing::error!(var, value = %value, error = %e, "Invalid metrics port; not serving metrics");
+        }
+    }
+}
+
+/// Serve `/metrics` for a relayer process on 127.0.0.1:<port>
+///
+/// The API server mounts its own `/metrics`; relayers run as separate processes
+/// and expose theirs on the port given by their METRICS_PORT setting.
+pub async fn serve(port: u16) {
+    let figment = rocket::Config::figment()
+        .merge(("port", port))
+        .merge(("address", "127.0.0.1"));
+
+    if let Err(e) = rocket::custom(figment)
+        .mount("/", rocket::routes![metrics_endpoint])
+        .launch()
+        .await
+    {
+        tracing::error!(port, error = %e, "Metrics server stopped");
+    }
+}
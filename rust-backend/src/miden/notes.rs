@@ -8,6 +8,8 @@ use miden_objects::{
     },
     FieldElement, Felt, NoteError, Word,
 };
+use crate::error::{BridgeError, BridgeResult};
+use crate::miden::bridge_scripts::crosschain;
 
 /// NoteTag use case for notes bridged from external chains into Miden
 /// Using 2005 as our bridge-specific use case identifier (from 20050519, using first 4 digits)
@@ -36,7 +38,7 @@ pub fn create_zcash_withdrawal_note(
     asset_amount: u64,
     sender: AccountId,
     note_tag: NoteTag,
-) -> Result<Note, NoteError> {
+) -> BridgeResult<Note> {
     // Create the asset (wTAZ tokens to burn)
     let asset = FungibleAsset::new(faucet_id, asset_amount)
         .map_err(|e| NoteError::AddFungibleAssetBalanceError(e))?;
@@ -131,7 +133,7 @@ pub fn reconstruct_deposit_note(
     secret: Word,
     faucet_id: AccountId,
     amount: u64,
-) -> Result<Note, NoteError> {
+) -> BridgeResult<Note> {
     // Build the recipient from account_id + secret
     let recipient = build_p2id_recipient(account_id, secret)
         .map_err(|e| NoteError::other(format!("Failed to build recipient: {:?}", e)))?;
@@ -159,12 +161,37 @@ pub fn reconstruct_deposit_note(
     Ok(note)
 }
 
+/// Public marker note the faucet emits for a commitment withdrawal
+///
+/// It carries no assets, just the commitment in its inputs, and has a zero serial number, so
+/// every instance builds the same note (and note ID) from the commitment.
+pub fn withdrawal_commitment_note(faucet_id: AccountId, commitment: Word) -> BridgeResult<Note> {
+    // The CROSSCHAIN script is only there because a public recipient needs one
+    let recipient = NoteRecipient::new(
+        Word::empty(),
+        crosschain(),
+        NoteInputs::new(commitment.iter().copied().collect())?,
+    );
+
+    let metadata = NoteMetadata::new(
+        faucet_id,
+        NoteType::Public, // PUBLIC so relayer can scan
+        NoteTag::for_local_use_case(BRIDGE_USECASE, 1)
+            .map_err(|e| NoteError::other(format!("Invalid tag: {:?}", e)))?,
+        NoteExecutionHint::always(),
+        Felt::ZERO,
+    )
+    .map_err(|e| NoteError::other(format!("Failed to create metadata: {:?}", e)))?;
+
+    Ok(Note::new(NoteAssets::new(vec![])?, metadata, recipient))
+}
+
 /// Encode a Zcash testnet z-address into 3 felts
 /// 
 /// Zcash testnet addresses are base58 encoded strings (~95 chars for z-addresses).
 /// We decode the base58, then split the bytes into 3 felts.
 /// Each felt is 252 bits (31.5 bytes), so 3 felts = 94.5 bytes max.
-pub fn encode_zcash_address(address: &str) -> Result<[Felt; 3], String> {
+pub fn encode_zcash_address(address: &str) -> BridgeResult<[Felt; 3]> {
     // Simple approach: hash the address string and split into felts
     // For production, we'd want proper base58 decoding
     use miden_crypto::hash::rpo::Rpo256;
@@ -185,7 +212,7 @@ pub fn encode_zcash_address(address: &str) -> Result<[Felt; 3], String> {
 /// 
 /// Note: This is a simplified version. For full functionality, we'd need
 /// to store the original address mapping or use a deterministic encoding.
-pub fn decode_zcash_address(felts: [Felt; 3]) -> Result<String, String> {
+pub fn decode_zcash_address(felts: [Felt; 3]) -> BridgeResult<String> {
     // This is a placeholder - in practice, we'd need to store the mapping
     // or use a deterministic encoding scheme
     // For now, return hex representation
@@ -201,3 +228,9 @@ pub fn get_bridge_note_tag() -> NoteTag {
         .expect("Bridge use case tag should be valid")
 }
 
+/// Note construction fails on bad inputs (amounts, tags, recipients)
+impl From<NoteError> for BridgeError {
+    fn from(e: NoteError) -> Self {
+        BridgeError::InvalidInput(format!("Invalid note: {}", e))
+    }
+}
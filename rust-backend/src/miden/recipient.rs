@@ -1,4 +1,5 @@
 use miden_lib::note::utils::build_p2id_recipient;
+use crate::error::{BridgeError, BridgeResult};
 use miden_objects::{account::AccountId, note::NoteRecipient, Word};
 
 /// Build a P2ID (Pay-to-ID) recipient for deposit notes
@@ -8,9 +9,9 @@ use miden_objects::{account::AccountId, note::NoteRecipient, Word};
 pub fn build_deposit_recipient(
     account_id: AccountId,
     secret: Word,
-) -> Result<NoteRecipient, String> {
+) -> BridgeResult<NoteRecipient> {
     build_p2id_recipient(account_id, secret)
-        .map_err(|e| format!("Failed to build recipient: {:?}", e))
+        .map_err(|e| BridgeError::InvalidInput(format!("Failed to build recipient: {:?}", e)))
 }
 
 /// Generate a random secret for a deposit
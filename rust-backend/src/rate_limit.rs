@@ -0,0 +1,337 @@
+use crate::error::{BridgeError, BridgeResult};
+use rocket::figment::Figment;
+use rocket::http::{Header, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+/// How long a proof-of-work challenge stays valid
+const POW_CHALLENGE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Prune expired windows once the table grows past this many keys
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Limits for public endpoints, loaded from the environment
+///
+/// RATE_LIMIT_PER_IP_PER_MINUTE     requests per IP per minute on public endpoints (default 60)
+/// RATE_LIMIT_STATE_PER_IP_PER_HOUR state-creating requests per IP per hour (default 10)
+/// RATE_LIMIT_PER_ACCOUNT_PER_HOUR  withdrawals per Miden account per hour (default 5)
+/// POW_DIFFICULTY                   leading zero bits required on state-creating endpoints (default 0 = off)
+/// TRUSTED_IP_HEADER                header carrying the client IP, set by the reverse proxy in front
+///                                  of the API (e.g. X-Real-IP); unset uses the peer address, since
+///                                  clients could otherwise pick their own IP per request
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub per_ip_per_minute: u32,
+    pub state_per_ip_per_hour: u32,
+    pub per_account_per_hour: u32,
+    pub pow_difficulty: u32,
+    pub trusted_ip_header: Option<String>,
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: u32| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<u32>().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            per_ip_per_minute: var("RATE_LIMIT_PER_IP_PER_MINUTE", 60),
+            state_per_ip_per_hour: var("RATE_LIMIT_STATE_PER_IP_PER_HOUR", 10),
+            per_account_per_hour: var("RATE_LIMIT_PER_ACCOUNT_PER_HOUR", 5),
+            pow_difficulty: var("POW_DIFFICULTY", 0).min(32),
+            trusted_ip_header: std::env::var("TRUSTED_IP_HEADER")
+                .ok()
+                .map(|header| header.trim().to_string())
+                .filter(|header| !header.is_empty()),
+        }
+    }
+
+    /// Rocket config reading the client IP from TRUSTED_IP_HEADER only
+    ///
+    /// Rocket trusts X-Real-IP by default, which lets any client pick its IP per request.
+    pub fn configure_ip_header(&self, figment: Figment) -> Figment {
+        match &self.trusted_ip_header {
+            Some(header) => figment.merge(("ip_header", header.as_str())),
+            None => figment.merge(("ip_header", false)),
+        }
+    }
+}
+
+/// A rejected request and when the caller may retry
+#[derive(Debug, Clone)]
+pub struct RateLimited {
+    pub message: String,
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PowChallenge {
+    pub challenge: String,
+    pub difficulty: u32,
+    pub expires_in_secs: u64,
+}
+
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+/// Fixed-window counters per IP / account, plus outstanding proof-of-work challenges
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    windows: Mutex<HashMap<String, Window>>,
+    challenges: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(HashMap::new()),
+            challenges: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Count a request against a bucket; a limit of 0 disables the bucket
+    fn hit(&self, bucket: String, limit: u32, period: Duration, what: &str) -> Result<(), RateLimited> {
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+
+        if windows.len() > MAX_TRACKED_KEYS {
+            windows.retain(|_, window| now.duration_since(window.started) < Duration::from_secs(60 * 60));
+        }
+
+        let window = windows.entry(bucket).or_insert(Window { started: now, count: 0 });
+        if now.duration_since(window.started) >= period {
+            window.started = now;
+            window.count = 0;
+        }
+
+        if window.count >= limit {
+            let retry_after = period.saturating_sub(now.duration_since(window.started));
+            return Err(RateLimited {
+                message: format!(
+                    "Too many {} (limit {} per {}s). Retry in {}s.",
+                    what,
+                    limit,
+                    period.as_secs(),
+                    retry_after.as_secs().max(1)
+                ),
+                retry_after_secs: retry_after.as_secs().max(1),
+            });
+        }
+
+        window.count += 1;
+        Ok(())
+    }
+
+    /// General per-IP limit for public endpoints
+    pub fn check_ip(&self, ip: &str) -> Result<(), RateLimited> {
+        self.hit(
+            format!("ip:{}", ip),
+            self.config.per_ip_per_minute,
+            Duration::from_secs(60),
+            "requests from this IP",
+        )
+    }
+
+    /// Hourly quota for endpoints that create state (accounts, withdrawal notes, addresses)
+    pub fn check_state_creation(&self, ip: &str) -> Result<(), RateLimited> {
+        self.hit(
+            format!("state:{}", ip),
+            self.config.state_per_ip_per_hour,
+            Duration::from_secs(60 * 60),
+            "state-creating requests from this IP",
+        )
+    }
+
+    /// Hourly limit per Miden account
+    pub fn check_account(&self, account_id: &str) -> Result<(), RateLimited> {
+        self.hit(
+            format!("account:{}", account_id.trim().to_lowercase()),
+            self.config.per_account_per_hour,
+            Duration::from_secs(60 * 60),
+            "requests for this account",
+        )
+    }
+
+    /// Hand out a one-time proof-of-work challenge
+    pub fn issue_challenge(&self) -> PowChallenge {
+        use rand::RngCore;
+
+        let mut bytes = [0u8; 16];
+        rand::rng().fill_bytes(&mut bytes);
+        let challenge = hex::encode(bytes);
+
+        let now = Instant::now();
+        let mut challenges = self.challenges.lock().unwrap();
+        challenges.retain(|_, issued| now.duration_since(*issued) < POW_CHALLENGE_TTL);
+        challenges.insert(challenge.clone(), now);
+
+        PowChallenge {
+            challenge,
+            difficulty: self.config.pow_difficulty,
+            expires_in_secs: POW_CHALLENGE_TTL.as_secs(),
+        }
+    }
+
+    /// Check a solved challenge: SHA-256("<challenge>:<nonce>") must start with `difficulty` zero bits
+    ///
+    /// Each challenge can only be used once. Fails with `InvalidProofOfWork`.
+    pub fn verify_pow(&self, challenge: &str, nonce: &str) -> BridgeResult<()> {
+        let invalid = |message: &str| Err(BridgeError::InvalidProofOfWork(message.to_string()));
+
+        let issued = self.challenges.lock().unwrap().remove(challenge);
+        match issued {
+            Some(issued) if issued.elapsed() < POW_CHALLENGE_TTL => {}
+            Some(_) => return invalid("Proof-of-work challenge expired. Fetch a new one from /pow/challenge."),
+            None => return invalid("Unknown or already used proof-of-work challenge. Fetch a new one from /pow/challenge."),
+        }
+
+        let digest = Sha256::digest(format!("{}:{}", challenge, nonce).as_bytes());
+        if leading_zero_bits(&digest) < self.config.pow_difficulty {
+            return Err(BridgeError::InvalidProofOfWork(format!(
+                "Proof-of-work does not meet difficulty {}.",
+                self.config.pow_difficulty
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in bytes {
+        if *byte == 0 {
+            bits += 8;
+        } else {
+            bits += byte.leading_zeros();
+            break;
+        }
+    }
+    bits
+}
+
+/// Why a request was rate limited, cached on the request for the 429 catcher
+pub struct RateLimitFailure(pub Option<RateLimited>);
+
+/// Why the proof-of-work check rejected a request, cached on the request for the 403 catcher
+pub struct PowFailure(pub Option<BridgeError>);
+
+/// The peer address, or the TRUSTED_IP_HEADER value when the API runs behind a proxy
+fn client_key(request: &Request<'_>) -> String {
+    request
+        .client_ip()
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn limiter<'r>(request: &'r Request<'_>) -> Option<&'r RateLimiter> {
+    request.rocket().state::<RateLimiter>()
+}
+
+fn reject<T>(request: &Request<'_>, limited: RateLimited) -> Outcome<T, String> {
+    let message = limited.message.clone();
+    request.local_cache(|| RateLimitFailure(Some(limited.clone())));
+    Outcome::Error((Status::TooManyRequests, message))
+}
+
+/// Request guard applying the per-IP limit to public endpoints
+pub struct RateLimit;
+
+/// Request guard for state-creating endpoints: per-IP limit, hourly quota and proof-of-work
+///
+/// When POW_DIFFICULTY > 0, requests must carry `X-PoW-Challenge` and `X-PoW-Nonce` headers;
+/// a missing or bad proof is rejected with 403 `invalid_proof_of_work`.
+pub struct StateCreationLimit;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimit {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(limiter) = limiter(request) else {
+            return Outcome::Success(RateLimit);
+        };
+
+        match limiter.check_ip(&client_key(request)) {
+            Ok(()) => Outcome::Success(RateLimit),
+            Err(limited) => reject(request, limited),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for StateCreationLimit {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(limiter) = limiter(request) else {
+            return Outcome::Success(StateCreationLimit);
+        };
+        let ip = client_key(request);
+
+        if let Err(limited) = limiter.check_ip(&ip) {
+            return reject(request, limited);
+        }
+
+        if limiter.config().pow_difficulty > 0 {
+            let challenge = request.headers().get_one("X-PoW-Challenge");
+            let nonce = request.headers().get_one("X-PoW-Nonce");
+            let result = match (challenge, nonce) {
+                (Some(challenge), Some(nonce)) => limiter.verify_pow(challenge.trim(), nonce.trim()),
+                _ => Err(BridgeError::InvalidProofOfWork(
+                    "Proof-of-work required: fetch /pow/challenge and send X-PoW-Challenge and X-PoW-Nonce headers.".to_string(),
+                )),
+            };
+
+            // A bad proof is the client's fault, not an exhausted quota, so it gets 403 rather than 429
+            if let Err(error) = result {
+                let message = error.to_string();
+                request.local_cache(|| PowFailure(Some(error)));
+                return Outcome::Error((Status::Forbidden, message));
+            }
+        }
+
+        // Quota is only spent once the proof-of-work checks out
+        match limiter.check_state_creation(&ip) {
+            Ok(()) => Outcome::Success(StateCreationLimit),
+            Err(limited) => reject(request, limited),
+        }
+    }
+}
+
+/// 429 response carrying a Retry-After header
+pub struct TooManyRequests<R> {
+    pub retry_after_secs: u64,
+    pub body: R,
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for TooManyRequests<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = Response::build_from(self.body.respond_to(request)?);
+        response.status(Status::TooManyRequests);
+        if self.retry_after_secs > 0 {
+            response.header(Header::new("Retry-After", self.retry_after_secs.to_string()));
+        }
+        response.ok()
+    }
+}
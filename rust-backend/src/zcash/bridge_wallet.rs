@@ -1,12 +1,17 @@
+use crate::error::{BridgeError, BridgeResult};
+use std::io::Write;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
 use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ZcashBalance {
     pub total: String,
     pub spendable: String,
     pub pending: String,
+    pub spendable_by_pool: std::collections::BTreeMap<String, String>, // "sapling", "orchard", "transparent"
+    pub height: Option<u64>, // Chain height the wallet has seen
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,9 +23,78 @@ pub struct ZcashAddress {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TransactionInfo {
     pub txid: String,
-    pub amount: u64,
+    pub amount: u64, // Net amount of the whole transaction, in zatoshis (its sign is in `sent`)
+    pub sent: bool, // Whether the wallet spent funds in it (negative net amount)
+    pub outputs: Vec<OutputInfo>,
+}
+
+impl TransactionInfo {
+    /// Whether the wallet only received in this transaction
+    ///
+    /// Deposits are funded by someone else, so their net amount is incoming. Payouts, refunds
+    /// and their change outputs (which go back to a bridge address) come from transactions the
+    /// wallet spent in, and must never be read as deposits.
+    pub fn is_incoming(&self) -> bool {
+        !self.sent && self.amount > 0
+    }
+}
+
+/// A single shielded or transparent output of a transaction ("Output N (POOL)" block)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OutputInfo {
+    pub pool: String, // "orchard", "sapling" or "transparent"
+    pub index: u32,
+    pub value: u64,
+    pub to_address: Option<String>,
     pub memo: Option<String>,
+}
+
+/// An incoming output to the bridge wallet - each one is processed as its own deposit
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DepositOutput {
+    pub txid: String,
+    pub pool: String,
+    pub index: u32,
+    pub value: u64,
     pub to_address: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl DepositOutput {
+    /// Unique key of the deposit: (txid, pool, output index)
+    pub fn key(&self) -> String {
+        format!("{}:{}:{}", self.txid, self.pool, self.index)
+    }
+}
+
+/// Check that an address looks like a Zcash testnet address (unified, Sapling or transparent)
+pub fn is_testnet_address(address: &str) -> bool {
+    let address = address.trim();
+    (address.starts_with("utest1") || address.starts_with("ztest") || address.starts_with("tm"))
+        && address.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The txid zcash-devtool printed for a sent transaction: the last 64-digit hex word of its output
+///
+/// The output can carry progress lines and labels around the txid, so the whole of it isn't one.
+pub fn parse_txid(output: &str) -> Option<String> {
+    output
+        .split(|c: char| !c.is_ascii_hexdigit())
+        .rfind(|word| word.len() == 64)
+        .map(|txid| txid.to_lowercase())
+}
+
+/// Map a failed zcash-devtool run to an error, telling a short wallet apart from other failures
+fn check_status(output: &Output) -> BridgeResult<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.to_lowercase().contains("insufficient") {
+        return Err(BridgeError::InsufficientLiquidity(format!("Bridge wallet cannot cover the payout: {}", stderr.trim())));
+    }
+    Err(BridgeError::WalletUnavailable(format!("Command failed: {}", stderr)))
 }
 
 pub struct BridgeWallet {
@@ -32,6 +106,11 @@ pub struct BridgeWallet {
 impl BridgeWallet {
     pub fn new(project_root: PathBuf) -> Self {
         let wallet_dir = project_root.join("wallet").join("bridge_wallet");
+        Self::with_wallet_dir(project_root, wallet_dir)
+    }
+
+    /// A wallet in another directory, run with the same zcash-devtool checkout
+    pub fn with_wallet_dir(project_root: PathBuf, wallet_dir: PathBuf) -> Self {
         let identity_file = wallet_dir.join("key.txt");
         let zcash_devtool_dir = project_root.join("wallet").join("zcash-devtool");
         
@@ -42,28 +121,51 @@ impl BridgeWallet {
         }
     }
 
-    /// Execute a zcash-devtool command
-    fn exec_command(&self, args: Vec<&str>) -> Result<String, String> {
+    /// zcash-devtool invocation, without arguments
+    fn devtool(&self) -> Command {
         let mut cmd = Command::new("cargo");
         cmd.args(&["run", "--release", "--all-features", "--"]);
-        cmd.args(&args);
         cmd.current_dir(&self.zcash_devtool_dir);
+        cmd
+    }
+
+    /// Execute a zcash-devtool command
+    fn exec_command(&self, args: Vec<&str>) -> BridgeResult<String> {
+        let output = self.devtool()
+            .args(&args)
+            .output()
+            .map_err(|e| BridgeError::WalletUnavailable(format!("Failed to execute command: {}", e)))?;
         
-        let output = cmd.output()
-            .map_err(|e| format!("Failed to execute command: {}", e))?;
+        check_status(&output)?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Execute a zcash-devtool pczt command, which reads a PCZT on stdin and writes one to stdout
+    fn exec_pczt(&self, args: Vec<&str>, input: Option<&[u8]>) -> BridgeResult<Vec<u8>> {
+        let mut child = self.devtool()
+            .args(&args)
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| BridgeError::WalletUnavailable(format!("Failed to execute command: {}", e)))?;
         
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Command failed: {}", stderr));
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin.write_all(input)
+                .map_err(|e| BridgeError::WalletUnavailable(format!("Failed to pass PCZT to zcash-devtool: {}", e)))?;
         }
         
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        let output = child.wait_with_output()
+            .map_err(|e| BridgeError::WalletUnavailable(format!("Failed to execute command: {}", e)))?;
+        
+        check_status(&output)?;
+        Ok(output.stdout)
     }
 
     /// Get bridge wallet balance
-    pub fn get_balance(&self) -> Result<ZcashBalance, String> {
+    pub fn get_balance(&self) -> BridgeResult<ZcashBalance> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         
         let output = self.exec_command(vec![
             "wallet",
@@ -75,9 +177,9 @@ impl BridgeWallet {
     }
 
     /// Sync bridge wallet
-    pub fn sync(&self) -> Result<String, String> {
+    pub fn sync(&self) -> BridgeResult<String> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         
         self.exec_command(vec![
             "wallet",
@@ -88,9 +190,9 @@ impl BridgeWallet {
     }
 
     /// List addresses in bridge wallet
-    pub fn list_addresses(&self, account_id: Option<&str>) -> Result<Vec<ZcashAddress>, String> {
+    pub fn list_addresses(&self, account_id: Option<&str>) -> BridgeResult<Vec<ZcashAddress>> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         
         let mut args = vec!["wallet", "-w", wallet_path, "list-addresses"];
         if let Some(acc_id) = account_id {
@@ -102,10 +204,115 @@ impl BridgeWallet {
         self.parse_addresses(&output)
     }
 
+    /// List account UUIDs in bridge wallet
+    pub fn list_accounts(&self) -> BridgeResult<Vec<String>> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        
+        let output = self.exec_command(vec![
+            "wallet",
+            "-w", wallet_path,
+            "list-accounts",
+        ])?;
+        
+        // Account UUIDs look like 8-4-4-4-12 hex groups
+        let accounts = output
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_ascii_hexdigit() && c != '-'))
+            .filter(|word| {
+                word.len() == 36
+                    && word.split('-').map(str::len).eq([8, 4, 4, 4, 12])
+                    && word.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+            })
+            .map(str::to_string)
+            .collect();
+        
+        Ok(accounts)
+    }
+
+    /// The bridge account: BRIDGE_ZCASH_ACCOUNT_ID if set, otherwise the first account in the wallet
+    fn bridge_account_id(&self) -> BridgeResult<String> {
+        match std::env::var("BRIDGE_ZCASH_ACCOUNT_ID") {
+            Ok(account_id) => Ok(account_id),
+            Err(_) => self.list_accounts()?
+                .into_iter()
+                .next()
+                .ok_or_else(|| BridgeError::WalletUnavailable("Bridge wallet has no accounts".to_string())),
+        }
+    }
+
+    /// Unified full viewing key of the bridge account, as list-accounts prints it
+    ///
+    /// Anyone holding it can see every transaction of the account, but can't spend.
+    pub fn full_viewing_key(&self) -> BridgeResult<String> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        
+        let account_id = self.bridge_account_id()?;
+        let output = self.exec_command(vec![
+            "wallet",
+            "-w", wallet_path,
+            "list-accounts",
+        ])?;
+        
+        // Each "Account <uuid>" line is followed by its key lines ("UFVK: uviewtest1...")
+        let mut in_account = false;
+        for line in output.lines() {
+            let line = line.trim();
+            if line.starts_with("Account") {
+                in_account = line.contains(&account_id);
+            } else if in_account && let Some(ufvk) = line.strip_prefix("UFVK:") {
+                return Ok(ufvk.trim().to_string());
+            }
+        }
+        
+        Err(BridgeError::WalletUnavailable(format!("No UFVK listed for account {}", account_id)))
+    }
+
+    /// Set this directory up as a view-only wallet for a unified full viewing key
+    pub fn init_view_only(&self, ufvk: &str, birthday_height: u64) -> BridgeResult<String> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        let birthday = birthday_height.to_string();
+        
+        self.exec_command(vec![
+            "wallet",
+            "-w", wallet_path,
+            "init-fvk",
+            "--name", "reserves",
+            "--fvk", ufvk,
+            "--birthday", &birthday,
+            "-s", "zecrocks",
+        ])
+    }
+
+    /// Generate a fresh diversified unified address for the bridge account
+    ///
+    /// Uses BRIDGE_ZCASH_ACCOUNT_ID if set, otherwise the first account in the wallet.
+    pub fn generate_address(&self) -> BridgeResult<String> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        
+        let account_id = self.bridge_account_id()?;
+        
+        let output = self.exec_command(vec![
+            "wallet",
+            "-w", wallet_path,
+            "gen-addr",
+            "--account-id", &account_id,
+        ])?;
+        
+        output
+            .split_whitespace()
+            .find(|word| word.starts_with("utest1"))
+            .map(str::to_string)
+            .ok_or_else(|| BridgeError::WalletUnavailable(format!("No unified address in gen-addr output: {}", output.trim())))
+    }
+
     /// Enhance transactions to get memo data
-    pub fn enhance_transactions(&self) -> Result<String, String> {
+    pub fn enhance_transactions(&self) -> BridgeResult<String> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         
         self.exec_command(vec![
             "wallet",
@@ -115,26 +322,29 @@ impl BridgeWallet {
         ])
     }
 
-    /// Get memos from incoming transactions (deposits) after enhancing
-    /// This will sync, enhance, and then extract memos from received transactions only
-    pub fn extract_all_memos(&self) -> Result<Vec<(String, String, u64)>, String> {
+    /// Get incoming outputs (deposits) with memos after enhancing
+    /// This will sync, enhance, and then extract memos from received outputs only
+    ///
+    /// Outputs paid to one of `deposit_addresses` (per-user diversified addresses)
+    /// are returned even without a memo, since the address identifies the recipient.
+    pub fn extract_all_memos(&self, deposit_addresses: &std::collections::HashSet<String>) -> BridgeResult<Vec<DepositOutput>> {
         // Step 1: Sync wallet to get latest transactions from chain
-        println!("[Bridge Wallet] Syncing wallet...");
+        debug!("Syncing bridge wallet");
         self.sync()?;
-        println!("[Bridge Wallet] Sync complete");
+        debug!("Bridge wallet sync complete");
         
         // Step 2: Enhance transactions to download memo data
-        println!("[Bridge Wallet] Enhancing transactions to get memo data...");
+        debug!("Enhancing transactions to get memo data");
         self.enhance_transactions()?;
-        println!("[Bridge Wallet] Enhancement complete");
+        debug!("Enhancement complete");
         
         // Step 3: List all transactions
-        println!("[Bridge Wallet] Listing transactions...");
+        debug!("Listing transactions");
         let tx_output = self.list_transactions(None)?;
         
         // Step 4: Parse transactions to extract memos
         let transactions = self.parse_transactions(&tx_output)?;
-        println!("[Bridge Wallet] Parsed {} transactions", transactions.len());
+        debug!(transactions = transactions.len(), "Parsed transactions");
         
         // Get bridge wallet addresses to filter for incoming transactions only
         let bridge_addresses = match self.list_addresses(None) {
@@ -143,11 +353,11 @@ impl BridgeWallet {
                     .into_iter()
                     .map(|addr| addr.address)
                     .collect();
-                println!("[Bridge Wallet] Successfully loaded {} bridge addresses: {:?}", addr_set.len(), addr_set);
+                debug!(addresses = addr_set.len(), "Loaded bridge addresses");
                 addr_set
             }
             Err(e) => {
-                eprintln!("[Bridge Wallet] ⚠️ Failed to list addresses: {}. Will process all transactions with valid memos.", e);
+                warn!(error = %e, "Failed to list addresses, processing all transactions with valid memos");
                 // If we can't get addresses, process all transactions with valid memos
                 // This is acceptable for a private chain where only the bridge wallet can see transactions
                 std::collections::HashSet::new()
@@ -158,39 +368,72 @@ impl BridgeWallet {
         // If bridge_addresses is empty (couldn't load), process all transactions with valid memos
         // Otherwise, only process transactions sent TO bridge addresses
         let check_address = !bridge_addresses.is_empty();
-        println!("[Bridge Wallet] Address filtering: {}", if check_address { "enabled" } else { "disabled (processing all valid memos)" });
+        debug!(address_filtering = check_address, "Extracting deposit outputs");
         
         let mut memos = Vec::new();
         for tx in &transactions {
-            // Only process transactions that:
-            // 1. Have a memo
-            // 2. Have positive amount (money coming in)
-            // 3. (If addresses loaded) Are sent TO one of the bridge wallet addresses
-            if let Some(memo) = &tx.memo {
+            if !tx.is_incoming() {
+                continue;
+            }
+
+            for output in &tx.outputs {
+                if output.value == 0 {
+                    continue;
+                }
+                
+                // Outputs to a per-user deposit address are attributed by address, memo or not
+                let to_deposit_address = output.to_address.as_ref()
+                    .map(|to_addr| deposit_addresses.contains(to_addr))
+                    .unwrap_or(false);
+                if to_deposit_address {
+                    info!(txid = %tx.txid, pool = %output.pool, output_index = output.index, "Found output to deposit address");
+                    memos.push(DepositOutput {
+                        txid: tx.txid.clone(),
+                        pool: output.pool.clone(),
+                        index: output.index,
+                        value: output.value,
+                        to_address: output.to_address.clone(),
+                        memo: output.memo.clone(),
+                    });
+                    continue;
+                }
+                
+                // Otherwise only process outputs that:
+                // 1. Have a memo
+                // 2. Have positive value (money coming in)
+                // 3. (If addresses loaded) Are sent TO one of the bridge wallet addresses
+                let Some(memo) = &output.memo else {
+                    continue;
+                };
                 let memo_trimmed = memo.trim();
                 
-                if !memo_trimmed.is_empty() 
-                    && memo_trimmed != "Empty" 
-                    && !memo_trimmed.starts_with("Memo::Empty")
-                    && tx.amount > 0 {
-                    
-                    // If we have bridge addresses, check to_address matches
-                    // If we don't have addresses (private chain), process all valid memos
-                    let should_process = if check_address {
-                        if let Some(to_addr) = &tx.to_address {
-                            bridge_addresses.contains(to_addr)
-                        } else {
-                            false
-                        }
-                    } else {
-                        // No address filtering - process all transactions with valid memos
-                        true
-                    };
-                    
-                    if should_process {
-                        println!("[Bridge Wallet] ✅ Processing tx {} with memo: {}", tx.txid, memo_trimmed);
-                        memos.push((tx.txid.clone(), memo.clone(), tx.amount));
-                    }
+                if memo_trimmed.is_empty() 
+                    || memo_trimmed == "Empty" 
+                    || memo_trimmed.starts_with("Memo::Empty") {
+                    continue;
+                }
+                
+                // If we have bridge addresses, check to_address matches
+                // If we don't have addresses (private chain), process all valid memos
+                let should_process = if check_address {
+                    output.to_address.as_ref()
+                        .map(|to_addr| bridge_addresses.contains(to_addr))
+                        .unwrap_or(false)
+                } else {
+                    // No address filtering - process all outputs with valid memos
+                    true
+                };
+                
+                if should_process {
+                    info!(txid = %tx.txid, pool = %output.pool, output_index = output.index, "Found output with memo");
+                    memos.push(DepositOutput {
+                        txid: tx.txid.clone(),
+                        pool: output.pool.clone(),
+                        index: output.index,
+                        value: output.value,
+                        to_address: output.to_address.clone(),
+                        memo: Some(memo.clone()),
+                    });
                 }
             }
         }
@@ -199,9 +442,9 @@ impl BridgeWallet {
     }
 
     /// List transactions
-    pub fn list_transactions(&self, account_id: Option<&str>) -> Result<String, String> {
+    pub fn list_transactions(&self, account_id: Option<&str>) -> BridgeResult<String> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         
         let mut args = vec!["wallet", "-w", wallet_path, "list-tx"];
         if let Some(acc_id) = account_id {
@@ -212,24 +455,25 @@ impl BridgeWallet {
         self.exec_command(args)
     }
 
-    /// Parse transaction output to extract memo and amount
+    /// Parse transaction output to extract per-output values, recipients and memos
     /// 
     /// The list-tx output format is:
     /// <txid_hex>
     ///      Mined: <height> (<timestamp>)
-    ///     Amount: <amount> TAZ
+    ///     Amount: <amount> TAZ (negative when the wallet sent funds)
     ///   Fee paid: <fee>
     ///   Sent X notes, received Y notes, Z memos
     ///   Output 0 (ORCHARD)
     ///     Value: <amount> TAZ
     ///     To: <address>
     ///     Memo: <memo>
-    pub fn parse_transactions(&self, output: &str) -> Result<Vec<TransactionInfo>, String> {
+    ///   Output 1 (SAPLING)
+    ///     ...
+    pub fn parse_transactions(&self, output: &str) -> BridgeResult<Vec<TransactionInfo>> {
         let mut transactions = Vec::new();
         let lines: Vec<&str> = output.lines().collect();
         
         let mut current_tx: Option<TransactionInfo> = None;
-        let mut in_output = false;
         
         for line in lines {
             let line = line.trim();
@@ -247,10 +491,9 @@ impl BridgeWallet {
                 current_tx = Some(TransactionInfo {
                     txid: line.to_string(),
                     amount: 0,
-                    memo: None,
-                    to_address: None,
+                    sent: false,
+                    outputs: Vec::new(),
                 });
-                in_output = false;
                 continue;
             }
             
@@ -259,28 +502,58 @@ impl BridgeWallet {
                 if line.starts_with("Amount:") {
                     let parts: Vec<&str> = line.split_whitespace().collect();
                     if parts.len() >= 2 {
+                        tx.sent = parts[1].starts_with('-');
                         if let Ok(amount_taz) = parts[1].parse::<f64>() {
-                            // Convert TAZ to zatoshis (8 decimals)
-                            tx.amount = (amount_taz * 100_000_000.0) as u64;
+                            // Convert TAZ to zatoshis (8 decimals), rounding like output values
+                            tx.amount = (amount_taz.abs() * 100_000_000.0).round() as u64;
                         }
                     }
                 }
                 
-                // Check if we're in an output section
-                if line.starts_with("Output") {
-                    in_output = true;
+                // "Output N (POOL)" starts a new output section
+                if let Some(header) = line.strip_prefix("Output") {
+                    let mut parts = header.split_whitespace();
+                    let index = parts.next()
+                        .and_then(|i| i.parse::<u32>().ok())
+                        .unwrap_or(tx.outputs.len() as u32);
+                    let pool = parts.next()
+                        .map(|p| p.trim_matches(|c| c == '(' || c == ')').to_lowercase())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    tx.outputs.push(OutputInfo {
+                        pool,
+                        index,
+                        value: 0,
+                        to_address: None,
+                        memo: None,
+                    });
+                    continue;
+                }
+                
+                // Output fields apply to the most recent "Output N" block
+                let Some(output) = tx.outputs.last_mut() else {
+                    continue;
+                };
+                
+                // Parse "Value: X.XXXXXXXX TAZ"
+                if line.starts_with("Value:") {
+                    let parts: Vec<&str> = line.split_whitespace().collect();
+                    if parts.len() >= 2 {
+                        if let Ok(value_taz) = parts[1].parse::<f64>() {
+                            output.value = (value_taz * 100_000_000.0).round() as u64;
+                        }
+                    }
                 }
                 
                 // Parse "To: <address>"
-                if in_output && line.starts_with("To:") {
+                if line.starts_with("To:") {
                     let addr = line.strip_prefix("To:").unwrap_or("").trim().to_string();
                     if !addr.is_empty() {
-                        tx.to_address = Some(addr);
+                        output.to_address = Some(addr);
                     }
                 }
                 
                 // Parse "Memo: <memo>"
-                if in_output && line.starts_with("Memo:") {
+                if line.starts_with("Memo:") {
                     let memo_part = line.strip_prefix("Memo:").unwrap_or("").trim();
                     // Handle different memo formats
                     if memo_part.starts_with("Text(") {
@@ -290,20 +563,15 @@ impl BridgeWallet {
                                 if end > start {
                                     let memo_text = &memo_part[start+1..end];
                                     if !memo_text.is_empty() {
-                                        tx.memo = Some(memo_text.to_string());
+                                        output.memo = Some(memo_text.to_string());
                                     }
                                 }
                             }
                         }
                     } else if !memo_part.is_empty() && memo_part != "Empty" {
-                        tx.memo = Some(memo_part.to_string());
+                        output.memo = Some(memo_part.to_string());
                     }
                 }
-                
-                // Reset output flag when we hit a new transaction section
-                if line.starts_with("Mined:") || line.starts_with("Unmined") || line.starts_with("Expired") {
-                    in_output = false;
-                }
             }
         }
         
@@ -321,11 +589,11 @@ impl BridgeWallet {
         amount: &str,
         memo: Option<&str>,
         account_id: Option<&str>,
-    ) -> Result<String, String> {
+    ) -> BridgeResult<String> {
         let wallet_path = self.wallet_dir.to_str()
-            .ok_or("Invalid wallet path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
         let identity_path = self.identity_file.to_str()
-            .ok_or("Invalid identity path")?;
+            .ok_or_else(|| BridgeError::Internal("Invalid identity path".to_string()))?;
         
         let mut args = vec![
             "wallet",
@@ -351,15 +619,84 @@ impl BridgeWallet {
         self.exec_command(args)
     }
 
+    /// Build a payout PCZT and prove it, without the spending key
+    ///
+    /// Works from a view-only wallet (see `init_view_only`). The result still needs
+    /// `sign_pczt` on the host holding the key before it can be sent.
+    pub fn create_pczt(&self, address: &str, amount: &str, memo: Option<&str>) -> BridgeResult<Vec<u8>> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        
+        let mut args = vec![
+            "pczt", "create",
+            "-w", wallet_path,
+            "--address", address,
+            "--value", amount,
+        ];
+        
+        if let Some(m) = memo {
+            args.push("--memo");
+            args.push(m);
+        }
+        
+        let created = self.exec_pczt(args, None)?;
+        self.exec_pczt(vec!["pczt", "prove", "-w", wallet_path], Some(&created))
+    }
+
+    /// Add the spend authorizations to a PCZT with the bridge spending key (`key.txt`)
+    ///
+    /// Meant for the offline signer host; the API host never needs the key for this.
+    pub fn sign_pczt(&self, pczt: &[u8]) -> BridgeResult<Vec<u8>> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        let identity_path = self.identity_file.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid identity path".to_string()))?;
+        
+        self.exec_pczt(
+            vec!["pczt", "sign", "-w", wallet_path, "--identity", identity_path],
+            Some(pczt),
+        )
+    }
+
+    /// Extract the transaction from a signed PCZT and broadcast it, returning the txid
+    pub fn send_pczt(&self, pczt: &[u8]) -> BridgeResult<String> {
+        let wallet_path = self.wallet_dir.to_str()
+            .ok_or_else(|| BridgeError::Internal("Invalid wallet path".to_string()))?;
+        
+        let output = self.exec_pczt(
+            vec!["pczt", "send", "-w", wallet_path, "-s", "zecrocks"],
+            Some(pczt),
+        )?;
+        let output = String::from_utf8_lossy(&output);
+        parse_txid(&output).ok_or_else(|| BridgeError::WalletUnavailable(format!(
+            "zcash-devtool sent the PCZT but printed no txid: {}",
+            output.trim()
+        )))
+    }
+
     /// Parse balance from CLI output
-    fn parse_balance(&self, output: &str) -> Result<ZcashBalance, String> {
+    fn parse_balance(&self, output: &str) -> BridgeResult<ZcashBalance> {
         let lines: Vec<&str> = output.lines().collect();
         let mut total = "0".to_string();
         let mut spendable = "0".to_string();
         let pending = "0".to_string();
+        let mut spendable_by_pool = std::collections::BTreeMap::new();
+        let mut height = None;
         
         for line in lines {
             let line = line.trim();
+            if line.starts_with("Height:") {
+                height = line.split_whitespace().nth(1).and_then(|h| h.parse::<u64>().ok());
+            }
+            for (label, pool) in [
+                ("Sapling Spendable:", "sapling"),
+                ("Orchard Spendable:", "orchard"),
+                ("Unshielded Spendable:", "transparent"),
+            ] {
+                if let Some(value) = line.strip_prefix(label).and_then(|rest| rest.split_whitespace().next()) {
+                    spendable_by_pool.insert(pool.to_string(), value.to_string());
+                }
+            }
             if line.starts_with("Balance:") {
                 // Parse "Balance:   0.19990000 TAZ"
                 let parts: Vec<&str> = line.split_whitespace().collect();
@@ -391,11 +728,13 @@ impl BridgeWallet {
             total,
             spendable,
             pending,
+            spendable_by_pool,
+            height,
         })
     }
 
     /// Parse addresses from CLI output
-    fn parse_addresses(&self, output: &str) -> Result<Vec<ZcashAddress>, String> {
+    fn parse_addresses(&self, output: &str) -> BridgeResult<Vec<ZcashAddress>> {
         // Simple parsing - adjust based on actual CLI output format
         let mut addresses = Vec::new();
         
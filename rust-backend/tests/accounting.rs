@@ -0,0 +1,103 @@
+//! Accounting export of deposits, withdrawals and fees
+
+mod common;
+
+use common::quote;
+use rust_backend::bridge::accounting::{export, ExportRange, ExportTable};
+use rust_backend::bridge::fees::FeeDirection;
+use rust_backend::db::events::EventState;
+use rust_backend::db::{BridgeStore, SqliteStore};
+
+fn day(offset: i64) -> String {
+    (chrono::Utc::now().date_naive() + chrono::Duration::days(offset)).to_string()
+}
+
+/// A minted deposit, a paid withdrawal and an exit paid by the exit relayer, all recorded today
+fn populated_store(project_root: &std::path::Path) -> SqliteStore {
+    let store = SqliteStore::open(project_root).unwrap();
+
+    store.deposits().record_claim("0xaa", "tx1", "orchard", 0, 10_000).unwrap();
+    store.fees().record_fee(&quote(FeeDirection::Deposit, 10_000, 100), "tx1:orchard:0").unwrap();
+    store.events().record_deposit("tx1", "orchard", 0, Some("0xaa"), EventState::Minted, Some("0xn1"), None).unwrap();
+    store.deposit_addresses().assign("utest1addr", "0xaa", Some("0xacc"), Some("0xsecret")).unwrap();
+
+    store.withdrawals().record_withdrawal("0xc1", "0xn2", 3_000, 42).unwrap();
+    store.withdrawals().mark_claimed("0xc1", "ztx1").unwrap();
+    store.fees().record_fee(&quote(FeeDirection::Withdrawal, 3_000, 30), "0xc1").unwrap();
+    store.events().record_withdrawal("0xc1", EventState::Paid, Some("0xn2"), Some("ztx1")).unwrap();
+
+    store.fees().record_fee(&quote(FeeDirection::Withdrawal, 2_000, 20), "0xn3").unwrap();
+    store.events().record_withdrawal("0xn3", EventState::Paid, Some("0xn3"), Some("ztx2")).unwrap();
+
+    store
+}
+
+#[test]
+fn export_lists_flows_without_private_columns() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = populated_store(project_root.path());
+
+    let ledger = export(&store, ExportRange::parse(&day(0), &day(0)).unwrap(), false).unwrap();
+
+    assert_eq!(ledger.deposits.len(), 1);
+    let deposit = &ledger.deposits[0];
+    assert_eq!((deposit.amount, deposit.fee, deposit.minted), (10_000, Some(100), Some(9_900)));
+    assert_eq!(deposit.note_id.as_deref(), Some("0xn1"));
+    assert!(deposit.recipient_hash.is_none() && deposit.account_id.is_none());
+
+    assert_eq!(ledger.withdrawals.len(), 2);
+    let withdrawal = ledger.withdrawals.iter().find(|w| w.commitment.as_deref() == Some("0xc1")).unwrap();
+    assert_eq!((withdrawal.amount, withdrawal.fee, withdrawal.payout), (3_000, Some(30), Some(2_970)));
+    assert_eq!(withdrawal.payout_txid.as_deref(), Some("ztx1"));
+    let exit = ledger.withdrawals.iter().find(|w| w.commitment.is_none()).unwrap();
+    assert_eq!((exit.burn_note_id.as_str(), exit.amount, exit.payout_txid.as_deref()), ("0xn3", 2_000, Some("ztx2")));
+
+    let today = &ledger.daily[0];
+    assert_eq!((today.deposits, today.deposited, today.deposit_fees), (1, 10_000, 100));
+    assert_eq!((today.withdrawals, today.withdrawn, today.withdrawal_fees), (2, 5_000, 50));
+
+    let csv = ledger.to_csv(ExportTable::Deposits);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("claimed_at,txid,pool,output_index,amount,fee,minted,note_id"));
+    assert!(lines.next().unwrap().ends_with(",tx1,orchard,0,10000,100,9900,0xn1"));
+    assert!(!csv.contains("0xaa") && !csv.contains("0xacc"));
+}
+
+#[test]
+fn private_columns_only_on_request() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = populated_store(project_root.path());
+
+    let ledger = export(&store, ExportRange::parse(&day(0), &day(0)).unwrap(), true).unwrap();
+    let deposit = &ledger.deposits[0];
+    assert_eq!(deposit.recipient_hash.as_deref(), Some("0xaa"));
+    assert_eq!(deposit.account_id.as_deref(), Some("0xacc"));
+
+    let csv = ledger.to_csv(ExportTable::Deposits);
+    assert!(csv.lines().next().unwrap().ends_with(",note_id,recipient_hash,account_id"));
+    assert!(csv.contains(",0xaa,0xacc"));
+}
+
+#[test]
+fn running_totals_carry_over_earlier_days() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = populated_store(project_root.path());
+
+    // Nothing happened in the range itself, but the totals include today's flows
+    let ledger = export(&store, ExportRange::parse(&day(1), &day(3)).unwrap(), false).unwrap();
+    assert!(ledger.deposits.is_empty() && ledger.withdrawals.is_empty());
+    assert_eq!(ledger.daily.len(), 3);
+    for totals in &ledger.daily {
+        assert_eq!(totals.deposits, 0);
+        assert_eq!((totals.total_deposited, totals.total_withdrawn, totals.total_fees), (10_000, 5_000, 150));
+    }
+
+    // Nor does a range that ended before them
+    let ledger = export(&store, ExportRange::parse(&day(-3), &day(-1)).unwrap(), false).unwrap();
+    assert!(ledger.deposits.is_empty() && ledger.withdrawals.is_empty());
+    assert!(ledger.daily.iter().all(|totals| (totals.total_deposited, totals.total_fees) == (0, 0)));
+
+    assert!(ExportRange::parse(&day(3), &day(1)).is_err());
+    assert!(ExportRange::parse("2026-01-01", "2027-06-01").is_err());
+    assert!(ExportRange::parse("2026-13-01", "2026-12-01").is_err());
+}
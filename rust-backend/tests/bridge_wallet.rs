@@ -0,0 +1,137 @@
+//! Parsing zcash-devtool output
+
+use rust_backend::zcash::bridge_wallet::{parse_txid, BridgeWallet, TransactionInfo};
+use std::path::PathBuf;
+
+const TXID: &str = "3f1c0b6e9a2d4f8e7c5b1a0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f";
+
+/// A deposit paying two bridge addresses, in both pools, in one transaction
+const MULTI_OUTPUT_DEPOSIT: &str = r#"Transactions:
+a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90
+     Mined: 3401234 (2025-06-01 12:00:00 UTC)
+    Amount: 0.86 TAZ
+  Received 2 notes, 2 memos
+  Output 0 (ORCHARD)
+    Value: 0.29 TAZ
+    Received by account: 0f3c1d52-4c1e-4b1e-9d5c-0a1b2c3d4e5f
+    To: utest1bridgeorchard
+    Memo: Memo::Text("0x963d201a234dd49058f6277026d177|0x3ab276b56e938be6234ad1bfcb58db485af70dec66342cb94185c51b86db110b")
+  Output 1 (SAPLING)
+    Value: 0.57 TAZ
+    Received by account: 0f3c1d52-4c1e-4b1e-9d5c-0a1b2c3d4e5f
+    To: ztestsapling1bridge
+    Memo: Text("REF2345X")
+"#;
+
+/// A payout the wallet sent: the user's output, and change back to a bridge address
+const SENT_WITH_CHANGE: &str = r#"Transactions:
+b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a1
+     Mined: 3401300 (2025-06-01 13:00:00 UTC)
+    Amount: -1.15 TAZ
+  Fee paid: 0.0001 TAZ
+  Sent 1 notes, received 1 notes, 0 memos
+  Output 0 (ORCHARD)
+    Value: 1.1499 TAZ
+    To: utest1user
+    Memo: Empty
+  Output 1 (ORCHARD)
+    Value: 0.3 TAZ
+    Received by account: 0f3c1d52-4c1e-4b1e-9d5c-0a1b2c3d4e5f
+    To: utest1bridgeorchard
+    Memo: Empty
+"#;
+
+fn wallet() -> BridgeWallet {
+    BridgeWallet::new(PathBuf::from("/nonexistent"))
+}
+
+fn parse(output: &str) -> Vec<TransactionInfo> {
+    wallet().parse_transactions(output).unwrap()
+}
+
+#[test]
+fn every_output_of_a_deposit_is_parsed() {
+    let transactions = parse(MULTI_OUTPUT_DEPOSIT);
+    assert_eq!(transactions.len(), 1);
+
+    let tx = &transactions[0];
+    assert_eq!(tx.txid, "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90");
+    assert!(!tx.sent);
+    // 0.86 TAZ is 85999999.99... zatoshis as an f64
+    assert_eq!(tx.amount, 86_000_000);
+    assert!(tx.is_incoming());
+
+    assert_eq!(tx.outputs.len(), 2);
+    let orchard = &tx.outputs[0];
+    assert_eq!((orchard.pool.as_str(), orchard.index, orchard.value), ("orchard", 0, 29_000_000));
+    assert_eq!(orchard.to_address.as_deref(), Some("utest1bridgeorchard"));
+    assert!(orchard.memo.as_deref().unwrap().contains("|0x3ab276b5"));
+
+    let sapling = &tx.outputs[1];
+    assert_eq!((sapling.pool.as_str(), sapling.index, sapling.value), ("sapling", 1, 57_000_000));
+    assert_eq!(sapling.to_address.as_deref(), Some("ztestsapling1bridge"));
+    assert_eq!(sapling.memo.as_deref(), Some("REF2345X"));
+}
+
+#[test]
+fn sent_transaction_with_change_is_not_incoming() {
+    let transactions = parse(SENT_WITH_CHANGE);
+    assert_eq!(transactions.len(), 1);
+
+    let tx = &transactions[0];
+    assert!(tx.sent);
+    assert_eq!(tx.amount, 115_000_000);
+    // Its change output goes to a bridge address, but it's no deposit
+    assert!(!tx.is_incoming());
+
+    let values: Vec<u64> = tx.outputs.iter().map(|output| output.value).collect();
+    assert_eq!(values, [114_990_000, 30_000_000]);
+    assert_eq!(tx.outputs[1].to_address.as_deref(), Some("utest1bridgeorchard"));
+    assert!(tx.outputs.iter().all(|output| output.memo.is_none()));
+}
+
+#[test]
+fn transactions_are_split_on_their_txid() {
+    let listing = format!("{}{}", MULTI_OUTPUT_DEPOSIT, SENT_WITH_CHANGE.trim_start_matches("Transactions:\n"));
+    let transactions = parse(&listing);
+
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].outputs.len(), 2);
+    assert_eq!(transactions[1].outputs.len(), 2);
+    assert_eq!(
+        transactions.iter().map(TransactionInfo::is_incoming).collect::<Vec<_>>(),
+        [true, false]
+    );
+    assert!(parse("Transactions:\n").is_empty());
+}
+
+#[test]
+fn zero_amount_transaction_is_not_incoming() {
+    let tx = TransactionInfo {
+        txid: TXID.to_string(),
+        amount: 0,
+        sent: false,
+        outputs: Vec::new(),
+    };
+    assert!(!tx.is_incoming());
+    assert!(TransactionInfo { amount: 1, ..tx.clone() }.is_incoming());
+    assert!(!TransactionInfo { amount: 1, sent: true, ..tx }.is_incoming());
+}
+
+#[test]
+fn txid_is_parsed_out_of_send_output() {
+    assert_eq!(parse_txid(TXID).as_deref(), Some(TXID));
+    assert_eq!(parse_txid(&format!("{}\n", TXID.to_uppercase())).as_deref(), Some(TXID));
+
+    let output = format!("Extracting transaction\nBroadcasting to zecrocks\nSent transaction: {}\n", TXID);
+    assert_eq!(parse_txid(&output).as_deref(), Some(TXID));
+}
+
+#[test]
+fn send_output_without_a_txid_is_rejected() {
+    assert_eq!(parse_txid(""), None);
+    assert_eq!(parse_txid("Broadcast failed: connection reset"), None);
+    // Longer or shorter hex runs aren't txids
+    assert_eq!(parse_txid(&format!("{}00", TXID)), None);
+    assert_eq!(parse_txid(&TXID[..63]), None);
+}
@@ -0,0 +1,15 @@
+//! Fixtures shared by the integration tests
+
+use rust_backend::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use rust_backend::bridge::fees::{FeeDirection, FeeQuote};
+
+/// Fee quote for the wTAZ faucet, keyed by origin network like the bridge records them
+pub fn quote(direction: FeeDirection, gross_amount: u64, fee: u64) -> FeeQuote {
+    FeeQuote {
+        direction,
+        token: ZCASH_ORIGIN_NETWORK.to_string(),
+        gross_amount,
+        fee,
+        net_amount: gross_amount - fee,
+    }
+}
@@ -0,0 +1,116 @@
+//! Fee rules and quotes from the fee schedule
+
+use rust_backend::bridge::fees::{FeeDirection, FeeRule, FeeSchedule};
+use rust_backend::error::BridgeError;
+
+fn schedule(json: &str) -> FeeSchedule {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn proportional_fee_rounds_down() {
+    let rule = FeeRule { bps: 30, ..Default::default() };
+
+    assert_eq!(rule.fee_for(10_000), 30);
+    // 0.3% of 3_333 is 9.999
+    assert_eq!(rule.fee_for(3_333), 9);
+    assert_eq!(rule.fee_for(333), 0);
+    assert_eq!(rule.fee_for(0), 0);
+
+    let rule = FeeRule { flat: 1_000, bps: 10, ..Default::default() };
+    assert_eq!(rule.fee_for(1_999_999), 1_000 + 1_999);
+}
+
+#[test]
+fn fee_is_clamped_to_min_and_max() {
+    let rule = FeeRule { flat: 0, bps: 100, min: 500, max: Some(2_000) };
+
+    assert_eq!(rule.fee_for(10_000), 500);
+    assert_eq!(rule.fee_for(100_000), 1_000);
+    assert_eq!(rule.fee_for(1_000_000), 2_000);
+
+    // The cap wins over the minimum if a schedule sets them the wrong way round
+    let rule = FeeRule { min: 5_000, max: Some(2_000), ..Default::default() };
+    assert_eq!(rule.fee_for(1_000_000), 2_000);
+}
+
+#[test]
+fn large_amounts_do_not_overflow() {
+    let rule = FeeRule { bps: 10_000, ..Default::default() };
+    assert_eq!(rule.fee_for(u64::MAX), u64::MAX);
+
+    let rule = FeeRule { bps: 25, ..Default::default() };
+    assert_eq!(rule.fee_for(u64::MAX), (u64::MAX as u128 * 25 / 10_000) as u64);
+
+    // The flat part saturates instead of wrapping
+    let rule = FeeRule { flat: u64::MAX, bps: 10_000, ..Default::default() };
+    assert_eq!(rule.fee_for(u64::MAX), u64::MAX);
+
+    let fees = schedule(r#"{ "zcash_testnet": { "deposit": { "bps": 10 } } }"#);
+    let quote = fees.quote(FeeDirection::Deposit, "zcash_testnet", u64::MAX).unwrap();
+    assert_eq!(quote.gross_amount, u64::MAX);
+    assert_eq!(quote.fee + quote.net_amount, u64::MAX);
+}
+
+#[test]
+fn quote_splits_the_amount() {
+    let fees = schedule(r#"{ "zcash_testnet": { "withdrawal": { "flat": 15000 } } }"#);
+
+    let quote = fees.quote(FeeDirection::Withdrawal, "zcash_testnet", 100_000).unwrap();
+    assert_eq!(quote.direction, FeeDirection::Withdrawal);
+    assert_eq!(quote.token, "zcash_testnet");
+    assert_eq!(quote.gross_amount, 100_000);
+    assert_eq!(quote.fee, 15_000);
+    assert_eq!(quote.net_amount, 85_000);
+}
+
+#[test]
+fn quote_rejects_fee_covering_the_amount() {
+    let fees = schedule(r#"{ "zcash_testnet": { "deposit": { "flat": 10000 } } }"#);
+
+    assert!(matches!(
+        fees.quote(FeeDirection::Deposit, "zcash_testnet", 10_000),
+        Err(BridgeError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        fees.quote(FeeDirection::Deposit, "zcash_testnet", 9_999),
+        Err(BridgeError::InvalidInput(_))
+    ));
+    assert_eq!(fees.quote(FeeDirection::Deposit, "zcash_testnet", 10_001).unwrap().net_amount, 1);
+
+    // Even without a fee, a zero amount can't be bridged
+    assert!(fees.quote(FeeDirection::Withdrawal, "zcash_testnet", 0).is_err());
+}
+
+#[test]
+fn missing_token_or_direction_is_free() {
+    let fees = schedule(r#"{ "zcash_testnet": { "deposit": { "flat": 10000 } } }"#);
+
+    let quote = fees.quote(FeeDirection::Withdrawal, "zcash_testnet", 5_000).unwrap();
+    assert_eq!((quote.fee, quote.net_amount), (0, 5_000));
+    assert_eq!(fees.quote(FeeDirection::Deposit, "other", 5_000).unwrap().fee, 0);
+
+    // Refunds have their own rule, so a deposit below the deposit fee can still go back
+    assert_eq!(fees.quote(FeeDirection::Refund, "zcash_testnet", 5_000).unwrap().net_amount, 5_000);
+}
+
+#[test]
+fn schedule_loads_from_the_project_root() {
+    let project_root = tempfile::tempdir().unwrap();
+
+    // No file at all charges nothing
+    let fees = FeeSchedule::load(project_root.path()).unwrap();
+    assert_eq!(fees.quote(FeeDirection::Deposit, "zcash_testnet", 1).unwrap().fee, 0);
+
+    std::fs::write(
+        project_root.path().join("fee_schedule.json"),
+        r#"{ "zcash_testnet": { "deposit": { "flat": 10000, "bps": 10, "min": 10000, "max": 1000000 } } }"#,
+    )
+    .unwrap();
+    let fees = FeeSchedule::load(project_root.path()).unwrap();
+    assert_eq!(fees.quote(FeeDirection::Deposit, "zcash_testnet", 100_000_000).unwrap().fee, 110_000);
+    assert_eq!(fees.quote(FeeDirection::Withdrawal, "zcash_testnet", 100_000_000).unwrap().fee, 0);
+
+    std::fs::write(project_root.path().join("fee_schedule.json"), "{ not json").unwrap();
+    assert!(FeeSchedule::load(project_root.path()).is_err());
+}
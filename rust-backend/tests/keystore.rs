@@ -0,0 +1,82 @@
+//! Encrypted-at-rest keystore
+
+use miden_client::auth::AuthSecretKey;
+use miden_objects::utils::Serializable;
+use miden_objects::Word;
+use rust_backend::account::keystore::{EncryptedKeyStore, KeystoreCipher, KeystoreSecret};
+
+fn passphrase(passphrase: &str) -> KeystoreSecret {
+    KeystoreSecret::Passphrase(passphrase.to_string())
+}
+
+fn commitment(key: &AuthSecretKey) -> Word {
+    Word::from(key.public_key().to_commitment())
+}
+
+#[test]
+fn cipher_binds_entries_to_their_label() {
+    let cipher = KeystoreCipher::new(&passphrase("correct horse"), b"0123456789abcdef").unwrap();
+    let sealed = cipher.seal("a", b"secret key bytes").unwrap();
+
+    assert_eq!(cipher.open("a", &sealed).unwrap(), b"secret key bytes");
+    assert!(cipher.open("b", &sealed).is_err());
+
+    let other = KeystoreCipher::new(&passphrase("battery staple"), b"0123456789abcdef").unwrap();
+    assert!(other.open("a", &sealed).is_err());
+
+    let raw = KeystoreSecret::parse_key(&format!("0x{}", "11".repeat(32))).unwrap();
+    let raw_cipher = KeystoreCipher::new(&raw, b"ignored").unwrap();
+    assert_eq!(raw_cipher.open("a", &raw_cipher.seal("a", b"x").unwrap()).unwrap(), b"x");
+    assert!(KeystoreSecret::parse_key("abcd").is_err());
+}
+
+#[test]
+fn keys_survive_reopen_but_not_a_wrong_passphrase() {
+    let dir = tempfile::tempdir().unwrap();
+    let key = AuthSecretKey::new_rpo_falcon512();
+
+    let keystore = EncryptedKeyStore::create(dir.path(), &passphrase("correct horse")).unwrap();
+    keystore.add_key(&key).unwrap();
+
+    // Nothing on disk holds the key in the clear
+    let serialized = hex::encode(key.to_bytes());
+    for entry in std::fs::read_dir(dir.path()).unwrap() {
+        let contents = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+        assert!(!contents.contains(&serialized));
+    }
+
+    let reopened = EncryptedKeyStore::open(dir.path(), &passphrase("correct horse")).unwrap();
+    assert_eq!(reopened.get_key(commitment(&key)).unwrap().unwrap().to_bytes(), key.to_bytes());
+
+    assert!(EncryptedKeyStore::open(dir.path(), &passphrase("battery staple")).is_err());
+    assert!(EncryptedKeyStore::create(dir.path(), &passphrase("correct horse")).is_err());
+}
+
+#[test]
+fn plaintext_keys_import_and_rekey() {
+    let parent = tempfile::tempdir().unwrap();
+    let dir = parent.path().join("keystore");
+    std::fs::create_dir(&dir).unwrap();
+
+    // FilesystemKeyStore layout: one hex-encoded key per file
+    let key = AuthSecretKey::new_rpo_falcon512();
+    std::fs::write(dir.join("1234567890"), hex::encode(key.to_bytes())).unwrap();
+    assert_eq!(EncryptedKeyStore::plaintext_files(&dir).unwrap().len(), 1);
+
+    let keystore = EncryptedKeyStore::create(&dir, &passphrase("old")).unwrap();
+    let imported = keystore.import_plaintext(&dir).unwrap();
+    assert_eq!(imported.len(), 1);
+    assert!(keystore.get_key(commitment(&key)).unwrap().is_some());
+
+    // Rekeying refuses while the plain file is still there
+    let keystore = EncryptedKeyStore::open(&dir, &passphrase("old")).unwrap();
+    assert!(keystore.rekey(&passphrase("new")).is_err());
+    std::fs::remove_file(&imported[0]).unwrap();
+
+    let keystore = EncryptedKeyStore::open(&dir, &passphrase("old")).unwrap();
+    let rekeyed = keystore.rekey(&passphrase("new")).unwrap();
+    assert_eq!(rekeyed.public_keys().unwrap().len(), 1);
+    assert!(EncryptedKeyStore::open(&dir, &passphrase("old")).is_err());
+    assert!(EncryptedKeyStore::open(&dir, &passphrase("new")).unwrap().get_key(commitment(&key)).unwrap().is_some());
+    assert!(!parent.path().join("keystore.old").exists() && !parent.path().join("keystore.rekey").exists());
+}
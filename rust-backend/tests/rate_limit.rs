@@ -0,0 +1,151 @@
+//! Rate-limit windows, proof-of-work challenges and which client IP the limits count
+
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rust_backend::rate_limit::{RateLimit, RateLimitConfig, RateLimiter};
+use sha2::{Digest, Sha256};
+
+fn config() -> RateLimitConfig {
+    RateLimitConfig {
+        per_ip_per_minute: 3,
+        state_per_ip_per_hour: 2,
+        per_account_per_hour: 1,
+        pow_difficulty: 8,
+        trusted_ip_header: None,
+    }
+}
+
+/// Nonce whose SHA-256("<challenge>:<nonce>") starts with `difficulty` zero bits
+fn solve(challenge: &str, difficulty: u32) -> String {
+    (0u64..)
+        .map(|nonce| nonce.to_string())
+        .find(|nonce| {
+            let digest = Sha256::digest(format!("{}:{}", challenge, nonce).as_bytes());
+            let zeros = u128::from_be_bytes(digest[..16].try_into().unwrap()).leading_zeros();
+            zeros >= difficulty
+        })
+        .unwrap()
+}
+
+#[test]
+fn ip_limit_counts_each_ip_separately() {
+    let limiter = RateLimiter::new(config());
+
+    for _ in 0..3 {
+        limiter.check_ip("10.0.0.1").unwrap();
+    }
+    let limited = limiter.check_ip("10.0.0.1").unwrap_err();
+    assert!(limited.retry_after_secs >= 1 && limited.retry_after_secs <= 60);
+    assert!(limited.message.contains("limit 3 per 60s"), "{}", limited.message);
+
+    limiter.check_ip("10.0.0.2").unwrap();
+}
+
+#[test]
+fn buckets_are_independent() {
+    let limiter = RateLimiter::new(config());
+
+    limiter.check_state_creation("10.0.0.1").unwrap();
+    limiter.check_state_creation("10.0.0.1").unwrap();
+    let limited = limiter.check_state_creation("10.0.0.1").unwrap_err();
+    assert!(limited.retry_after_secs > 60);
+
+    // The hourly quota doesn't use up the per-minute limit of the same IP
+    limiter.check_ip("10.0.0.1").unwrap();
+}
+
+#[test]
+fn account_limit_ignores_case_and_whitespace() {
+    let limiter = RateLimiter::new(config());
+
+    limiter.check_account("0xABCDEF").unwrap();
+    assert!(limiter.check_account(" 0xabcdef ").is_err());
+    limiter.check_account("0x123456").unwrap();
+}
+
+#[test]
+fn zero_limit_disables_the_bucket() {
+    let limiter = RateLimiter::new(RateLimitConfig { per_ip_per_minute: 0, ..config() });
+
+    for _ in 0..100 {
+        limiter.check_ip("10.0.0.1").unwrap();
+    }
+}
+
+#[test]
+fn solved_challenge_is_accepted_once() {
+    let limiter = RateLimiter::new(config());
+    let challenge = limiter.issue_challenge();
+    assert_eq!(challenge.difficulty, 8);
+
+    let nonce = solve(&challenge.challenge, challenge.difficulty);
+    limiter.verify_pow(&challenge.challenge, &nonce).unwrap();
+
+    let reused = limiter.verify_pow(&challenge.challenge, &nonce).unwrap_err();
+    assert_eq!(reused.code(), "invalid_proof_of_work");
+    assert!(reused.message().contains("already used"), "{}", reused);
+}
+
+#[test]
+fn unknown_challenge_is_rejected() {
+    let limiter = RateLimiter::new(config());
+
+    let error = limiter.verify_pow("00112233445566778899aabbccddeeff", "0").unwrap_err();
+    assert!(error.message().contains("Unknown"), "{}", error);
+}
+
+#[test]
+fn insufficient_work_is_rejected_and_burns_the_challenge() {
+    let limiter = RateLimiter::new(RateLimitConfig { pow_difficulty: 32, ..config() });
+    let challenge = limiter.issue_challenge();
+
+    // A nonce meeting 32 bits by chance is a 1 in 4 billion shot
+    let nonce = (0u64..)
+        .map(|nonce| nonce.to_string())
+        .find(|nonce| Sha256::digest(format!("{}:{}", challenge.challenge, nonce).as_bytes())[0] != 0)
+        .unwrap();
+    let error = limiter.verify_pow(&challenge.challenge, &nonce).unwrap_err();
+    assert!(error.message().contains("difficulty 32"), "{}", error);
+
+    let retried = limiter.verify_pow(&challenge.challenge, &nonce).unwrap_err();
+    assert!(retried.message().contains("already used"), "{}", retried);
+}
+
+#[rocket::get("/")]
+fn limited(_limit: RateLimit) -> &'static str {
+    "ok"
+}
+
+/// Five requests, each claiming a different client IP in X-Real-IP
+async fn spoofed_statuses(config: RateLimitConfig) -> Vec<Status> {
+    let figment = config.configure_ip_header(rocket::Config::figment());
+    let rocket = rocket::custom(figment)
+        .manage(RateLimiter::new(config))
+        .mount("/", rocket::routes![limited]);
+    let client = Client::untracked(rocket).await.unwrap();
+
+    let mut statuses = Vec::new();
+    for i in 0..5 {
+        let response = client.get("/")
+            .remote("192.0.2.1:4000".parse().unwrap())
+            .header(Header::new("X-Real-IP", format!("198.51.100.{}", i)))
+            .dispatch()
+            .await;
+        statuses.push(response.status());
+    }
+    statuses
+}
+
+#[rocket::async_test]
+async fn client_ip_header_is_ignored_unless_trusted() {
+    let statuses = spoofed_statuses(config()).await;
+    assert_eq!(statuses[..3], [Status::Ok; 3]);
+    assert_eq!(statuses[3..], [Status::TooManyRequests; 2]);
+
+    let statuses = spoofed_statuses(RateLimitConfig {
+        trusted_ip_header: Some("X-Real-IP".to_string()),
+        ..config()
+    })
+    .await;
+    assert_eq!(statuses, [Status::Ok; 5]);
+}
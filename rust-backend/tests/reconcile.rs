@@ -0,0 +1,134 @@
+//! Reconciliation of the wallet, bridge database and Miden store, on an SQLite store
+
+mod common;
+
+use common::quote;
+use rust_backend::bridge::fees::FeeDirection;
+use rust_backend::bridge::reconcile::{
+    payouts, reconcile, IssueKind, MidenLedger, MidenNote, WalletLedger, WalletPayout,
+};
+use rust_backend::db::audit::{inputs_digest, AuditAction};
+use rust_backend::db::events::EventState;
+use rust_backend::db::{BridgeStore, SqliteStore};
+use rust_backend::zcash::bridge_wallet::{DepositOutput, OutputInfo, TransactionInfo};
+use std::collections::HashSet;
+
+fn output(txid: &str, value: u64) -> DepositOutput {
+    DepositOutput {
+        txid: txid.to_string(),
+        pool: "orchard".to_string(),
+        index: 0,
+        value,
+        to_address: None,
+        memo: Some("memo".to_string()),
+    }
+}
+
+fn note(note_id: &str, amount: u64) -> MidenNote {
+    MidenNote {
+        note_id: note_id.to_string(),
+        amount,
+    }
+}
+
+/// One minted deposit, one rejected deposit and one paid withdrawal, all consistent
+fn consistent(store: &dyn BridgeStore) -> (WalletLedger, MidenLedger) {
+    store.events().record_deposit("a1", "orchard", 0, None, EventState::Minted, Some("0xn1"), None).unwrap();
+    store.fees().record_fee(&quote(FeeDirection::Deposit, 10_000, 100), "a1:orchard:0").unwrap();
+    store.audit().append(AuditAction::DepositMint, &inputs_digest(&serde_json::json!({})), Some("0xn1"), "test").unwrap();
+
+    store.refunds().record_rejection("r1", "orchard", 0, 3_000, "bad memo", None, None, None).unwrap();
+
+    store.withdrawals().record_withdrawal("0xc1", "0xw1", 5_000, 1).unwrap();
+    store.withdrawals().mark_claimed("0xc1", "zpay1").unwrap();
+    store.fees().record_fee(&quote(FeeDirection::Withdrawal, 5_000, 50), "0xc1").unwrap();
+
+    let wallet = WalletLedger {
+        deposits: vec![output("a1", 10_000), output("r1", 3_000)],
+        payouts: vec![WalletPayout { txid: "zpay1".to_string(), value: 4_950 }],
+    };
+    let miden = MidenLedger {
+        mints: vec![note("0xn1", 9_900)],
+        withdrawal_notes: HashSet::from(["0xw1".to_string()]),
+        exit_burns: Vec::new(),
+        issued_supply: 9_900,
+    };
+    (wallet, miden)
+}
+
+#[test]
+fn consistent_sides_reconcile_clean() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = SqliteStore::open(project_root.path()).unwrap();
+    let (wallet, miden) = consistent(&store);
+
+    let report = reconcile(&wallet, &store, &miden).unwrap();
+    assert!(report.is_clean(), "{:?}", report.issues);
+    assert_eq!(report.totals.zcash_deposited, 13_000);
+    assert_eq!(report.totals.miden_minted, 9_900);
+    assert_eq!(report.totals.ledger_minted, 9_900);
+    assert_eq!(report.totals.zcash_paid_out, 4_950);
+    assert_eq!(report.totals.ledger_paid_out, 4_950);
+}
+
+#[test]
+fn discrepancies_are_reported() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = SqliteStore::open(project_root.path()).unwrap();
+    let (mut wallet, mut miden) = consistent(&store);
+
+    // Deposit nobody minted
+    wallet.deposits.push(output("b1", 2_000));
+    // Deposit minted a second time: the audit ledger has it, the events don't
+    store.audit().append(AuditAction::DepositMint, &inputs_digest(&serde_json::json!({})), Some("0xn2"), "test").unwrap();
+    miden.mints.push(note("0xn2", 9_900));
+    // Tokens minted outside the bridge flows
+    miden.mints.push(note("0xn9", 1_000));
+    // Exit burn nobody paid
+    miden.exit_burns.push(note("0xe1", 3_000));
+    // Payout nothing asked for
+    wallet.payouts.push(WalletPayout { txid: "zrogue".to_string(), value: 700 });
+    // Claim recorded for a different amount than the wallet received
+    store.deposits().record_claim("0xrh", "a1", "orchard", 0, 12_000).unwrap();
+
+    let report = reconcile(&wallet, &store, &miden).unwrap();
+    for kind in IssueKind::ALL {
+        assert_eq!(report.count(kind), 1, "{}: {:?}", kind.as_str(), report.issues);
+    }
+    let issue = |kind| report.issues.iter().find(|issue| issue.kind == kind).unwrap();
+    assert_eq!(issue(IssueKind::MissingMint).key, "b1:orchard:0");
+    assert_eq!(issue(IssueKind::DoubleMint).key, "0xn2");
+    assert_eq!(issue(IssueKind::UnbackedMint).key, "0xn9");
+    assert_eq!(issue(IssueKind::UnpaidBurn).key, "0xe1");
+    assert_eq!(issue(IssueKind::UnbackedPayout).key, "zrogue");
+    assert_eq!(issue(IssueKind::AmountMismatch).key, "a1:orchard:0");
+}
+
+#[test]
+fn payouts_skip_received_transactions_and_change() {
+    let output = |to_address: &str, value| OutputInfo {
+        pool: "orchard".to_string(),
+        index: 0,
+        value,
+        to_address: Some(to_address.to_string()),
+        memo: None,
+    };
+    let transactions = vec![
+        TransactionInfo {
+            txid: "sent".to_string(),
+            amount: 0,
+            sent: true,
+            outputs: vec![output("utest1user", 4_950), output("utest1bridge", 10_000)],
+        },
+        TransactionInfo {
+            txid: "received".to_string(),
+            amount: 10_000,
+            sent: false,
+            outputs: vec![output("utest1bridge", 10_000)],
+        },
+    ];
+
+    let payouts = payouts(&transactions, &HashSet::from(["utest1bridge".to_string()]));
+    assert_eq!(payouts.len(), 1);
+    assert_eq!((payouts[0].txid.as_str(), payouts[0].value), ("sent", 4_950));
+}
@@ -0,0 +1,67 @@
+//! Signer process policy and audit log
+#![cfg(unix)]
+
+use miden_objects::account::AccountId;
+use miden_objects::testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET;
+use rust_backend::account::remote_signer::{PolicyGuard, SignerAudit, SignerAuditEntry, SignerPolicy};
+
+fn faucet() -> AccountId {
+    AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap()
+}
+
+fn entry(signed: bool) -> SignerAuditEntry {
+    SignerAuditEntry {
+        at: 0,
+        pub_key: "0xk1".to_string(),
+        account: Some(faucet().to_hex()),
+        amount: Some(100),
+        message: Some("0xm1".to_string()),
+        signed,
+        reason: (!signed).then(|| "amount over the limit".to_string()),
+        prev_hash: String::new(),
+    }
+}
+
+#[test]
+fn policy_limits_account_amount_and_rate() {
+    let guard = PolicyGuard::new(SignerPolicy {
+        allowed_accounts: vec![faucet()],
+        max_amount: Some(1_000),
+        max_per_hour: 2,
+    });
+
+    assert!(guard.admit(faucet(), 1_001).unwrap_err().contains("limit"));
+    assert!(guard.admit(faucet(), 1_000).is_ok());
+    assert!(guard.admit(faucet(), 10).is_ok());
+
+    // Refusals don't count against the rate, but admitted transactions do
+    assert!(guard.admit(faucet(), 10).unwrap_err().contains("per hour"));
+
+    let other = PolicyGuard::new(SignerPolicy { allowed_accounts: Vec::new(), max_amount: None, max_per_hour: 10 });
+    assert!(other.admit(faucet(), 1).unwrap_err().contains("not allowed"));
+}
+
+#[test]
+fn audit_log_chains_and_detects_edits() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("signer_audit.jsonl");
+
+    let audit = SignerAudit::open(&path).unwrap();
+    audit.append(entry(true)).unwrap();
+    audit.append(entry(false)).unwrap();
+    let (entries, last_hash) = SignerAudit::verify(&path).unwrap();
+    assert_eq!(entries, 2);
+
+    // Reopening continues the chain
+    SignerAudit::open(&path).unwrap().append(entry(true)).unwrap();
+    let (entries, _) = SignerAudit::verify(&path).unwrap();
+    assert_eq!(entries, 3);
+    assert_ne!(SignerAudit::verify(&path).unwrap().1, last_hash);
+
+    // Dropping the first entry breaks the chain, and the log no longer opens
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let rest: Vec<_> = contents.lines().skip(1).collect();
+    std::fs::write(&path, rest.join("\n") + "\n").unwrap();
+    assert!(SignerAudit::verify(&path).is_err());
+    assert!(SignerAudit::open(&path).is_err());
+}
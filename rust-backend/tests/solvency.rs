@@ -0,0 +1,57 @@
+//! Solvency of the wTAZ supply against the bridge wallet's reserves
+
+use rust_backend::bridge::solvency::{assess, parse_zatoshis, reserves_by_pool};
+use rust_backend::zcash::bridge_wallet::ZcashBalance;
+use std::collections::BTreeMap;
+
+fn balance(pools: &[(&str, &str)]) -> ZcashBalance {
+    ZcashBalance {
+        total: "0".to_string(),
+        spendable: "0".to_string(),
+        pending: "0".to_string(),
+        spendable_by_pool: pools.iter().map(|(pool, value)| (pool.to_string(), value.to_string())).collect(),
+        height: None,
+    }
+}
+
+#[test]
+fn amounts_parse_to_exact_zatoshis() {
+    assert_eq!(parse_zatoshis("0.29000000").unwrap(), 29_000_000);
+    assert_eq!(parse_zatoshis("1.5").unwrap(), 150_000_000);
+    assert_eq!(parse_zatoshis("12").unwrap(), 1_200_000_000);
+    assert_eq!(parse_zatoshis(".00000001").unwrap(), 1);
+    assert!(parse_zatoshis("0.000000001").is_err());
+    assert!(parse_zatoshis("-1").is_err());
+    assert!(parse_zatoshis("").is_err());
+}
+
+#[test]
+fn reserves_cover_outstanding_supply() {
+    let reserves = reserves_by_pool(&balance(&[("orchard", "0.60000000"), ("sapling", "0.40000000")])).unwrap();
+
+    // 1.2 issued, 0.3 burned: 0.9 outstanding against 1.0 in reserve
+    let report = assess(120_000_000, 30_000_000, reserves, 0);
+    assert_eq!(report.outstanding, 90_000_000);
+    assert_eq!(report.reserves, 100_000_000);
+    assert_eq!(report.deficit, 0);
+    assert!(!report.halted);
+    assert!(report.ratio.unwrap() > 1.1);
+}
+
+#[test]
+fn deficit_past_threshold_halts() {
+    let reserves = BTreeMap::from([("orchard".to_string(), 95_000_000)]);
+
+    let report = assess(100_000_000, 0, reserves.clone(), 5_000_000);
+    assert_eq!(report.deficit, 5_000_000);
+    assert!(!report.halted, "a deficit at the threshold is tolerated");
+
+    let report = assess(100_000_001, 0, reserves, 5_000_000);
+    assert!(report.halted);
+    assert_eq!(report.ratio, Some(95_000_000.0 / 100_000_001.0));
+
+    // Nothing outstanding: no ratio, nothing to halt for
+    let report = assess(0, 0, BTreeMap::new(), 0);
+    assert_eq!(report.ratio, None);
+    assert!(!report.halted);
+}
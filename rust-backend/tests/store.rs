@@ -0,0 +1,541 @@
+//! Shared test suite for the bridge database backends
+//!
+//! The SQLite run always happens. The Postgres run needs `--features postgres` and a
+//! server in BRIDGE_TEST_POSTGRES_URL; scripts/test-postgres.sh provides one.
+
+mod common;
+
+use common::quote;
+use miden_objects::account::AccountId;
+use miden_objects::testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET;
+use rust_backend::bridge::deposit::ZCASH_ORIGIN_NETWORK;
+use rust_backend::bridge::fees::FeeDirection;
+use rust_backend::db::audit::{inputs_digest, AuditAction, ChainVerifier, GENESIS_HASH};
+use rust_backend::db::events::EventState;
+use rust_backend::db::fees::LedgerTotals;
+use rust_backend::db::intents::IntentStatus;
+use rust_backend::db::mint_approvals::{MintProposal, ProposalStatus};
+use rust_backend::db::payouts::{Payout, PayoutKind, PayoutStatus};
+use rust_backend::db::refunds::RefundStatus;
+use rust_backend::db::webhooks::DeliveryStatus;
+use rust_backend::db::{BridgeStore, SqliteStore};
+use rust_backend::error::BridgeError;
+use rust_backend::zcash::bridge_wallet::DepositOutput;
+
+fn output(txid: &str, index: u32, value: u64) -> DepositOutput {
+    DepositOutput {
+        txid: txid.to_string(),
+        pool: "orchard".to_string(),
+        index,
+        value,
+        to_address: None,
+        memo: None,
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn deposits(store: &dyn BridgeStore) {
+    let deposits = store.deposits();
+
+    assert!(!deposits.is_claimed("0xaa").unwrap());
+    assert!(deposits.record_claim("0xaa", "tx1", "orchard", 0, 5_000).unwrap());
+    assert!(deposits.is_claimed("0xaa").unwrap());
+    assert!(deposits.is_output_claimed("tx1", "orchard", 0).unwrap());
+    assert!(!deposits.is_output_claimed("tx1", "orchard", 1).unwrap());
+
+    // Same recipient hash, or same output under another hash, is a second claim
+    assert!(!deposits.record_claim("0xaa", "tx2", "orchard", 0, 5_000).unwrap());
+    assert!(!deposits.record_claim("0xbb", "tx1", "orchard", 0, 5_000).unwrap());
+
+    let record = deposits.get_deposit("0xaa").unwrap().unwrap();
+    assert_eq!(record.txid, "tx1");
+    assert_eq!(record.pool.as_deref(), Some("orchard"));
+    assert_eq!(record.output_index, Some(0));
+    assert_eq!(record.amount, 5_000);
+    assert!(deposits.get_deposit("0xbb").unwrap().is_none());
+
+    let claims = deposits.list_claims().unwrap();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].recipient_hash, "0xaa");
+
+    // A reservation blocks a second one of the same output, but isn't a claim until confirmed
+    assert!(deposits.reserve_claim("0xcc", "tx3", "sapling", 1, 7_000).unwrap());
+    assert!(!deposits.reserve_claim("0xdd", "tx3", "sapling", 1, 7_000).unwrap());
+    assert!(!deposits.reserve_claim("0xcc", "tx4", "sapling", 0, 7_000).unwrap());
+    assert!(!deposits.reserve_claim("0xaa", "tx5", "orchard", 0, 7_000).unwrap());
+    assert!(deposits.is_output_claimed("tx3", "sapling", 1).unwrap());
+    assert!(deposits.get_deposit("0xcc").unwrap().is_none());
+    assert_eq!(deposits.list_claims().unwrap().len(), 1);
+    assert_eq!(deposits.pending_claims().unwrap()[0].recipient_hash, "0xcc");
+
+    // Released, the output can be reserved again
+    deposits.release_claim("tx3", "sapling", 1).unwrap();
+    assert!(!deposits.is_output_claimed("tx3", "sapling", 1).unwrap());
+    assert!(deposits.reserve_claim("0xdd", "tx3", "sapling", 1, 7_000).unwrap());
+
+    // Confirmed, it's a claim that release leaves alone
+    assert!(deposits.confirm_claim("tx3", "sapling", 1).unwrap());
+    assert!(!deposits.confirm_claim("tx3", "sapling", 1).unwrap());
+    deposits.release_claim("tx3", "sapling", 1).unwrap();
+    assert_eq!(deposits.get_deposit("0xdd").unwrap().unwrap().amount, 7_000);
+    assert_eq!(deposits.list_claims().unwrap().len(), 2);
+    assert!(deposits.pending_claims().unwrap().is_empty());
+
+    assert_eq!(deposits.list_claims_between(now() - 60, now() + 60).unwrap().len(), 2);
+    assert!(deposits.list_claims_between(now() + 60, now() + 120).unwrap().is_empty());
+}
+
+fn withdrawals(store: &dyn BridgeStore) {
+    let withdrawals = store.withdrawals();
+
+    assert!(withdrawals.record_withdrawal("0xc1", "0xn1", 7_000, 42).unwrap());
+    assert!(!withdrawals.record_withdrawal("0xc1", "0xn2", 7_000, 42).unwrap());
+    assert!(!withdrawals.record_withdrawal("0xc2", "0xn1", 7_000, 42).unwrap());
+    assert!(withdrawals.record_withdrawal("0xc3", "0xn3", 1_000, 43).unwrap());
+
+    let record = withdrawals.get_withdrawal_by_note_id("0xn1").unwrap().unwrap();
+    assert_eq!(record.commitment, "0xc1");
+    assert_eq!(record.amount, 7_000);
+    assert_eq!(record.block_number, 42);
+    assert_eq!(withdrawals.get_unclaimed_withdrawals().unwrap().len(), 2);
+
+    withdrawals.mark_claimed("0xc1", "ztx1").unwrap();
+    assert!(withdrawals.is_claimed("0xc1").unwrap());
+    assert!(!withdrawals.is_claimed("0xc3").unwrap());
+    let record = withdrawals.get_withdrawal("0xc1").unwrap().unwrap();
+    assert_eq!(record.zcash_txid.as_deref(), Some("ztx1"));
+    assert!(record.claimed_at.is_some());
+    assert_eq!(withdrawals.get_unclaimed_withdrawals().unwrap().len(), 1);
+
+    let all = withdrawals.list_withdrawals().unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.iter().filter(|w| w.zcash_txid.is_some()).count(), 1);
+    assert_eq!(withdrawals.list_withdrawals_between(now() - 60, now() + 60).unwrap().len(), 2);
+    assert!(withdrawals.list_withdrawals_between(now() - 120, now() - 60).unwrap().is_empty());
+}
+
+fn faucets(store: &dyn BridgeStore) {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+
+    assert!(store.faucets().get_faucet_id("zcash_testnet").unwrap().is_none());
+    store.faucets().store_faucet_id("zcash_testnet", &faucet_id).unwrap();
+    store.faucets().store_faucet_id("zcash_testnet", &faucet_id).unwrap();
+    assert_eq!(store.faucets().get_faucet_id("zcash_testnet").unwrap(), Some(faucet_id));
+}
+
+fn fees(store: &dyn BridgeStore) {
+    let fees = store.fees();
+
+    fees.record_fee(&quote(FeeDirection::Deposit, 10_000, 100), "tx1:orchard:0").unwrap();
+    // Retries don't accrue twice
+    fees.record_fee(&quote(FeeDirection::Deposit, 10_000, 100), "tx1:orchard:0").unwrap();
+    fees.record_fee(&quote(FeeDirection::Withdrawal, 3_000, 30), "0xc1").unwrap();
+    fees.record_fee(&quote(FeeDirection::Withdrawal, 500, 5), "refund:tx9:orchard:0").unwrap();
+
+    let summary = fees.summary().unwrap();
+    let deposit = summary.iter().find(|s| s.direction == "deposit").unwrap();
+    assert_eq!((deposit.transfers, deposit.gross_amount, deposit.fees, deposit.net_amount), (1, 10_000, 100, 9_900));
+    let withdrawal = summary.iter().find(|s| s.direction == "withdrawal").unwrap();
+    assert_eq!((withdrawal.transfers, withdrawal.fees), (2, 35));
+
+    let accruals = fees.accruals().unwrap();
+    assert_eq!(accruals.len(), 3);
+    assert_eq!((accruals[0].reference.as_str(), accruals[0].fee, accruals[0].net_amount), ("tx1:orchard:0", 100, 9_900));
+    assert_eq!(accruals[1].direction, "withdrawal");
+    assert_eq!(fees.accruals_since(now() - 60).unwrap().len(), 3);
+    assert!(fees.accruals_since(now() + 60).unwrap().is_empty());
+
+    // The claims and withdrawals recorded above, with the fees charged on them; refunds don't count
+    let totals = fees.totals_before(now() + 60).unwrap();
+    assert_eq!((totals.deposited, totals.withdrawn, totals.fees), (12_000, 8_000, 130));
+    assert_eq!(fees.totals_before(now() - 60).unwrap(), LedgerTotals::default());
+
+    // Refunds were never minted, so they don't reduce the supply
+    assert_eq!(fees.outstanding_supply(ZCASH_ORIGIN_NETWORK).unwrap(), 9_900 - 3_000);
+    assert_eq!(fees.outstanding_supply("other").unwrap(), 0);
+}
+
+fn refunds(store: &dyn BridgeStore) {
+    let refunds = store.refunds();
+
+    refunds
+        .record_rejection("tx5", "orchard", 1, 2_000, "bad memo", Some("md"), None, Some("utest1refund"))
+        .unwrap();
+    refunds.record_rejection("tx5", "orchard", 1, 2_000, "bad memo", None, None, None).unwrap();
+    assert!(refunds.is_rejected("tx5", "orchard", 1).unwrap());
+    assert!(!refunds.is_rejected("tx5", "orchard", 2).unwrap());
+
+    let rejected = refunds.get_rejected("tx5", "orchard", 1).unwrap().unwrap();
+    assert_eq!(rejected.status, RefundStatus::Rejected);
+    assert_eq!(rejected.memo_digest.as_deref(), Some("md"));
+    assert_eq!(rejected.memo_refund_address.as_deref(), Some("utest1refund"));
+
+    // Approval needs a request first, and mark_refunded needs an approval
+    assert!(!refunds.approve("tx5", "orchard", 1).unwrap());
+    assert!(refunds.request_refund("tx5", "orchard", 1, "utest1user").unwrap());
+    assert_eq!(refunds.list(Some(RefundStatus::Requested)).unwrap().len(), 1);
+    assert!(refunds.approve("tx5", "orchard", 1).unwrap());
+    assert!(!refunds.request_refund("tx5", "orchard", 1, "utest1other").unwrap());
+    refunds.mark_refunded("tx5", "orchard", 1, "ztx5").unwrap();
+
+    let refunded = refunds.get_rejected_for_tx("tx5").unwrap();
+    assert_eq!(refunded.len(), 1);
+    assert_eq!(refunded[0].status, RefundStatus::Refunded);
+    assert_eq!(refunded[0].refund_address.as_deref(), Some("utest1user"));
+    assert_eq!(refunded[0].refund_txid.as_deref(), Some("ztx5"));
+    assert_eq!(refunds.list(None).unwrap().len(), 1);
+    assert!(refunds.list(Some(RefundStatus::Rejected)).unwrap().is_empty());
+
+    // Only an approved refund can be given up on, and it stays closed
+    refunds.record_rejection("tx6", "sapling", 0, 500, "below fee", None, None, None).unwrap();
+    assert!(!refunds.mark_unrefundable("tx6", "sapling", 0).unwrap());
+    assert!(refunds.request_refund("tx6", "sapling", 0, "utest1user").unwrap());
+    assert!(refunds.approve("tx6", "sapling", 0).unwrap());
+    assert!(refunds.mark_unrefundable("tx6", "sapling", 0).unwrap());
+    assert!(!refunds.mark_unrefundable("tx5", "orchard", 1).unwrap());
+    assert!(!refunds.request_refund("tx6", "sapling", 0, "utest1other").unwrap());
+    let unrefundable = refunds.list(Some(RefundStatus::Unrefundable)).unwrap();
+    assert_eq!(unrefundable.len(), 1);
+    assert_eq!(unrefundable[0].txid, "tx6");
+}
+
+fn deposit_addresses(store: &dyn BridgeStore) {
+    let addresses = store.deposit_addresses();
+
+    addresses.assign("utest1a", "0xr1", None, None).unwrap();
+    addresses.assign("utest1b", "0xr1", Some("0xacc"), Some("secret")).unwrap();
+    addresses.assign("utest1c", "0xr2", None, None).unwrap();
+    assert!(addresses.assign("utest1a", "0xr2", None, None).is_err());
+
+    assert!(!addresses.get("utest1a").unwrap().unwrap().auto_mint());
+    assert!(addresses.get("utest1b").unwrap().unwrap().auto_mint());
+    assert!(addresses.get("utest1z").unwrap().is_none());
+    assert_eq!(addresses.addresses_for_recipient("0xr1").unwrap().len(), 2);
+    assert_eq!(addresses.all_addresses().unwrap().len(), 3);
+}
+
+fn intents(store: &dyn BridgeStore) {
+    let intents = store.intents();
+
+    let exact = intents.create("0xr1", None, None, Some(1_000), 3_600).unwrap();
+    let any = intents.create("0xr1", Some("0xacc"), Some("secret"), None, 3_600).unwrap();
+    let stale = intents.create("0xr2", None, None, None, -1).unwrap();
+
+    // Codes are looked up case-insensitively
+    let found = intents.get(&exact.code.to_lowercase()).unwrap().unwrap();
+    assert_eq!(found.expected_amount, Some(1_000));
+    assert_eq!(intents.codes_for_recipient("0xr1").unwrap().len(), 2);
+
+    assert_eq!(intents.mark_received(&exact.code, &output("tx7", 0, 999)).unwrap(), Some(IntentStatus::AmountMismatch));
+    assert_eq!(intents.mark_received(&any.code, &output("tx7", 1, 5)).unwrap(), Some(IntentStatus::Received));
+    assert_eq!(intents.mark_received(&any.code, &output("tx8", 0, 5)).unwrap(), None);
+
+    let received = intents.get(&any.code).unwrap().unwrap();
+    assert!(received.matches_output(&output("tx7", 1, 5)));
+    assert_eq!(received.received_amount, Some(5));
+
+    assert_eq!(intents.expire_stale().unwrap(), 1);
+    assert_eq!(intents.get(&stale.code).unwrap().unwrap().status, IntentStatus::Expired);
+}
+
+fn events(store: &dyn BridgeStore) {
+    let events = store.events();
+
+    assert!(events.record_deposit("ab12", "orchard", 0, Some("0xr1"), EventState::Received, None, None).unwrap());
+    // Relayers report some states on every scan
+    assert!(!events.record_deposit("ab12", "orchard", 0, Some("0xr1"), EventState::Received, None, None).unwrap());
+    assert!(events.record_deposit("ab12", "orchard", 0, Some("0xr1"), EventState::Minted, Some("0xn9"), None).unwrap());
+    assert!(events.record_withdrawal("0xc1", EventState::Created, Some("0xn1"), None).unwrap());
+    assert!(events.record_withdrawal("0xc1", EventState::Paid, None, Some("ztx1")).unwrap());
+
+    let by_txid = events.deposit_events("AB12", 0).unwrap();
+    assert_eq!(by_txid.len(), 2);
+    assert_eq!(by_txid[1].state, EventState::Minted);
+    assert_eq!(events.deposit_events("ab12:orchard:0", 0).unwrap().len(), 2);
+    assert_eq!(events.deposit_events("0xR1", 0).unwrap().len(), 2);
+    assert_eq!(events.deposit_events("r1", by_txid[0].id).unwrap().len(), 1);
+
+    assert_eq!(events.withdrawal_events("C1", 0).unwrap().len(), 2);
+    assert_eq!(events.withdrawal_events("0xn1", 0).unwrap().len(), 1);
+
+    let all = events.events_after(0).unwrap();
+    assert_eq!(all.len(), 4);
+    assert!(all.windows(2).all(|pair| pair[0].id < pair[1].id));
+    assert_eq!(all[3].event_type(), "withdrawal.paid");
+    assert!(events.events_after(all[3].id).unwrap().is_empty());
+    assert_eq!(events.events_since(now() - 60, all[1].id).unwrap().len(), 2);
+    assert!(events.events_since(now() + 60, 0).unwrap().is_empty());
+}
+
+fn webhooks(store: &dyn BridgeStore) {
+    let webhooks = store.webhooks();
+
+    assert_eq!(webhooks.last_event_id().unwrap(), 0);
+    webhooks.advance_cursor(7).unwrap();
+    webhooks.advance_cursor(3).unwrap();
+    assert_eq!(webhooks.last_event_id().unwrap(), 7);
+
+    assert!(webhooks.enqueue("ops", 1, "deposit.minted", "{}").unwrap());
+    assert!(!webhooks.enqueue("ops", 1, "deposit.minted", "{}").unwrap());
+    assert!(webhooks.enqueue("ops", 2, "withdrawal.paid", "{}").unwrap());
+
+    let due = webhooks.due(10).unwrap();
+    assert_eq!(due.len(), 2);
+    webhooks.mark_delivered(due[0].id).unwrap();
+    webhooks.mark_failed(due[1].id, "timeout", Some(i64::MAX)).unwrap();
+    assert!(webhooks.due(10).unwrap().is_empty());
+
+    webhooks.mark_failed(due[1].id, "timeout", None).unwrap();
+    let dead = webhooks.list(Some(DeliveryStatus::Dead), 10).unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].attempts, 2);
+    assert_eq!(dead[0].last_error.as_deref(), Some("timeout"));
+
+    assert!(webhooks.retry(due[1].id).unwrap());
+    assert!(!webhooks.retry(due[0].id).unwrap());
+    assert_eq!(webhooks.due(10).unwrap().len(), 1);
+    assert_eq!(webhooks.list(None, 10).unwrap().len(), 2);
+}
+
+fn transactions(store: &dyn BridgeStore) {
+    let failed: Result<(), BridgeError> = store.transaction(|db| {
+        db.deposits().record_claim("0xtx", "tx10", "orchard", 0, 1)?;
+        // Nested calls join the outer transaction, so this rolls back with it
+        db.transaction(|db| db.withdrawals().record_withdrawal("0xtx", "0xntx", 1, 1))?;
+        Err(BridgeError::Internal("abort".to_string()))
+    });
+    assert!(failed.is_err());
+    assert!(!store.deposits().is_claimed("0xtx").unwrap());
+    assert!(store.withdrawals().get_withdrawal("0xtx").unwrap().is_none());
+
+    let claimed = store
+        .transaction(|db| {
+            let claimed = db.deposits().record_claim("0xtx", "tx10", "orchard", 0, 1)?;
+            db.fees().record_fee(&quote(FeeDirection::Deposit, 1, 0), "tx10:orchard:0")?;
+            Ok(claimed)
+        })
+        .unwrap();
+    assert!(claimed);
+    assert!(store.deposits().is_claimed("0xtx").unwrap());
+}
+
+fn audit(store: &dyn BridgeStore) {
+    let audit = store.audit();
+    let digest = inputs_digest(&serde_json::json!({ "amount": 1 }));
+
+    let first = audit.append(AuditAction::DepositMint, &digest, Some("0xnote"), "zcash_relayer").unwrap();
+    assert_eq!(first.id, 1);
+    assert_eq!(first.prev_hash, GENESIS_HASH);
+    store
+        .transaction(|db| db.audit().append(AuditAction::WithdrawalPayout, &digest, Some("txid"), "api"))
+        .unwrap();
+    audit.append(AuditAction::RefundApprove, &digest, None, "refund_deposits:ops").unwrap();
+
+    let entries = audit.entries(0, 10).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].prev_hash, first.hash);
+    assert_eq!(audit.entries(2, 10).unwrap().len(), 1);
+
+    let mut verifier = ChainVerifier::new();
+    for entry in &entries {
+        verifier.check(entry).unwrap();
+    }
+    assert_eq!(verifier.len(), 3);
+    assert_eq!(verifier.head(), entries[2].hash);
+
+    // A changed field, a removed entry and a re-hashed entry each break the chain
+    let mut modified = entries[1].clone();
+    modified.result = Some("other".to_string());
+    let mut verifier = ChainVerifier::new();
+    verifier.check(&entries[0]).unwrap();
+    assert!(verifier.check(&modified).is_err());
+
+    let mut verifier = ChainVerifier::new();
+    verifier.check(&entries[0]).unwrap();
+    assert!(verifier.check(&entries[2]).is_err());
+
+    modified.hash = modified.compute_hash();
+    let mut verifier = ChainVerifier::new();
+    verifier.check(&entries[0]).unwrap();
+    verifier.check(&modified).unwrap();
+    assert!(verifier.check(&entries[2]).is_err());
+}
+
+fn mint_approvals(store: &dyn BridgeStore) {
+    let approvals = store.mint_approvals();
+    let proposal = MintProposal {
+        deposit: "tx20:orchard:0".to_string(),
+        recipient_hash: "0xmint".to_string(),
+        amount: 10_000,
+        net_amount: 9_900,
+        message: "0xm1".to_string(),
+        proposer: "relayer-a".to_string(),
+        status: ProposalStatus::Pending,
+        tx_id: None,
+        detail: None,
+        created_at: 0,
+    };
+
+    assert!(approvals.propose(&proposal).unwrap());
+    assert!(!approvals.propose(&MintProposal { proposer: "relayer-b".to_string(), ..proposal.clone() }).unwrap());
+    assert_eq!(approvals.get("tx20:orchard:0").unwrap().unwrap().proposer, "relayer-a");
+    assert_eq!(approvals.pending().unwrap().len(), 1);
+
+    assert!(approvals.add_signature("tx20:orchard:0", "0xk1", "0xs1").unwrap());
+    assert!(!approvals.add_signature("tx20:orchard:0", "0xk1", "0xs1").unwrap());
+    assert!(approvals.add_signature("tx20:orchard:0", "0xk2", "0xs2").unwrap());
+    assert!(!approvals.add_signature("tx21:orchard:0", "0xk1", "0xs1").unwrap());
+    assert_eq!(approvals.signatures("tx20:orchard:0").unwrap().len(), 2);
+
+    // Only one relayer gets to submit, and signing stops once it does
+    assert!(approvals.start_submission("tx20:orchard:0").unwrap());
+    assert!(!approvals.start_submission("tx20:orchard:0").unwrap());
+    assert!(!approvals.add_signature("tx20:orchard:0", "0xk3", "0xs3").unwrap());
+    assert!(approvals.pending().unwrap().is_empty());
+
+    // A failed proposal can be proposed again, starting without signatures
+    approvals.finish("tx20:orchard:0", ProposalStatus::Failed, None, Some("stale")).unwrap();
+    assert!(approvals.propose(&MintProposal { message: "0xm2".to_string(), ..proposal.clone() }).unwrap());
+    let reproposed = approvals.get("tx20:orchard:0").unwrap().unwrap();
+    assert_eq!((reproposed.status, reproposed.message.as_str(), reproposed.detail), (ProposalStatus::Pending, "0xm2", None));
+    assert!(approvals.signatures("tx20:orchard:0").unwrap().is_empty());
+
+    approvals.start_submission("tx20:orchard:0").unwrap();
+    approvals.finish("tx20:orchard:0", ProposalStatus::Submitted, Some("0xtx"), None).unwrap();
+    let submitted = approvals.get("tx20:orchard:0").unwrap().unwrap();
+    assert_eq!((submitted.status, submitted.tx_id.as_deref()), (ProposalStatus::Submitted, Some("0xtx")));
+    assert!(!approvals.propose(&proposal).unwrap());
+}
+
+fn payouts(store: &dyn BridgeStore) {
+    let payouts = store.payouts();
+    let payout = Payout {
+        id: "0xc30".to_string(),
+        kind: PayoutKind::Withdrawal,
+        note_id: "0xn30".to_string(),
+        zcash_address: "utest1payout".to_string(),
+        amount: 1000,
+        fee: 10,
+        net_amount: 990,
+        status: PayoutStatus::Unsigned,
+        pczt: "50435a54".to_string(),
+        signed_pczt: None,
+        txid: None,
+        detail: None,
+        created_at: 0,
+        updated_at: 0,
+    };
+
+    assert!(payouts.queue(&payout).unwrap());
+    assert!(!payouts.queue(&payout).unwrap());
+    assert_eq!(payouts.with_status(PayoutStatus::Unsigned).unwrap().len(), 1);
+
+    // Nothing to broadcast before it's signed, and only one process gets to broadcast it
+    assert!(!payouts.start_broadcast("0xc30").unwrap());
+    assert!(payouts.attach_signed("0xc30", "5349474e").unwrap());
+    assert!(!payouts.attach_signed("0xc30", "ffff").unwrap());
+    assert!(payouts.start_broadcast("0xc30").unwrap());
+    assert!(!payouts.start_broadcast("0xc30").unwrap());
+
+    // Only a failed payout can be rebuilt
+    assert!(!payouts.start_build("0xc30").unwrap());
+    payouts.finish("0xc30", PayoutStatus::Failed, None, Some("anchor too old")).unwrap();
+    assert!(!payouts.attach_pczt("0xc30", "50435a55").unwrap());
+    assert!(payouts.start_build("0xc30").unwrap());
+    assert!(payouts.attach_pczt("0xc30", "50435a55").unwrap());
+    let rebuilt = payouts.get("0xc30").unwrap().unwrap();
+    assert_eq!((rebuilt.status, rebuilt.pczt.as_str(), rebuilt.signed_pczt, rebuilt.detail), (PayoutStatus::Unsigned, "50435a55", None, None));
+
+    // A payout queued behind it waits until its PCZT is no longer out
+    let next = Payout { id: "0xc31".to_string(), status: PayoutStatus::Queued, pczt: String::new(), ..payout.clone() };
+    assert!(payouts.queue(&next).unwrap());
+    assert_eq!(payouts.get("0xc31").unwrap().unwrap().status, PayoutStatus::Queued);
+    assert!(!payouts.start_build("0xc31").unwrap());
+
+    payouts.attach_signed("0xc30", "5349474f").unwrap();
+    assert!(!payouts.start_build("0xc31").unwrap());
+    payouts.start_broadcast("0xc30").unwrap();
+    payouts.finish("0xc30", PayoutStatus::Broadcast, Some("ztx30"), None).unwrap();
+    let broadcast = payouts.get("0xc30").unwrap().unwrap();
+    assert_eq!((broadcast.kind, broadcast.status, broadcast.txid.as_deref()), (PayoutKind::Withdrawal, PayoutStatus::Broadcast, Some("ztx30")));
+    assert_eq!((broadcast.amount, broadcast.fee, broadcast.net_amount), (1000, 10, 990));
+    assert!(payouts.with_status(PayoutStatus::Unsigned).unwrap().is_empty());
+
+    assert!(payouts.start_build("0xc31").unwrap());
+    assert!(!payouts.start_build("0xc31").unwrap());
+    assert_eq!(payouts.get("0xc31").unwrap().unwrap().status, PayoutStatus::Building);
+    assert_eq!(payouts.list().unwrap().len(), 2);
+}
+
+fn run_suite(store: &dyn BridgeStore) {
+    assert!(store.schema_version().unwrap() >= 1);
+
+    deposits(store);
+    withdrawals(store);
+    faucets(store);
+    fees(store);
+    refunds(store);
+    deposit_addresses(store);
+    intents(store);
+    events(store);
+    webhooks(store);
+    transactions(store);
+    audit(store);
+    mint_approvals(store);
+    payouts(store);
+}
+
+#[test]
+fn sqlite_store() {
+    let project_root = tempfile::tempdir().unwrap();
+    let store = SqliteStore::open(project_root.path()).unwrap();
+    run_suite(&store);
+
+    // Reopening finds the schema already up to date
+    let reopened = SqliteStore::open(project_root.path()).unwrap();
+    assert!(reopened.deposits().is_claimed("0xaa").unwrap());
+
+    // The audit table itself refuses edits
+    let conn = rusqlite::Connection::open(project_root.path().join("bridge.db")).unwrap();
+    assert!(conn.execute("UPDATE audit_log SET actor = 'x' WHERE id = 1", []).is_err());
+    assert!(conn.execute("DELETE FROM audit_log WHERE id = 3", []).is_err());
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres_store() {
+    use rust_backend::db::postgres::PostgresStore;
+
+    let Ok(url) = std::env::var("BRIDGE_TEST_POSTGRES_URL") else {
+        eprintln!("BRIDGE_TEST_POSTGRES_URL not set, skipping the Postgres run (see scripts/test-postgres.sh)");
+        return;
+    };
+
+    // Each run gets an empty schema of its own
+    let schema = format!("bridge_test_{}", std::process::id());
+    let mut admin = postgres::Client::connect(&url, postgres::NoTls).unwrap();
+    admin
+        .batch_execute(&format!("DROP SCHEMA IF EXISTS {0} CASCADE; CREATE SCHEMA {0}", schema))
+        .unwrap();
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let schema_url = format!("{}{}options=-csearch_path%3D{}", url, separator, schema);
+
+    let store = PostgresStore::connect(&schema_url).unwrap();
+    run_suite(&store);
+
+    let reopened = PostgresStore::connect(&schema_url).unwrap();
+    assert!(reopened.deposits().is_claimed("0xaa").unwrap());
+    drop((store, reopened));
+
+    // The audit table itself refuses edits
+    let audit_log = format!("{}.audit_log", schema);
+    assert!(admin.execute(&format!("UPDATE {} SET actor = 'x' WHERE id = 1", audit_log), &[]).is_err());
+    assert!(admin.execute(&format!("DELETE FROM {} WHERE id = 3", audit_log), &[]).is_err());
+    assert!(admin.batch_execute(&format!("TRUNCATE {}", audit_log)).is_err());
+
+    admin.batch_execute(&format!("DROP SCHEMA {} CASCADE", schema)).unwrap();
+}
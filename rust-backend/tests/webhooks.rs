@@ -0,0 +1,152 @@
+//! Webhook signatures, retry backoff and dead-lettering
+
+use rust_backend::bridge::webhooks::{
+    sign, verify_signature, WebhookConfig, WebhookDispatcher, DELIVERY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use rust_backend::db::events::EventState;
+use rust_backend::db::webhooks::DeliveryStatus;
+use rust_backend::db::{BridgeStore, SqliteStore};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Headers and body of one request the listener received
+struct Received {
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Local receiver that answers every delivery with HTTP 500
+async fn failing_receiver() -> (String, Arc<Mutex<Vec<Received>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let log = received.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+
+            // Read the head, then as much body as Content-Length announces
+            let (head_len, headers) = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    let head = String::from_utf8_lossy(&request[..end]).to_string();
+                    let headers: HashMap<_, _> = head
+                        .lines()
+                        .skip(1)
+                        .filter_map(|line| line.split_once(':'))
+                        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+                        .collect();
+                    break (end + 4, headers);
+                }
+            };
+            let length: usize = headers.get("content-length").map(|len| len.parse().unwrap()).unwrap_or(0);
+            while request.len() < head_len + length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            log.lock().unwrap().push(Received { headers, body: request[head_len..].to_vec() });
+            stream
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+        }
+    });
+
+    (url, received)
+}
+
+#[test]
+fn signature_round_trip() {
+    let body = br#"{"id":1,"type":"deposit.minted"}"#;
+    let signature = sign("s3cret", 1_700_000_000, body);
+
+    assert!(signature.starts_with("sha256="));
+    assert!(verify_signature("s3cret", "1700000000", body, &signature));
+    assert!(verify_signature("s3cret", " 1700000000 ", body, &format!(" {} ", signature)));
+
+    // Any change to the body, timestamp or key breaks it
+    assert!(!verify_signature("s3cret", "1700000000", br#"{"id":2,"type":"deposit.minted"}"#, &signature));
+    assert!(!verify_signature("s3cret", "1700000001", body, &signature));
+    assert!(!verify_signature("other", "1700000000", body, &signature));
+
+    // Malformed headers are rejected, not panicked on
+    assert!(!verify_signature("s3cret", "1700000000", body, signature.trim_start_matches("sha256=")));
+    assert!(!verify_signature("s3cret", "1700000000", body, "sha256=zz"));
+    assert!(!verify_signature("s3cret", "1700000000", body, ""));
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let config = WebhookConfig { initial_backoff_secs: 30, max_backoff_secs: 600, ..Default::default() };
+
+    assert_eq!(config.backoff_secs(0), 30);
+    assert_eq!(config.backoff_secs(1), 30);
+    assert_eq!(config.backoff_secs(2), 60);
+    assert_eq!(config.backoff_secs(3), 120);
+    assert_eq!(config.backoff_secs(5), 480);
+    assert_eq!(config.backoff_secs(6), 600);
+    assert_eq!(config.backoff_secs(u32::MAX), 600);
+
+    // Huge settings saturate instead of overflowing
+    let config = WebhookConfig { initial_backoff_secs: u64::MAX / 2, max_backoff_secs: u64::MAX, ..Default::default() };
+    assert_eq!(config.backoff_secs(40), u64::MAX);
+}
+
+#[tokio::test]
+async fn failing_delivery_moves_to_dead_letters() {
+    let project_root = tempfile::tempdir().unwrap();
+    let (url, received) = failing_receiver().await;
+
+    std::fs::write(
+        project_root.path().join("webhooks.json"),
+        serde_json::json!({
+            "subscriptions": [{ "name": "ops", "url": url, "secret": "s3cret" }],
+            "max_attempts": 2,
+            "initial_backoff_secs": 0,
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let store = SqliteStore::open(project_root.path()).unwrap();
+    store.events().record_deposit("ab12", "orchard", 0, Some("0xr1"), EventState::Minted, Some("0xn9"), None).unwrap();
+
+    let dispatcher = WebhookDispatcher::new(project_root.path().to_path_buf(), 1);
+
+    // First failure is retried (right away, with no backoff configured)
+    assert!(dispatcher.tick().await);
+    let pending = store.webhooks().list(Some(DeliveryStatus::Pending), 10).unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].attempts, 1);
+    assert!(pending[0].last_error.as_deref().unwrap().contains("500"));
+
+    // The second one is the last allowed attempt
+    assert!(dispatcher.tick().await);
+    assert!(store.webhooks().list(Some(DeliveryStatus::Pending), 10).unwrap().is_empty());
+    let dead = store.webhooks().list(Some(DeliveryStatus::Dead), 10).unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].attempts, 2);
+
+    // Nothing is sent once a delivery is dead
+    assert!(dispatcher.tick().await);
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 2);
+    for request in received.iter() {
+        assert_eq!(request.headers[&DELIVERY_HEADER.to_lowercase()], dead[0].id.to_string());
+        assert_eq!(request.body, dead[0].payload.as_bytes());
+        assert!(verify_signature(
+            "s3cret",
+            &request.headers[&TIMESTAMP_HEADER.to_lowercase()],
+            &request.body,
+            &request.headers[&SIGNATURE_HEADER.to_lowercase()],
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+#!/bin/bash
+# Compare the API spec generated from the Rust types with the committed rust-backend/openapi.json
+#
+#   scripts/check-openapi.sh            exit 1 and print a diff if the API changed
+#   scripts/check-openapi.sh --update   rewrite openapi.json after an intended API change
+
+set -euo pipefail
+
+cd "$(dirname "$0")/../rust-backend"
+
+generated=$(mktemp)
+trap 'rm -f "$generated"' EXIT
+
+cargo run --quiet --bin rust-backend -- --print-openapi > "$generated"
+
+if [ "${1:-}" = "--update" ]; then
+    cp "$generated" openapi.json
+    echo "Wrote rust-backend/openapi.json"
+    exit 0
+fi
+
+if [ ! -f openapi.json ]; then
+    echo "rust-backend/openapi.json is missing; run scripts/check-openapi.sh --update and commit it."
+    exit 1
+fi
+
+if ! diff -u openapi.json "$generated"; then
+    echo ""
+    echo "The API spec changed. Review the diff above for breaking changes (removed paths, fields or enum values,"
+    echo "new required fields), then run scripts/check-openapi.sh --update and commit openapi.json."
+    exit 1
+fi
+
+echo "OpenAPI spec is up to date"
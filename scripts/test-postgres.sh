@@ -0,0 +1,33 @@
+#!/bin/bash
+# Run the bridge database test suite against Postgres as well as SQLite
+#
+#   scripts/test-postgres.sh    start a throwaway Postgres, run the suite, stop it
+#
+# Uses a docker container when docker is available, and a temporary local cluster
+# (initdb + pg_ctl) otherwise. Set BRIDGE_TEST_POSTGRES_URL to use a running server instead.
+
+set -euo pipefail
+
+cd "$(dirname "$0")/../rust-backend"
+
+port=${BRIDGE_TEST_POSTGRES_PORT:-55432}
+
+if [ -z "${BRIDGE_TEST_POSTGRES_URL:-}" ]; then
+    if command -v docker >/dev/null && docker info >/dev/null 2>&1; then
+        container=$(docker run --rm -d -e POSTGRES_HOST_AUTH_METHOD=trust -p "$port:5432" postgres:16)
+        trap 'docker stop "$container" >/dev/null' EXIT
+        until docker exec "$container" pg_isready -h 127.0.0.1 -U postgres >/dev/null 2>&1; do sleep 1; done
+    elif command -v initdb >/dev/null && command -v pg_ctl >/dev/null; then
+        datadir=$(mktemp -d)
+        trap 'pg_ctl -D "$datadir" -m fast stop >/dev/null; rm -rf "$datadir"' EXIT
+        initdb -D "$datadir" -U postgres --auth=trust >/dev/null
+        pg_ctl -D "$datadir" -o "-p $port -k $datadir" -l "$datadir/postgres.log" -w start >/dev/null
+    else
+        echo "Need docker or a local Postgres (initdb, pg_ctl) to run the Postgres tests" >&2
+        exit 1
+    fi
+
+    export BRIDGE_TEST_POSTGRES_URL="postgres://postgres@127.0.0.1:$port/postgres"
+fi
+
+cargo test --features postgres --test store